@@ -0,0 +1,13 @@
+-- idempotency_keys テーブル: Idempotency-Key ヘッダー付きの予約受付の結果を依頼者ごとに保持する
+CREATE TABLE idempotency_keys (
+    requester_id UUID NOT NULL, -- 依頼者ID (キーのスコープ)
+    idempotency_key VARCHAR(255) NOT NULL, -- クライアントが指定したキー
+    request_fingerprint TEXT NOT NULL, -- 最初のリクエスト内容 (再送時の同一性判定用)
+    reservation_id UUID, -- 受け付けた予約ID (処理中は NULL)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(), -- 作成日時
+    expires_at TIMESTAMPTZ NOT NULL, -- 有効期限 (過ぎたキーは再利用できる)
+    PRIMARY KEY (requester_id, idempotency_key)
+);
+
+-- 期限切れキーの掃除用
+CREATE INDEX idx_idempotency_keys_expires_at ON idempotency_keys (expires_at);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+ALTER TABLE reservations DROP COLUMN IF EXISTS desired_delivery_time_slot;
+
+ALTER TABLE reservations
+    ALTER COLUMN desired_delivery_date TYPE TIMESTAMPTZ
+        USING (desired_delivery_date::timestamp AT TIME ZONE 'Asia/Tokyo');
@@ -0,0 +1,7 @@
+-- 配送希望を「日付 + 時間帯」で保持する (自由入力の日時から置き換え)
+ALTER TABLE reservations
+    ALTER COLUMN desired_delivery_date TYPE DATE
+        USING (desired_delivery_date AT TIME ZONE 'Asia/Tokyo')::date;
+
+ALTER TABLE reservations
+    ADD COLUMN desired_delivery_time_slot VARCHAR(50); -- 配送時間帯 (配送希望日がある場合のみ)
@@ -0,0 +1,6 @@
+-- Add down migration script here
+ALTER TABLE reservations DROP CONSTRAINT IF EXISTS reservations_forwarding_details_check;
+ALTER TABLE reservations
+    DROP COLUMN IF EXISTS forwarding_arrival_date,
+    DROP COLUMN IF EXISTS forwarding_description,
+    DROP COLUMN IF EXISTS gift_type;
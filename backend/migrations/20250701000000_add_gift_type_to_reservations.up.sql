@@ -0,0 +1,12 @@
+-- プレゼント内容の種類 (発送代行 / 手配) と、発送代行の場合のプレゼント情報を保持する
+-- 既存の予約はすべて商品IDを指定した手配として扱う
+ALTER TABLE reservations
+    ADD COLUMN gift_type VARCHAR(20) NOT NULL DEFAULT 'Arrangement'
+        CHECK (gift_type IN ('Forwarding', 'Arrangement')), -- プレゼント内容の種類
+    ADD COLUMN forwarding_description TEXT, -- 発送代行プレゼント情報 (発送代行のみ)
+    ADD COLUMN forwarding_arrival_date DATE; -- 倉庫到着予定日 (発送代行のみ)
+
+ALTER TABLE reservations
+    ADD CONSTRAINT reservations_forwarding_details_check CHECK (
+        (gift_type = 'Forwarding') = (forwarding_description IS NOT NULL AND forwarding_arrival_date IS NOT NULL)
+    );
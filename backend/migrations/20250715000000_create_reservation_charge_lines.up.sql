@@ -0,0 +1,14 @@
+-- reservation_charge_lines テーブル: 予約の料金内訳 (明細 1 行ごとに税区分と消費税額を持つ)
+-- 料金計算の導入前に受け付けた予約には明細がなく、reservations.total_amount のみを持つ
+CREATE TABLE reservation_charge_lines (
+    reservation_id UUID NOT NULL REFERENCES reservations(id) ON DELETE CASCADE, -- 予約ID (FK)
+    line_no INTEGER NOT NULL, -- 明細の並び順
+    item_kind VARCHAR(20) NOT NULL
+        CHECK (item_kind IN ('Product', 'ForwardingFee', 'Wrapping', 'Shipping')), -- 料金項目
+    product_id UUID, -- 商品ID (商品の明細のみ)
+    amount_excluding_tax INTEGER NOT NULL CHECK (amount_excluding_tax >= 0), -- 税抜金額
+    tax_category VARCHAR(10) NOT NULL CHECK (tax_category IN ('Standard', 'Reduced')), -- 税区分
+    tax_amount INTEGER NOT NULL CHECK (tax_amount >= 0), -- 消費税額
+    PRIMARY KEY (reservation_id, line_no),
+    CHECK ((item_kind = 'Product') = (product_id IS NOT NULL))
+);
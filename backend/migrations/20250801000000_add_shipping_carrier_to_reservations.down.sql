@@ -0,0 +1,3 @@
+-- Add down migration script here
+ALTER TABLE reservations DROP CONSTRAINT IF EXISTS reservations_shipping_details_check;
+ALTER TABLE reservations DROP COLUMN IF EXISTS shipping_carrier;
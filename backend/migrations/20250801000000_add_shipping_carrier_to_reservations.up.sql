@@ -0,0 +1,12 @@
+-- 配送伝票番号を発行した配送業者
+-- 既存の発送済み予約はヤマト運輸で発送したものとして扱う
+ALTER TABLE reservations
+    ADD COLUMN shipping_carrier VARCHAR(20)
+        CHECK (shipping_carrier IN ('Yamato', 'Sagawa', 'JapanPost')); -- 配送業者 (NULL可)
+
+UPDATE reservations SET shipping_carrier = 'Yamato' WHERE shipping_slip_number IS NOT NULL;
+
+ALTER TABLE reservations
+    ADD CONSTRAINT reservations_shipping_details_check CHECK (
+        (shipping_carrier IS NULL) = (shipping_slip_number IS NULL)
+    );
@@ -0,0 +1,3 @@
+-- Add down migration script here
+ALTER TABLE reservations DROP CONSTRAINT IF EXISTS reservations_cancellation_details_check;
+ALTER TABLE reservations DROP COLUMN IF EXISTS cancellation_fee;
@@ -0,0 +1,14 @@
+-- キャンセル時に確定したキャンセル料
+-- 既存のキャンセル済み予約はキャンセル料なし、キャンセル日時は最終更新日時として扱う
+ALTER TABLE reservations
+    ADD COLUMN cancellation_fee INTEGER CHECK (cancellation_fee >= 0); -- キャンセル料 (円、NULL可)
+
+UPDATE reservations
+SET cancellation_fee = 0,
+    cancelled_at = COALESCE(cancelled_at, updated_at)
+WHERE status = 'Cancelled';
+
+ALTER TABLE reservations
+    ADD CONSTRAINT reservations_cancellation_details_check CHECK (
+        status <> 'Cancelled' OR (cancelled_at IS NOT NULL AND cancellation_fee IS NOT NULL)
+    );
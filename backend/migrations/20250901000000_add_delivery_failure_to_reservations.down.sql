@@ -0,0 +1,21 @@
+-- Add down migration script here
+-- 配送失敗・返送済みの予約は以前のステータスに戻せないため、残っている場合はロールバックを中止する
+DO $$
+DECLARE
+    remaining BIGINT;
+BEGIN
+    SELECT COUNT(*) INTO remaining
+    FROM reservations
+    WHERE status IN ('DeliveryFailed', 'Returned');
+    IF remaining > 0 THEN
+        RAISE EXCEPTION
+            'Cannot roll back: % reservation(s) are DeliveryFailed or Returned. Re-ship or cancel them first.',
+            remaining;
+    END IF;
+END
+$$;
+
+ALTER TABLE reservations DROP CONSTRAINT IF EXISTS reservations_delivery_failure_details_check;
+ALTER TABLE reservations DROP COLUMN IF EXISTS returned_at;
+ALTER TABLE reservations DROP COLUMN IF EXISTS delivery_failed_at;
+ALTER TABLE reservations DROP COLUMN IF EXISTS delivery_failure_reason;
@@ -0,0 +1,14 @@
+-- 配送失敗と返送の記録
+-- status には 'DeliveryFailed' (配送失敗) と 'Returned' (返送済み) が加わる
+ALTER TABLE reservations
+    ADD COLUMN delivery_failure_reason VARCHAR(20)
+        CHECK (delivery_failure_reason IN ('Refused', 'UnknownAddress', 'Unclaimed')), -- 配送失敗理由 (NULL可)
+    ADD COLUMN delivery_failed_at TIMESTAMPTZ, -- 配送失敗日時 (NULL可)
+    ADD COLUMN returned_at TIMESTAMPTZ; -- 返送日時 (NULL可)
+
+ALTER TABLE reservations
+    ADD CONSTRAINT reservations_delivery_failure_details_check CHECK (
+        (delivery_failure_reason IS NULL) = (delivery_failed_at IS NULL)
+        AND (status <> 'DeliveryFailed' OR delivery_failed_at IS NOT NULL)
+        AND (status <> 'Returned' OR (delivery_failed_at IS NOT NULL AND returned_at IS NOT NULL))
+    );
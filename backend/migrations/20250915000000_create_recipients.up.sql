@@ -0,0 +1,18 @@
+-- recipients テーブル: 依頼者が登録した届け先
+-- reservations.recipient_id は登録前に受け付けた予約があるため外部キーにしない
+CREATE TABLE recipients (
+    id UUID PRIMARY KEY, -- 届け先ID
+    owner_id UUID NOT NULL, -- 所有者 (依頼者) のユーザーID
+    name TEXT NOT NULL, -- 氏名 (文字数はアプリケーションで検証する)
+    name_kana TEXT NOT NULL, -- フリガナ (全角カタカナ)
+    postal_code CHAR(7) NOT NULL CHECK (postal_code ~ '^[0-9]{7}$'), -- 郵便番号 (ハイフンなし)
+    prefecture_code SMALLINT NOT NULL CHECK (prefecture_code BETWEEN 1 AND 47), -- 都道府県コード (JIS X 0401)
+    address TEXT NOT NULL, -- 住所 (市区町村以降)
+    building TEXT, -- 建物名 (NULL可)
+    phone_number VARCHAR(11) NOT NULL CHECK (phone_number ~ '^0[0-9]{9,10}$'), -- 電話番号 (ハイフンなし)
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- 所有者ごとの一覧取得 (登録順)
+CREATE INDEX idx_recipients_owner_id ON recipients (owner_id, created_at);
@@ -0,0 +1,16 @@
+-- user_accounts テーブル: ユーザーIDと認証基盤の利用者を結び付けるアカウント
+-- 予約・届け先の依頼者IDはアカウント登録前のものがあるため外部キーにしない
+CREATE TABLE user_accounts (
+    id UUID PRIMARY KEY, -- ユーザーID
+    auth_subject VARCHAR(255) NOT NULL, -- 認証基盤の subject
+    email VARCHAR(254) NOT NULL, -- メールアドレス (ドメイン部は小文字)
+    display_name TEXT NOT NULL, -- 表示名
+    default_sender_name TEXT, -- 既定の差出人名 (NULL可)
+    role VARCHAR(20) NOT NULL CHECK (role IN ('Customer', 'Admin')), -- ロール
+    status VARCHAR(20) NOT NULL CHECK (status IN ('Active', 'Suspended')), -- アカウント状態
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- 認証基盤の利用者 1 人につきアカウントは 1 つ
+CREATE UNIQUE INDEX idx_user_accounts_auth_subject ON user_accounts (auth_subject);
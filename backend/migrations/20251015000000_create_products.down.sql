@@ -0,0 +1,3 @@
+-- Add down migration script here
+ALTER TABLE reservation_products DROP CONSTRAINT IF EXISTS fk_reservation_products_product;
+DROP TABLE IF EXISTS products;
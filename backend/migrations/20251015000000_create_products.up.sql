@@ -0,0 +1,31 @@
+-- products テーブル: 手配で届ける商品のカタログ
+-- 販売を終了した商品も受付済みの予約から参照されるため削除せず、on_sale で管理する
+CREATE TABLE products (
+    id UUID PRIMARY KEY, -- 商品ID
+    name TEXT NOT NULL, -- 商品名
+    description TEXT NOT NULL DEFAULT '', -- 商品説明
+    price INTEGER NOT NULL CHECK (price > 0), -- 税込価格
+    tax_category VARCHAR(10) NOT NULL CHECK (tax_category IN ('Standard', 'Reduced')), -- 税区分
+    image_keys TEXT[] NOT NULL DEFAULT '{}', -- 商品画像のキー (先頭がメイン画像)
+    on_sale BOOLEAN NOT NULL DEFAULT TRUE, -- 販売中か
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- 既存の予約が参照している商品は販売終了の商品として登録する
+-- (価格は受付時の料金明細の税込金額。明細がない予約の商品は 1 円・標準税率の仮の値)
+INSERT INTO products (id, name, price, tax_category, on_sale)
+SELECT
+    rp.product_id,
+    '商品 ' || rp.product_id::TEXT,
+    COALESCE(MAX(cl.amount_excluding_tax + cl.tax_amount), 1),
+    COALESCE(MAX(cl.tax_category), 'Standard'),
+    FALSE
+FROM reservation_products rp
+LEFT JOIN reservation_charge_lines cl
+    ON cl.item_kind = 'Product' AND cl.product_id = rp.product_id
+GROUP BY rp.product_id;
+
+ALTER TABLE reservation_products
+    ADD CONSTRAINT fk_reservation_products_product
+    FOREIGN KEY (product_id) REFERENCES products(id);
@@ -0,0 +1,3 @@
+-- Add down migration script here
+DROP TABLE IF EXISTS reservation_stock_allocations;
+DROP TABLE IF EXISTS product_stocks;
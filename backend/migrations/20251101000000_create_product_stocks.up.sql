@@ -0,0 +1,19 @@
+-- product_stocks テーブル: 商品ごとの在庫 (行がない商品は在庫 0 として扱う)
+CREATE TABLE product_stocks (
+    product_id UUID PRIMARY KEY REFERENCES products(id), -- 商品ID (FK)
+    on_hand INTEGER NOT NULL DEFAULT 0 CHECK (on_hand >= 0), -- 在庫数
+    allocated INTEGER NOT NULL DEFAULT 0 CHECK (allocated >= 0), -- 受付済みで未発送の予約に引き当てた数
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (allocated <= on_hand)
+);
+
+-- reservation_stock_allocations テーブル: 予約ごとに引き当てた在庫
+-- キャンセル・発送完了ではこの行を削除し、引き当てた分だけ在庫を戻す・出庫する
+-- (在庫管理の導入前に受け付けた予約には行がなく、在庫は変わらない)
+CREATE TABLE reservation_stock_allocations (
+    reservation_id UUID NOT NULL REFERENCES reservations(id) ON DELETE CASCADE, -- 予約ID (FK)
+    product_id UUID NOT NULL REFERENCES products(id), -- 商品ID (FK)
+    quantity INTEGER NOT NULL CHECK (quantity > 0), -- 引当数
+    allocated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(), -- 引当日時
+    PRIMARY KEY (reservation_id, product_id)
+);
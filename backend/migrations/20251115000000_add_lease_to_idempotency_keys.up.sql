@@ -0,0 +1,7 @@
+-- 処理中の Idempotency-Key の確保期限
+-- 確保期限を過ぎても予約IDが記録されていないキー (処理が中断されたもの) は、同じキーで確保し直せる
+-- 既存の処理中のキーはすぐに確保し直せるものとして扱う
+ALTER TABLE idempotency_keys
+    ADD COLUMN lease_expires_at TIMESTAMPTZ NOT NULL DEFAULT NOW(); -- 確保期限
+
+ALTER TABLE idempotency_keys ALTER COLUMN lease_expires_at DROP DEFAULT;
@@ -1,12 +1,22 @@
 use crate::domain::{
-    self, DomainError, InfrastructureError, プレゼント予約Repository, プレゼント予約状態,
-    ユーザーID, ラッピング種類, 予約ID, 商品ID, 届け先ID, 支払いID, 記念日, 金額,
+    self, DatabaseStatus, DomainError, InfrastructureError, イベント発行者, キャンセルポリシー,
+    キャンセル判定, ドメインイベント, プレゼント予約Repository, プレゼント予約状態, プレゼント内容,
+    プロフィール, メッセージ内容, ユーザーID, ユーザーアカウント, ユーザーアカウントRepository,
+    ラッピング種類, 予約ID, 予約ステータス, 予約一覧カーソル, 予約一覧ページ, 予約変更内容,
+    予約検索条件, 予約検索結果, 住所候補, 冪等性キーの確保, 商品, 商品ID, 商品Repository, 商品価格,
+    商品内容, 商品在庫, 商品在庫Repository, 商品検索条件, 商品検索結果, 在庫移動, 届け先, 届け先ID,
+    届け先Repository, 届け先内容, 支払いID, 料金計算, 発送リードタイム, 記念日, 認証サブジェクト,
+    郵便番号, 郵便番号辞書, 配送伝票番号, 配送失敗理由, 配送希望, 金額,
 };
 use anyhow::Result; // anyhow::Result を使う想定
-use chrono::DateTime;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use chrono_tz::Asia::Tokyo;
 use chrono_tz::Tz;
-use std::collections::HashSet;
+use std::collections::HashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
 // --- アプリケーションエラー ---
@@ -16,6 +26,12 @@ pub enum ApplicationError {
     Domain(#[from] DomainError),
     #[error("リポジトリ操作エラー: {0}")]
     Repository(String),
+    #[error("入力値が不正です: {field} ({reason})")]
+    InvalidInput { field: String, reason: String },
+    #[error("Idempotency-Key が異なるリクエスト内容で再利用されました")]
+    IdempotencyKeyReused,
+    #[error("同じ Idempotency-Key のリクエストを処理中です")]
+    IdempotencyKeyInProgress,
     #[allow(dead_code)]
     #[error("予期せぬエラー: {0}")]
     Unexpected(String),
@@ -24,71 +40,567 @@ pub enum ApplicationError {
 // Result型エイリアス
 pub type AppResult<T> = Result<T, ApplicationError>;
 
+/// 一覧取得で1ページに含められる最大件数
+pub const 一覧取得の最大件数: usize = 100;
+
+/// readiness 判定でデータストアの応答を待つ上限
+pub const READINESS_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+// --- 時計 ---
+
+/// 現在時刻の取得元 (テストで時刻を固定できるように注入する)
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// システム時刻を返す時計
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// 常に同じ時刻を返す時計
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+// --- 冪等性キー (Idempotency-Key) ---
+
+/// Idempotency-Key を保持する期間
+pub const IDEMPOTENCY_KEY_TTL: chrono::Duration = chrono::Duration::hours(24);
+
+/// 処理中のキーを確保しておく期間
+///
+/// 処理が中断された (プロセスが落ちたなど) キーは、この期間を過ぎると同じキーで再試行できる。
+pub const IDEMPOTENCY_KEY_LEASE: chrono::Duration = chrono::Duration::seconds(30);
+
+/// 冪等性キーと、それに紐づくリクエスト
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdempotencyKey {
+    /// キーのスコープ (同じキーでも依頼者が異なれば別物として扱う)
+    pub requester_id: ユーザーID,
+    pub key: String,
+    /// リクエスト内容を表す文字列 (同じキーで内容が変わっていないかの判定に使う)
+    pub fingerprint: String,
+}
+
+/// 保存された冪等性キーの記録
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdempotencyRecord {
+    pub requester_id: ユーザーID,
+    pub key: String,
+    pub fingerprint: String,
+    /// 受け付けた予約ID (処理中は None)
+    pub reservation_id: Option<予約ID>,
+    /// キーを確保した日時 (期限切れなどで確保し直すと更新される)
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    /// 処理中のキーの確保期限 (過ぎると別のリクエストが確保し直せる)
+    pub lease_expires_at: DateTime<Utc>,
+}
+
+impl IdempotencyRecord {
+    /// 予約の保存と同じトランザクションで予約IDを記録するための確保情報
+    pub fn 確保(&self) -> 冪等性キーの確保 {
+        冪等性キーの確保 {
+            キー: self.key.clone(),
+            確保日時: self.created_at,
+        }
+    }
+}
+
+/// キーの使用開始を試みた結果
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyBegin {
+    /// 初めてのキー (または期限切れ・確保期限切れ) なので処理を開始してよい
+    Started,
+    /// 有効期限内の記録がすでにある
+    Existing(IdempotencyRecord),
+}
+
+/// 予約受付を冪等に実行した結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdempotentOutcome {
+    /// 新しく受け付けた
+    Created(予約ID),
+    /// 以前の結果を再送した
+    Replayed(予約ID),
+}
+
+impl IdempotentOutcome {
+    pub fn 予約id(&self) -> 予約ID {
+        match self {
+            IdempotentOutcome::Created(id) | IdempotentOutcome::Replayed(id) => *id,
+        }
+    }
+}
+
+/// 冪等性キーの保存先
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// 記録がない (または期限切れの、処理中のまま確保期限を過ぎた) 場合は処理中として記録し Started を返す。
+    /// 有効な記録がある場合はそれを返す。判定と記録は不可分に行う。
+    ///
+    /// 予約IDの記録は プレゼント予約Repository::save_accepted_with_idempotency_key で予約の保存と同時に行う。
+    async fn begin(
+        &self,
+        record: &IdempotencyRecord,
+    ) -> Result<IdempotencyBegin, InfrastructureError>;
+
+    /// 処理に失敗したキーを破棄する (同じキーで再試行できるようにする)
+    ///
+    /// record で確保した処理中の記録だけを対象にする (確保し直した別のリクエストの記録は消さない)。
+    async fn abandon(&self, record: &IdempotencyRecord) -> Result<(), InfrastructureError>;
+}
+
+// --- ヘルスチェック ---
+
+/// コンポーネントの稼働状態
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentStatus {
+    Up,
+    Down,
+}
+
+/// readiness 判定に失敗した理由の種類
+///
+/// 認証なしで公開するため種類だけを返し、エラーの詳細はログに出す。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadinessFailure {
+    /// データストアがエラーを返した
+    Unavailable,
+    /// 時間内に応答がなかった
+    Timeout,
+}
+
+/// データベースの readiness 判定結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatabaseReadiness {
+    pub status: ComponentStatus,
+    /// 稼働状況 (取得できた場合のみ)
+    pub details: Option<DatabaseStatus>,
+    /// 取得に失敗した理由
+    pub error: Option<ReadinessFailure>,
+}
+
+/// サービス全体の readiness 判定結果
+///
+/// すべてのコンポーネントが Up の場合のみ全体も Up になる。
+/// バックグラウンドワーカーなどを追加する場合はフィールドとして追加する。
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReadinessReport {
+    pub status: ComponentStatus,
+    pub database: DatabaseReadiness,
+}
+
 // --- ユースケース / ワークフロー ---
 
 /// プレゼント予約に関するユースケースを提供するサービス
 pub struct プレゼント予約サービス {
     reservation_repo: Arc<dyn プレゼント予約Repository>,
+    idempotency_store: Option<Arc<dyn IdempotencyStore>>,
+    event_publisher: Option<Arc<dyn イベント発行者>>,
+    product_repo: Option<Arc<dyn 商品Repository>>,
+    recipient_repo: Option<Arc<dyn 届け先Repository>>,
+    account_repo: Option<Arc<dyn ユーザーアカウントRepository>>,
+    clock: Arc<dyn Clock>,
+    発送リードタイム: 発送リードタイム,
+    料金計算: 料金計算,
+    キャンセルポリシー: キャンセルポリシー,
     // 必要に応じて他のリポジトリ (例: 商品リポジトリ) も追加
 }
 
 impl プレゼント予約サービス {
     /// 新しいプレゼント予約サービスを生成する
     pub fn new(reservation_repo: Arc<dyn プレゼント予約Repository>) -> Self {
-        Self { reservation_repo }
+        Self {
+            reservation_repo,
+            idempotency_store: None,
+            event_publisher: None,
+            product_repo: None,
+            recipient_repo: None,
+            account_repo: None,
+            clock: Arc::new(SystemClock),
+            発送リードタイム: 発送リードタイム::default(),
+            料金計算: 料金計算::default(),
+            キャンセルポリシー: キャンセルポリシー::default(),
+        }
+    }
+
+    /// 受付日時などに使う時計を設定する
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// 記念日の判定に使う発送リードタイムを設定する
+    pub fn with_shipping_lead_time(mut self, リードタイム: 発送リードタイム) -> Self {
+        self.発送リードタイム = リードタイム;
+        self
+    }
+
+    /// 手数料・ラッピング料・送料を設定する
+    pub fn with_pricing(mut self, 料金計算: 料金計算) -> Self {
+        self.料金計算 = 料金計算;
+        self
+    }
+
+    /// キャンセルの可否とキャンセル料を決めるキャンセルポリシーを設定する
+    pub fn with_cancellation_policy(mut self, ポリシー: キャンセルポリシー) -> Self {
+        self.キャンセルポリシー = ポリシー;
+        self
+    }
+
+    /// 手配する商品を確認する商品カタログを設定する (未設定の場合、手配の予約は受け付けない)
+    pub fn with_products(mut self, product_repo: Arc<dyn 商品Repository>) -> Self {
+        self.product_repo = Some(product_repo);
+        self
+    }
+
+    /// 予約受付で届け先を確認するリポジトリを設定する (未設定の場合、予約は受け付けない)
+    pub fn with_recipients(mut self, recipient_repo: Arc<dyn 届け先Repository>) -> Self {
+        self.recipient_repo = Some(recipient_repo);
+        self
+    }
+
+    /// 予約受付で依頼者のアカウントの状態を確認するリポジトリを設定する
+    pub fn with_accounts(
+        mut self, account_repo: Arc<dyn ユーザーアカウントRepository>
+    ) -> Self {
+        self.account_repo = Some(account_repo);
+        self
+    }
+
+    /// Idempotency-Key の保存先を設定する
+    pub fn with_idempotency_store(mut self, store: Arc<dyn IdempotencyStore>) -> Self {
+        self.idempotency_store = Some(store);
+        self
+    }
+
+    /// ドメインイベントの発行先を設定する
+    pub fn with_event_publisher(mut self, publisher: Arc<dyn イベント発行者>) -> Self {
+        self.event_publisher = Some(publisher);
+        self
+    }
+
+    /// 予約受付を冪等に実行する
+    ///
+    /// 同じ依頼者・キー・リクエスト内容の再送には最初の結果を返し、
+    /// 同じキーで内容が異なる場合は IdempotencyKeyReused を返す。
+    /// 受付 には確保したキーを渡すので、プレゼント予約受付 に渡して予約と同時に記録させる。
+    pub async fn 冪等に予約を受け付ける<F, Fut>(
+        &self,
+        idempotency_key: &IdempotencyKey,
+        受付: F,
+    ) -> AppResult<IdempotentOutcome>
+    where
+        F: FnOnce(Option<冪等性キーの確保>) -> Fut + Send,
+        Fut: Future<Output = AppResult<予約ID>> + Send,
+    {
+        let Some(store) = &self.idempotency_store else {
+            tracing::warn!("Idempotency store is not configured; Idempotency-Key is ignored");
+            return 受付(None).await.map(IdempotentOutcome::Created);
+        };
+        let repository_error = |e: InfrastructureError| ApplicationError::Repository(e.to_string());
+
+        let now = self.clock.now();
+        let record = IdempotencyRecord {
+            requester_id: idempotency_key.requester_id,
+            key: idempotency_key.key.clone(),
+            fingerprint: idempotency_key.fingerprint.clone(),
+            reservation_id: None,
+            created_at: now,
+            expires_at: now + IDEMPOTENCY_KEY_TTL,
+            lease_expires_at: now + IDEMPOTENCY_KEY_LEASE,
+        };
+        match store.begin(&record).await.map_err(repository_error)? {
+            IdempotencyBegin::Existing(existing) => {
+                if existing.fingerprint != record.fingerprint {
+                    return Err(ApplicationError::IdempotencyKeyReused);
+                }
+                return existing
+                    .reservation_id
+                    .map(IdempotentOutcome::Replayed)
+                    .ok_or(ApplicationError::IdempotencyKeyInProgress);
+            }
+            IdempotencyBegin::Started => {}
+        }
+
+        // 予約IDは予約の保存と同じトランザクションで記録される
+        match 受付(Some(record.確保())).await {
+            Ok(予約id) => Ok(IdempotentOutcome::Created(予約id)),
+            Err(e) => {
+                // 失敗した結果は保存せず、同じキーでの再試行を許可する
+                if let Err(abandon_error) = store.abandon(&record).await {
+                    tracing::error!("Failed to abandon idempotency key: {:?}", abandon_error);
+                }
+                Err(e)
+            }
+        }
     }
 
     /// プレゼント予約を受け付ける (MVP: 発送代行を想定)
+    ///
+    /// 料金はサーバーで計算し、依頼者が確認した見積金額と一致しない場合は受け付けない。
+    /// 届け先は依頼者が登録したものに限る。アカウントが停止されている依頼者の予約は受け付けない。
+    /// 冪等性キー を指定した場合は、予約と同じトランザクションでキーに予約IDを記録する。
     #[allow(clippy::too_many_arguments)]
     pub async fn プレゼント予約受付(
         &self,
         依頼者id: ユーザーID,
         届け先id: 届け先ID,
         記念日: 記念日,
-        メッセージ内容: Option<String>,
+        メッセージ内容: Option<メッセージ内容>,
         ラッピング: ラッピング種類,
-        配送希望日時: Option<DateTime<Tz>>,
-        商品idリスト: HashSet<商品ID>, // MVPでは手配品情報かもしれないが、一旦IDリストで
-        支払いid: 支払いID,            // 支払い処理はMVP以降で実装想定
-        合計金額: 金額,
+        配送希望: Option<配送希望>,
+        プレゼント内容: プレゼント内容,
+        支払いid: 支払いID, // 支払い処理はMVP以降で実装想定
+        見積金額: 金額,
+        冪等性キー: Option<冪等性キーの確保>,
     ) -> AppResult<予約ID> {
-        // 1. ドメインのファクトリ関数を呼び出して予約を作成
-        let reservation_result = domain::予約を受け付ける(
-            依頼者id,
-            届け先id,
-            記念日,
-            メッセージ内容,
-            ラッピング,
-            配送希望日時,
-            商品idリスト,
-            支払いid,
-            合計金額,
-        );
+        // 1. 依頼者が予約できるか、届け先が依頼者のものか確認する
+        self.依頼者を確認する(&依頼者id).await?;
+        self.届け先を確認する(&依頼者id, &届け先id).await?;
+
+        // 2. 手配する商品が販売中か確認し、その価格から料金を計算する
+        let 商品価格 = self.手配商品を確認する(&プレゼント内容).await?;
+        let 料金内訳 = self
+            .料金計算
+            .計算する(&プレゼント内容, ラッピング, &商品価格)?;
+
+        // 3. プレゼント内容に応じたドメインのファクトリ関数を呼び出して予約を作成
+        let 受付日時 = self.現在日時();
+        let reservation_result = match プレゼント内容 {
+            プレゼント内容::発送代行 {
+                情報,
+                倉庫到着予定日,
+            } => domain::発送代行予約を受け付ける(
+                依頼者id,
+                届け先id,
+                記念日,
+                メッセージ内容,
+                ラッピング,
+                配送希望,
+                情報,
+                倉庫到着予定日,
+                支払いid,
+                料金内訳,
+                受付日時,
+                self.発送リードタイム,
+            ),
+            プレゼント内容::手配 { 商品idリスト } => {
+                domain::手配予約を受け付ける(
+                    依頼者id,
+                    届け先id,
+                    記念日,
+                    メッセージ内容,
+                    ラッピング,
+                    配送希望,
+                    商品idリスト,
+                    支払いid,
+                    料金内訳,
+                    受付日時,
+                    self.発送リードタイム,
+                )
+            }
+        };
 
-        // 2. 結果を検証し、リポジトリで永続化
-        let received_reservation = reservation_result.map_err(ApplicationError::from)?;
+        // 4. 結果と見積金額を検証し、リポジトリで永続化
+        let (received_reservation, イベント) =
+            reservation_result.map_err(ApplicationError::from)?;
+        received_reservation
+            .base
+            .料金内訳
+            .見積と照合する(見積金額)?;
 
-        // ↓↓↓ await と map_err の順序変更 ↓↓↓
+        // 手配商品の在庫は予約の保存と同じトランザクションで引き当てる
         let reservation_id = received_reservation.base.id;
         let reservation_state =
             プレゼント予約状態::予約受付済み(received_reservation);
-        self.reservation_repo
-            .save(&reservation_state)
-            .await // await を追加
-            .map_err(|e| ApplicationError::Repository(e.to_string()))?; // Repository エラーをラップ
+        match &冪等性キー {
+            Some(キー) => {
+                self.reservation_repo
+                    .save_accepted_with_idempotency_key(&reservation_state, キー)
+                    .await
+            }
+            None => {
+                self.reservation_repo
+                    .save_with_stock_movement(&reservation_state, 在庫移動::引当)
+                    .await
+            }
+        }
+        .map_err(在庫移動のエラー)?;
+        self.イベントを発行する(イベント).await;
         Ok(reservation_id)
     }
 
-    /// 指定されたIDの予約詳細を取得する
+    /// 依頼者がアカウントを登録済みで、停止されていないことを確認する
+    ///
+    /// 未登録の依頼者は受け付けない (停止中のアカウントが別の ID で予約するのを防ぐ)。
+    async fn 依頼者を確認する(&self, 依頼者id: &ユーザーID) -> AppResult<()> {
+        let Some(repo) = &self.account_repo else {
+            return Ok(());
+        };
+        let アカウント = repo
+            .find_by_id(依頼者id)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))?;
+        match アカウント {
+            Some(アカウント) => Ok(アカウント.予約できるか確認する()?),
+            None => Err(DomainError::アカウント未登録(*依頼者id).into()),
+        }
+    }
+
+    /// 届け先が存在し、依頼者が登録したものであることを確認する
+    ///
+    /// 他のユーザーの届け先は存在しないものとして扱う (届け先の有無を推測させない)。
+    async fn 届け先を確認する(
+        &self,
+        依頼者id: &ユーザーID,
+        届け先id: &届け先ID,
+    ) -> AppResult<()> {
+        let 届け先 = match &self.recipient_repo {
+            Some(repo) => repo
+                .find_by_id(届け先id)
+                .await
+                .map_err(|e| ApplicationError::Repository(e.to_string()))?,
+            None => None,
+        };
+        match 届け先 {
+            Some(届け先) if 届け先.所有者か(依頼者id) => Ok(()),
+            _ => Err(DomainError::届け先NotFound(*届け先id).into()),
+        }
+    }
+
+    /// 手配する商品をカタログで確認し、価格を集める
+    ///
+    /// カタログにない商品は 商品NotFound、販売を終了した商品は 商品販売終了 とする。
+    async fn 手配商品を確認する(
+        &self,
+        プレゼント内容: &プレゼント内容,
+    ) -> AppResult<HashMap<商品ID, 商品価格>> {
+        let mut 商品価格 = HashMap::new();
+        for 商品id in プレゼント内容.手配商品リスト() {
+            let 商品 = match &self.product_repo {
+                Some(repo) => repo
+                    .find_by_id(&商品id)
+                    .await
+                    .map_err(|e| ApplicationError::Repository(e.to_string()))?,
+                None => None,
+            };
+            let 商品 = 商品.ok_or(DomainError::商品NotFound(商品id))?;
+            商品価格.insert(商品id, 商品.手配できるか確認する()?);
+        }
+        Ok(商品価格)
+    }
+
+    /// 指定されたIDの予約詳細を取得する (他の依頼者の予約は見つからないものとして扱う)
     pub async fn 予約詳細取得(
         &self,
         予約id: &予約ID,
+        依頼者id: &ユーザーID,
     ) -> AppResult<Option<プレゼント予約状態>> {
         // ↓↓↓ await と map_err の順序変更 ↓↓↓
-        self.reservation_repo
+        Ok(self
+            .reservation_repo
             .find_by_id(予約id)
             .await // await を追加
-            .map_err(|e| ApplicationError::Repository(e.to_string())) // Repository エラーをラップ
+            .map_err(|e| ApplicationError::Repository(e.to_string()))? // Repository エラーをラップ
+            .filter(|state| state.base().依頼者id == *依頼者id))
+    }
+
+    /// 依頼者の予約一覧を 記念日 → 作成日時 の順にページ単位で取得する
+    pub async fn 依頼者の予約一覧取得(
+        &self,
+        依頼者id: &ユーザーID,
+        cursor: Option<予約一覧カーソル>,
+        limit: usize,
+    ) -> AppResult<予約一覧ページ> {
+        validate_limit(limit)?;
+        self.reservation_repo
+            .find_by_requester(依頼者id, cursor, limit)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))
+    }
+
+    /// (管理者) 条件に一致する予約を検索する
+    pub async fn 予約検索(
+        &self,
+        条件: &予約検索条件,
+        offset: usize,
+        limit: usize,
+    ) -> AppResult<予約検索結果> {
+        validate_limit(limit)?;
+        if let (Some(from), Some(to)) = (条件.記念日_開始, 条件.記念日_終了) {
+            if from > to {
+                return Err(ApplicationError::InvalidInput {
+                    field: "anniversary_from".to_string(),
+                    reason: "記念日の開始日が終了日より後になっています".to_string(),
+                });
+            }
+        }
+        self.reservation_repo
+            .search(条件, offset, limit)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))
+    }
+
+    /// 依頼者が予約内容 (メッセージ・ラッピング・配送希望) を変更する
+    ///
+    /// 変更できるのは予約受付済みの予約のみ。他の依頼者の予約は見つからないものとして扱う。
+    pub async fn 予約内容を変更する(
+        &self,
+        予約id: &予約ID,
+        依頼者id: &ユーザーID,
+        変更: 予約変更内容,
+    ) -> AppResult<プレゼント予約状態> {
+        let current_state = self
+            .reservation_repo
+            .find_by_id(予約id)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))?
+            .filter(|state| state.base().依頼者id == *依頼者id)
+            .ok_or(ApplicationError::Domain(DomainError::予約NotFound(
+                *予約id,
+            )))?;
+
+        match current_state {
+            プレゼント予約状態::予約受付済み(received_reservation) => {
+                let (changed_reservation, イベント) = received_reservation
+                    .予約内容を変更する(
+                        変更,
+                        self.現在日時(),
+                        self.発送リードタイム,
+                        &self.料金計算,
+                    )
+                    .map_err(ApplicationError::from)?;
+
+                let new_state =
+                    プレゼント予約状態::予約受付済み(changed_reservation);
+                self.reservation_repo
+                    .save(&new_state)
+                    .await
+                    .map_err(保存のエラー)?;
+                self.イベントを発行する(イベント).await;
+                Ok(new_state)
+            }
+            // 発送準備が始まった予約は変更できない
+            _ => Err(ApplicationError::Domain(
+                DomainError::不正な状態遷移 {
+                    current_state_type: format!("{:?}", current_state),
+                },
+            )),
+        }
     }
 
     /// 予約を発送準備中にする
@@ -96,7 +608,7 @@ impl プレゼント予約サービス {
         &self,
         予約id: &予約ID,
         梱包担当者id: ユーザーID,
-    ) -> AppResult<()> {
+    ) -> AppResult<プレゼント予約状態> {
         // 1. 予約をリポジトリから取得
         let current_state = self
             .reservation_repo
@@ -111,8 +623,8 @@ impl プレゼント予約サービス {
         match current_state {
             プレゼント予約状態::予約受付済み(received_reservation) => {
                 // ドメインオブジェクトのメソッドを呼び出して状態遷移
-                let preparing_reservation = received_reservation
-                    .発送準備を開始する(梱包担当者id)
+                let (preparing_reservation, イベント) = received_reservation
+                    .発送準備を開始する(梱包担当者id, self.現在日時())
                     .map_err(ApplicationError::from)?; // DomainErrorをラップ
 
                 // 3. 新しい状態をリポジトリに保存
@@ -120,8 +632,9 @@ impl プレゼント予約サービス {
                 self.reservation_repo
                     .save(&new_state)
                     .await // await を追加
-                    .map_err(|e| ApplicationError::Repository(e.to_string()))?; // Repository エラーをラップ
-                Ok(()) // 成功時は Ok(()) を返す
+                    .map_err(保存のエラー)?;
+                self.イベントを発行する(イベント).await;
+                Ok(new_state) // 成功時は遷移後の状態を返す
             }
             // 他の状態からの遷移は不正とする
             _ => Err(ApplicationError::Domain(
@@ -136,8 +649,9 @@ impl プレゼント予約サービス {
     pub async fn 発送を完了する(
         &self,
         予約id: &予約ID,
-        配送伝票番号: String,
-    ) -> AppResult<()> {
+        配送伝票番号: 配送伝票番号,
+        実行者: Option<ユーザーID>,
+    ) -> AppResult<プレゼント予約状態> {
         // 1. 予約をリポジトリから取得
         let current_state = self
             .reservation_repo
@@ -152,17 +666,18 @@ impl プレゼント予約サービス {
         match current_state {
             プレゼント予約状態::発送準備中(preparing_reservation) => {
                 // ドメインオブジェクトのメソッドを呼び出して状態遷移
-                let shipped_reservation = preparing_reservation
-                    .発送を完了する(配送伝票番号)
+                let (shipped_reservation, イベント) = preparing_reservation
+                    .発送を完了する(配送伝票番号, 実行者, self.現在日時())
                     .map_err(ApplicationError::from)?; // DomainErrorをラップ
 
-                // 3. 新しい状態をリポジトリに保存
+                // 3. 新しい状態をリポジトリに保存し、引き当てた在庫を出庫する
                 let new_state = プレゼント予約状態::発送済み(shipped_reservation);
                 self.reservation_repo
-                    .save(&new_state)
-                    .await // await を追加
-                    .map_err(|e| ApplicationError::Repository(e.to_string()))?; // Repository エラーをラップ
-                Ok(()) // 成功時は Ok(()) を返す
+                    .save_with_stock_movement(&new_state, 在庫移動::出庫)
+                    .await
+                    .map_err(在庫移動のエラー)?;
+                self.イベントを発行する(イベント).await;
+                Ok(new_state) // 成功時は遷移後の状態を返す
             }
             // 他の状態からの遷移は不正とする
             _ => Err(ApplicationError::Domain(
@@ -173,13 +688,49 @@ impl プレゼント予約サービス {
         }
     }
 
-    /// 予約をキャンセルする
+    /// 今キャンセルした場合の可否とキャンセル料を返す (予約は変更しない)
+    pub async fn キャンセルを試算する(
+        &self,
+        予約id: &予約ID,
+    ) -> AppResult<キャンセル判定> {
+        let current_state = self
+            .reservation_repo
+            .find_by_id(予約id)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))?
+            .ok_or(ApplicationError::Domain(DomainError::予約NotFound(
+                *予約id,
+            )))?;
+        Ok(current_state.キャンセルを判定する(&self.キャンセルポリシー, self.現在日時()))
+    }
+
+    /// 依頼者が自分の予約を今キャンセルした場合の可否とキャンセル料を返す (予約は変更しない)
+    ///
+    /// 他の依頼者の予約は見つからないものとして扱う。
+    pub async fn 依頼者のキャンセルを試算する(
+        &self,
+        予約id: &予約ID,
+        依頼者id: &ユーザーID,
+    ) -> AppResult<キャンセル判定> {
+        let current_state = self
+            .reservation_repo
+            .find_by_id(予約id)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))?
+            .filter(|state| state.base().依頼者id == *依頼者id)
+            .ok_or(ApplicationError::Domain(DomainError::予約NotFound(
+                *予約id,
+            )))?;
+        Ok(current_state.キャンセルを判定する(&self.キャンセルポリシー, self.現在日時()))
+    }
+
+    /// 予約をキャンセルする (キャンセル日時は現在日時、キャンセル料はキャンセルポリシーで決まる)
     pub async fn 予約をキャンセルする(
         &self,
         予約id: &予約ID,
         理由: Option<String>,
-        日時: Option<DateTime<Tz>>,
-    ) -> AppResult<()> {
+        実行者: Option<ユーザーID>,
+    ) -> AppResult<プレゼント予約状態> {
         // 1. 予約をリポジトリから取得
         let current_state = self
             .reservation_repo
@@ -191,13 +742,17 @@ impl プレゼント予約サービス {
             )))?; // ok_or_else を ok_or に修正
 
         // 2. 現在の状態に応じてキャンセル処理を実行
+        let 実行日時 = self.現在日時();
         let cancelled_reservation_result = match current_state {
             プレゼント予約状態::予約受付済み(received) => received
-                .予約をキャンセルする(理由, 日時)
+                .予約をキャンセルする(理由, &self.キャンセルポリシー, 実行者, 実行日時)
                 .map_err(ApplicationError::from), // DomainError -> ApplicationError
             プレゼント予約状態::発送準備中(preparing) => preparing
-                .予約をキャンセルする(理由, 日時)
+                .予約をキャンセルする(理由, &self.キャンセルポリシー, 実行者, 実行日時)
                 .map_err(ApplicationError::from), // DomainError -> ApplicationError
+            プレゼント予約状態::返送済み(returned) => returned
+                .予約をキャンセルする(理由, &self.キャンセルポリシー, 実行者, 実行日時)
+                .map_err(ApplicationError::from),
             // 他の状態からのキャンセルは ApplicationError::Domain を返す
             _ => Err(ApplicationError::Domain(
                 DomainError::不正な状態遷移 {
@@ -208,14 +763,16 @@ impl プレゼント予約サービス {
 
         // 3. ドメイン処理が成功した場合、新しい状態を保存
         match cancelled_reservation_result {
-            Ok(cancelled_reservation) => {
+            Ok((cancelled_reservation, イベント)) => {
+                // 発送前の予約に引き当てた在庫は戻す (返送済みの予約は発送時に出庫済み)
                 let new_state =
                     プレゼント予約状態::キャンセル済み(cancelled_reservation);
                 self.reservation_repo
-                    .save(&new_state)
+                    .save_with_stock_movement(&new_state, 在庫移動::引当解除)
                     .await
-                    .map_err(|e| ApplicationError::Repository(e.to_string()))?;
-                Ok(())
+                    .map_err(在庫移動のエラー)?;
+                self.イベントを発行する(イベント).await;
+                Ok(new_state)
             }
             Err(e) => Err(e), // エラーはそのまま返す (型は ApplicationError になっているはず)
         }
@@ -226,7 +783,8 @@ impl プレゼント予約サービス {
         &self,
         予約id: &予約ID,
         記録日時: DateTime<Tz>,
-    ) -> AppResult<()> {
+        実行者: Option<ユーザーID>,
+    ) -> AppResult<プレゼント予約状態> {
         // 1. 予約をリポジトリから取得
         let current_state = self
             .reservation_repo
@@ -241,8 +799,8 @@ impl プレゼント予約サービス {
         match current_state {
             プレゼント予約状態::発送済み(shipped_reservation) => {
                 // ドメインオブジェクトのメソッドを呼び出して状態遷移
-                let delivered_reservation = shipped_reservation
-                    .配送完了を記録する(記録日時)
+                let (delivered_reservation, イベント) = shipped_reservation
+                    .配送完了を記録する(記録日時, 実行者)
                     .map_err(ApplicationError::from)?; // DomainErrorをラップ
 
                 // 3. 新しい状態をリポジトリに保存
@@ -250,8 +808,9 @@ impl プレゼント予約サービス {
                 self.reservation_repo
                     .save(&new_state)
                     .await // await を追加
-                    .map_err(|e| ApplicationError::Repository(e.to_string()))?; // Repository エラーをラップ
-                Ok(()) // 成功時は Ok(()) を返す
+                    .map_err(保存のエラー)?;
+                self.イベントを発行する(イベント).await;
+                Ok(new_state) // 成功時は遷移後の状態を返す
             }
             // 他の状態からの遷移は不正とする
             _ => Err(ApplicationError::Domain(
@@ -262,8 +821,173 @@ impl プレゼント予約サービス {
         }
     }
 
+    /// 発送済みの予約の配送失敗 (受取拒否・宛先不明など) を記録する
+    pub async fn 配送失敗を記録する(
+        &self,
+        予約id: &予約ID,
+        理由: 配送失敗理由,
+        記録日時: DateTime<Tz>,
+        実行者: Option<ユーザーID>,
+    ) -> AppResult<プレゼント予約状態> {
+        let current_state = self.予約を取得する(予約id).await?;
+        match current_state {
+            プレゼント予約状態::発送済み(shipped_reservation) => {
+                let (failed_reservation, イベント) = shipped_reservation
+                    .配送失敗を記録する(理由, 記録日時, 実行者)
+                    .map_err(ApplicationError::from)?;
+                self.遷移後の状態を保存する(
+                    プレゼント予約状態::配送失敗(failed_reservation),
+                    イベント,
+                )
+                .await
+            }
+            _ => Err(ApplicationError::Domain(
+                DomainError::不正な状態遷移 {
+                    current_state_type: format!("{:?}", current_state),
+                },
+            )),
+        }
+    }
+
+    /// 配送に失敗した荷物が倉庫に戻ったことを記録する
+    pub async fn 返送を記録する(
+        &self,
+        予約id: &予約ID,
+        実行者: Option<ユーザーID>,
+    ) -> AppResult<プレゼント予約状態> {
+        let current_state = self.予約を取得する(予約id).await?;
+        match current_state {
+            プレゼント予約状態::配送失敗(failed_reservation) => {
+                let (returned_reservation, イベント) = failed_reservation
+                    .返送を記録する(self.現在日時(), 実行者)
+                    .map_err(ApplicationError::from)?;
+                self.遷移後の状態を保存する(
+                    プレゼント予約状態::返送済み(returned_reservation),
+                    イベント,
+                )
+                .await
+            }
+            _ => Err(ApplicationError::Domain(
+                DomainError::不正な状態遷移 {
+                    current_state_type: format!("{:?}", current_state),
+                },
+            )),
+        }
+    }
+
+    /// 返送済みの予約を新しい配送伝票番号で発送し直す
+    pub async fn 再発送する(
+        &self,
+        予約id: &予約ID,
+        配送伝票番号: 配送伝票番号,
+        実行者: Option<ユーザーID>,
+    ) -> AppResult<プレゼント予約状態> {
+        let current_state = self.予約を取得する(予約id).await?;
+        match current_state {
+            プレゼント予約状態::返送済み(returned_reservation) => {
+                let (shipped_reservation, イベント) = returned_reservation
+                    .再発送する(配送伝票番号, 実行者, self.現在日時())
+                    .map_err(ApplicationError::from)?;
+                self.遷移後の状態を保存する(
+                    プレゼント予約状態::発送済み(shipped_reservation),
+                    イベント,
+                )
+                .await
+            }
+            _ => Err(ApplicationError::Domain(
+                DomainError::不正な状態遷移 {
+                    current_state_type: format!("{:?}", current_state),
+                },
+            )),
+        }
+    }
+
     // 他のユースケースメソッド (発送準備開始、発送完了など) もここに追加していく
 
+    /// 予約をリポジトリから取得する (存在しない場合は 予約NotFound)
+    async fn 予約を取得する(
+        &self,
+        予約id: &予約ID,
+    ) -> AppResult<プレゼント予約状態> {
+        self.reservation_repo
+            .find_by_id(予約id)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))?
+            .ok_or(ApplicationError::Domain(DomainError::予約NotFound(
+                *予約id,
+            )))
+    }
+
+    /// 遷移後の状態を保存し、発生したイベントを発行する
+    async fn 遷移後の状態を保存する(
+        &self,
+        new_state: プレゼント予約状態,
+        イベント: Vec<ドメインイベント>,
+    ) -> AppResult<プレゼント予約状態> {
+        self.reservation_repo
+            .save(&new_state)
+            .await
+            .map_err(保存のエラー)?;
+        self.イベントを発行する(イベント).await;
+        Ok(new_state)
+    }
+
+    /// 業務上の日時 (日本時間) での現在時刻
+    fn 現在日時(&self) -> DateTime<Tz> {
+        self.clock.now().with_timezone(&Tokyo)
+    }
+
+    /// 保存済みの状態遷移で発生したイベントを発行する
+    ///
+    /// 状態はすでに保存されているため、発行に失敗しても操作自体は失敗させずにログに残す。
+    async fn イベントを発行する(&self, イベント: Vec<ドメインイベント>) {
+        let Some(publisher) = &self.event_publisher else {
+            return;
+        };
+        if let Err(e) = publisher.発行する(&イベント).await {
+            tracing::error!("Failed to publish domain events {:?}: {:?}", イベント, e);
+        }
+    }
+
+    /// サービスの readiness を判定する (依存コンポーネントごとの状態を返す)
+    pub async fn check_readiness(&self) -> ReadinessReport {
+        let database = match tokio::time::timeout(
+            READINESS_CHECK_TIMEOUT,
+            self.reservation_repo.database_status(),
+        )
+        .await
+        {
+            Ok(Ok(details)) => DatabaseReadiness {
+                status: ComponentStatus::Up,
+                details: Some(details),
+                error: None,
+            },
+            Ok(Err(e)) => {
+                tracing::warn!("Database readiness check failed: {:?}", e);
+                DatabaseReadiness {
+                    status: ComponentStatus::Down,
+                    details: None,
+                    error: Some(ReadinessFailure::Unavailable),
+                }
+            }
+            Err(_) => {
+                tracing::warn!(
+                    "Database readiness check timed out after {}ms",
+                    READINESS_CHECK_TIMEOUT.as_millis()
+                );
+                DatabaseReadiness {
+                    status: ComponentStatus::Down,
+                    details: None,
+                    error: Some(ReadinessFailure::Timeout),
+                }
+            }
+        };
+        ReadinessReport {
+            status: database.status,
+            database,
+        }
+    }
+
     /// サービスのヘルスチェック (DB接続確認など)
     pub async fn check_health(&self) -> Result<(), ApplicationError> {
         self.reservation_repo
@@ -281,16 +1005,436 @@ impl プレゼント予約サービス {
     }
 }
 
-// --- Application Tests ---
+/// 在庫を移動する保存のエラーを変換する
+/// 予約の保存で返されたエラーを変換する
+fn 保存のエラー(e: DomainError) -> ApplicationError {
+    match e {
+        // 読み込んだ後に他のリクエストが予約の状態を変えていた (リポジトリの障害ではない)
+        DomainError::不正な状態遷移 { .. } => ApplicationError::Domain(e),
+        _ => ApplicationError::Repository(e.to_string()),
+    }
+}
+
+fn 在庫移動のエラー(e: DomainError) -> ApplicationError {
+    match e {
+        // 在庫不足 はリポジトリの障害ではなく業務上のエラー
+        DomainError::在庫不足(_) => ApplicationError::Domain(e),
+        // 確保し直した別のリクエストが処理している
+        DomainError::冪等性キー確保切れ => ApplicationError::IdempotencyKeyInProgress,
+        _ => 保存のエラー(e),
+    }
+}
+
+/// 一覧取得の件数を検証する
+fn validate_limit(limit: usize) -> AppResult<()> {
+    if limit == 0 || limit > 一覧取得の最大件数 {
+        return Err(ApplicationError::InvalidInput {
+            field: "limit".to_string(),
+            reason: format!("1 以上 {} 以下で指定してください", 一覧取得の最大件数),
+        });
+    }
+    Ok(())
+}
+
+// --- 届け先 ---
+
+/// 届け先を削除できない (進行中の予約で使われている) 予約ステータス
+const 届け先を使う予約ステータス: [予約ステータス; 5] = [
+    予約ステータス::予約受付済み,
+    予約ステータス::発送準備中,
+    予約ステータス::発送済み,
+    予約ステータス::配送失敗,
+    予約ステータス::返送済み,
+];
+
+/// 届け先の登録・変更・削除のユースケースを提供するサービス
+///
+/// 届け先は所有者だけが操作でき、他のユーザーの届け先は存在しないものとして扱う。
+pub struct 届け先サービス {
+    recipient_repo: Arc<dyn 届け先Repository>,
+    reservation_repo: Arc<dyn プレゼント予約Repository>,
+    postal_codes: Option<Arc<dyn 郵便番号辞書>>,
+}
+
+impl 届け先サービス {
+    /// 新しい届け先サービスを生成する (予約リポジトリは削除時の利用状況の確認に使う)
+    pub fn new(
+        recipient_repo: Arc<dyn 届け先Repository>,
+        reservation_repo: Arc<dyn プレゼント予約Repository>,
+    ) -> Self {
+        Self {
+            recipient_repo,
+            reservation_repo,
+            postal_codes: None,
+        }
+    }
+
+    /// 住所の検索と照合に使う郵便番号辞書を設定する (未設定の場合、住所は照合しない)
+    pub fn with_postal_codes(mut self, postal_codes: Arc<dyn 郵便番号辞書>) -> Self {
+        self.postal_codes = Some(postal_codes);
+        self
+    }
+
+    /// 郵便番号から住所の候補を検索する (住所の自動入力用)
+    pub async fn 住所候補を検索する(
+        &self,
+        番号: &郵便番号,
+    ) -> AppResult<Vec<住所候補>> {
+        let Some(postal_codes) = &self.postal_codes else {
+            return Err(ApplicationError::Repository(
+                "郵便番号データが読み込まれていません".to_string(),
+            ));
+        };
+        postal_codes
+            .住所候補を取得する(番号)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))
+    }
+
+    /// 届け先の住所を郵便番号の住所候補と照合する
+    async fn 住所を照合する(&self, 内容: &届け先内容) -> AppResult<()> {
+        let Some(postal_codes) = &self.postal_codes else {
+            return Ok(());
+        };
+        let 候補 = postal_codes
+            .住所候補を取得する(&内容.郵便番号)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))?;
+        内容.住所候補と照合する(&候補)?;
+        Ok(())
+    }
+
+    /// 届け先を登録する (住所は郵便番号と照合する)
+    pub async fn 届け先を登録する(
+        &self,
+        所有者id: ユーザーID,
+        内容: 届け先内容,
+    ) -> AppResult<届け先> {
+        self.住所を照合する(&内容).await?;
+        let 届け先 = 届け先::登録する(所有者id, 内容);
+        self.recipient_repo
+            .save(&届け先)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))?;
+        Ok(届け先)
+    }
+
+    /// 所有者の届け先を登録順に取得する
+    pub async fn 届け先一覧取得(
+        &self, 所有者id: &ユーザーID
+    ) -> AppResult<Vec<届け先>> {
+        self.recipient_repo
+            .find_by_owner(所有者id)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))
+    }
+
+    /// 所有者の届け先を取得する (存在しない場合は 届け先NotFound)
+    pub async fn 届け先詳細取得(
+        &self,
+        所有者id: &ユーザーID,
+        届け先id: &届け先ID,
+    ) -> AppResult<届け先> {
+        let 届け先 = self
+            .recipient_repo
+            .find_by_id(届け先id)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))?;
+        match 届け先 {
+            Some(届け先) if 届け先.所有者か(所有者id) => Ok(届け先),
+            _ => Err(DomainError::届け先NotFound(*届け先id).into()),
+        }
+    }
+
+    /// 届け先の内容を変更する (住所は郵便番号と照合する)
+    ///
+    /// 予約は届け先IDを参照するため、変更後の内容はその届け先を使う予約にも反映される。
+    pub async fn 届け先を変更する(
+        &self,
+        所有者id: &ユーザーID,
+        届け先id: &届け先ID,
+        内容: 届け先内容,
+    ) -> AppResult<届け先> {
+        let mut 届け先 = self.届け先詳細取得(所有者id, 届け先id).await?;
+        self.住所を照合する(&内容).await?;
+        届け先.内容を変更する(内容);
+        self.recipient_repo
+            .save(&届け先)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))?;
+        Ok(届け先)
+    }
+
+    /// 届け先を削除する (進行中の予約で使われている場合は 届け先使用中)
+    pub async fn 届け先を削除する(
+        &self,
+        所有者id: &ユーザーID,
+        届け先id: &届け先ID,
+    ) -> AppResult<()> {
+        self.届け先詳細取得(所有者id, 届け先id).await?;
+        let 条件 = 予約検索条件 {
+            ステータス: 届け先を使う予約ステータス.to_vec(),
+            届け先id: Some(*届け先id),
+            ..Default::default()
+        };
+        let 使用中の予約 = self
+            .reservation_repo
+            .search(&条件, 0, 1)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))?;
+        if 使用中の予約.総件数 > 0 {
+            return Err(DomainError::届け先使用中(*届け先id).into());
+        }
+        self.recipient_repo
+            .delete(届け先id)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))
+    }
+}
+
+/// ユーザーアカウントの登録・プロフィール変更・停止のユースケースを提供するサービス
+pub struct ユーザーアカウントサービス {
+    account_repo: Arc<dyn ユーザーアカウントRepository>,
+}
+
+impl ユーザーアカウントサービス {
+    pub fn new(account_repo: Arc<dyn ユーザーアカウントRepository>) -> Self {
+        Self { account_repo }
+    }
+
+    /// ユーザーIDに認証サブジェクトを結び付けてアカウントを登録する
+    ///
+    /// 同じユーザーIDのアカウントがある場合は ユーザーアカウント登録済み、
+    /// 認証サブジェクトが別のアカウントで使われている場合は 認証サブジェクト使用中 とする。
+    pub async fn アカウントを登録する(
+        &self,
+        id: ユーザーID,
+        サブジェクト: 認証サブジェクト,
+        プロフィール: プロフィール,
+    ) -> AppResult<ユーザーアカウント> {
+        if self.アカウントを探す(&id).await?.is_some() {
+            return Err(DomainError::ユーザーアカウント登録済み(id).into());
+        }
+        let 使用中 = self
+            .account_repo
+            .find_by_auth_subject(&サブジェクト)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))?;
+        if 使用中.is_some() {
+            return Err(DomainError::認証サブジェクト使用中.into());
+        }
+        let アカウント = ユーザーアカウント::登録する(id, サブジェクト, プロフィール);
+        self.account_repo
+            .insert(&アカウント)
+            .await
+            .map_err(アカウント登録のエラー)?;
+        Ok(アカウント)
+    }
+
+    /// アカウントを取得する (存在しない場合は ユーザーアカウントNotFound)
+    pub async fn アカウント取得(
+        &self,
+        id: &ユーザーID,
+    ) -> AppResult<ユーザーアカウント> {
+        self.アカウントを探す(id)
+            .await?
+            .ok_or_else(|| DomainError::ユーザーアカウントNotFound(*id).into())
+    }
+
+    /// プロフィールを変更する (停止中のアカウントでも変更できる)
+    pub async fn プロフィールを変更する(
+        &self,
+        id: &ユーザーID,
+        プロフィール: プロフィール,
+    ) -> AppResult<ユーザーアカウント> {
+        let mut アカウント = self.アカウント取得(id).await?;
+        アカウント.プロフィールを変更する(プロフィール);
+        self.保存する(&アカウント).await?;
+        Ok(アカウント)
+    }
+
+    /// (管理者) アカウントを停止する
+    pub async fn アカウントを停止する(
+        &self,
+        id: &ユーザーID,
+    ) -> AppResult<ユーザーアカウント> {
+        let mut アカウント = self.アカウント取得(id).await?;
+        アカウント.停止する();
+        self.保存する(&アカウント).await?;
+        Ok(アカウント)
+    }
+
+    /// (管理者) 停止したアカウントを再開する
+    pub async fn アカウントを再開する(
+        &self,
+        id: &ユーザーID,
+    ) -> AppResult<ユーザーアカウント> {
+        let mut アカウント = self.アカウント取得(id).await?;
+        アカウント.再開する();
+        self.保存する(&アカウント).await?;
+        Ok(アカウント)
+    }
+
+    /// 有効な管理者のアカウントであることを確認する
+    ///
+    /// アカウントが登録されていない場合も 管理者権限なし とする。
+    pub async fn 管理者を確認する(&self, id: &ユーザーID) -> AppResult<()> {
+        match self.アカウントを探す(id).await? {
+            Some(アカウント) => Ok(アカウント.管理操作できるか確認する()?),
+            None => Err(DomainError::管理者権限なし(*id).into()),
+        }
+    }
+
+    async fn アカウントを探す(
+        &self,
+        id: &ユーザーID,
+    ) -> AppResult<Option<ユーザーアカウント>> {
+        self.account_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))
+    }
+
+    async fn 保存する(&self, アカウント: &ユーザーアカウント) -> AppResult<()> {
+        self.account_repo
+            .save(アカウント)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))
+    }
+}
+
+/// アカウントを新規保存するエラーを変換する
+fn アカウント登録のエラー(e: DomainError) -> ApplicationError {
+    match e {
+        // 確認の後に同時に登録された場合は一意制約で検出される
+        DomainError::ユーザーアカウント登録済み(_) | DomainError::認証サブジェクト使用中 => {
+            ApplicationError::Domain(e)
+        }
+        _ => ApplicationError::Repository(e.to_string()),
+    }
+}
+
+// --- 商品カタログ ---
+
+/// 商品カタログの検索と (管理者による) 商品・在庫の管理のユースケースを提供するサービス
+pub struct 商品カタログサービス {
+    product_repo: Arc<dyn 商品Repository>,
+    stock_repo: Arc<dyn 商品在庫Repository>,
+}
+
+impl 商品カタログサービス {
+    pub fn new(
+        product_repo: Arc<dyn 商品Repository>,
+        stock_repo: Arc<dyn 商品在庫Repository>,
+    ) -> Self {
+        Self {
+            product_repo,
+            stock_repo,
+        }
+    }
+
+    /// 条件に一致する商品を検索する
+    pub async fn 商品を検索する(
+        &self,
+        条件: &商品検索条件,
+        offset: usize,
+        limit: usize,
+    ) -> AppResult<商品検索結果> {
+        validate_limit(limit)?;
+        if let (Some(min), Some(max)) = (条件.最低価格, 条件.最高価格) {
+            if min > max {
+                return Err(ApplicationError::InvalidInput {
+                    field: "min_price".to_string(),
+                    reason: "最低価格が最高価格より高くなっています".to_string(),
+                });
+            }
+        }
+        self.product_repo
+            .search(条件, offset, limit)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))
+    }
+
+    /// 商品を取得する (存在しない場合は 商品NotFound)
+    pub async fn 商品詳細取得(&self, id: &商品ID) -> AppResult<商品> {
+        self.product_repo
+            .find_by_id(id)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))?
+            .ok_or_else(|| DomainError::商品NotFound(*id).into())
+    }
+
+    /// (管理者) 販売中の商品として登録する
+    pub async fn 商品を登録する(&self, 内容: 商品内容) -> AppResult<商品> {
+        let 商品 = 商品::登録する(内容);
+        self.保存する(&商品).await?;
+        Ok(商品)
+    }
+
+    /// (管理者) 商品の内容と販売状態を変更する
+    ///
+    /// 価格を変更しても、受付済みの予約の料金内訳は受付時の価格のまま変わらない。
+    pub async fn 商品を変更する(
+        &self,
+        id: &商品ID,
+        内容: 商品内容,
+        販売中: bool,
+    ) -> AppResult<商品> {
+        let mut 商品 = self.商品詳細取得(id).await?;
+        商品.内容を変更する(内容);
+        商品.販売状態を変更する(販売中);
+        self.保存する(&商品).await?;
+        Ok(商品)
+    }
+
+    /// (管理者) 商品の在庫を取得する
+    pub async fn 在庫取得(&self, id: &商品ID) -> AppResult<商品在庫> {
+        self.商品詳細取得(id).await?;
+        self.stock_repo
+            .find_by_product_id(id)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))
+    }
+
+    /// (管理者) 棚卸しや入荷に合わせて在庫数を変更する (引当数より少なくはできない)
+    pub async fn 在庫数を変更する(
+        &self,
+        id: &商品ID,
+        在庫数: u32,
+    ) -> AppResult<商品在庫> {
+        self.商品詳細取得(id).await?;
+        self.stock_repo
+            .set_on_hand(id, 在庫数)
+            .await
+            .map_err(|e| match e {
+                DomainError::在庫数不正 { .. } => ApplicationError::Domain(e),
+                _ => ApplicationError::Repository(e.to_string()),
+            })
+    }
+
+    async fn 保存する(&self, 商品: &商品) -> AppResult<()> {
+        self.product_repo
+            .save(商品)
+            .await
+            .map_err(|e| ApplicationError::Repository(e.to_string()))
+    }
+}
+
+// --- Application Tests ---
 #[cfg(test)]
 mod tests {
     use super::*; // 親モジュール(application)の要素を使う
     use crate::domain; // ドメイン層の型やモックを使う
-    use crate::domain::Mockプレゼント予約Repository; // Mock を use
-    use chrono::NaiveDate;
+    use crate::domain::{
+        Mockイベント発行者, Mockプレゼント予約Repository, MockユーザーアカウントRepository,
+        Mock商品Repository, Mock商品在庫Repository, Mock届け先Repository, Mock郵便番号辞書,
+    }; // Mock を use
+    use crate::domain::{イベント情報, 予約ステータス, 配送時間帯};
     use chrono::Utc; // Utc をインポート
+    use chrono::{NaiveDate, TimeZone};
     use chrono_tz::Asia::Tokyo;
     use mockall::predicate::*; // mockall のマッチャーを使う
+    use std::collections::HashSet;
     use std::sync::Arc; // Tokyo をインポート
 
     // --- テスト用のヘルパー関数やデータ ---
@@ -309,8 +1453,106 @@ mod tests {
         }
     }
 
-    fn create_dummy_kingaku() -> 金額 {
-        金額::new(5000).unwrap()
+    /// テスト用の商品 (税込 4,320 円・軽減税率)
+    fn テスト商品(id: 商品ID, 販売中: bool) -> 商品 {
+        商品 {
+            id,
+            内容: 商品内容 {
+                商品名: domain::商品名::new("焼き菓子の詰め合わせ").unwrap(),
+                説明: domain::商品説明::default(),
+                価格: 商品価格 {
+                    税込価格: 金額::new(4320).unwrap(),
+                    税区分: domain::税区分::軽減税率,
+                },
+                画像キー: vec![],
+            },
+            販売中,
+        }
+    }
+
+    /// テスト用の商品カタログ (どの商品も販売中のテスト商品として見つかる)
+    fn テスト用商品カタログ() -> Arc<dyn 商品Repository> {
+        let mut mock_products = Mock商品Repository::new();
+        mock_products
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(テスト商品(*id, true))));
+        Arc::new(mock_products)
+    }
+
+    /// 商品 1 点 (税込 4,320 円) と送料 (800 円 + 税 80 円) にラッピング料 (税込) を加えた見積金額
+    fn テスト見積金額(ラッピング: ラッピング種類) -> 金額 {
+        let ラッピング料 = match ラッピング {
+            ラッピング種類::なし => 0,
+            ラッピング種類::標準 => 330,
+            ラッピング種類::特別 => 880,
+        };
+        金額::new(4320 + 880 + ラッピング料).unwrap()
+    }
+
+    /// テスト用の料金内訳 (送料のみ)
+    fn テスト料金内訳() -> domain::料金内訳 {
+        domain::料金内訳 {
+            明細: vec![domain::料金明細::new(
+                domain::料金項目::送料,
+                800,
+                domain::税区分::標準税率,
+            )],
+        }
+    }
+
+    /// テスト用の配送伝票番号 (ヤマト運輸の有効な番号)
+    fn テスト配送伝票番号() -> domain::配送伝票番号 {
+        domain::配送伝票番号::new(domain::配送業者::ヤマト運輸, "1234-5678-9013").unwrap()
+    }
+
+    /// テスト用の届け先内容
+    fn テスト届け先内容() -> 届け先内容 {
+        届け先内容 {
+            氏名: domain::氏名::new("山田 花子", "ヤマダ ハナコ").unwrap(),
+            郵便番号: domain::郵便番号::new("100-0001").unwrap(),
+            都道府県: domain::都道府県::東京都,
+            住所: domain::住所::new("千代田区千代田1-1").unwrap(),
+            建物名: None,
+            電話番号: domain::電話番号::new("03-1234-5678").unwrap(),
+        }
+    }
+
+    /// テスト用の届け先リポジトリ (どの届け先IDも指定した依頼者が登録したものとして返す)
+    fn テスト用届け先リポジトリ(
+        依頼者id: ユーザーID
+    ) -> Arc<dyn 届け先Repository> {
+        let mut mock_recipients = Mock届け先Repository::new();
+        mock_recipients.expect_find_by_id().returning(move |id| {
+            Ok(Some(届け先 {
+                id: *id,
+                所有者id: 依頼者id,
+                内容: テスト届け先内容(),
+            }))
+        });
+        Arc::new(mock_recipients)
+    }
+
+    /// テスト用のユーザーアカウント (有効な顧客)
+    fn テストアカウント(id: ユーザーID) -> ユーザーアカウント {
+        ユーザーアカウント::登録する(
+            id,
+            認証サブジェクト::new("auth0|test").unwrap(),
+            プロフィール {
+                メールアドレス: domain::メールアドレス::new("hanako@example.com").unwrap(),
+                表示名: domain::表示名::new("はなこ").unwrap(),
+                既定の差出人名: None,
+            },
+        )
+    }
+
+    /// テスト用の受付日時 (create_dummy_kinenbi より十分前の平日)
+    fn テスト受付日時() -> DateTime<Tz> {
+        Tokyo.with_ymd_and_hms(2025, 12, 1, 10, 0, 0).unwrap()
+    }
+
+    /// テスト用の受付日時に固定した時計
+    fn テスト用時計() -> Arc<dyn Clock> {
+        Arc::new(FixedClock(テスト受付日時().with_timezone(&Utc)))
     }
 
     // --- 予約受付ユースケースのテスト ---
@@ -320,49 +1562,57 @@ mod tests {
         // fn -> async fn
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let メッセージ = Some("テストメッセージ".to_string());
+        let 金額 = テスト見積金額(ラッピング種類::標準);
+        let メッセージ = Some(メッセージ内容::new("テストメッセージ").unwrap());
         let ラッピング = ラッピング種類::標準;
-        let 配送日時 = None; // Option<DateTime<Tz>>
+        let 配送日時 = Some(配送希望 {
+            配送日: NaiveDate::from_ymd_opt(2025, 12, 24).unwrap(),
+            時間帯: 配送時間帯::午後2時から4時,
+        });
 
         let mut mock_repo = Mockプレゼント予約Repository::new();
 
         // save が呼ばれることを期待する
         // 引数の検証: 渡される reservation が期待通りか確認
         // 予約IDは内部で生成されるため、他のフィールドが一致するかを withf でチェック
-        let expected_依頼者id = 依頼者id.clone();
-        let expected_届け先id = 届け先id.clone();
+        let expected_依頼者id = 依頼者id;
+        let expected_届け先id = 届け先id;
         let expected_記念日 = 記念日.clone();
         let expected_商品idリスト = 商品idリスト.clone();
-        let expected_支払いid = 支払いid.clone();
-        let expected_金額 = 金額.clone();
+        let expected_支払いid = 支払いid;
+        let expected_金額 = 金額;
         let expected_メッセージ = メッセージ.clone();
-        let expected_ラッピング = ラッピング.clone();
-        let expected_配送日時 = 配送日時.clone();
+        let expected_ラッピング = ラッピング;
+        let expected_配送日時 = 配送日時;
 
         mock_repo
-            .expect_save()
-            .withf(move |state: &プレゼント予約状態| {
-                match state {
-                    プレゼント予約状態::予約受付済み(ref received) => {
-                        // 予約ID 以外の一致を確認
-                        received.base.依頼者id == expected_依頼者id &&
-                        received.base.届け先id == expected_届け先id &&
-                        received.base.記念日 == expected_記念日 &&
-                        received.base.メッセージ内容 == expected_メッセージ &&
-                        received.base.ラッピング == expected_ラッピング &&
-                        received.base.配送希望日時 == expected_配送日時 && // Option<DateTime> の比較
-                        received.base.手配商品リスト == expected_商品idリスト &&
-                        received.base.支払いid == expected_支払いid &&
-                        received.base.合計金額 == expected_金額
+            .expect_save_with_stock_movement()
+            .withf(move |state: &プレゼント予約状態, 移動: &在庫移動| {
+                *移動 == 在庫移動::引当
+                    && match state {
+                        プレゼント予約状態::予約受付済み(ref received) => {
+                            // 予約ID 以外の一致を確認
+                            received.base.依頼者id == expected_依頼者id
+                                && received.base.届け先id == expected_届け先id
+                                && received.base.記念日 == expected_記念日
+                                && received.base.メッセージ内容 == expected_メッセージ
+                                && received.base.ラッピング == expected_ラッピング
+                                && received.base.配送希望 == expected_配送日時
+                                && received.base.プレゼント内容.手配商品リスト()
+                                    == expected_商品idリスト
+                                && received.base.支払いid == expected_支払いid
+                                && received.base.合計金額 == expected_金額
+                        }
+                        _ => false, // 他の状態が来たらテスト失敗
                     }
-                    _ => false, // 他の状態が来たらテスト失敗
-                }
             })
             .times(1) // 1回だけ呼ばれる
-            .returning(|_| Ok(()));
+            .returning(|_, _| Ok(()));
 
-        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo))
+            .with_clock(テスト用時計())
+            .with_recipients(テスト用届け先リポジトリ(依頼者id))
+            .with_products(テスト用商品カタログ());
 
         let result = service
             .プレゼント予約受付(
@@ -372,9 +1622,10 @@ mod tests {
                 メッセージ,
                 ラッピング,
                 配送日時,
-                商品idリスト,
+                プレゼント内容::手配 { 商品idリスト },
                 支払いid,
                 金額,
+                None,
             )
             .await;
 
@@ -388,16 +1639,18 @@ mod tests {
         // fn -> async fn
         let (依頼者id, 届け先id, 支払いid, _) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
+        let 金額 = テスト見積金額(ラッピング種類::なし);
         let メッセージ = None;
         let ラッピング = ラッピング種類::なし;
         let 配送日時 = None;
         let 商品idリスト = HashSet::new(); // 空の商品リスト
 
         let mut mock_repo = Mockプレゼント予約Repository::new();
-        mock_repo.expect_save().times(0); // save は呼ばれないはず
+        mock_repo.expect_save_with_stock_movement().times(0); // save は呼ばれないはず
 
-        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo))
+            .with_clock(テスト用時計())
+            .with_recipients(テスト用届け先リポジトリ(依頼者id));
 
         let result = service
             .プレゼント予約受付(
@@ -407,9 +1660,10 @@ mod tests {
                 メッセージ,
                 ラッピング,
                 配送日時,
-                商品idリスト,
+                プレゼント内容::手配 { 商品idリスト },
                 支払いid,
                 金額,
+                None,
             )
             .await;
 
@@ -429,21 +1683,27 @@ mod tests {
         // fn -> async fn
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let メッセージ = Some("テストメッセージ".to_string());
+        let 金額 = テスト見積金額(ラッピング種類::標準);
+        let メッセージ = Some(メッセージ内容::new("テストメッセージ").unwrap());
         let ラッピング = ラッピング種類::標準;
         let 配送日時 = None;
 
         let mut mock_repo = Mockプレゼント予約Repository::new();
 
         // save が呼ばれるが、エラーを返すように設定
-        mock_repo.expect_save().times(1).returning(|_| {
-            Err(DomainError::必須項目不足 {
-                field: "テストエラー".to_string(),
-            })
-        });
+        mock_repo
+            .expect_save_with_stock_movement()
+            .times(1)
+            .returning(|_, _| {
+                Err(DomainError::必須項目不足 {
+                    field: "テストエラー".to_string(),
+                })
+            });
 
-        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo))
+            .with_clock(テスト用時計())
+            .with_recipients(テスト用届け先リポジトリ(依頼者id))
+            .with_products(テスト用商品カタログ());
 
         let result = service
             .プレゼント予約受付(
@@ -453,9 +1713,10 @@ mod tests {
                 メッセージ,
                 ラッピング,
                 配送日時,
-                商品idリスト,
+                プレゼント内容::手配 { 商品idリスト },
                 支払いid,
                 金額,
+                None,
             )
             .await;
 
@@ -467,101 +1728,1040 @@ mod tests {
         ));
     }
 
-    // --- 予約詳細取得ユースケースのテスト ---
-
-    #[tokio::test] // #[test] -> #[tokio::test]
-    async fn test_予約詳細取得_success_found() {
-        // fn -> async fn
-        let target_id = 予約ID::new();
-        // 適当な予約状態を作成 (ここでは予約受付済みとする)
+    #[tokio::test]
+    async fn test_プレゼント予約受付_fail_out_of_stock() {
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
-        let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received_reservation = domain::予約を受け付ける(
-            依頼者id,
-            届け先id,
-            記念日.clone(),
-            None,
-            ラッピング種類::なし,
-            None,
-            商品idリスト.clone(),
-            支払いid,
-            金額,
-        )
-        .unwrap();
-        // ID を差し替える (本来はリポジトリが永続化時に ID を持つので、 find_by_id は既存のIDで検索するはず)
-        // しかし、テストのために `予約を受け付ける` で生成されたIDを無視し、 target_id を持つ予約状態を作る
-        let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
-            ..received_reservation.base // 他のフィールドはコピー
-        };
-        let expected_state =
-            プレゼント予約状態::予約受付済み(domain::予約受付済みプレゼント予約型 {
-                base: base_with_target_id,
-            });
-        let expected_state_clone = expected_state.clone();
-
+        let 在庫切れ商品id = *商品idリスト.iter().next().unwrap();
         let mut mock_repo = Mockプレゼント予約Repository::new();
         mock_repo
-            .expect_find_by_id()
-            .with(eq(target_id)) // 特定のIDで呼ばれることを期待
+            .expect_save_with_stock_movement()
             .times(1)
-            .returning(move |_| Ok(Some(expected_state_clone.clone())));
-
-        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+            .returning(move |_, _| Err(DomainError::在庫不足(在庫切れ商品id)));
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo))
+            .with_clock(テスト用時計())
+            .with_recipients(テスト用届け先リポジトリ(依頼者id))
+            .with_products(テスト用商品カタログ());
 
-        let result = service.予約詳細取得(&target_id).await;
+        let result = service
+            .プレゼント予約受付(
+                依頼者id,
+                届け先id,
+                create_dummy_kinenbi(),
+                None,
+                ラッピング種類::標準,
+                None,
+                プレゼント内容::手配 { 商品idリスト },
+                支払いid,
+                テスト見積金額(ラッピング種類::標準),
+                None,
+            )
+            .await;
 
-        // 結果が Ok(Some(期待する予約状態)) であることを確認
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), Some(expected_state));
+        // 在庫不足はリポジトリの障害ではなく業務エラーとして返す
+        assert_eq!(
+            result,
+            Err(ApplicationError::Domain(DomainError::在庫不足(
+                在庫切れ商品id
+            )))
+        );
     }
 
-    #[tokio::test] // #[test] -> #[tokio::test]
-    async fn test_予約詳細取得_success_not_found() {
-        // fn -> async fn
-        let target_id = 予約ID::new();
-
+    #[tokio::test]
+    async fn test_プレゼント予約受付_fail_estimate_mismatch() {
+        let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let mut mock_repo = Mockプレゼント予約Repository::new();
-        mock_repo
-            .expect_find_by_id()
-            .with(eq(target_id))
-            .times(1)
-            .returning(|_| Ok(None));
+        mock_repo.expect_save_with_stock_movement().times(0); // 見積金額が違う場合は保存しない
 
-        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo))
+            .with_clock(テスト用時計())
+            .with_recipients(テスト用届け先リポジトリ(依頼者id))
+            .with_products(テスト用商品カタログ());
 
-        let result = service.予約詳細取得(&target_id).await;
+        let result = service
+            .プレゼント予約受付(
+                依頼者id,
+                届け先id,
+                create_dummy_kinenbi(),
+                None,
+                ラッピング種類::特別,
+                None,
+                プレゼント内容::手配 { 商品idリスト },
+                支払いid,
+                テスト見積金額(ラッピング種類::標準), // 古い見積のまま特別ラッピングに変えた
+                None,
+            )
+            .await;
 
-        // 結果が Ok(None) であることを確認
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), None);
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(
+                DomainError::見積金額不一致 {
+                    estimated: 5530,
+                    calculated: 6080,
+                }
+            ))
+        ));
     }
 
-    #[tokio::test] // #[test] -> #[tokio::test]
-    async fn test_予約詳細取得_fail_repo_error() {
-        // fn -> async fn
-        let target_id = 予約ID::new();
-
+    #[tokio::test]
+    async fn test_プレゼント予約受付_fail_without_product_catalog() {
+        let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
+        let 商品id = *商品idリスト.iter().next().unwrap();
         let mut mock_repo = Mockプレゼント予約Repository::new();
-        mock_repo
-            .expect_find_by_id()
-            .with(eq(target_id))
-            .times(1)
-            .returning(move |_| Err(DomainError::予約NotFound(target_id)));
-
-        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+        mock_repo.expect_save_with_stock_movement().times(0);
 
-        let result = service.予約詳細取得(&target_id).await;
+        // 商品カタログが未設定の場合、手配の商品は見つからないものとして扱う
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo))
+            .with_clock(テスト用時計())
+            .with_recipients(テスト用届け先リポジトリ(依頼者id));
 
-        // 結果が Err で、中身が ApplicationError::Repository であることを確認
-        assert!(result.is_err());
-        assert!(matches!(
-            result.err().unwrap(),
+        let result = service
+            .プレゼント予約受付(
+                依頼者id,
+                届け先id,
+                create_dummy_kinenbi(),
+                None,
+                ラッピング種類::なし,
+                None,
+                プレゼント内容::手配 { 商品idリスト },
+                支払いid,
+                テスト見積金額(ラッピング種類::なし),
+                None,
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DomainError::商品NotFound(id))) if id == 商品id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_プレゼント予約受付_fail_discontinued_product() {
+        let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
+        let 商品id = *商品idリスト.iter().next().unwrap();
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo.expect_save_with_stock_movement().times(0);
+        let mut mock_products = Mock商品Repository::new();
+        mock_products
+            .expect_find_by_id()
+            .returning(|id| Ok(Some(テスト商品(*id, false))));
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo))
+            .with_clock(テスト用時計())
+            .with_recipients(テスト用届け先リポジトリ(依頼者id))
+            .with_products(Arc::new(mock_products));
+
+        let result = service
+            .プレゼント予約受付(
+                依頼者id,
+                届け先id,
+                create_dummy_kinenbi(),
+                None,
+                ラッピング種類::なし,
+                None,
+                プレゼント内容::手配 { 商品idリスト },
+                支払いid,
+                テスト見積金額(ラッピング種類::なし),
+                None,
+            )
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DomainError::商品販売終了(id))) if id == 商品id
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_プレゼント予約受付_fail_recipient_of_other_user() {
+        let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo.expect_save_with_stock_movement().times(0);
+
+        // 他のユーザーが登録した届け先は見つからないものとして扱う
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo))
+            .with_clock(テスト用時計())
+            .with_recipients(テスト用届け先リポジトリ(ユーザーID::new()))
+            .with_products(テスト用商品カタログ());
+
+        let result = service
+            .プレゼント予約受付(
+                依頼者id,
+                届け先id,
+                create_dummy_kinenbi(),
+                None,
+                ラッピング種類::なし,
+                None,
+                プレゼント内容::手配 { 商品idリスト },
+                支払いid,
+                テスト見積金額(ラッピング種類::なし),
+                None,
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(ApplicationError::Domain(DomainError::届け先NotFound(
+                届け先id
+            )))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_プレゼント予約受付_fail_suspended_requester() {
+        let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo.expect_save_with_stock_movement().times(0);
+        let mut mock_accounts = MockユーザーアカウントRepository::new();
+        mock_accounts.expect_find_by_id().returning(|id| {
+            let mut アカウント = テストアカウント(*id);
+            アカウント.停止する();
+            Ok(Some(アカウント))
+        });
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo))
+            .with_clock(テスト用時計())
+            .with_recipients(テスト用届け先リポジトリ(依頼者id))
+            .with_accounts(Arc::new(mock_accounts))
+            .with_products(テスト用商品カタログ());
+
+        let result = service
+            .プレゼント予約受付(
+                依頼者id,
+                届け先id,
+                create_dummy_kinenbi(),
+                None,
+                ラッピング種類::なし,
+                None,
+                プレゼント内容::手配 { 商品idリスト },
+                支払いid,
+                テスト見積金額(ラッピング種類::なし),
+                None,
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(ApplicationError::Domain(
+                DomainError::アカウント停止中(依頼者id)
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_プレゼント予約受付_fail_unregistered_requester() {
+        let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo.expect_save_with_stock_movement().times(0);
+        let mut mock_accounts = MockユーザーアカウントRepository::new();
+        mock_accounts.expect_find_by_id().returning(|_| Ok(None));
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo))
+            .with_clock(テスト用時計())
+            .with_recipients(テスト用届け先リポジトリ(依頼者id))
+            .with_accounts(Arc::new(mock_accounts))
+            .with_products(テスト用商品カタログ());
+
+        let result = service
+            .プレゼント予約受付(
+                依頼者id,
+                届け先id,
+                create_dummy_kinenbi(),
+                None,
+                ラッピング種類::なし,
+                None,
+                プレゼント内容::手配 { 商品idリスト },
+                支払いid,
+                テスト見積金額(ラッピング種類::なし),
+                None,
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(ApplicationError::Domain(
+                DomainError::アカウント未登録(依頼者id)
+            ))
+        );
+    }
+
+    // --- ドメインイベント発行のテスト ---
+
+    async fn イベント発行者付きで受け付ける(
+        mock_repo: Mockプレゼント予約Repository,
+        mock_publisher: Mockイベント発行者,
+        依頼者id: ユーザーID,
+    ) -> AppResult<予約ID> {
+        let (_, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo))
+            .with_clock(テスト用時計())
+            .with_recipients(テスト用届け先リポジトリ(依頼者id))
+            .with_products(テスト用商品カタログ())
+            .with_event_publisher(Arc::new(mock_publisher));
+        service
+            .プレゼント予約受付(
+                依頼者id,
+                届け先id,
+                create_dummy_kinenbi(),
+                None,
+                ラッピング種類::なし,
+                None,
+                プレゼント内容::手配 { 商品idリスト },
+                支払いid,
+                テスト見積金額(ラッピング種類::なし),
+                None,
+            )
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_プレゼント予約受付_publishes_event_after_save() {
+        let 依頼者id = ユーザーID::new();
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_save_with_stock_movement()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let mut mock_publisher = Mockイベント発行者::new();
+        mock_publisher
+            .expect_発行する()
+            .withf(move |イベント: &[ドメインイベント]| match イベント {
+                [ドメインイベント::プレゼント予約受け付け完了(
+                    イベント情報 {
+                        実行者, 発生日時,
+                    ..
+                    },
+                )] => *実行者 == Some(依頼者id) && *発生日時 == テスト受付日時(),
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let result =
+            イベント発行者付きで受け付ける(mock_repo, mock_publisher, 依頼者id).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_プレゼント予約受付_does_not_publish_when_save_fails() {
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_save_with_stock_movement()
+            .times(1)
+            .returning(|_, _| {
+                Err(DomainError::必須項目不足 {
+                    field: "テストエラー".to_string(),
+                })
+            });
+        let mut mock_publisher = Mockイベント発行者::new();
+        mock_publisher.expect_発行する().times(0);
+
+        let result = イベント発行者付きで受け付ける(
+            mock_repo,
+            mock_publisher,
+            ユーザーID::new(),
+        )
+        .await;
+
+        assert!(matches!(result, Err(ApplicationError::Repository(_))));
+    }
+
+    #[tokio::test]
+    async fn test_プレゼント予約受付_succeeds_even_if_publishing_fails() {
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_save_with_stock_movement()
+            .times(1)
+            .returning(|_, _| Ok(()));
+        let mut mock_publisher = Mockイベント発行者::new();
+        mock_publisher.expect_発行する().times(1).returning(|_| {
+            Err(InfrastructureError::ConnectionError(
+                "broker down".to_string(),
+            ))
+        });
+
+        let result = イベント発行者付きで受け付ける(
+            mock_repo,
+            mock_publisher,
+            ユーザーID::new(),
+        )
+        .await;
+
+        assert!(result.is_ok());
+    }
+
+    // --- 予約詳細取得ユースケースのテスト ---
+
+    #[tokio::test] // #[test] -> #[tokio::test]
+    async fn test_予約詳細取得_success_found() {
+        // fn -> async fn
+        let target_id = 予約ID::new();
+        // 適当な予約状態を作成 (ここでは予約受付済みとする)
+        let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
+        let 記念日 = create_dummy_kinenbi();
+        let (received_reservation, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
+            記念日.clone(),
+            None,
+            ラッピング種類::なし,
+            None,
+            商品idリスト.clone(),
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+        .unwrap();
+        // ID を差し替える (本来はリポジトリが永続化時に ID を持つので、 find_by_id は既存のIDで検索するはず)
+        // しかし、テストのために `手配予約を受け付ける` で生成されたIDを無視し、 target_id を持つ予約状態を作る
+        let base_with_target_id = domain::プレゼント予約ベース {
+            id: target_id,
+            ..received_reservation.base // 他のフィールドはコピー
+        };
+        let expected_state =
+            プレゼント予約状態::予約受付済み(domain::予約受付済みプレゼント予約型 {
+                base: base_with_target_id,
+            });
+        let expected_state_clone = expected_state.clone();
+
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(target_id)) // 特定のIDで呼ばれることを期待
+            .times(1)
+            .returning(move |_| Ok(Some(expected_state_clone.clone())));
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+
+        let result = service.予約詳細取得(&target_id, &依頼者id).await;
+
+        // 結果が Ok(Some(期待する予約状態)) であることを確認
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Some(expected_state));
+    }
+
+    #[tokio::test]
+    async fn test_予約詳細取得_other_requester_is_not_found() {
+        let received = 受付済みの予約(ユーザーID::new());
+        let target_id = received.base.id;
+        let initial_state = プレゼント予約状態::予約受付済み(received);
+
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(target_id))
+            .times(1)
+            .returning(move |_| Ok(Some(initial_state.clone())));
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+
+        let result = service.予約詳細取得(&target_id, &ユーザーID::new()).await;
+
+        assert_eq!(result, Ok(None));
+    }
+
+    #[tokio::test] // #[test] -> #[tokio::test]
+    async fn test_予約詳細取得_success_not_found() {
+        // fn -> async fn
+        let target_id = 予約ID::new();
+
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(target_id))
+            .times(1)
+            .returning(|_| Ok(None));
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+
+        let result = service.予約詳細取得(&target_id, &ユーザーID::new()).await;
+
+        // 結果が Ok(None) であることを確認
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[tokio::test] // #[test] -> #[tokio::test]
+    async fn test_予約詳細取得_fail_repo_error() {
+        // fn -> async fn
+        let target_id = 予約ID::new();
+
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(target_id))
+            .times(1)
+            .returning(move |_| Err(DomainError::予約NotFound(target_id)));
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+
+        let result = service.予約詳細取得(&target_id, &ユーザーID::new()).await;
+
+        // 結果が Err で、中身が ApplicationError::Repository であることを確認
+        assert!(result.is_err());
+        assert!(matches!(
+            result.err().unwrap(),
             ApplicationError::Repository(_)
         ));
     }
 
+    // --- 依頼者の予約一覧取得ユースケースのテスト ---
+
+    #[tokio::test]
+    async fn test_依頼者の予約一覧取得_success() {
+        let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
+        let (received, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
+            create_dummy_kinenbi(),
+            None,
+            ラッピング種類::なし,
+            None,
+            商品idリスト,
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+        .unwrap();
+        let expected_page = 予約一覧ページ {
+            予約リスト: vec![プレゼント予約状態::予約受付済み(received)],
+            次のカーソル: None,
+        };
+        let expected_page_clone = expected_page.clone();
+
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_find_by_requester()
+            .withf(move |id, cursor, limit| *id == 依頼者id && cursor.is_none() && *limit == 20)
+            .times(1)
+            .returning(move |_, _, _| Ok(expected_page_clone.clone()));
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+        let result = service.依頼者の予約一覧取得(&依頼者id, None, 20).await;
+
+        assert_eq!(result, Ok(expected_page));
+    }
+
+    #[tokio::test]
+    async fn test_依頼者の予約一覧取得_fail_invalid_limit() {
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo.expect_find_by_requester().times(0);
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+
+        for limit in [0, 一覧取得の最大件数 + 1] {
+            let result = service
+                .依頼者の予約一覧取得(&ユーザーID::new(), None, limit)
+                .await;
+            assert!(matches!(
+                result,
+                Err(ApplicationError::InvalidInput { ref field, .. }) if field == "limit"
+            ));
+        }
+    }
+
+    // --- 冪等な予約受付のテスト ---
+
+    fn create_idempotency_key() -> IdempotencyKey {
+        IdempotencyKey {
+            requester_id: ユーザーID::new(),
+            key: "retry-key".to_string(),
+            fingerprint: "{\"total_amount\":5000}".to_string(),
+        }
+    }
+
+    fn existing_record(
+        key: &IdempotencyKey,
+        fingerprint: &str,
+        reservation_id: Option<予約ID>,
+    ) -> IdempotencyRecord {
+        let now = Utc::now();
+        IdempotencyRecord {
+            requester_id: key.requester_id,
+            key: key.key.clone(),
+            fingerprint: fingerprint.to_string(),
+            reservation_id,
+            created_at: now,
+            expires_at: now + IDEMPOTENCY_KEY_TTL,
+            lease_expires_at: now + IDEMPOTENCY_KEY_LEASE,
+        }
+    }
+
+    /// Idempotency-Key 付きで手配の予約を受け付ける (route の create_reservation と同じ呼び出し方)
+    async fn 冪等に手配予約を受け付ける(
+        service: &プレゼント予約サービス,
+        key: &IdempotencyKey,
+    ) -> AppResult<IdempotentOutcome> {
+        let (_, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
+        service
+            .冪等に予約を受け付ける(key, |確保| {
+                service.プレゼント予約受付(
+                    key.requester_id,
+                    届け先id,
+                    create_dummy_kinenbi(),
+                    None,
+                    ラッピング種類::なし,
+                    None,
+                    プレゼント内容::手配 { 商品idリスト },
+                    支払いid,
+                    テスト見積金額(ラッピング種類::なし),
+                    確保,
+                )
+            })
+            .await
+    }
+
+    /// 予約受付に必要なリポジトリを設定したサービス
+    fn 冪等受付用サービス(
+        key: &IdempotencyKey,
+        mock_repo: Mockプレゼント予約Repository,
+        mock_store: MockIdempotencyStore,
+    ) -> プレゼント予約サービス {
+        プレゼント予約サービス::new(Arc::new(mock_repo))
+            .with_clock(テスト用時計())
+            .with_recipients(テスト用届け先リポジトリ(key.requester_id))
+            .with_products(テスト用商品カタログ())
+            .with_idempotency_store(Arc::new(mock_store))
+    }
+
+    #[tokio::test]
+    async fn test_冪等に予約を受け付ける_first_request_is_recorded_with_reservation() {
+        let key = create_idempotency_key();
+        let mut mock_store = MockIdempotencyStore::new();
+        mock_store
+            .expect_begin()
+            .withf(|record| {
+                record.reservation_id.is_none()
+                    && record.lease_expires_at == record.created_at + IDEMPOTENCY_KEY_LEASE
+            })
+            .times(1)
+            .returning(|_| Ok(IdempotencyBegin::Started));
+        mock_store.expect_abandon().times(0);
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        // 予約IDは予約の保存と同じ呼び出しで記録する
+        let 受付日時 = テスト受付日時().with_timezone(&Utc);
+        mock_repo
+            .expect_save_accepted_with_idempotency_key()
+            .withf(move |_, キー| キー.キー == "retry-key" && キー.確保日時 == 受付日時)
+            .times(1)
+            .returning(|_, _| Ok(()));
+        mock_repo.expect_save_with_stock_movement().times(0);
+
+        let service = 冪等受付用サービス(&key, mock_repo, mock_store);
+        let result = 冪等に手配予約を受け付ける(&service, &key).await;
+
+        assert!(matches!(result, Ok(IdempotentOutcome::Created(_))));
+    }
+
+    #[tokio::test]
+    async fn test_冪等に予約を受け付ける_abandons_key_when_recording_fails() {
+        let key = create_idempotency_key();
+        let mut mock_store = MockIdempotencyStore::new();
+        mock_store
+            .expect_begin()
+            .times(1)
+            .returning(|_| Ok(IdempotencyBegin::Started));
+        mock_store
+            .expect_abandon()
+            .withf(|record| record.key == "retry-key")
+            .times(1)
+            .returning(|_| Ok(()));
+        // キーへの記録に失敗した場合は予約も保存されない (同じトランザクション)
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_save_accepted_with_idempotency_key()
+            .times(1)
+            .returning(|_, _| {
+                Err(DomainError::必須項目不足 {
+                    field: "テストエラー".to_string(),
+                })
+            });
+
+        let service = 冪等受付用サービス(&key, mock_repo, mock_store);
+        let result = 冪等に手配予約を受け付ける(&service, &key).await;
+
+        assert!(matches!(result, Err(ApplicationError::Repository(_))));
+    }
+
+    #[tokio::test]
+    async fn test_冪等に予約を受け付ける_fail_when_key_was_reclaimed() {
+        let key = create_idempotency_key();
+        let mut mock_store = MockIdempotencyStore::new();
+        mock_store
+            .expect_begin()
+            .times(1)
+            .returning(|_| Ok(IdempotencyBegin::Started));
+        mock_store.expect_abandon().times(1).returning(|_| Ok(()));
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_save_accepted_with_idempotency_key()
+            .times(1)
+            .returning(|_, _| Err(DomainError::冪等性キー確保切れ));
+
+        let service = 冪等受付用サービス(&key, mock_repo, mock_store);
+        let result = 冪等に手配予約を受け付ける(&service, &key).await;
+
+        assert_eq!(result, Err(ApplicationError::IdempotencyKeyInProgress));
+    }
+
+    #[tokio::test]
+    async fn test_冪等に予約を受け付ける_replays_completed_request() {
+        let key = create_idempotency_key();
+        let 予約id = 予約ID::new();
+        let record = existing_record(&key, &key.fingerprint, Some(予約id));
+        let mut mock_store = MockIdempotencyStore::new();
+        mock_store
+            .expect_begin()
+            .times(1)
+            .returning(move |_| Ok(IdempotencyBegin::Existing(record.clone())));
+
+        let service =
+            プレゼント予約サービス::new(Arc::new(Mockプレゼント予約Repository::new()))
+                .with_idempotency_store(Arc::new(mock_store));
+        let result = service
+            .冪等に予約を受け付ける(&key, |_| async {
+                panic!("reservation must not be accepted twice")
+            })
+            .await;
+
+        assert_eq!(result, Ok(IdempotentOutcome::Replayed(予約id)));
+    }
+
+    #[tokio::test]
+    async fn test_冪等に予約を受け付ける_fail_reused_with_different_request() {
+        let key = create_idempotency_key();
+        let record = existing_record(&key, "{\"total_amount\":6000}", Some(予約ID::new()));
+        let mut mock_store = MockIdempotencyStore::new();
+        mock_store
+            .expect_begin()
+            .times(1)
+            .returning(move |_| Ok(IdempotencyBegin::Existing(record.clone())));
+
+        let service =
+            プレゼント予約サービス::new(Arc::new(Mockプレゼント予約Repository::new()))
+                .with_idempotency_store(Arc::new(mock_store));
+        let result = service
+            .冪等に予約を受け付ける(&key, |_| async {
+                panic!("reservation must not be accepted")
+            })
+            .await;
+
+        assert_eq!(result, Err(ApplicationError::IdempotencyKeyReused));
+    }
+
+    #[tokio::test]
+    async fn test_冪等に予約を受け付ける_fail_in_progress() {
+        let key = create_idempotency_key();
+        let record = existing_record(&key, &key.fingerprint, None);
+        let mut mock_store = MockIdempotencyStore::new();
+        mock_store
+            .expect_begin()
+            .times(1)
+            .returning(move |_| Ok(IdempotencyBegin::Existing(record.clone())));
+
+        let service =
+            プレゼント予約サービス::new(Arc::new(Mockプレゼント予約Repository::new()))
+                .with_idempotency_store(Arc::new(mock_store));
+        let result = service
+            .冪等に予約を受け付ける(&key, |_| async {
+                panic!("reservation must not be accepted")
+            })
+            .await;
+
+        assert_eq!(result, Err(ApplicationError::IdempotencyKeyInProgress));
+    }
+
+    #[tokio::test]
+    async fn test_冪等に予約を受け付ける_abandons_key_on_failure() {
+        let key = create_idempotency_key();
+        let mut mock_store = MockIdempotencyStore::new();
+        mock_store
+            .expect_begin()
+            .times(1)
+            .returning(|_| Ok(IdempotencyBegin::Started));
+        mock_store
+            .expect_abandon()
+            .withf(|record| record.key == "retry-key")
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service =
+            プレゼント予約サービス::new(Arc::new(Mockプレゼント予約Repository::new()))
+                .with_idempotency_store(Arc::new(mock_store));
+        let result = service
+            .冪等に予約を受け付ける(&key, |_| async {
+                Err(ApplicationError::Domain(DomainError::予約商品空エラー))
+            })
+            .await;
+
+        assert_eq!(
+            result,
+            Err(ApplicationError::Domain(DomainError::予約商品空エラー))
+        );
+    }
+
+    // --- readiness 判定のテスト ---
+
+    #[tokio::test]
+    async fn test_check_readiness_up() {
+        let status = DatabaseStatus {
+            latency: Duration::from_millis(3),
+            migration_version: Some(20250101000000),
+            pool_size: 5,
+            idle_connections: 4,
+        };
+        let status_clone = status.clone();
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_database_status()
+            .times(1)
+            .returning(move || Ok(status_clone.clone()));
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+        let report = service.check_readiness().await;
+
+        assert_eq!(report.status, ComponentStatus::Up);
+        assert_eq!(report.database.details, Some(status));
+        assert_eq!(report.database.error, None);
+    }
+
+    #[tokio::test]
+    async fn test_check_readiness_down_on_connection_error() {
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_database_status()
+            .times(1)
+            .returning(|| Err(InfrastructureError::ConnectionError("refused".to_string())));
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+        let report = service.check_readiness().await;
+
+        assert_eq!(report.status, ComponentStatus::Down);
+        assert_eq!(report.database.details, None);
+        assert_eq!(report.database.error, Some(ReadinessFailure::Unavailable));
+    }
+
+    // --- 予約検索ユースケースのテスト ---
+
+    #[tokio::test]
+    async fn test_予約検索_success() {
+        let 条件 = 予約検索条件 {
+            ステータス: vec![予約ステータス::予約受付済み],
+            ..Default::default()
+        };
+        let expected = 予約検索結果 {
+            予約リスト: vec![],
+            総件数: 0,
+        };
+        let expected_clone = expected.clone();
+        let 条件_clone = 条件.clone();
+
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_search()
+            .withf(move |c, offset, limit| *c == 条件_clone && *offset == 40 && *limit == 20)
+            .times(1)
+            .returning(move |_, _, _| Ok(expected_clone.clone()));
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+        let result = service.予約検索(&条件, 40, 20).await;
+
+        assert_eq!(result, Ok(expected));
+    }
+
+    #[tokio::test]
+    async fn test_予約検索_fail_reversed_anniversary_range() {
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo.expect_search().times(0);
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
+        let 条件 = 予約検索条件 {
+            記念日_開始: NaiveDate::from_ymd_opt(2025, 12, 31),
+            記念日_終了: NaiveDate::from_ymd_opt(2025, 1, 1),
+            ..Default::default()
+        };
+        let result = service.予約検索(&条件, 0, 20).await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::InvalidInput { ref field, .. }) if field == "anniversary_from"
+        ));
+    }
+
+    // --- 予約内容変更ユースケースのテスト ---
+
+    fn 受付済みの予約(
+        依頼者id: ユーザーID,
+    ) -> domain::予約受付済みプレゼント予約型 {
+        let (_, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
+        let (reservation, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
+            create_dummy_kinenbi(),
+            None,
+            ラッピング種類::なし,
+            None,
+            商品idリスト,
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+        .unwrap();
+        reservation
+    }
+
+    #[tokio::test]
+    async fn test_予約内容を変更する_success() {
+        let 依頼者id = ユーザーID::new();
+        let received = 受付済みの予約(依頼者id);
+        let target_id = received.base.id;
+        // 特別ラッピング料 (800 円 + 税 80 円) が加わった合計金額
+        let 変更後の合計 = 金額::new(received.base.合計金額.value() + 880).unwrap();
+        let initial_state = プレゼント予約状態::予約受付済み(received);
+        let 新しいメッセージ = メッセージ内容::new("いつもありがとう").unwrap();
+        let expected_message = 新しいメッセージ.clone();
+
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(target_id))
+            .times(1)
+            .returning(move |_| Ok(Some(initial_state.clone())));
+        mock_repo
+            .expect_save()
+            .withf(move |state: &プレゼント予約状態| match state {
+                プレゼント予約状態::予約受付済み(ref received) => {
+                    received.base.id == target_id
+                        && received.base.メッセージ内容.as_ref() == Some(&expected_message)
+                        && received.base.ラッピング == ラッピング種類::特別
+                        && received.base.合計金額 == 変更後の合計
+                }
+                _ => false,
+            })
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo)).with_clock(テスト用時計());
+        let result = service
+            .予約内容を変更する(
+                &target_id,
+                &依頼者id,
+                予約変更内容 {
+                    メッセージ内容: Some(Some(新しいメッセージ)),
+                    ラッピング: Some(ラッピング種類::特別),
+                    配送希望: None,
+                    見積金額: Some(変更後の合計),
+                },
+            )
+            .await;
+
+        assert!(matches!(result, Ok(プレゼント予約状態::予約受付済み(_))));
+    }
+
+    #[tokio::test]
+    async fn test_予約内容を変更する_fail_other_requester() {
+        let received = 受付済みの予約(ユーザーID::new());
+        let target_id = received.base.id;
+        let initial_state = プレゼント予約状態::予約受付済み(received);
+
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(initial_state.clone())));
+        mock_repo.expect_save().times(0);
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo)).with_clock(テスト用時計());
+        let result = service
+            .予約内容を変更する(&target_id, &ユーザーID::new(), 予約変更内容::default())
+            .await;
+
+        assert_eq!(
+            result.err(),
+            Some(ApplicationError::Domain(DomainError::予約NotFound(
+                target_id
+            )))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_予約内容を変更する_fail_invalid_state() {
+        let 依頼者id = ユーザーID::new();
+        let (preparing, _) = 受付済みの予約(依頼者id)
+            .発送準備を開始する(ユーザーID::new(), テスト受付日時())
+            .unwrap();
+        let target_id = preparing.base.id;
+        let initial_state = プレゼント予約状態::発送準備中(preparing);
+
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(initial_state.clone())));
+        mock_repo.expect_save().times(0);
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo)).with_clock(テスト用時計());
+        let result = service
+            .予約内容を変更する(
+                &target_id,
+                &依頼者id,
+                予約変更内容 {
+                    ラッピング: Some(ラッピング種類::標準),
+                    ..Default::default()
+                },
+            )
+            .await;
+
+        match result.err().unwrap() {
+            ApplicationError::Domain(DomainError::不正な状態遷移 { current_state_type }) => {
+                assert!(current_state_type.contains("発送準備中"));
+            }
+            e => panic!(
+                "Expected ApplicationError::Domain(不正な状態遷移), got {:?}",
+                e
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_予約内容を変更する_fail_changed_concurrently() {
+        let 依頼者id = ユーザーID::new();
+        let received = 受付済みの予約(依頼者id);
+        let target_id = received.base.id;
+        let initial_state = プレゼント予約状態::予約受付済み(received);
+
+        // 読み込んだ後に発送準備が始まっていた場合、リポジトリは保存せずに 不正な状態遷移 を返す
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_find_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(initial_state.clone())));
+        mock_repo.expect_save().times(1).returning(|_| {
+            Err(DomainError::不正な状態遷移 {
+                current_state_type: "Preparing".to_string(),
+            })
+        });
+
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo)).with_clock(テスト用時計());
+        let result = service
+            .予約内容を変更する(&target_id, &依頼者id, 予約変更内容::default())
+            .await;
+
+        assert_eq!(
+            result.err(),
+            Some(ApplicationError::Domain(
+                DomainError::不正な状態遷移 {
+                    current_state_type: "Preparing".to_string(),
+                }
+            ))
+        );
+    }
+
     // --- 発送準備開始ユースケースのテスト ---
 
     #[tokio::test] // #[test] -> #[tokio::test]
@@ -572,8 +2772,7 @@ mod tests {
         // find_by_id が返す予約受付済み状態を作成
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received_reservation = domain::予約を受け付ける(
+        let (received_reservation, _) = domain::手配予約を受け付ける(
             依頼者id,
             届け先id,
             記念日.clone(),
@@ -582,11 +2781,13 @@ mod tests {
             None,
             商品idリスト.clone(),
             支払いid,
-            金額,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..received_reservation.base
         };
         let initial_state =
@@ -621,6 +2822,8 @@ mod tests {
         let result = service.発送準備を開始する(&target_id, handler_id).await;
 
         assert!(result.is_ok()); // Future ではなく Result に対して is_ok()
+                                 // 遷移後の状態が返される
+        assert!(matches!(result.unwrap(), プレゼント予約状態::発送準備中(_)));
     }
 
     #[tokio::test] // #[test] -> #[tokio::test]
@@ -656,24 +2859,29 @@ mod tests {
         // 不正な状態 (例: 発送済み) を返すようにモックを設定
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received = domain::予約を受け付ける(
-            依頼者id.clone(),
-            届け先id.clone(),
+        let (received, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
             記念日.clone(),
             None,
             ラッピング種類::なし,
             None,
             商品idリスト.clone(),
-            支払いid.clone(),
-            金額.clone(),
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
-        let preparing = received.発送準備を開始する(handler_id).unwrap();
-        let shipped = preparing.発送を完了する("dummy-slip".to_string()).unwrap(); // 発送済み状態
+        let (preparing, _) = received
+            .発送準備を開始する(handler_id, テスト受付日時())
+            .unwrap();
+        let (shipped, _) = preparing
+            .発送を完了する(テスト配送伝票番号(), None, テスト受付日時())
+            .unwrap(); // 発送済み状態
 
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..shipped.base
         };
         let invalid_state =
@@ -714,8 +2922,7 @@ mod tests {
         // find_by_id が返す予約受付済み状態を作成
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received_reservation = domain::予約を受け付ける(
+        let (received_reservation, _) = domain::手配予約を受け付ける(
             依頼者id,
             届け先id,
             記念日.clone(),
@@ -724,11 +2931,13 @@ mod tests {
             None,
             商品idリスト.clone(),
             支払いid,
-            金額,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..received_reservation.base
         };
         let initial_state =
@@ -798,28 +3007,31 @@ mod tests {
     async fn test_発送を完了する_success() {
         let target_id = 予約ID::new();
         let handler_id = ユーザーID::new();
-        let slip_number = "slip-12345".to_string();
+        let slip_number = テスト配送伝票番号();
 
         // find_by_id が返す発送準備中状態を作成
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received = domain::予約を受け付ける(
-            依頼者id.clone(),
-            届け先id.clone(),
+        let (received, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
             記念日.clone(),
             None,
             ラッピング種類::なし,
             None,
             商品idリスト.clone(),
-            支払いid.clone(),
-            金額.clone(),
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
-        let preparing = received.発送準備を開始する(handler_id).unwrap(); // 発送準備中状態
+        let (preparing, _) = received
+            .発送準備を開始する(handler_id, テスト受付日時())
+            .unwrap(); // 発送準備中状態
 
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..preparing.base
         };
         let initial_state =
@@ -841,19 +3053,22 @@ mod tests {
         // save の期待値 (発送済み状態になるはず)
         let expected_slip_number = slip_number.clone();
         mock_repo
-            .expect_save()
-            .withf(move |state: &プレゼント予約状態| match state {
-                プレゼント予約状態::発送済み(ref shipped) => {
-                    shipped.base == base_with_target_id
-                        && shipped.配送伝票番号 == expected_slip_number
-                }
-                _ => false,
+            .expect_save_with_stock_movement()
+            .withf(move |state: &プレゼント予約状態, 移動: &在庫移動| {
+                *移動 == 在庫移動::出庫
+                    && match state {
+                        プレゼント予約状態::発送済み(ref shipped) => {
+                            shipped.base == base_with_target_id
+                                && shipped.配送伝票番号 == expected_slip_number
+                        }
+                        _ => false,
+                    }
             })
             .times(1)
-            .returning(|_| Ok(()));
+            .returning(|_, _| Ok(()));
 
         let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service.発送を完了する(&target_id, slip_number).await;
+        let result = service.発送を完了する(&target_id, slip_number, None).await;
 
         assert!(result.is_ok());
     }
@@ -861,17 +3076,17 @@ mod tests {
     #[tokio::test]
     async fn test_発送を完了する_fail_not_found() {
         let target_id = 予約ID::new();
-        let slip_number = "slip-12345".to_string();
+        let slip_number = テスト配送伝票番号();
         let mut mock_repo = Mockプレゼント予約Repository::new();
         mock_repo
             .expect_find_by_id()
             .with(eq(target_id))
             .times(1)
             .returning(|_| Ok(None));
-        mock_repo.expect_save().times(0);
+        mock_repo.expect_save_with_stock_movement().times(0);
 
         let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service.発送を完了する(&target_id, slip_number).await;
+        let result = service.発送を完了する(&target_id, slip_number, None).await;
 
         assert!(result.is_err());
         assert!(matches!(
@@ -883,13 +3098,12 @@ mod tests {
     #[tokio::test]
     async fn test_発送を完了する_fail_invalid_state() {
         let target_id = 予約ID::new();
-        let slip_number = "slip-12345".to_string();
+        let slip_number = テスト配送伝票番号();
 
         // 不正な状態 (例: 予約受付済み) を返すようにモックを設定
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received = domain::予約を受け付ける(
+        let (received, _) = domain::手配予約を受け付ける(
             依頼者id,
             届け先id,
             記念日,
@@ -898,12 +3112,14 @@ mod tests {
             None,
             商品idリスト,
             支払いid,
-            金額,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap(); // 予約受付済み状態
 
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..received.base
         };
         let invalid_state =
@@ -918,10 +3134,10 @@ mod tests {
             .with(eq(target_id))
             .times(1)
             .returning(move |_| Ok(Some(invalid_state_clone.clone())));
-        mock_repo.expect_save().times(0);
+        mock_repo.expect_save_with_stock_movement().times(0);
 
         let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service.発送を完了する(&target_id, slip_number).await;
+        let result = service.発送を完了する(&target_id, slip_number, None).await;
 
         assert!(result.is_err());
         match result.err().unwrap() {
@@ -939,27 +3155,30 @@ mod tests {
     async fn test_発送を完了する_fail_repo_save_error() {
         let target_id = 予約ID::new();
         let handler_id = ユーザーID::new();
-        let slip_number = "slip-12345".to_string();
+        let slip_number = テスト配送伝票番号();
 
         // find_by_id が返す発送準備中状態を作成 (上と同様)
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received = domain::予約を受け付ける(
-            依頼者id.clone(),
-            届け先id.clone(),
+        let (received, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
             記念日.clone(),
             None,
             ラッピング種類::なし,
             None,
             商品idリスト.clone(),
-            支払いid.clone(),
-            金額.clone(),
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
-        let preparing = received.発送準備を開始する(handler_id).unwrap();
+        let (preparing, _) = received
+            .発送準備を開始する(handler_id, テスト受付日時())
+            .unwrap();
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..preparing.base
         };
         let initial_state =
@@ -975,14 +3194,17 @@ mod tests {
             .with(eq(target_id))
             .times(1)
             .returning(move |_| Ok(Some(initial_state_clone.clone())));
-        mock_repo.expect_save().times(1).returning(|_| {
-            Err(DomainError::必須項目不足 {
-                field: "DB save error".to_string(),
-            })
-        }); // save でエラー
+        mock_repo
+            .expect_save_with_stock_movement()
+            .times(1)
+            .returning(|_, _| {
+                Err(DomainError::必須項目不足 {
+                    field: "DB save error".to_string(),
+                })
+            }); // save でエラー
 
         let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service.発送を完了する(&target_id, slip_number).await;
+        let result = service.発送を完了する(&target_id, slip_number, None).await;
 
         assert!(result.is_err());
         assert!(matches!(
@@ -994,17 +3216,17 @@ mod tests {
     #[tokio::test]
     async fn test_発送を完了する_fail_repo_find_error() {
         let target_id = 予約ID::new();
-        let slip_number = "slip-12345".to_string();
+        let slip_number = テスト配送伝票番号();
         let mut mock_repo = Mockプレゼント予約Repository::new();
         mock_repo
             .expect_find_by_id()
             .with(eq(target_id))
             .times(1)
             .returning(move |_| Err(DomainError::予約NotFound(target_id))); // find_by_id でエラー
-        mock_repo.expect_save().times(0);
+        mock_repo.expect_save_with_stock_movement().times(0);
 
         let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service.発送を完了する(&target_id, slip_number).await;
+        let result = service.発送を完了する(&target_id, slip_number, None).await;
 
         assert!(result.is_err());
         assert!(matches!(
@@ -1019,30 +3241,35 @@ mod tests {
     async fn test_配送完了を記録する_success() {
         let target_id = 予約ID::new();
         let handler_id = ユーザーID::new();
-        let slip_number = "slip-12345".to_string();
+        let slip_number = テスト配送伝票番号();
         let delivered_at = Utc::now().with_timezone(&Tokyo);
 
         // find_by_id が返す発送済み状態を作成
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received = domain::予約を受け付ける(
-            依頼者id.clone(),
-            届け先id.clone(),
+        let (received, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
             記念日.clone(),
             None,
             ラッピング種類::なし,
             None,
             商品idリスト.clone(),
-            支払いid.clone(),
-            金額.clone(),
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
-        let preparing = received.発送準備を開始する(handler_id).unwrap();
-        let shipped = preparing.発送を完了する(slip_number.clone()).unwrap(); // 発送済み状態
+        let (preparing, _) = received
+            .発送準備を開始する(handler_id, テスト受付日時())
+            .unwrap();
+        let (shipped, _) = preparing
+            .発送を完了する(slip_number.clone(), None, テスト受付日時())
+            .unwrap(); // 発送済み状態
 
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..shipped.base
         };
         let initial_state =
@@ -1075,7 +3302,9 @@ mod tests {
             .returning(|_| Ok(()));
 
         let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service.配送完了を記録する(&target_id, delivered_at).await;
+        let result = service
+            .配送完了を記録する(&target_id, delivered_at, None)
+            .await;
 
         assert!(result.is_ok());
     }
@@ -1093,7 +3322,9 @@ mod tests {
         mock_repo.expect_save().times(0);
 
         let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service.配送完了を記録する(&target_id, delivered_at).await;
+        let result = service
+            .配送完了を記録する(&target_id, delivered_at, None)
+            .await;
 
         assert!(result.is_err());
         assert!(matches!(
@@ -1110,22 +3341,25 @@ mod tests {
         let handler_id = ユーザーID::new();
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received = domain::予約を受け付ける(
-            依頼者id.clone(),
-            届け先id.clone(),
+        let (received, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
             記念日.clone(),
             None,
             ラッピング種類::なし,
             None,
             商品idリスト.clone(),
-            支払いid.clone(),
-            金額.clone(),
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
-        let preparing = received.発送準備を開始する(handler_id).unwrap(); // 発送準備中状態
+        let (preparing, _) = received
+            .発送準備を開始する(handler_id, テスト受付日時())
+            .unwrap(); // 発送準備中状態
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..preparing.base
         };
         let invalid_state =
@@ -1144,7 +3378,9 @@ mod tests {
         mock_repo.expect_save().times(0);
 
         let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service.配送完了を記録する(&target_id, delivered_at).await;
+        let result = service
+            .配送完了を記録する(&target_id, delivered_at, None)
+            .await;
 
         assert!(result.is_err());
         match result.err().unwrap() {
@@ -1162,29 +3398,34 @@ mod tests {
     async fn test_配送完了を記録する_fail_repo_save_error() {
         let target_id = 予約ID::new();
         let handler_id = ユーザーID::new();
-        let slip_number = "slip-12345".to_string();
+        let slip_number = テスト配送伝票番号();
         let delivered_at = Utc::now().with_timezone(&Tokyo);
 
         // find_by_id が返す発送済み状態を作成 (上と同様)
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received = domain::予約を受け付ける(
-            依頼者id.clone(),
-            届け先id.clone(),
+        let (received, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
             記念日.clone(),
             None,
             ラッピング種類::なし,
             None,
             商品idリスト.clone(),
-            支払いid.clone(),
-            金額.clone(),
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
-        let preparing = received.発送準備を開始する(handler_id).unwrap();
-        let shipped = preparing.発送を完了する(slip_number.clone()).unwrap();
+        let (preparing, _) = received
+            .発送準備を開始する(handler_id, テスト受付日時())
+            .unwrap();
+        let (shipped, _) = preparing
+            .発送を完了する(slip_number.clone(), None, テスト受付日時())
+            .unwrap();
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..shipped.base
         };
         let initial_state =
@@ -1207,7 +3448,9 @@ mod tests {
         }); // save でエラー
 
         let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service.配送完了を記録する(&target_id, delivered_at).await;
+        let result = service
+            .配送完了を記録する(&target_id, delivered_at, None)
+            .await;
 
         assert!(result.is_err());
         assert!(matches!(
@@ -1229,7 +3472,9 @@ mod tests {
         mock_repo.expect_save().times(0);
 
         let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service.配送完了を記録する(&target_id, delivered_at).await;
+        let result = service
+            .配送完了を記録する(&target_id, delivered_at, None)
+            .await;
 
         assert!(result.is_err());
         assert!(matches!(
@@ -1244,26 +3489,27 @@ mod tests {
     async fn test_予約をキャンセルする_success_from_予約受付済み() {
         let target_id = 予約ID::new();
         let reason = Some("気が変わった".to_string());
-        let cancelled_at = Some(Utc::now().with_timezone(&Tokyo));
+        let cancelled_at = テスト受付日時();
 
         // find_by_id が返す予約受付済み状態を作成
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received = domain::予約を受け付ける(
-            依頼者id.clone(),
-            届け先id.clone(),
+        let (received, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
             記念日.clone(),
             None,
             ラッピング種類::なし,
             None,
             商品idリスト.clone(),
-            支払いid.clone(),
-            金額.clone(),
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..received.base
         };
         let initial_state =
@@ -1283,22 +3529,23 @@ mod tests {
         let expected_reason = reason.clone();
         let expected_cancelled_at = cancelled_at;
         mock_repo
-            .expect_save()
-            .withf(move |state: &プレゼント予約状態| match state {
-                プレゼント予約状態::キャンセル済み(ref cancelled) => {
-                    cancelled.base == base_with_target_id &&
+            .expect_save_with_stock_movement()
+            .withf(move |state: &プレゼント予約状態, 移動: &在庫移動| {
+                *移動 == 在庫移動::引当解除
+                    && match state {
+                        プレゼント予約状態::キャンセル済み(ref cancelled) => {
+                            cancelled.base == base_with_target_id &&
                     cancelled.キャンセル理由 == expected_reason && // 理由 -> キャンセル理由
                     cancelled.キャンセル日時 == expected_cancelled_at
-                }
-                _ => false,
+                        }
+                        _ => false,
+                    }
             })
             .times(1)
-            .returning(|_| Ok(()));
+            .returning(|_, _| Ok(()));
 
-        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service
-            .予約をキャンセルする(&target_id, reason, cancelled_at)
-            .await;
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo)).with_clock(テスト用時計());
+        let result = service.予約をキャンセルする(&target_id, reason, None).await;
 
         assert!(result.is_ok());
     }
@@ -1308,27 +3555,30 @@ mod tests {
         let target_id = 予約ID::new();
         let handler_id = ユーザーID::new();
         let reason = Some("準備中に問題発生".to_string());
-        let cancelled_at = Some(Utc::now().with_timezone(&Tokyo));
+        let cancelled_at = テスト受付日時();
 
         // find_by_id が返す発送準備中状態を作成
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received = domain::予約を受け付ける(
-            依頼者id.clone(),
-            届け先id.clone(),
+        let (received, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
             記念日.clone(),
             None,
             ラッピング種類::なし,
             None,
             商品idリスト.clone(),
-            支払いid.clone(),
-            金額.clone(),
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
-        let preparing = received.発送準備を開始する(handler_id).unwrap(); // 発送準備中状態
+        let (preparing, _) = received
+            .発送準備を開始する(handler_id, テスト受付日時())
+            .unwrap(); // 発送準備中状態
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..preparing.base
         };
         let initial_state =
@@ -1349,22 +3599,23 @@ mod tests {
         let expected_reason = reason.clone();
         let expected_cancelled_at = cancelled_at;
         mock_repo
-            .expect_save()
-            .withf(move |state: &プレゼント予約状態| match state {
-                プレゼント予約状態::キャンセル済み(ref cancelled) => {
-                    cancelled.base == base_with_target_id &&
+            .expect_save_with_stock_movement()
+            .withf(move |state: &プレゼント予約状態, 移動: &在庫移動| {
+                *移動 == 在庫移動::引当解除
+                    && match state {
+                        プレゼント予約状態::キャンセル済み(ref cancelled) => {
+                            cancelled.base == base_with_target_id &&
                     cancelled.キャンセル理由 == expected_reason && // 理由 -> キャンセル理由
                     cancelled.キャンセル日時 == expected_cancelled_at
-                }
-                _ => false,
+                        }
+                        _ => false,
+                    }
             })
             .times(1)
-            .returning(|_| Ok(()));
+            .returning(|_, _| Ok(()));
 
-        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service
-            .予約をキャンセルする(&target_id, reason, cancelled_at)
-            .await;
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo)).with_clock(テスト用時計());
+        let result = service.予約をキャンセルする(&target_id, reason, None).await;
 
         assert!(result.is_ok());
     }
@@ -1373,19 +3624,16 @@ mod tests {
     async fn test_予約をキャンセルする_fail_not_found() {
         let target_id = 予約ID::new();
         let reason = None;
-        let cancelled_at = None;
         let mut mock_repo = Mockプレゼント予約Repository::new();
         mock_repo
             .expect_find_by_id()
             .with(eq(target_id))
             .times(1)
             .returning(|_| Ok(None));
-        mock_repo.expect_save().times(0);
+        mock_repo.expect_save_with_stock_movement().times(0);
 
-        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service
-            .予約をキャンセルする(&target_id, reason, cancelled_at)
-            .await;
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo)).with_clock(テスト用時計());
+        let result = service.予約をキャンセルする(&target_id, reason, None).await;
 
         assert!(result.is_err());
         assert!(matches!(
@@ -1398,29 +3646,33 @@ mod tests {
     async fn test_予約をキャンセルする_fail_invalid_state() {
         let target_id = 予約ID::new();
         let reason = None;
-        let cancelled_at = None;
         // 不正な状態 (例: 発送済み)
         let handler_id = ユーザーID::new();
-        let slip_number = "slip-123".to_string();
+        let slip_number = テスト配送伝票番号();
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received = domain::予約を受け付ける(
-            依頼者id.clone(),
-            届け先id.clone(),
+        let (received, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
             記念日.clone(),
             None,
             ラッピング種類::なし,
             None,
             商品idリスト.clone(),
-            支払いid.clone(),
-            金額.clone(),
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
-        let preparing = received.発送準備を開始する(handler_id).unwrap();
-        let shipped = preparing.発送を完了する(slip_number.clone()).unwrap(); // 発送済み
+        let (preparing, _) = received
+            .発送準備を開始する(handler_id, テスト受付日時())
+            .unwrap();
+        let (shipped, _) = preparing
+            .発送を完了する(slip_number.clone(), None, テスト受付日時())
+            .unwrap(); // 発送済み
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..shipped.base
         };
         let invalid_state =
@@ -1436,12 +3688,10 @@ mod tests {
             .with(eq(target_id))
             .times(1)
             .returning(move |_| Ok(Some(invalid_state_clone.clone())));
-        mock_repo.expect_save().times(0);
+        mock_repo.expect_save_with_stock_movement().times(0);
 
-        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service
-            .予約をキャンセルする(&target_id, reason, cancelled_at)
-            .await;
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo)).with_clock(テスト用時計());
+        let result = service.予約をキャンセルする(&target_id, reason, None).await;
 
         assert!(result.is_err());
         match result.err().unwrap() {
@@ -1459,26 +3709,26 @@ mod tests {
     async fn test_予約をキャンセルする_fail_repo_save_error() {
         let target_id = 予約ID::new();
         let reason = None;
-        let cancelled_at = None;
 
         // find_by_id が返す予約受付済み状態を作成 (上と同様)
         let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
         let 記念日 = create_dummy_kinenbi();
-        let 金額 = create_dummy_kingaku();
-        let received = domain::予約を受け付ける(
-            依頼者id.clone(),
-            届け先id.clone(),
+        let (received, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
             記念日.clone(),
             None,
             ラッピング種類::なし,
             None,
             商品idリスト.clone(),
-            支払いid.clone(),
-            金額.clone(),
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
         let base_with_target_id = domain::プレゼント予約ベース {
-            id: target_id.clone(),
+            id: target_id,
             ..received.base
         };
         let initial_state =
@@ -1493,16 +3743,17 @@ mod tests {
             .with(eq(target_id))
             .times(1)
             .returning(move |_| Ok(Some(initial_state_clone.clone())));
-        mock_repo.expect_save().times(1).returning(|_| {
-            Err(DomainError::必須項目不足 {
-                field: "DB save error".to_string(),
-            })
-        }); // save でエラー
+        mock_repo
+            .expect_save_with_stock_movement()
+            .times(1)
+            .returning(|_, _| {
+                Err(DomainError::必須項目不足 {
+                    field: "DB save error".to_string(),
+                })
+            }); // save でエラー
 
-        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service
-            .予約をキャンセルする(&target_id, reason, cancelled_at)
-            .await;
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo)).with_clock(テスト用時計());
+        let result = service.予約をキャンセルする(&target_id, reason, None).await;
 
         assert!(result.is_err());
         assert!(matches!(
@@ -1515,19 +3766,16 @@ mod tests {
     async fn test_予約をキャンセルする_fail_repo_find_error() {
         let target_id = 予約ID::new();
         let reason = None;
-        let cancelled_at = None;
         let mut mock_repo = Mockプレゼント予約Repository::new();
         mock_repo
             .expect_find_by_id()
             .with(eq(target_id))
             .times(1)
             .returning(move |_| Err(DomainError::予約NotFound(target_id))); // find_by_id でエラー
-        mock_repo.expect_save().times(0);
+        mock_repo.expect_save_with_stock_movement().times(0);
 
-        let service = プレゼント予約サービス::new(Arc::new(mock_repo));
-        let result = service
-            .予約をキャンセルする(&target_id, reason, cancelled_at)
-            .await;
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo)).with_clock(テスト用時計());
+        let result = service.予約をキャンセルする(&target_id, reason, None).await;
 
         assert!(result.is_err());
         assert!(matches!(
@@ -1535,4 +3783,380 @@ mod tests {
             ApplicationError::Repository(_)
         ));
     }
+
+    /// 記念日 (2025-12-25) の直前の受付済み予約を返すリポジトリ (保存はしない)
+    fn 記念日直前の予約を返すリポジトリ(
+        target_id: 予約ID,
+    ) -> Mockプレゼント予約Repository {
+        let (依頼者id, 届け先id, 支払いid, 商品idリスト) = create_dummy_ids();
+        let (received, _) = domain::手配予約を受け付ける(
+            依頼者id,
+            届け先id,
+            create_dummy_kinenbi(),
+            None,
+            ラッピング種類::なし,
+            None,
+            商品idリスト,
+            支払いid,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+        .unwrap();
+        let state =
+            プレゼント予約状態::予約受付済み(domain::予約受付済みプレゼント予約型 {
+                base: domain::プレゼント予約ベース {
+                    id: target_id,
+                    ..received.base
+                },
+            });
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(target_id))
+            .returning(move |_| Ok(Some(state.clone())));
+        mock_repo.expect_save_with_stock_movement().times(0);
+        mock_repo
+    }
+
+    #[tokio::test]
+    async fn test_キャンセルを試算する_returns_fee_without_saving() {
+        let target_id = 予約ID::new();
+        let 記念日の3日前 = Tokyo.with_ymd_and_hms(2025, 12, 22, 10, 0, 0).unwrap();
+        let service = プレゼント予約サービス::new(Arc::new(
+            記念日直前の予約を返すリポジトリ(target_id),
+        ))
+        .with_clock(Arc::new(FixedClock(記念日の3日前.with_timezone(&Utc))));
+
+        let result = service.キャンセルを試算する(&target_id).await;
+
+        // 無料キャンセル期限 (7 日前) を過ぎているので合計金額 880 円の 50%
+        assert_eq!(
+            result,
+            Ok(キャンセル判定::可 {
+                キャンセル料: 440
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_依頼者のキャンセルを試算する_only_for_own_reservation() {
+        let 依頼者id = ユーザーID::new();
+        let received = 受付済みの予約(依頼者id);
+        let target_id = received.base.id;
+        let state = プレゼント予約状態::予約受付済み(received);
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_find_by_id()
+            .with(eq(target_id))
+            .times(2)
+            .returning(move |_| Ok(Some(state.clone())));
+        mock_repo.expect_save().times(0);
+        let service = プレゼント予約サービス::new(Arc::new(mock_repo)).with_clock(テスト用時計());
+
+        // 記念日まで十分日数があるので無料でキャンセルできる
+        let result = service
+            .依頼者のキャンセルを試算する(&target_id, &依頼者id)
+            .await;
+        assert_eq!(
+            result,
+            Ok(キャンセル判定::可 {
+                キャンセル料: 0
+            })
+        );
+
+        // 他の依頼者の予約は見つからないものとして扱う
+        let result = service
+            .依頼者のキャンセルを試算する(&target_id, &ユーザーID::new())
+            .await;
+        assert_eq!(
+            result,
+            Err(ApplicationError::Domain(DomainError::予約NotFound(
+                target_id
+            )))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_予約をキャンセルする_fail_denied_by_policy() {
+        let target_id = 予約ID::new();
+        let 記念日の3日前 = Tokyo.with_ymd_and_hms(2025, 12, 22, 10, 0, 0).unwrap();
+        let service = プレゼント予約サービス::new(Arc::new(
+            記念日直前の予約を返すリポジトリ(target_id),
+        ))
+        .with_clock(Arc::new(FixedClock(記念日の3日前.with_timezone(&Utc))))
+        .with_cancellation_policy(キャンセルポリシー {
+            キャンセル期限日数: 5,
+            ..キャンセルポリシー::default()
+        });
+
+        let preview = service.キャンセルを試算する(&target_id).await.unwrap();
+        let result = service.予約をキャンセルする(&target_id, None, None).await;
+
+        assert!(matches!(preview, キャンセル判定::不可 { .. }));
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DomainError::キャンセル不可 { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_返送を記録する_fail_before_delivery_failure() {
+        let target_id = 予約ID::new();
+        let service = プレゼント予約サービス::new(Arc::new(
+            記念日直前の予約を返すリポジトリ(target_id),
+        ))
+        .with_clock(テスト用時計());
+
+        // 予約受付済みの予約は返送を記録できない
+        let result = service.返送を記録する(&target_id, None).await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DomainError::不正な状態遷移 { .. }))
+        ));
+    }
+
+    // --- 届け先のユースケースのテスト ---
+
+    #[tokio::test]
+    async fn test_届け先詳細取得_fail_other_owner() {
+        let 所有者id = ユーザーID::new();
+        let 届け先id = 届け先ID::new();
+        let service = 届け先サービス::new(
+            テスト用届け先リポジトリ(所有者id),
+            Arc::new(Mockプレゼント予約Repository::new()),
+        );
+
+        let own = service.届け先詳細取得(&所有者id, &届け先id).await;
+        let other = service.届け先詳細取得(&ユーザーID::new(), &届け先id).await;
+
+        assert_eq!(own.unwrap().id, 届け先id);
+        assert_eq!(
+            other,
+            Err(ApplicationError::Domain(DomainError::届け先NotFound(
+                届け先id
+            )))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_届け先を登録する_fail_address_mismatch() {
+        let mut mock_recipients = Mock届け先Repository::new();
+        mock_recipients.expect_save().times(0);
+        let mut mock_postal_codes = Mock郵便番号辞書::new();
+        mock_postal_codes
+            .expect_住所候補を取得する()
+            .returning(|_| {
+                Ok(vec![住所候補 {
+                    都道府県: domain::都道府県::大阪府,
+                    市区町村: "大阪市北区".to_string(),
+                    町域: "梅田".to_string(),
+                }])
+            });
+        let service = 届け先サービス::new(
+            Arc::new(mock_recipients),
+            Arc::new(Mockプレゼント予約Repository::new()),
+        )
+        .with_postal_codes(Arc::new(mock_postal_codes));
+
+        // テスト届け先内容 は東京都千代田区の住所
+        let result = service
+            .届け先を登録する(ユーザーID::new(), テスト届け先内容())
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::Domain(DomainError::住所不一致 { .. }))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_届け先を削除する_fail_in_use() {
+        let 所有者id = ユーザーID::new();
+        let 届け先id = 届け先ID::new();
+        let mut mock_recipients = Mock届け先Repository::new();
+        mock_recipients.expect_find_by_id().returning(move |id| {
+            Ok(Some(届け先 {
+                id: *id,
+                所有者id,
+                内容: テスト届け先内容(),
+            }))
+        });
+        mock_recipients.expect_delete().times(0);
+        let mut mock_repo = Mockプレゼント予約Repository::new();
+        mock_repo
+            .expect_search()
+            .withf(move |条件, _, _| {
+                条件.届け先id == Some(届け先id)
+                    && !条件.ステータス.contains(&予約ステータス::キャンセル済み)
+            })
+            .returning(|_, _, _| {
+                Ok(予約検索結果 {
+                    予約リスト: vec![],
+                    総件数: 1,
+                })
+            });
+        let service = 届け先サービス::new(Arc::new(mock_recipients), Arc::new(mock_repo));
+
+        let result = service.届け先を削除する(&所有者id, &届け先id).await;
+
+        assert_eq!(
+            result,
+            Err(ApplicationError::Domain(DomainError::届け先使用中(
+                届け先id
+            )))
+        );
+    }
+
+    // --- ユーザーアカウントのテスト ---
+
+    #[tokio::test]
+    async fn test_アカウントを登録する_fail_auth_subject_in_use() {
+        let mut mock_accounts = MockユーザーアカウントRepository::new();
+        mock_accounts.expect_find_by_id().returning(|_| Ok(None));
+        mock_accounts
+            .expect_find_by_auth_subject()
+            .returning(|_| Ok(Some(テストアカウント(ユーザーID::new()))));
+        mock_accounts.expect_insert().times(0);
+        let service = ユーザーアカウントサービス::new(Arc::new(mock_accounts));
+
+        let 登録するアカウント = テストアカウント(ユーザーID::new());
+        let result = service
+            .アカウントを登録する(
+                登録するアカウント.id,
+                登録するアカウント.認証サブジェクト,
+                登録するアカウント.プロフィール,
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(ApplicationError::Domain(
+                DomainError::認証サブジェクト使用中
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_アカウントを登録する_fail_registered_concurrently() {
+        // 確認の時点ではなかったアカウントが、保存までに同じIDで登録された
+        let mut mock_accounts = MockユーザーアカウントRepository::new();
+        mock_accounts.expect_find_by_id().returning(|_| Ok(None));
+        mock_accounts
+            .expect_find_by_auth_subject()
+            .returning(|_| Ok(None));
+        mock_accounts
+            .expect_insert()
+            .times(1)
+            .returning(|アカウント| {
+                Err(DomainError::ユーザーアカウント登録済み(
+                    アカウント.id,
+                ))
+            });
+        let service = ユーザーアカウントサービス::new(Arc::new(mock_accounts));
+
+        let 登録するアカウント = テストアカウント(ユーザーID::new());
+        let result = service
+            .アカウントを登録する(
+                登録するアカウント.id,
+                登録するアカウント.認証サブジェクト,
+                登録するアカウント.プロフィール,
+            )
+            .await;
+
+        assert_eq!(
+            result,
+            Err(ApplicationError::Domain(
+                DomainError::ユーザーアカウント登録済み(登録するアカウント.id)
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_アカウントを停止する_saves_suspended_account() {
+        let id = ユーザーID::new();
+        let mut mock_accounts = MockユーザーアカウントRepository::new();
+        mock_accounts
+            .expect_find_by_id()
+            .with(eq(id))
+            .returning(|id| Ok(Some(テストアカウント(*id))));
+        mock_accounts
+            .expect_save()
+            .withf(|アカウント| アカウント.状態 == domain::アカウント状態::停止)
+            .times(1)
+            .returning(|_| Ok(()));
+        let service = ユーザーアカウントサービス::new(Arc::new(mock_accounts));
+
+        let アカウント = service.アカウントを停止する(&id).await.unwrap();
+
+        assert_eq!(アカウント.状態, domain::アカウント状態::停止);
+    }
+
+    #[tokio::test]
+    async fn test_アカウント取得_fail_not_found() {
+        let mut mock_accounts = MockユーザーアカウントRepository::new();
+        mock_accounts.expect_find_by_id().returning(|_| Ok(None));
+        let service = ユーザーアカウントサービス::new(Arc::new(mock_accounts));
+        let id = ユーザーID::new();
+
+        let result = service.アカウント取得(&id).await;
+
+        assert_eq!(
+            result,
+            Err(ApplicationError::Domain(
+                DomainError::ユーザーアカウントNotFound(id)
+            ))
+        );
+    }
+
+    // --- 商品カタログテスト ---
+
+    #[tokio::test]
+    async fn test_商品を変更する_saves_discontinued_product() {
+        let id = 商品ID::new();
+        let mut mock_products = Mock商品Repository::new();
+        mock_products
+            .expect_find_by_id()
+            .with(eq(id))
+            .returning(|id| Ok(Some(テスト商品(*id, true))));
+        mock_products
+            .expect_save()
+            .withf(|商品| !商品.販売中)
+            .times(1)
+            .returning(|_| Ok(()));
+        let service = 商品カタログサービス::new(
+            Arc::new(mock_products),
+            Arc::new(Mock商品在庫Repository::new()),
+        );
+
+        let 商品 = service
+            .商品を変更する(&id, テスト商品(id, true).内容, false)
+            .await
+            .unwrap();
+
+        assert!(!商品.販売中);
+    }
+
+    #[tokio::test]
+    async fn test_商品を検索する_fail_inverted_price_range() {
+        let mut mock_products = Mock商品Repository::new();
+        mock_products.expect_search().times(0);
+        let service = 商品カタログサービス::new(
+            Arc::new(mock_products),
+            Arc::new(Mock商品在庫Repository::new()),
+        );
+        let 条件 = 商品検索条件 {
+            最低価格: Some(5000),
+            最高価格: Some(3000),
+            ..Default::default()
+        };
+
+        let result = service.商品を検索する(&条件, 0, 20).await;
+
+        assert!(matches!(
+            result,
+            Err(ApplicationError::InvalidInput { field, .. }) if field == "min_price"
+        ));
+    }
 }
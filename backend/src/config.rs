@@ -0,0 +1,171 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use std::env;
+use std::path::PathBuf;
+
+use crate::domain::{キャンセルポリシー, 発送リードタイム};
+
+/// 現在の API バージョン (/api/{version} にマウントする)
+pub const CURRENT_API_VERSION: &str = "v1";
+
+/// アプリケーションの設定 (ルーター構築時に使う)
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    /// Swagger UI (/swagger-ui) を公開するか
+    pub swagger_ui_enabled: bool,
+    /// CORS で許可するオリジン (空の場合はすべて許可する)
+    pub cors_allowed_origins: Vec<String>,
+    /// 外部から見たサーバーの URL (OpenAPI の servers に使う)
+    pub public_base_url: String,
+    /// バージョンなしの旧パス (/api/...) を提供するか
+    pub legacy_api_enabled: bool,
+    /// 旧パスの提供終了予定日時 (Sunset ヘッダーに使う)
+    pub legacy_api_sunset: Option<DateTime<Utc>>,
+    /// 発送リードタイム (営業日数)。記念日はこれ以上先の日付でなければならない
+    pub shipping_lead_time_business_days: u32,
+    /// キャンセルの可否とキャンセル料を決めるキャンセルポリシー
+    pub cancellation_policy: キャンセルポリシー,
+    /// 郵便番号データ (KEN_ALL 形式の UTF-8 CSV) のパス。未設定の場合は住所の検索・照合を行わない
+    pub postal_code_data_path: Option<PathBuf>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            swagger_ui_enabled: true,
+            cors_allowed_origins: Vec::new(),
+            public_base_url: "http://localhost:8080".to_string(),
+            legacy_api_enabled: true,
+            legacy_api_sunset: None,
+            shipping_lead_time_business_days: 発送リードタイム::default().営業日数(),
+            cancellation_policy: キャンセルポリシー::default(),
+            postal_code_data_path: None,
+        }
+    }
+}
+
+impl AppConfig {
+    /// 環境変数から設定を読み込む (未設定の項目は既定値)
+    ///
+    /// - `SWAGGER_UI_ENABLED`: "false" または "0" で Swagger UI を無効化
+    /// - `CORS_ALLOWED_ORIGINS`: カンマ区切りのオリジン一覧
+    /// - `PUBLIC_BASE_URL`: 外部から見たサーバーの URL (例: https://api.example.com)
+    /// - `LEGACY_API_ENABLED`: "false" または "0" で旧パス (/api/...) を無効化
+    /// - `LEGACY_API_SUNSET`: 旧パスの提供終了日 (YYYY-MM-DD または RFC 3339)
+    /// - `SHIPPING_LEAD_TIME_BUSINESS_DAYS`: 発送リードタイムの営業日数
+    /// - `CANCELLATION_DEADLINE_DAYS`: 記念日の何日前までキャンセルを受け付けるか
+    /// - `FREE_CANCELLATION_DAYS`: 記念日の何日前までキャンセル料なしでキャンセルできるか
+    /// - `LATE_CANCELLATION_FEE_PERCENT`: 無料キャンセル期限を過ぎた後のキャンセル料率 (%)
+    /// - `PREPARING_CANCELLATION_FEE_PERCENT`: 発送準備中の予約のキャンセル料率 (%)
+    /// - `RETURNED_CANCELLATION_FEE_PERCENT`: 返送済みの予約のキャンセル料率 (%)
+    /// - `POSTAL_CODE_DATA_PATH`: 郵便番号データ (utf_ken_all.csv) のパス
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let swagger_ui_enabled = env_flag("SWAGGER_UI_ENABLED", default.swagger_ui_enabled);
+        let cors_allowed_origins = env::var("CORS_ALLOWED_ORIGINS")
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or(default.cors_allowed_origins);
+        let public_base_url = env::var("PUBLIC_BASE_URL")
+            .map(|v| v.trim().trim_end_matches('/').to_string())
+            .unwrap_or(default.public_base_url);
+        let legacy_api_enabled = env_flag("LEGACY_API_ENABLED", default.legacy_api_enabled);
+        let legacy_api_sunset =
+            env::var("LEGACY_API_SUNSET")
+                .ok()
+                .and_then(|v| match parse_sunset(v.trim()) {
+                    Some(sunset) => Some(sunset),
+                    None => {
+                        tracing::warn!("Ignoring invalid LEGACY_API_SUNSET: {}", v);
+                        None
+                    }
+                });
+        let shipping_lead_time_business_days = env_u32(
+            "SHIPPING_LEAD_TIME_BUSINESS_DAYS",
+            default.shipping_lead_time_business_days,
+        );
+        let cancellation_policy = キャンセルポリシー {
+            キャンセル期限日数: env_u32(
+                "CANCELLATION_DEADLINE_DAYS",
+                default.cancellation_policy.キャンセル期限日数,
+            ),
+            無料キャンセル期限日数: env_u32(
+                "FREE_CANCELLATION_DAYS",
+                default.cancellation_policy.無料キャンセル期限日数,
+            ),
+            直前キャンセル料率: env_u32(
+                "LATE_CANCELLATION_FEE_PERCENT",
+                default.cancellation_policy.直前キャンセル料率,
+            ),
+            発送準備中キャンセル料率: env_u32(
+                "PREPARING_CANCELLATION_FEE_PERCENT",
+                default.cancellation_policy.発送準備中キャンセル料率,
+            ),
+            返送済みキャンセル料率: env_u32(
+                "RETURNED_CANCELLATION_FEE_PERCENT",
+                default.cancellation_policy.返送済みキャンセル料率,
+            ),
+        };
+        let postal_code_data_path = env::var("POSTAL_CODE_DATA_PATH")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or(default.postal_code_data_path);
+        Self {
+            swagger_ui_enabled,
+            cors_allowed_origins,
+            public_base_url,
+            legacy_api_enabled,
+            legacy_api_sunset,
+            shipping_lead_time_business_days,
+            cancellation_policy,
+            postal_code_data_path,
+        }
+    }
+
+    /// 発送リードタイム
+    pub fn shipping_lead_time(&self) -> 発送リードタイム {
+        発送リードタイム::new(self.shipping_lead_time_business_days)
+    }
+
+    /// 現在のバージョンの API のベース URL (例: http://localhost:8080/api/v1)
+    pub fn api_base_url(&self) -> String {
+        format!("{}/api/{}", self.public_base_url, CURRENT_API_VERSION)
+    }
+}
+
+/// "false" または "0" のときだけ無効とみなすフラグ
+fn env_flag(name: &str, default: bool) -> bool {
+    env::var(name)
+        .map(|v| !matches!(v.trim(), "false" | "0"))
+        .unwrap_or(default)
+}
+
+/// 数値の設定 (未設定の場合と数値として読めない場合は既定値)
+fn env_u32(name: &str, default: u32) -> u32 {
+    match env::var(name) {
+        Ok(v) => v.trim().parse().unwrap_or_else(|_| {
+            tracing::warn!("Ignoring invalid {}: {}", name, v);
+            default
+        }),
+        Err(_) => default,
+    }
+}
+
+/// 日付のみの場合はその日の 00:00:00 UTC とする
+fn parse_sunset(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|dt| dt.and_utc())
+        })
+}
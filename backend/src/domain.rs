@@ -5,10 +5,12 @@
 // Define internal module and re-export
 pub mod core {
     use async_trait::async_trait;
-    use chrono::{DateTime, NaiveDate}; // Utc を復活 -> 再度削除
+    use chrono::{DateTime, Datelike, NaiveDate, Utc, Weekday};
     use chrono_tz::Tz;
-    use std::collections::HashSet; // List<商品ID> の代わりに HashSet を使う例
+    use std::collections::{HashMap, HashSet}; // List<商品ID> の代わりに HashSet を使う例
     use thiserror::Error;
+    use unicode_normalization::UnicodeNormalization;
+    use unicode_segmentation::UnicodeSegmentation;
     use uuid::Uuid; // Tz を use
 
     // --- 値オブジェクト ---
@@ -62,7 +64,7 @@ pub mod core {
     }
 
     // 商品IDはサンプルから流用、必要なら修正
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
     pub struct 商品ID(Uuid);
     impl 商品ID {
         pub fn new() -> Self {
@@ -94,7 +96,310 @@ pub mod core {
         }
     }
 
-    // 他の値オブジェクト（記念日、金額、メッセージ内容、ラッピングオプション、配送希望日時など）も必要に応じて追加
+    // 他の値オブジェクト（記念日、金額、メッセージ内容、ラッピングオプション、配送希望など）も必要に応じて追加
+
+    /// 発送リードタイム (予約受付から発送までに必要な営業日数)
+    ///
+    /// 営業日は土日を除く平日とする。祝日は現時点では考慮しない。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct 発送リードタイム {
+        営業日数: u32,
+    }
+    impl 発送リードタイム {
+        pub const fn new(営業日数: u32) -> Self {
+            Self { 営業日数 }
+        }
+        pub fn 営業日数(&self) -> u32 {
+            self.営業日数
+        }
+        /// 受付日から営業日数を数え、お届けできる最も早い日を返す (受付日より後の日付)
+        ///
+        /// 記念日と配送希望日はこの日以降でなければならない。
+        pub fn 最短配送日(&self, 受付日: NaiveDate) -> NaiveDate {
+            let mut date = 受付日;
+            let mut remaining = self.営業日数;
+            while remaining > 0 {
+                date = date.succ_opt().expect("date overflow");
+                if !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+                    remaining -= 1;
+                }
+            }
+            date.max(受付日.succ_opt().expect("date overflow"))
+        }
+    }
+    impl Default for 発送リードタイム {
+        /// 既定は 3 営業日
+        fn default() -> Self {
+            Self::new(3)
+        }
+    }
+
+    /// メッセージ内容 (メッセージカードに記載する文面)
+    ///
+    /// 全角・半角の表記ゆれを NFKC で正規化した上で、書記素クラスタ単位で 200 文字以内とする。
+    /// 改行 (LF) 以外の制御文字は受け付けない (CRLF / CR は LF に揃える)。
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct メッセージ内容 {
+        value: String,
+    }
+    impl メッセージ内容 {
+        pub const 最大文字数: usize = 200;
+
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            let normalized = 入力テキストを正規化する(value);
+            if normalized.trim().is_empty() {
+                return Err(DomainError::不正なメッセージ内容 {
+                    reason: "空のメッセージは指定できません".to_string(),
+                });
+            }
+            if let Some(c) = normalized.chars().find(|c| c.is_control() && *c != '\n') {
+                return Err(DomainError::不正なメッセージ内容 {
+                    reason: format!("制御文字 (U+{:04X}) は使用できません", c as u32),
+                });
+            }
+            let 文字数 = normalized.graphemes(true).count();
+            if 文字数 > Self::最大文字数 {
+                return Err(DomainError::不正なメッセージ内容 {
+                    reason: format!(
+                        "{} 文字以内で入力してください (現在 {} 文字)",
+                        Self::最大文字数,
+                        文字数
+                    ),
+                });
+            }
+            Ok(Self { value: normalized })
+        }
+        /// 保存済みの文面を検証せずに復元する
+        ///
+        /// 200 文字のルールを導入する前に受け付けた予約の文面は、ルールを満たさなくてもそのまま扱う。
+        pub fn 保存済みの値から復元する(value: String) -> Self {
+            Self { value }
+        }
+        pub fn value(&self) -> &str {
+            &self.value
+        }
+        /// 書記素クラスタ単位の文字数
+        pub fn 文字数(&self) -> usize {
+            self.value.graphemes(true).count()
+        }
+    }
+
+    /// 改行を LF に揃え、NFKC で正規化する (利用者が入力する文面に共通の前処理)
+    fn 入力テキストを正規化する(value: &str) -> String {
+        value
+            .replace("\r\n", "\n")
+            .replace('\r', "\n")
+            .nfkc()
+            .collect()
+    }
+
+    /// 発送代行プレゼント情報 (依頼者が倉庫に送るプレゼントの説明)
+    ///
+    /// 梱包担当者が届いた荷物を照合するための文面。メッセージ内容と同じ正規化を行い、
+    /// 書記素クラスタ単位で 500 文字以内とする。
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 発送代行プレゼント情報 {
+        value: String,
+    }
+    impl 発送代行プレゼント情報 {
+        pub const 最大文字数: usize = 500;
+
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            let normalized = 入力テキストを正規化する(value);
+            let trimmed = normalized.trim();
+            if trimmed.is_empty() {
+                return Err(DomainError::不正な発送代行プレゼント情報 {
+                    reason: "プレゼントの説明を入力してください".to_string(),
+                });
+            }
+            if let Some(c) = trimmed.chars().find(|c| c.is_control() && *c != '\n') {
+                return Err(DomainError::不正な発送代行プレゼント情報 {
+                    reason: format!("制御文字 (U+{:04X}) は使用できません", c as u32),
+                });
+            }
+            let 文字数 = trimmed.graphemes(true).count();
+            if 文字数 > Self::最大文字数 {
+                return Err(DomainError::不正な発送代行プレゼント情報 {
+                    reason: format!(
+                        "{} 文字以内で入力してください (現在 {} 文字)",
+                        Self::最大文字数,
+                        文字数
+                    ),
+                });
+            }
+            Ok(Self {
+                value: trimmed.to_string(),
+            })
+        }
+        pub fn value(&self) -> &str {
+            &self.value
+        }
+    }
+
+    /// プレゼント内容
+    ///
+    /// 発送代行は依頼者が用意したプレゼントを倉庫で受け取って発送する (MVP)。
+    /// 手配はカタログの商品をこちらで手配する。
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum プレゼント内容 {
+        発送代行 {
+            情報: 発送代行プレゼント情報,
+            倉庫到着予定日: NaiveDate,
+        },
+        手配 {
+            商品idリスト: HashSet<商品ID>
+        },
+    }
+
+    impl プレゼント内容 {
+        /// 手配する商品 (発送代行の場合は空)
+        pub fn 手配商品リスト(&self) -> HashSet<商品ID> {
+            match self {
+                プレゼント内容::発送代行 { .. } => HashSet::new(),
+                プレゼント内容::手配 { 商品idリスト } => 商品idリスト.clone(),
+            }
+        }
+    }
+
+    /// 消費税の税区分
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum 税区分 {
+        /// 10%
+        標準税率,
+        /// 8% (飲食料品などの軽減税率)
+        軽減税率,
+    }
+    impl 税区分 {
+        /// 税率 (%)
+        pub fn 税率(&self) -> u32 {
+            match self {
+                税区分::標準税率 => 10,
+                税区分::軽減税率 => 8,
+            }
+        }
+    }
+
+    /// 商品の価格 (税込。カタログに表示する価格)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct 商品価格 {
+        pub 税込価格: 金額,
+        pub 税区分: 税区分,
+    }
+    impl 商品価格 {
+        /// 税込価格の上限 (円)
+        pub const 上限: u32 = 10_000_000;
+
+        pub fn new(税込価格: u32, 税区分: 税区分) -> Result<Self, DomainError> {
+            if 税込価格 > Self::上限 {
+                return Err(DomainError::不正な商品価格 {
+                    value: 税込価格
+                });
+            }
+            let 税込価格 = 金額::new(税込価格).map_err(|_| DomainError::不正な商品価格 {
+                value: 税込価格,
+            })?;
+            Ok(Self {
+                税込価格, 税区分
+            })
+        }
+    }
+
+    /// 料金明細の項目 (明細はこの順に並べる)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub enum 料金項目 {
+        商品(商品ID),
+        発送代行手数料,
+        ラッピング料,
+        送料,
+    }
+
+    /// 料金明細 (1 行ごとに消費税を計算し、1 円未満は切り捨てる)
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct 料金明細 {
+        pub 項目: 料金項目,
+        pub 税抜金額: u32,
+        pub 税区分: 税区分,
+        pub 消費税額: u32,
+    }
+    impl 料金明細 {
+        pub fn new(項目: 料金項目, 税抜金額: u32, 税区分: 税区分) -> Self {
+            Self {
+                項目,
+                税抜金額,
+                税区分,
+                消費税額: 割合を掛ける(税抜金額, 税区分.税率(), 100),
+            }
+        }
+        /// 税込金額から明細を作る (内税。消費税額は 1 円未満を切り捨て、税込金額は変えない)
+        pub fn 税込から(項目: 料金項目, 税込金額: u32, 税区分: 税区分) -> Self {
+            let 消費税額 = 割合を掛ける(税込金額, 税区分.税率(), 100 + 税区分.税率());
+            Self {
+                項目,
+                税抜金額: 税込金額 - 消費税額,
+                税区分,
+                消費税額,
+            }
+        }
+        pub fn 税込金額(&self) -> u32 {
+            self.税抜金額 + self.消費税額
+        }
+    }
+
+    /// 金額に 分子/分母 (1 以下) を掛けて 1 円未満を切り捨てる
+    ///
+    /// u32 のまま掛けると大きな金額で桁あふれするため u64 で計算する。
+    fn 割合を掛ける(元の金額: u32, 分子: u32, 分母: u32) -> u32 {
+        debug_assert!(分子 <= 分母);
+        u32::try_from(u64::from(元の金額) * u64::from(分子) / u64::from(分母))
+            .expect("1 以下の割合を掛けても元の金額を超えない")
+    }
+
+    /// 料金内訳 (予約の合計金額の根拠)
+    ///
+    /// 料金計算の導入前に受け付けた予約は明細を持たない。
+    #[derive(Debug, Clone, Default, PartialEq, Eq)]
+    pub struct 料金内訳 {
+        pub 明細: Vec<料金明細>,
+    }
+    impl 料金内訳 {
+        pub fn 税抜合計(&self) -> Result<u32, DomainError> {
+            金額を合計する(self.明細.iter().map(|行| 行.税抜金額))
+        }
+        /// 税区分ごとの消費税額
+        pub fn 消費税額(&self, 税区分: 税区分) -> Result<u32, DomainError> {
+            金額を合計する(
+                self.明細
+                    .iter()
+                    .filter(|行| 行.税区分 == 税区分)
+                    .map(|行| 行.消費税額),
+            )
+        }
+        /// 税込の合計金額
+        pub fn 合計(&self) -> Result<u32, DomainError> {
+            金額を合計する(self.明細.iter().flat_map(|行| [行.税抜金額, 行.消費税額]))
+        }
+        /// 依頼者が画面で見た見積金額が計算結果と一致することを確認する
+        pub fn 見積と照合する(&self, 見積金額: 金額) -> Result<(), DomainError> {
+            let 合計 = self.合計()?;
+            if 見積金額.value() != 合計 {
+                return Err(DomainError::見積金額不一致 {
+                    estimated: 見積金額.value(),
+                    calculated: 合計,
+                });
+            }
+            Ok(())
+        }
+    }
+
+    /// 金額を合計する (商品の数に上限はないため、u32 に収まらない場合は 料金上限超過)
+    fn 金額を合計する(
+        金額リスト: impl IntoIterator<Item = u32>,
+    ) -> Result<u32, DomainError> {
+        金額リスト
+            .into_iter()
+            .try_fold(0u32, |合計, 値| 合計.checked_add(値))
+            .ok_or(DomainError::料金上限超過)
+    }
 
     #[derive(Debug, Clone, PartialEq, Eq, Hash)]
     pub struct 記念日 {
@@ -123,6 +428,92 @@ pub mod core {
         特別,
     }
 
+    /// 配送業者
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum 配送業者 {
+        ヤマト運輸,
+        佐川急便,
+        日本郵便,
+    }
+
+    /// 配送伝票番号 (配送業者ごとの形式とチェックディジットを満たす問い合わせ番号)
+    ///
+    /// ヤマト運輸・佐川急便は 12 桁で、先頭 11 桁を 7 で割った余りが末尾の 1 桁と一致する。
+    /// 日本郵便は 11〜13 桁の数字とする。ハイフンと空白は取り除いて保持する。
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 配送伝票番号 {
+        配送業者: 配送業者,
+        value: String,
+    }
+    impl 配送伝票番号 {
+        pub fn new(配送業者: 配送業者, value: &str) -> Result<Self, DomainError> {
+            let 番号: String = value.nfkc().filter(|c| !matches!(c, '-' | ' ')).collect();
+            let invalid = |reason: &str| DomainError::不正な配送伝票番号 {
+                reason: format!("{:?}: {}", 配送業者, reason),
+            };
+            if 番号.is_empty() || !番号.chars().all(|c| c.is_ascii_digit()) {
+                return Err(invalid("数字で入力してください"));
+            }
+            match 配送業者 {
+                配送業者::ヤマト運輸 | 配送業者::佐川急便 => {
+                    if 番号.len() != 12 {
+                        return Err(invalid("12 桁で入力してください"));
+                    }
+                    let (本体, チェックディジット) = 番号.split_at(11);
+                    let 本体: u64 = 本体.parse().expect("11 桁の数字");
+                    if (本体 % 7).to_string() != チェックディジット {
+                        return Err(invalid("チェックディジットが一致しません"));
+                    }
+                }
+                配送業者::日本郵便 => {
+                    if !(11..=13).contains(&番号.len()) {
+                        return Err(invalid("11〜13 桁で入力してください"));
+                    }
+                }
+            }
+            Ok(Self {
+                配送業者,
+                value: 番号,
+            })
+        }
+        /// 保存済みの伝票番号を検証せずに復元する
+        ///
+        /// 形式の検証を導入する前の伝票番号は自由入力だったため、形式を満たさなくてもそのまま扱う。
+        pub fn 保存済みの値から復元する(
+            配送業者: 配送業者, value: String
+        ) -> Self {
+            Self {
+                配送業者, value
+            }
+        }
+        pub fn 配送業者(&self) -> 配送業者 {
+            self.配送業者
+        }
+        pub fn value(&self) -> &str {
+            &self.value
+        }
+    }
+
+    /// 配送時間帯 (配送業者が受け付ける時間帯指定)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum 配送時間帯 {
+        指定なし,
+        午前中,
+        午後2時から4時,
+        午後4時から6時,
+        午後6時から8時,
+        午後7時から9時,
+    }
+
+    /// 配送希望 (配送日と時間帯の組み合わせ)
+    ///
+    /// 配送日が最短配送日から記念日までの間にあるかは予約受付時に検証する。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct 配送希望 {
+        pub 配送日: NaiveDate,
+        pub 時間帯: 配送時間帯,
+    }
+
     // --- エンティティと状態 ---
 
     /// プレゼント予約の状態 (ADR 0003)
@@ -132,9 +523,97 @@ pub mod core {
         発送準備中(発送準備中プレゼント予約型),
         発送済み(発送済みプレゼント予約型),
         配送完了(配送完了プレゼント予約型),
+        配送失敗(配送失敗プレゼント予約型),
+        返送済み(返送済みプレゼント予約型),
         キャンセル済み(キャンセル済みプレゼント予約型),
     }
 
+    /// 予約ステータス (検索条件などで状態の種類だけを扱う場合に使う)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum 予約ステータス {
+        予約受付済み,
+        発送準備中,
+        発送済み,
+        配送完了,
+        配送失敗,
+        返送済み,
+        キャンセル済み,
+    }
+
+    impl プレゼント予約状態 {
+        /// 現在の予約ステータス
+        pub fn ステータス(&self) -> 予約ステータス {
+            match self {
+                プレゼント予約状態::予約受付済み(_) => {
+                    予約ステータス::予約受付済み
+                }
+                プレゼント予約状態::発送準備中(_) => {
+                    予約ステータス::発送準備中
+                }
+                プレゼント予約状態::発送済み(_) => 予約ステータス::発送済み,
+                プレゼント予約状態::配送完了(_) => 予約ステータス::配送完了,
+                プレゼント予約状態::配送失敗(_) => 予約ステータス::配送失敗,
+                プレゼント予約状態::返送済み(_) => 予約ステータス::返送済み,
+                プレゼント予約状態::キャンセル済み(_) => {
+                    予約ステータス::キャンセル済み
+                }
+            }
+        }
+
+        /// この状態を保存する直前に予約がなっているべきステータス
+        ///
+        /// 読み込んでから保存するまでに他の操作で状態が変わっていないかの確認に使う。
+        /// 予約受付済みは内容の変更で予約受付済みのまま保存し直す。
+        pub fn 遷移元のステータス(&self) -> &'static [予約ステータス] {
+            match self {
+                プレゼント予約状態::予約受付済み(_) => {
+                    &[予約ステータス::予約受付済み]
+                }
+                プレゼント予約状態::発送準備中(_) => &[予約ステータス::予約受付済み],
+                // 返送済みからの再発送も発送済みになる
+                プレゼント予約状態::発送済み(_) => {
+                    &[予約ステータス::発送準備中, 予約ステータス::返送済み]
+                }
+                プレゼント予約状態::配送完了(_) | プレゼント予約状態::配送失敗(_) => {
+                    &[予約ステータス::発送済み]
+                }
+                プレゼント予約状態::返送済み(_) => &[予約ステータス::配送失敗],
+                プレゼント予約状態::キャンセル済み(_) => &[
+                    予約ステータス::予約受付済み,
+                    予約ステータス::発送準備中,
+                    予約ステータス::返送済み,
+                ],
+            }
+        }
+
+        /// 状態によらず共通データを参照する
+        pub fn base(&self) -> &プレゼント予約ベース {
+            match self {
+                プレゼント予約状態::予約受付済み(r) => &r.base,
+                プレゼント予約状態::発送準備中(r) => &r.base,
+                プレゼント予約状態::発送済み(r) => &r.base,
+                プレゼント予約状態::配送完了(r) => &r.base,
+                プレゼント予約状態::配送失敗(r) => &r.base,
+                プレゼント予約状態::返送済み(r) => &r.base,
+                プレゼント予約状態::キャンセル済み(r) => &r.base,
+            }
+        }
+
+        /// 判定日時にキャンセルした場合の可否とキャンセル料
+        pub fn キャンセルを判定する(
+            &self,
+            ポリシー: &キャンセルポリシー,
+            判定日時: DateTime<Tz>,
+        ) -> キャンセル判定 {
+            let base = self.base();
+            ポリシー.判定する(
+                self.ステータス(),
+                base.記念日までの日数(判定日時),
+                base.合計金額,
+            )
+        }
+    }
+
     /// 各状態に共通のデータ (トレイトや抽象クラスの代わり)
     #[derive(Debug, Clone, PartialEq)]
     pub struct プレゼント予約ベース {
@@ -142,12 +621,19 @@ pub mod core {
         pub 依頼者id: ユーザーID,
         pub 届け先id: 届け先ID,
         pub 記念日: 記念日,
-        pub メッセージ内容: Option<String>,
+        pub メッセージ内容: Option<メッセージ内容>,
         pub ラッピング: ラッピング種類,
-        pub 配送希望日時: Option<DateTime<Tz>>, // Tokyo -> Tz
+        pub 配送希望: Option<配送希望>,
         pub 合計金額: 金額,
+        pub 料金内訳: 料金内訳,
         pub 支払いid: 支払いID,
-        pub 手配商品リスト: HashSet<商品ID>, // どの状態でも持ちそうなのでベースに含める例
+        pub プレゼント内容: プレゼント内容,
+    }
+    impl プレゼント予約ベース {
+        /// 基準日時 (の日本時間の日付) から記念日までの日数 (記念日を過ぎていれば負)
+        pub fn 記念日までの日数(&self, 基準日時: DateTime<Tz>) -> i64 {
+            (self.記念日.value - 基準日時.date_naive()).num_days()
+        }
     }
 
     /// 予約受付済み状態のデータと振る舞い
@@ -168,195 +654,1897 @@ pub mod core {
     #[derive(Debug, Clone, PartialEq)]
     pub struct 発送済みプレゼント予約型 {
         pub base: プレゼント予約ベース,
-        pub 配送伝票番号: String,
+        pub 配送伝票番号: 配送伝票番号,
     }
 
     /// 配送完了状態のデータと振る舞い
     #[derive(Debug, Clone, PartialEq)]
     pub struct 配送完了プレゼント予約型 {
         pub base: プレゼント予約ベース,
-        pub 配送伝票番号: String,
+        pub 配送伝票番号: 配送伝票番号,
         pub 配送完了日時: DateTime<Tz>, // Tokyo -> Tz
     }
 
+    /// 配送失敗の理由
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum 配送失敗理由 {
+        受取拒否,
+        宛先不明,
+        保管期限切れ,
+    }
+
+    /// 配送失敗状態のデータと振る舞い (荷物は配送業者から返送される途中)
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct 配送失敗プレゼント予約型 {
+        pub base: プレゼント予約ベース,
+        pub 配送伝票番号: 配送伝票番号,
+        pub 配送失敗理由: 配送失敗理由,
+        pub 配送失敗日時: DateTime<Tz>,
+    }
+
+    /// 返送済み状態のデータと振る舞い (荷物が倉庫に戻り、再発送かキャンセルを待つ)
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct 返送済みプレゼント予約型 {
+        pub base: プレゼント予約ベース,
+        /// 返送された荷物の配送伝票番号
+        pub 配送伝票番号: 配送伝票番号,
+        pub 配送失敗理由: 配送失敗理由,
+        pub 配送失敗日時: DateTime<Tz>,
+        pub 返送日時: DateTime<Tz>,
+    }
+
     /// キャンセル済み状態のデータと振る舞い
     #[derive(Debug, Clone, PartialEq)]
     pub struct キャンセル済みプレゼント予約型 {
         pub base: プレゼント予約ベース,
         pub キャンセル理由: Option<String>,
-        pub キャンセル日時: Option<DateTime<Tz>>, // Tokyo -> Tz
+        pub キャンセル日時: DateTime<Tz>,
+        /// キャンセル時に確定したキャンセル料 (円、税込)
+        pub キャンセル料: u32,
     }
 
-    // --- ドメインエラー ---
-    #[derive(Error, Debug, PartialEq)]
-    pub enum DomainError {
-        #[error("プレゼント予約が見つかりません: ID={0:?}")]
-        予約NotFound(予約ID),
-        #[error("不正な状態遷移です: 現在の状態={current_state_type}")]
-        不正な状態遷移 { current_state_type: String }, // どの型からの遷移が不正かを示す
-        #[error("必須項目が不足しています: {field}")]
-        必須項目不足 { field: String },
-        #[error("予約に商品が含まれていません")]
-        予約商品空エラー, // 新しいエラーを追加
-        #[error("商品が見つかりません: ID={0:?}")]
-        商品NotFound(商品ID), // これは商品ドメインのエラーかもしれない
-        #[error("不正な金額が指定されました: value={value}")]
-        不正な金額エラー { value: u32 },
-        // 他に必要なドメイン固有のエラーを追加
+    // --- 届け先 ---
+
+    /// 氏名 (表記とフリガナ)
+    ///
+    /// 表記は 50 文字以内、フリガナは 100 文字以内とする (文字数は書記素クラスタ単位)。
+    /// フリガナはひらがな・半角カナでの入力も受け付け、全角カタカナに揃えて保持する。
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 氏名 {
+        表記: String,
+        フリガナ: String,
     }
+    impl 氏名 {
+        pub const 表記の最大文字数: usize = 50;
+        pub const フリガナの最大文字数: usize = 100;
 
-    // --- インフラストラクチャエラー (リポジトリ操作のエラーを表現) ---
-    #[derive(Error, Debug, PartialEq)]
-    pub enum InfrastructureError {
-        #[error("データベース操作エラー: {0}")]
-        DatabaseError(String), // sqlx::Error などをラップすることを想定
-        #[error("接続エラー: {0}")]
-        ConnectionError(String),
-        // 必要に応じて他のインフラエラーを追加
+        pub fn new(表記: &str, フリガナ: &str) -> Result<Self, DomainError> {
+            let 表記 = 一行テキストを検証する(表記, Self::表記の最大文字数)
+                .map_err(|reason| DomainError::不正な氏名 { reason })?;
+            let フリガナ: String =
+                一行テキストを検証する(フリガナ, Self::フリガナの最大文字数)
+                    .map_err(|reason| DomainError::不正なフリガナ { reason })?
+                    .chars()
+                    .map(ひらがなをカタカナにする)
+                    .collect();
+            if let Some(c) = フリガナ
+                .chars()
+                .find(|c| !matches!(c, 'ァ'..='ヺ' | 'ー' | '・' | ' '))
+            {
+                return Err(DomainError::不正なフリガナ {
+                    reason: format!("カタカナで入力してください (使用できない文字: {})", c),
+                });
+            }
+            Ok(Self {
+                表記, フリガナ
+            })
+        }
+        pub fn 表記(&self) -> &str {
+            &self.表記
+        }
+        pub fn フリガナ(&self) -> &str {
+            &self.フリガナ
+        }
     }
 
-    // --- ドメインサービス / ロジック関数 ---
-    // 例: 予約を受け付ける関数
-    #[allow(clippy::too_many_arguments)] // TODO: 引数が多いのでコマンドオブジェクト等でのリファクタリングを検討
-    pub fn 予約を受け付ける(
-        依頼者id: ユーザーID,
-        届け先id: 届け先ID,
-        記念日: 記念日,
-        メッセージ内容: Option<String>,
-        ラッピング: ラッピング種類,
-        配送希望日時: Option<DateTime<Tz>>, // Tokyo -> Tz
-        商品idリスト: HashSet<商品ID>,
-        支払いid: 支払いID,
-        合計金額: 金額,
-    ) -> Result<予約受付済みプレゼント予約型, DomainError> {
-        if 商品idリスト.is_empty() {
-            return Err(DomainError::予約商品空エラー);
-        }
-        let 予約id = 予約ID::new();
-        let base = プレゼント予約ベース {
-            id: 予約id,
-            依頼者id,
-            届け先id,
-            記念日,
-            メッセージ内容,
-            ラッピング,
-            配送希望日時,
-            合計金額,
-            支払いid,
-            手配商品リスト: 商品idリスト,
-        };
-        Ok(予約受付済みプレゼント予約型 { base })
+    fn ひらがなをカタカナにする(c: char) -> char {
+        match c {
+            'ぁ'..='ゖ' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+            _ => c,
+        }
     }
 
-    // 例: 状態遷移の関数
-    impl 予約受付済みプレゼント予約型 {
-        pub fn 発送準備を開始する(
-            self,
-            梱包担当者id: ユーザーID,
-        ) -> Result<発送準備中プレゼント予約型, DomainError> {
-            Ok(発送準備中プレゼント予約型 {
-                base: self.base,
-                梱包担当者id, // 受け取ったIDを設定
-            })
+    /// NFKC で正規化して前後の空白を除き、空・制御文字・文字数超過を拒否する (改行も不可)
+    fn 一行テキストを検証する(
+        value: &str, 最大文字数: usize
+    ) -> Result<String, String> {
+        let normalized: String = value.nfkc().collect();
+        let trimmed = normalized.trim();
+        if trimmed.is_empty() {
+            return Err("入力してください".to_string());
         }
-        pub fn 予約をキャンセルする(
-            self,
-            理由: Option<String>,
-            日時: Option<DateTime<Tz>>, // Tokyo -> Tz
-        ) -> Result<キャンセル済みプレゼント予約型, DomainError> {
-            // 予約受付済み -> キャンセル済み は許可される
-            Ok(キャンセル済みプレゼント予約型 {
-                base: self.base,
-                キャンセル理由: 理由,
-                キャンセル日時: 日時,
-            })
+        if let Some(c) = trimmed.chars().find(|c| c.is_control()) {
+            return Err(format!("制御文字 (U+{:04X}) は使用できません", c as u32));
+        }
+        let 文字数 = trimmed.graphemes(true).count();
+        if 文字数 > 最大文字数 {
+            return Err(format!(
+                "{} 文字以内で入力してください (現在 {} 文字)",
+                最大文字数, 文字数
+            ));
         }
+        Ok(trimmed.to_string())
     }
 
-    impl 発送準備中プレゼント予約型 {
-        pub fn 発送を完了する(
-            self,
-            配送伝票番号: String,
-        ) -> Result<発送済みプレゼント予約型, DomainError> {
-            Ok(発送済みプレゼント予約型 {
-                base: self.base,
-                配送伝票番号,
-            })
+    /// 郵便番号 (7 桁の数字。ハイフン・空白・先頭の〒は取り除いて保持する)
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 郵便番号(String);
+    impl 郵便番号 {
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            let normalized: String = value.nfkc().collect();
+            let 番号: String = normalized
+                .trim()
+                .trim_start_matches('〒')
+                .chars()
+                .filter(|c| !matches!(c, '-' | ' '))
+                .collect();
+            if 番号.len() != 7 || !番号.chars().all(|c| c.is_ascii_digit()) {
+                return Err(DomainError::不正な郵便番号 {
+                    reason: "7 桁の数字で入力してください".to_string(),
+                });
+            }
+            Ok(Self(番号))
         }
-        pub fn 予約をキャンセルする(
-            self,
-            理由: Option<String>,
-            日時: Option<DateTime<Tz>>, // Tokyo -> Tz
-        ) -> Result<キャンセル済みプレゼント予約型, DomainError> {
-            // 発送準備中 -> キャンセル済み は許可される
-            Ok(キャンセル済みプレゼント予約型 {
-                base: self.base,
-                キャンセル理由: 理由,
-                キャンセル日時: 日時,
-            })
+        pub fn value(&self) -> &str {
+            &self.0
+        }
+        /// 表示用の形式 (例: 100-0001)
+        pub fn 表示(&self) -> String {
+            format!("{}-{}", &self.0[..3], &self.0[3..])
         }
     }
 
-    impl 発送済みプレゼント予約型 {
-        pub fn 配送完了を記録する(
-            self,
-            記録日時: DateTime<Tz>, // Tokyo -> Tz
-        ) -> Result<配送完了プレゼント予約型, DomainError> {
-            Ok(配送完了プレゼント予約型 {
-                base: self.base,
-                配送伝票番号: self.配送伝票番号,
-                配送完了日時: 記録日時,
-            })
-        }
-        // 他の遷移メソッド (例: キャンセルは不可？)
+    /// 都道府県 (JIS X 0401 の都道府県コード順)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum 都道府県 {
+        北海道,
+        青森県,
+        岩手県,
+        宮城県,
+        秋田県,
+        山形県,
+        福島県,
+        茨城県,
+        栃木県,
+        群馬県,
+        埼玉県,
+        千葉県,
+        東京都,
+        神奈川県,
+        新潟県,
+        富山県,
+        石川県,
+        福井県,
+        山梨県,
+        長野県,
+        岐阜県,
+        静岡県,
+        愛知県,
+        三重県,
+        滋賀県,
+        京都府,
+        大阪府,
+        兵庫県,
+        奈良県,
+        和歌山県,
+        鳥取県,
+        島根県,
+        岡山県,
+        広島県,
+        山口県,
+        徳島県,
+        香川県,
+        愛媛県,
+        高知県,
+        福岡県,
+        佐賀県,
+        長崎県,
+        熊本県,
+        大分県,
+        宮崎県,
+        鹿児島県,
+        沖縄県,
     }
+    impl 都道府県 {
+        /// すべての都道府県 (都道府県コード順)
+        pub const 一覧: [都道府県; 47] = [
+            都道府県::北海道,
+            都道府県::青森県,
+            都道府県::岩手県,
+            都道府県::宮城県,
+            都道府県::秋田県,
+            都道府県::山形県,
+            都道府県::福島県,
+            都道府県::茨城県,
+            都道府県::栃木県,
+            都道府県::群馬県,
+            都道府県::埼玉県,
+            都道府県::千葉県,
+            都道府県::東京都,
+            都道府県::神奈川県,
+            都道府県::新潟県,
+            都道府県::富山県,
+            都道府県::石川県,
+            都道府県::福井県,
+            都道府県::山梨県,
+            都道府県::長野県,
+            都道府県::岐阜県,
+            都道府県::静岡県,
+            都道府県::愛知県,
+            都道府県::三重県,
+            都道府県::滋賀県,
+            都道府県::京都府,
+            都道府県::大阪府,
+            都道府県::兵庫県,
+            都道府県::奈良県,
+            都道府県::和歌山県,
+            都道府県::鳥取県,
+            都道府県::島根県,
+            都道府県::岡山県,
+            都道府県::広島県,
+            都道府県::山口県,
+            都道府県::徳島県,
+            都道府県::香川県,
+            都道府県::愛媛県,
+            都道府県::高知県,
+            都道府県::福岡県,
+            都道府県::佐賀県,
+            都道府県::長崎県,
+            都道府県::熊本県,
+            都道府県::大分県,
+            都道府県::宮崎県,
+            都道府県::鹿児島県,
+            都道府県::沖縄県,
+        ];
 
-    // 他の状態遷移関数も同様に定義
+        /// 都道府県コード (01〜47)
+        pub fn コード(self) -> u8 {
+            self as u8 + 1
+        }
+        pub fn from_コード(コード: u8) -> Option<Self> {
+            Self::一覧.get(usize::from(コード).checked_sub(1)?).copied()
+        }
+        /// 表示名 (例: 東京都)
+        pub fn 名前(self) -> String {
+            format!("{:?}", self)
+        }
+        pub fn from_名前(名前: &str) -> Result<Self, DomainError> {
+            let 名前: String = 名前.nfkc().collect();
+            Self::一覧
+                .into_iter()
+                .find(|p| p.名前() == 名前.trim())
+                .ok_or_else(|| DomainError::不正な都道府県 {
+                    value: 名前.trim().to_string(),
+                })
+        }
+    }
 
-    // --- リポジトリインターフェース (トレイト) ---
-    #[cfg_attr(test, mockall::automock)]
-    #[async_trait]
-    pub trait プレゼント予約Repository: Send + Sync {
-        async fn save(&self, reservation: &プレゼント予約状態) -> Result<(), DomainError>;
-        async fn find_by_id(
-            &self,
-            id: &予約ID,
-        ) -> Result<Option<プレゼント予約状態>, DomainError>;
-        // 必要に応じて他の検索メソッドを追加 (例: find_by_user_id)
+    /// 住所 (都道府県より後の市区町村・町域・番地。100 文字以内)
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 住所(String);
+    impl 住所 {
+        pub const 最大文字数: usize = 100;
 
-        /// リポジトリ（主にDB）への接続性を確認する
-        async fn check_db_connection(&self) -> Result<(), InfrastructureError>; // これで InfrastructureError が見つかるはず
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            一行テキストを検証する(value, Self::最大文字数)
+                .map(Self)
+                .map_err(|reason| DomainError::不正な住所 { reason })
+        }
+        pub fn value(&self) -> &str {
+            &self.0
+        }
     }
 
-    // 商品リポジトリはシンプル化のため一旦コメントアウト or 削除しても良い
-    // #[cfg_attr(test, mockall::automock)]
-    // pub trait 商品Repository: Send + Sync {
-    //     fn find_by_id(&self, id: &商品ID) -> Result<Option<商品>, DomainError>;
-    // }
-} // End of pub mod core
+    /// 建物名 (建物名・部屋番号。100 文字以内)
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 建物名(String);
+    impl 建物名 {
+        pub const 最大文字数: usize = 100;
 
-// Re-export all public items from the core module
-pub use core::*;
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            一行テキストを検証する(value, Self::最大文字数)
+                .map(Self)
+                .map_err(|reason| DomainError::不正な建物名 { reason })
+        }
+        pub fn value(&self) -> &str {
+            &self.0
+        }
+    }
 
-// --- Domain Tests ---
-#[cfg(test)]
-mod tests {
-    use super::core::*; // Use items from the inner core module now
-    use chrono::{NaiveDate, TimeZone, Utc}; // TimeZone, Utc を削除
-    use chrono_tz::Asia::Tokyo;
-    use std::collections::HashSet;
-    use uuid::Uuid; // Tokyo を use -> 削除
+    /// 電話番号 (0 から始まる 10〜11 桁の数字。ハイフン・空白・括弧は取り除いて保持する)
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 電話番号(String);
+    impl 電話番号 {
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            let 番号: String = value
+                .nfkc()
+                .filter(|c| !matches!(c, '-' | ' ' | '(' | ')'))
+                .collect();
+            if !番号.chars().all(|c| c.is_ascii_digit())
+                || !番号.starts_with('0')
+                || !(10..=11).contains(&番号.len())
+            {
+                return Err(DomainError::不正な電話番号 {
+                    reason: "0 から始まる 10〜11 桁の数字で入力してください".to_string(),
+                });
+            }
+            Ok(Self(番号))
+        }
+        pub fn value(&self) -> &str {
+            &self.0
+        }
+    }
 
-    // --- 値オブジェクトのテスト ---
+    /// 届け先の登録・変更で指定する内容
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct 届け先内容 {
+        pub 氏名: 氏名,
+        pub 郵便番号: 郵便番号,
+        pub 都道府県: 都道府県,
+        pub 住所: 住所,
+        pub 建物名: Option<建物名>,
+        pub 電話番号: 電話番号,
+    }
 
-    #[test]
-    fn test_id_value_objects_creation_and_access() {
-        // new() で生成
-        let yoyaku_id = 予約ID::new();
-        let user_id = ユーザーID::new();
-        let todokesaki_id = 届け先ID::new();
-        let shohin_id = 商品ID::new();
-        let shiharai_id = 支払いID::new();
+    impl 届け先内容 {
+        /// 郵便番号の住所候補と照合する
+        ///
+        /// 都道府県が一致し、住所がその候補の市区町村から始まる候補が 1 件以上あればよい。
+        /// 町域以降は表記ゆれが多いため照合しない。
+        pub fn 住所候補と照合する(
+            &self, 候補: &[住所候補]
+        ) -> Result<(), DomainError> {
+            if 候補.is_empty() {
+                return Err(DomainError::不正な郵便番号 {
+                    reason: format!("{} は郵便番号データにありません", self.郵便番号.表示()),
+                });
+            }
+            if 候補
+                .iter()
+                .any(|c| c.都道府県 == self.都道府県 && self.住所.value().starts_with(&c.市区町村))
+            {
+                return Ok(());
+            }
+            let 候補の住所: Vec<String> = 候補
+                .iter()
+                .map(|c| format!("{}{}", c.都道府県.名前(), c.市区町村))
+                .collect();
+            Err(DomainError::住所不一致 {
+                reason: format!(
+                    "郵便番号 {} の住所 ({}) と一致しません",
+                    self.郵便番号.表示(),
+                    候補の住所.join(" / ")
+                ),
+            })
+        }
+    }
+
+    /// 郵便番号に対応する住所の候補 (郵便番号データの 1 件)
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 住所候補 {
+        pub 都道府県: 都道府県,
+        pub 市区町村: String,
+        /// 町域 (郵便番号が市区町村内の特定の町域に対応しない場合は空)
+        pub 町域: String,
+    }
+
+    /// 届け先 (依頼者が登録したプレゼントの送り先。所有者以外には公開しない)
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct 届け先 {
+        pub id: 届け先ID,
+        pub 所有者id: ユーザーID,
+        pub 内容: 届け先内容,
+    }
+    impl 届け先 {
+        pub fn 登録する(所有者id: ユーザーID, 内容: 届け先内容) -> Self {
+            Self {
+                id: 届け先ID::new(),
+                所有者id,
+                内容,
+            }
+        }
+        pub fn 所有者か(&self, ユーザーid: &ユーザーID) -> bool {
+            self.所有者id == *ユーザーid
+        }
+        pub fn 内容を変更する(&mut self, 内容: 届け先内容) {
+            self.内容 = 内容;
+        }
+    }
+
+    // --- 商品 ---
+
+    /// 商品名 (100 文字以内)
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 商品名(String);
+    impl 商品名 {
+        pub const 最大文字数: usize = 100;
+
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            一行テキストを検証する(value, Self::最大文字数)
+                .map(Self)
+                .map_err(|reason| DomainError::不正な商品名 { reason })
+        }
+        pub fn value(&self) -> &str {
+            &self.0
+        }
+    }
+
+    /// 商品説明 (2000 文字以内。改行を含められる。空の場合は説明なし)
+    #[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+    pub struct 商品説明(String);
+    impl 商品説明 {
+        pub const 最大文字数: usize = 2000;
+
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            let normalized = 入力テキストを正規化する(value);
+            let trimmed = normalized.trim();
+            if let Some(c) = trimmed.chars().find(|c| c.is_control() && *c != '\n') {
+                return Err(DomainError::不正な商品説明 {
+                    reason: format!("制御文字 (U+{:04X}) は使用できません", c as u32),
+                });
+            }
+            let 文字数 = trimmed.graphemes(true).count();
+            if 文字数 > Self::最大文字数 {
+                return Err(DomainError::不正な商品説明 {
+                    reason: format!(
+                        "{} 文字以内で入力してください (現在 {} 文字)",
+                        Self::最大文字数,
+                        文字数
+                    ),
+                });
+            }
+            Ok(Self(trimmed.to_string()))
+        }
+        pub fn value(&self) -> &str {
+            &self.0
+        }
+    }
+
+    /// 商品画像のキー (画像ストレージ上のオブジェクトキー。例: products/rose-bouquet/main.jpg)
+    ///
+    /// URL の組み立てはプレゼンテーション層に任せ、ここではキーとして安全な形式かだけを確認する。
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 画像キー(String);
+    impl 画像キー {
+        pub const 最大文字数: usize = 255;
+
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            let invalid = |reason: &str| DomainError::不正な画像キー {
+                reason: reason.to_string(),
+            };
+            if value.is_empty() || value.len() > Self::最大文字数 {
+                return Err(invalid("1〜255 文字で指定してください"));
+            }
+            if !value
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '-' | '_' | '.'))
+            {
+                return Err(invalid("英数字と / - _ . のみ使用できます"));
+            }
+            if value.starts_with('/')
+                || value.split('/').any(|part| part.is_empty() || part == "..")
+            {
+                return Err(invalid("空のパスや .. は使用できません"));
+            }
+            Ok(Self(value.to_string()))
+        }
+        pub fn value(&self) -> &str {
+            &self.0
+        }
+    }
+
+    /// 商品の登録・変更で指定する内容
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct 商品内容 {
+        pub 商品名: 商品名,
+        pub 説明: 商品説明,
+        pub 価格: 商品価格,
+        /// 商品画像 (先頭をメイン画像とする)
+        pub 画像キー: Vec<画像キー>,
+    }
+
+    /// 商品 (手配で届けるカタログの商品)
+    ///
+    /// 販売を終了した商品は新しい予約では選べないが、受付済みの予約からは参照され続けるため削除しない。
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct 商品 {
+        pub id: 商品ID,
+        pub 内容: 商品内容,
+        pub 販売中: bool,
+    }
+    impl 商品 {
+        /// 販売中の商品として登録する
+        pub fn 登録する(内容: 商品内容) -> Self {
+            Self {
+                id: 商品ID::new(),
+                内容,
+                販売中: true,
+            }
+        }
+        pub fn 内容を変更する(&mut self, 内容: 商品内容) {
+            self.内容 = 内容;
+        }
+        pub fn 販売状態を変更する(&mut self, 販売中: bool) {
+            self.販売中 = 販売中;
+        }
+        /// 手配できる商品か確認し、料金計算に使う価格を返す (販売終了は 商品販売終了)
+        pub fn 手配できるか確認する(&self) -> Result<商品価格, DomainError> {
+            if !self.販売中 {
+                return Err(DomainError::商品販売終了(self.id));
+            }
+            Ok(self.内容.価格)
+        }
+    }
+
+    /// 商品検索条件 (カタログ向け)。None の条件は絞り込まない
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct 商品検索条件 {
+        /// 商品名・説明に含まれる文字列 (英字の大文字・小文字は区別しない)
+        pub キーワード: Option<String>,
+        pub 税区分: Option<税区分>,
+        /// 税込価格の下限 (この金額を含む)
+        pub 最低価格: Option<u32>,
+        /// 税込価格の上限 (この金額を含む)
+        pub 最高価格: Option<u32>,
+        /// 販売中の商品だけに絞り込むか
+        pub 販売中のみ: bool,
+    }
+
+    impl 商品検索条件 {
+        pub fn 一致する(&self, 商品: &商品) -> bool {
+            let 内容 = &商品.内容;
+            let 価格 = 内容.価格.税込価格.value();
+            self.キーワード.as_ref().map_or(true, |k| {
+                let k = k.to_lowercase();
+                内容.商品名.value().to_lowercase().contains(&k)
+                    || 内容.説明.value().to_lowercase().contains(&k)
+            }) && self.税区分.map_or(true, |t| 内容.価格.税区分 == t)
+                && self.最低価格.map_or(true, |min| 価格 >= min)
+                && self.最高価格.map_or(true, |max| 価格 <= max)
+                && (!self.販売中のみ || 商品.販売中)
+        }
+    }
+
+    /// 商品検索の結果
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct 商品検索結果 {
+        pub 商品リスト: Vec<商品>,
+        /// ページングに関係なく条件に一致した件数
+        pub 総件数: usize,
+    }
+
+    // --- 在庫 ---
+
+    /// 商品の在庫
+    ///
+    /// 手配の予約を受け付けると商品 1 点につき 1 個を引き当て、発送すると在庫数と引当数の両方から出庫する。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct 商品在庫 {
+        pub 商品id: 商品ID,
+        /// 倉庫にある数
+        pub 在庫数: u32,
+        /// 受付済みで未発送の予約に引き当てた数
+        pub 引当数: u32,
+    }
+    impl 商品在庫 {
+        /// 在庫を登録していない商品の在庫 (予約できない)
+        pub fn 在庫なし(商品id: 商品ID) -> Self {
+            Self {
+                商品id,
+                在庫数: 0,
+                引当数: 0,
+            }
+        }
+        /// 新しい予約に引き当てられる数
+        pub fn 引当可能数(&self) -> u32 {
+            self.在庫数.saturating_sub(self.引当数)
+        }
+        /// 予約に 1 個引き当てる (引当可能数がない場合は 在庫不足)
+        pub fn 引き当てる(&mut self) -> Result<(), DomainError> {
+            if self.引当可能数() == 0 {
+                return Err(DomainError::在庫不足(self.商品id));
+            }
+            self.引当数 += 1;
+            Ok(())
+        }
+        /// キャンセルされた予約に引き当てた 1 個を戻す
+        pub fn 引当を解除する(&mut self) {
+            self.引当数 = self.引当数.saturating_sub(1);
+        }
+        /// 発送した予約に引き当てた 1 個を出庫する
+        pub fn 出庫する(&mut self) {
+            self.在庫数 = self.在庫数.saturating_sub(1);
+            self.引当数 = self.引当数.saturating_sub(1);
+        }
+        /// 棚卸しや入荷で在庫数を変更する (引当数より少なくはできない)
+        pub fn 在庫数を変更する(&mut self, 在庫数: u32) -> Result<(), DomainError> {
+            if 在庫数 < self.引当数 {
+                return Err(DomainError::在庫数不正 {
+                    商品id: self.商品id,
+                    引当数: self.引当数,
+                });
+            }
+            self.在庫数 = 在庫数;
+            Ok(())
+        }
+    }
+
+    /// 予約の保存と同じトランザクションで行う在庫の移動
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum 在庫移動 {
+        /// 予約の手配商品を引き当てる (予約受付)
+        引当,
+        /// 予約に引き当てた在庫を戻す (キャンセル)
+        引当解除,
+        /// 予約に引き当てた在庫を出庫する (発送完了)
+        出庫,
+    }
+
+    /// 予約受付の保存と同じトランザクションで予約IDを記録する Idempotency-Key
+    ///
+    /// 依頼者は予約の依頼者とする。確保日時はキーの使用を開始した (処理中として記録した) 日時で、
+    /// キーを確保したのが自分であることの確認に使う。
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct 冪等性キーの確保 {
+        pub キー: String,
+        pub 確保日時: DateTime<Utc>,
+    }
+
+    // --- ユーザーアカウント ---
+
+    /// 認証基盤が発行する利用者の識別子 (OpenID Connect の sub など。255 文字以内)
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 認証サブジェクト(String);
+    impl 認証サブジェクト {
+        pub const 最大文字数: usize = 255;
+
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            let value = value.trim();
+            if value.is_empty() {
+                return Err(DomainError::不正な認証サブジェクト {
+                    reason: "入力してください".to_string(),
+                });
+            }
+            if value.chars().any(|c| c.is_whitespace() || c.is_control()) {
+                return Err(DomainError::不正な認証サブジェクト {
+                    reason: "空白・制御文字は使用できません".to_string(),
+                });
+            }
+            if value.chars().count() > Self::最大文字数 {
+                return Err(DomainError::不正な認証サブジェクト {
+                    reason: format!("{} 文字以内で指定してください", Self::最大文字数),
+                });
+            }
+            Ok(Self(value.to_string()))
+        }
+        pub fn value(&self) -> &str {
+            &self.0
+        }
+    }
+
+    /// メールアドレス (254 文字以内。ドメイン部は小文字に揃えて保持する)
+    ///
+    /// 到達確認は認証基盤に任せ、ここでは形式だけを確認する。
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct メールアドレス(String);
+    impl メールアドレス {
+        pub const 最大文字数: usize = 254;
+
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            let invalid = || DomainError::不正なメールアドレス {
+                reason: "メールアドレスの形式で入力してください".to_string(),
+            };
+            let value = value.trim();
+            let (ローカル部, ドメイン部) = value.rsplit_once('@').ok_or_else(invalid)?;
+            if ローカル部.is_empty()
+                || ドメイン部.is_empty()
+                || !ドメイン部.contains('.')
+                || ドメイン部.starts_with('.')
+                || ドメイン部.ends_with('.')
+                || !value.is_ascii()
+                || value
+                    .chars()
+                    .any(|c| c.is_ascii_whitespace() || c.is_ascii_control())
+                || ローカル部.contains('@')
+            {
+                return Err(invalid());
+            }
+            if value.len() > Self::最大文字数 {
+                return Err(DomainError::不正なメールアドレス {
+                    reason: format!("{} 文字以内で入力してください", Self::最大文字数),
+                });
+            }
+            Ok(Self(format!(
+                "{}@{}",
+                ローカル部,
+                ドメイン部.to_ascii_lowercase()
+            )))
+        }
+        pub fn value(&self) -> &str {
+            &self.0
+        }
+    }
+
+    /// 表示名 (画面に表示する利用者の名前。50 文字以内)
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 表示名(String);
+    impl 表示名 {
+        pub const 最大文字数: usize = 50;
+
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            一行テキストを検証する(value, Self::最大文字数)
+                .map(Self)
+                .map_err(|reason| DomainError::不正な表示名 { reason })
+        }
+        pub fn value(&self) -> &str {
+            &self.0
+        }
+    }
+
+    /// 差出人名 (プレゼントの送り主として届け先に伝える名前。50 文字以内)
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct 差出人名(String);
+    impl 差出人名 {
+        pub const 最大文字数: usize = 50;
+
+        pub fn new(value: &str) -> Result<Self, DomainError> {
+            一行テキストを検証する(value, Self::最大文字数)
+                .map(Self)
+                .map_err(|reason| DomainError::不正な差出人名 { reason })
+        }
+        pub fn value(&self) -> &str {
+            &self.0
+        }
+    }
+
+    /// ユーザーの役割
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum ユーザーロール {
+        顧客,
+        管理者,
+    }
+
+    /// アカウントの状態 (停止中のアカウントは予約できない)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum アカウント状態 {
+        有効,
+        停止,
+    }
+
+    /// 利用者が変更できるプロフィール
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct プロフィール {
+        pub メールアドレス: メールアドレス,
+        pub 表示名: 表示名,
+        /// 予約時に差出人名を指定しない場合に使う差出人名
+        pub 既定の差出人名: Option<差出人名>,
+    }
+
+    /// ユーザーアカウント (ユーザーIDと認証基盤の利用者を結び付ける)
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct ユーザーアカウント {
+        pub id: ユーザーID,
+        pub 認証サブジェクト: 認証サブジェクト,
+        pub プロフィール: プロフィール,
+        pub ロール: ユーザーロール,
+        pub 状態: アカウント状態,
+    }
+    impl ユーザーアカウント {
+        /// 顧客として有効なアカウントを登録する (管理者への変更は運用で行う)
+        pub fn 登録する(
+            id: ユーザーID,
+            サブジェクト: 認証サブジェクト,
+            プロフィール: プロフィール,
+        ) -> Self {
+            Self {
+                id,
+                認証サブジェクト: サブジェクト,
+                プロフィール,
+                ロール: ユーザーロール::顧客,
+                状態: アカウント状態::有効,
+            }
+        }
+        pub fn プロフィールを変更する(
+            &mut self, プロフィール: プロフィール
+        ) {
+            self.プロフィール = プロフィール;
+        }
+        /// アカウントを停止する (停止中の場合は何もしない)
+        pub fn 停止する(&mut self) {
+            self.状態 = アカウント状態::停止;
+        }
+        /// 停止したアカウントを有効に戻す (有効な場合は何もしない)
+        pub fn 再開する(&mut self) {
+            self.状態 = アカウント状態::有効;
+        }
+        /// 予約できる状態か確認する (停止中は アカウント停止中)
+        pub fn 予約できるか確認する(&self) -> Result<(), DomainError> {
+            match self.状態 {
+                アカウント状態::有効 => Ok(()),
+                アカウント状態::停止 => {
+                    Err(DomainError::アカウント停止中(self.id))
+                }
+            }
+        }
+        /// 管理操作ができるか確認する (停止中は アカウント停止中、顧客は 管理者権限なし)
+        pub fn 管理操作できるか確認する(&self) -> Result<(), DomainError> {
+            self.予約できるか確認する()?;
+            match self.ロール {
+                ユーザーロール::管理者 => Ok(()),
+                ユーザーロール::顧客 => Err(DomainError::管理者権限なし(self.id)),
+            }
+        }
+    }
+
+    // --- ドメインエラー ---
+    #[derive(Error, Debug, PartialEq)]
+    pub enum DomainError {
+        #[error("プレゼント予約が見つかりません: ID={0:?}")]
+        予約NotFound(予約ID),
+        #[error("不正な状態遷移です: 現在の状態={current_state_type}")]
+        不正な状態遷移 { current_state_type: String }, // どの型からの遷移が不正かを示す
+        #[error("必須項目が不足しています: {field}")]
+        必須項目不足 { field: String },
+        #[error("予約に商品が含まれていません")]
+        予約商品空エラー, // 新しいエラーを追加
+        #[error("商品が見つかりません: ID={0:?}")]
+        商品NotFound(商品ID), // これは商品ドメインのエラーかもしれない
+        #[error("販売を終了した商品です: ID={0:?}")]
+        商品販売終了(商品ID),
+        #[error("商品名が不正です: {reason}")]
+        不正な商品名 { reason: String },
+        #[error("商品説明が不正です: {reason}")]
+        不正な商品説明 { reason: String },
+        #[error("画像キーが不正です: {reason}")]
+        不正な画像キー { reason: String },
+        #[error(
+            "商品の価格は 1 円以上 {} 円以下で指定してください: value={value}",
+            商品価格::上限
+        )]
+        不正な商品価格 { value: u32 },
+        #[error("在庫が足りません: 商品ID={0:?}")]
+        在庫不足(商品ID),
+        #[error("在庫数を引当数 ({引当数}) より少なくできません: 商品ID={商品id:?}")]
+        在庫数不正 { 商品id: 商品ID, 引当数: u32 },
+        #[error("不正な金額が指定されました: value={value}")]
+        不正な金額エラー { value: u32 },
+        #[error("不正なメッセージ内容です: {reason}")]
+        不正なメッセージ内容 { reason: String },
+        #[error("記念日が早すぎます: 記念日={anniversary}, 指定できる最も早い日={earliest}")]
+        記念日リードタイム不足 {
+            anniversary: NaiveDate,
+            earliest: NaiveDate,
+        },
+        #[error("発送代行プレゼント情報が不正です: {reason}")]
+        不正な発送代行プレゼント情報 { reason: String },
+        #[error("倉庫到着予定日は {earliest} から {latest} までの日付を指定してください: 倉庫到着予定日={arrival}")]
+        倉庫到着予定日不正 {
+            arrival: NaiveDate,
+            earliest: NaiveDate,
+            latest: NaiveDate,
+        },
+        #[error("キャンセルできません: {reason}")]
+        キャンセル不可 { reason: String },
+        #[error("配送伝票番号が不正です: {reason}")]
+        不正な配送伝票番号 { reason: String },
+        #[error("Idempotency-Key の確保が切れています (別のリクエストが確保し直しました)")]
+        冪等性キー確保切れ,
+        #[error("見積金額が料金と一致しません: 見積金額={estimated}, 料金={calculated}")]
+        見積金額不一致 { estimated: u32, calculated: u32 },
+        #[error("料金の合計が扱える金額の上限を超えています")]
+        料金上限超過,
+        #[error("配送希望日は {earliest} から {latest} までの日付を指定してください: 配送希望日={delivery_date}")]
+        配送希望日範囲外 {
+            delivery_date: NaiveDate,
+            earliest: NaiveDate,
+            latest: NaiveDate,
+        },
+        #[error("届け先が見つかりません: ID={0:?}")]
+        届け先NotFound(届け先ID),
+        #[error("進行中の予約で使われている届け先は削除できません: ID={0:?}")]
+        届け先使用中(届け先ID),
+        #[error("氏名が不正です: {reason}")]
+        不正な氏名 { reason: String },
+        #[error("フリガナが不正です: {reason}")]
+        不正なフリガナ { reason: String },
+        #[error("郵便番号が不正です: {reason}")]
+        不正な郵便番号 { reason: String },
+        #[error("都道府県が不正です: {value}")]
+        不正な都道府県 { value: String },
+        #[error("住所が不正です: {reason}")]
+        不正な住所 { reason: String },
+        #[error("建物名が不正です: {reason}")]
+        不正な建物名 { reason: String },
+        #[error("電話番号が不正です: {reason}")]
+        不正な電話番号 { reason: String },
+        #[error("住所が郵便番号と一致しません: {reason}")]
+        住所不一致 { reason: String },
+        #[error("郵便番号が見つかりません: {0}")]
+        郵便番号NotFound(String),
+        #[error("ユーザーアカウントが見つかりません: ID={0:?}")]
+        ユーザーアカウントNotFound(ユーザーID),
+        #[error("ユーザーアカウントは登録済みです: ID={0:?}")]
+        ユーザーアカウント登録済み(ユーザーID),
+        #[error("認証サブジェクトは別のアカウントで使われています")]
+        認証サブジェクト使用中,
+        #[error("アカウントが停止されています: ID={0:?}")]
+        アカウント停止中(ユーザーID),
+        #[error("アカウントが登録されていません: ID={0:?}")]
+        アカウント未登録(ユーザーID),
+        #[error("管理者の権限がありません: ID={0:?}")]
+        管理者権限なし(ユーザーID),
+        #[error("認証サブジェクトが不正です: {reason}")]
+        不正な認証サブジェクト { reason: String },
+        #[error("メールアドレスが不正です: {reason}")]
+        不正なメールアドレス { reason: String },
+        #[error("表示名が不正です: {reason}")]
+        不正な表示名 { reason: String },
+        #[error("差出人名が不正です: {reason}")]
+        不正な差出人名 { reason: String },
+        // 他に必要なドメイン固有のエラーを追加
+    }
+
+    // --- インフラストラクチャエラー (リポジトリ操作のエラーを表現) ---
+    #[derive(Error, Debug, PartialEq)]
+    pub enum InfrastructureError {
+        #[error("データベース操作エラー: {0}")]
+        DatabaseError(String), // sqlx::Error などをラップすることを想定
+        #[error("接続エラー: {0}")]
+        ConnectionError(String),
+        // 必要に応じて他のインフラエラーを追加
+    }
+
+    /// データストアの稼働状況 (readiness 判定用)
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct DatabaseStatus {
+        /// 疎通確認クエリの往復時間
+        pub latency: std::time::Duration,
+        /// 適用済みの最新マイグレーションのバージョン (管理テーブルがない場合は None)
+        pub migration_version: Option<i64>,
+        /// 接続プールの接続数
+        pub pool_size: u32,
+        /// 接続プールのアイドル接続数
+        pub idle_connections: u32,
+    }
+
+    // --- ドメインイベント ---
+
+    /// 各ドメインイベントに共通する情報
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct イベント情報 {
+        pub 予約id: 予約ID,
+        /// 操作したユーザー (管理操作などで特定できない場合は None)
+        pub 実行者: Option<ユーザーID>,
+        pub 発生日時: DateTime<Tz>,
+    }
+
+    /// プレゼント予約で発生したドメインイベント
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum ドメインイベント {
+        プレゼント予約受け付け完了(イベント情報),
+        予約内容変更完了(イベント情報),
+        予約発送準備開始(イベント情報),
+        予約発送完了(イベント情報),
+        予約配送完了(イベント情報),
+        予約配送失敗(イベント情報),
+        予約返送完了(イベント情報),
+        予約再発送完了(イベント情報),
+        予約キャンセル完了(イベント情報),
+    }
+
+    impl ドメインイベント {
+        /// イベントの共通情報
+        pub fn 情報(&self) -> &イベント情報 {
+            match self {
+                ドメインイベント::プレゼント予約受け付け完了(info)
+                | ドメインイベント::予約内容変更完了(info)
+                | ドメインイベント::予約発送準備開始(info)
+                | ドメインイベント::予約発送完了(info)
+                | ドメインイベント::予約配送完了(info)
+                | ドメインイベント::予約配送失敗(info)
+                | ドメインイベント::予約返送完了(info)
+                | ドメインイベント::予約再発送完了(info)
+                | ドメインイベント::予約キャンセル完了(info) => info,
+            }
+        }
+
+        /// イベント名 (ログや外部連携での識別に使う)
+        pub fn 名前(&self) -> &'static str {
+            match self {
+                ドメインイベント::プレゼント予約受け付け完了(_) => {
+                    "プレゼント予約受け付け完了"
+                }
+                ドメインイベント::予約内容変更完了(_) => "予約内容変更完了",
+                ドメインイベント::予約発送準備開始(_) => "予約発送準備開始",
+                ドメインイベント::予約発送完了(_) => "予約発送完了",
+                ドメインイベント::予約配送完了(_) => "予約配送完了",
+                ドメインイベント::予約配送失敗(_) => "予約配送失敗",
+                ドメインイベント::予約返送完了(_) => "予約返送完了",
+                ドメインイベント::予約再発送完了(_) => "予約再発送完了",
+                ドメインイベント::予約キャンセル完了(_) => "予約キャンセル完了",
+            }
+        }
+    }
+
+    // --- ドメインサービス / ロジック関数 ---
+
+    /// 料金計算 (商品代金・手数料・ラッピング料・送料と消費税から予約の料金を求める)
+    ///
+    /// 金額はいずれも税抜。手数料・ラッピング料・送料は標準税率で課税する。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct 料金計算 {
+        pub 発送代行手数料: u32,
+        pub 標準ラッピング料: u32,
+        pub 特別ラッピング料: u32,
+        pub 送料: u32,
+    }
+    impl Default for 料金計算 {
+        fn default() -> Self {
+            Self {
+                発送代行手数料: 500,
+                標準ラッピング料: 300,
+                特別ラッピング料: 800,
+                送料: 800,
+            }
+        }
+    }
+    impl 料金計算 {
+        /// 料金内訳を計算する (手配の商品はすべて商品価格に含まれている必要がある)
+        pub fn 計算する(
+            &self,
+            プレゼント内容: &プレゼント内容,
+            ラッピング: ラッピング種類,
+            商品価格: &HashMap<商品ID, 商品価格>,
+        ) -> Result<料金内訳, DomainError> {
+            let mut 明細 = Vec::new();
+            match プレゼント内容 {
+                プレゼント内容::発送代行 { .. } => 明細.push(料金明細::new(
+                    料金項目::発送代行手数料,
+                    self.発送代行手数料,
+                    税区分::標準税率,
+                )),
+                プレゼント内容::手配 { 商品idリスト } => {
+                    for 商品id in 商品idリスト {
+                        let 価格 = 商品価格
+                            .get(商品id)
+                            .ok_or(DomainError::商品NotFound(*商品id))?;
+                        明細.push(料金明細::税込から(
+                            料金項目::商品(*商品id),
+                            価格.税込価格.value(),
+                            価格.税区分,
+                        ));
+                    }
+                }
+            }
+            if let Some(行) = self.ラッピング料の明細(ラッピング) {
+                明細.push(行);
+            }
+            明細.push(料金明細::new(
+                料金項目::送料,
+                self.送料,
+                税区分::標準税率,
+            ));
+            明細.sort_by_key(|行| 行.項目);
+            Ok(料金内訳 { 明細 })
+        }
+
+        /// ラッピングを変更した後の料金内訳 (ラッピング料の明細だけを差し替える)
+        pub fn ラッピングを変更する(
+            &self,
+            内訳: &料金内訳,
+            ラッピング: ラッピング種類,
+        ) -> 料金内訳 {
+            let mut 明細: Vec<料金明細> = 内訳
+                .明細
+                .iter()
+                .filter(|行| 行.項目 != 料金項目::ラッピング料)
+                .cloned()
+                .collect();
+            if let Some(行) = self.ラッピング料の明細(ラッピング) {
+                明細.push(行);
+            }
+            明細.sort_by_key(|行| 行.項目);
+            料金内訳 { 明細 }
+        }
+
+        fn ラッピング料の明細(
+            &self, ラッピング: ラッピング種類
+        ) -> Option<料金明細> {
+            let 料金 = match ラッピング {
+                ラッピング種類::なし => return None,
+                ラッピング種類::標準 => self.標準ラッピング料,
+                ラッピング種類::特別 => self.特別ラッピング料,
+            };
+            Some(料金明細::new(
+                料金項目::ラッピング料,
+                料金,
+                税区分::標準税率,
+            ))
+        }
+    }
+
+    /// キャンセルポリシー (記念日までの日数と予約の状態からキャンセルの可否とキャンセル料を決める)
+    ///
+    /// キャンセル料は合計金額に料率を掛けて 1 円未満を切り捨てる。
+    /// 発送済み以降の予約はキャンセルできないが、返送済みの予約は記念日によらずキャンセルできる。
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct キャンセルポリシー {
+        /// 記念日までの日数がこれ未満になるとキャンセルを受け付けない
+        pub キャンセル期限日数: u32,
+        /// 記念日までの日数がこれ以上あれば予約受付済みのキャンセルは無料
+        pub 無料キャンセル期限日数: u32,
+        /// 無料キャンセル期限を過ぎた後のキャンセル料率 (%)
+        pub 直前キャンセル料率: u32,
+        /// 発送準備中の予約のキャンセル料率 (%)。日数による料率と高い方を使う
+        pub 発送準備中キャンセル料率: u32,
+        /// 返送済みの予約のキャンセル料率 (%)
+        pub 返送済みキャンセル料率: u32,
+    }
+    impl Default for キャンセルポリシー {
+        fn default() -> Self {
+            Self {
+                キャンセル期限日数: 1,
+                無料キャンセル期限日数: 7,
+                直前キャンセル料率: 50,
+                発送準備中キャンセル料率: 30,
+                返送済みキャンセル料率: 50,
+            }
+        }
+    }
+    impl キャンセルポリシー {
+        /// 予約の状態と記念日までの日数からキャンセルの可否とキャンセル料を判定する
+        pub fn 判定する(
+            &self,
+            ステータス: 予約ステータス,
+            記念日までの日数: i64,
+            合計金額: 金額,
+        ) -> キャンセル判定 {
+            let 状態による料率 = match ステータス {
+                予約ステータス::予約受付済み => 0,
+                予約ステータス::発送準備中 => self.発送準備中キャンセル料率,
+                予約ステータス::発送済み | 予約ステータス::配送完了 => {
+                    return キャンセル判定::不可 {
+                        理由: "発送後の予約はキャンセルできません".to_string(),
+                    }
+                }
+                予約ステータス::配送失敗 => {
+                    return キャンセル判定::不可 {
+                        理由: "返送を記録してからキャンセルしてください".to_string(),
+                    }
+                }
+                予約ステータス::返送済み => {
+                    // 記念日を過ぎてから戻ることもあるので期限は適用しない
+                    return キャンセル判定::可 {
+                        キャンセル料: 料率を掛ける(
+                            合計金額,
+                            self.返送済みキャンセル料率,
+                        ),
+                    };
+                }
+                予約ステータス::キャンセル済み => {
+                    return キャンセル判定::不可 {
+                        理由: "既にキャンセルされています".to_string(),
+                    }
+                }
+            };
+            if 記念日までの日数 < i64::from(self.キャンセル期限日数) {
+                return キャンセル判定::不可 {
+                    理由: format!(
+                        "記念日の {} 日前を過ぎた予約はキャンセルできません",
+                        self.キャンセル期限日数
+                    ),
+                };
+            }
+            let 日数による料率 = if 記念日までの日数 >= i64::from(self.無料キャンセル期限日数)
+            {
+                0
+            } else {
+                self.直前キャンセル料率
+            };
+            キャンセル判定::可 {
+                キャンセル料: 料率を掛ける(
+                    合計金額,
+                    状態による料率.max(日数による料率),
+                ),
+            }
+        }
+    }
+
+    /// 合計金額に料率 (%、100 が上限) を掛けて 1 円未満を切り捨てる
+    fn 料率を掛ける(合計金額: 金額, 料率: u32) -> u32 {
+        // u32 のまま掛けると 4,300 万円ほどで桁あふれするため u64 で計算する
+        let キャンセル料 = u64::from(合計金額.value()) * u64::from(料率.min(100)) / 100;
+        u32::try_from(キャンセル料).expect("料率は 100% 以下なので合計金額を超えない")
+    }
+
+    /// キャンセルポリシーによる判定結果
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum キャンセル判定 {
+        /// キャンセルできる (キャンセル料は円、税込)
+        可 { キャンセル料: u32 },
+        /// キャンセルできない
+        不可 { 理由: String },
+    }
+
+    /// 手配 (カタログ商品) の予約を受け付ける
+    #[allow(clippy::too_many_arguments)] // TODO: 引数が多いのでコマンドオブジェクト等でのリファクタリングを検討
+    pub fn 手配予約を受け付ける(
+        依頼者id: ユーザーID,
+        届け先id: 届け先ID,
+        記念日: 記念日,
+        メッセージ内容: Option<メッセージ内容>,
+        ラッピング: ラッピング種類,
+        配送希望: Option<配送希望>,
+        商品idリスト: HashSet<商品ID>,
+        支払いid: 支払いID,
+        料金内訳: 料金内訳,
+        受付日時: DateTime<Tz>,
+        リードタイム: 発送リードタイム,
+    ) -> Result<(予約受付済みプレゼント予約型, Vec<ドメインイベント>), DomainError> {
+        予約を受け付ける(
+            プレゼント予約ベース {
+                id: 予約ID::new(),
+                依頼者id,
+                届け先id,
+                記念日,
+                メッセージ内容,
+                ラッピング,
+                配送希望,
+                合計金額: 金額::new(料金内訳.合計()?)?,
+                料金内訳,
+                支払いid,
+                プレゼント内容: プレゼント内容::手配 { 商品idリスト },
+            },
+            受付日時,
+            リードタイム,
+        )
+    }
+
+    /// 発送代行 (依頼者が用意したプレゼントを倉庫から発送する) の予約を受け付ける
+    ///
+    /// 倉庫到着予定日は受付日以降でなければならない。
+    #[allow(clippy::too_many_arguments)] // TODO: 引数が多いのでコマンドオブジェクト等でのリファクタリングを検討
+    pub fn 発送代行予約を受け付ける(
+        依頼者id: ユーザーID,
+        届け先id: 届け先ID,
+        記念日: 記念日,
+        メッセージ内容: Option<メッセージ内容>,
+        ラッピング: ラッピング種類,
+        配送希望: Option<配送希望>,
+        情報: 発送代行プレゼント情報,
+        倉庫到着予定日: NaiveDate,
+        支払いid: 支払いID,
+        料金内訳: 料金内訳,
+        受付日時: DateTime<Tz>,
+        リードタイム: 発送リードタイム,
+    ) -> Result<(予約受付済みプレゼント予約型, Vec<ドメインイベント>), DomainError> {
+        let 受付日 = 受付日時.date_naive();
+        if 倉庫到着予定日 < 受付日 {
+            return Err(DomainError::倉庫到着予定日不正 {
+                arrival: 倉庫到着予定日,
+                earliest: 受付日,
+                latest: 倉庫到着期限(配送希望.as_ref(), &記念日),
+            });
+        }
+        予約を受け付ける(
+            プレゼント予約ベース {
+                id: 予約ID::new(),
+                依頼者id,
+                届け先id,
+                記念日,
+                メッセージ内容,
+                ラッピング,
+                配送希望,
+                合計金額: 金額::new(料金内訳.合計()?)?,
+                料金内訳,
+                支払いid,
+                プレゼント内容: プレゼント内容::発送代行 {
+                    情報,
+                    倉庫到着予定日,
+                },
+            },
+            受付日時,
+            リードタイム,
+        )
+    }
+
+    /// 受付条件を満たす場合に予約受付済みの予約を作る (プレゼント内容の種類によらない共通処理)
+    fn 予約を受け付ける(
+        base: プレゼント予約ベース,
+        受付日時: DateTime<Tz>,
+        リードタイム: 発送リードタイム,
+    ) -> Result<(予約受付済みプレゼント予約型, Vec<ドメインイベント>), DomainError> {
+        受付条件を検証する(&base, 受付日時, リードタイム)?;
+        let イベント =
+            ドメインイベント::プレゼント予約受け付け完了(イベント情報 {
+                予約id: base.id,
+                実行者: Some(base.依頼者id),
+                発生日時: 受付日時,
+            });
+        Ok((予約受付済みプレゼント予約型 { base }, vec![イベント]))
+    }
+
+    /// 発送代行のプレゼントが倉庫に届いていなければならない日 (お届け日の前日)
+    fn 倉庫到着期限(配送希望: Option<&配送希望>, 記念日: &記念日) -> NaiveDate {
+        let お届け日 = 配送希望.map_or(記念日.value, |希望| 希望.配送日);
+        お届け日.pred_opt().unwrap_or(お届け日)
+    }
+
+    /// 予約受付時の不変条件 (予約の変更時にも同じ条件を満たす必要がある)
+    fn 受付条件を検証する(
+        base: &プレゼント予約ベース,
+        基準日時: DateTime<Tz>,
+        リードタイム: 発送リードタイム,
+    ) -> Result<(), DomainError> {
+        match &base.プレゼント内容 {
+            プレゼント内容::手配 { 商品idリスト } if 商品idリスト.is_empty() => {
+                return Err(DomainError::予約商品空エラー);
+            }
+            // 発送代行のプレゼントはお届け日の前日までに倉庫へ届いている必要がある
+            プレゼント内容::発送代行 {
+                倉庫到着予定日,
+            ..
+            } => {
+                let 期限 = 倉庫到着期限(base.配送希望.as_ref(), &base.記念日);
+                if *倉庫到着予定日 > 期限 {
+                    return Err(DomainError::倉庫到着予定日不正 {
+                        arrival: *倉庫到着予定日,
+                        earliest: 基準日時.date_naive(),
+                        latest: 期限,
+                    });
+                }
+            }
+            _ => {}
+        }
+        // 記念日は受付日から発送リードタイム以上先の日付でなければならない
+        let 最短配送日 = リードタイム.最短配送日(基準日時.date_naive());
+        if base.記念日.value < 最短配送日 {
+            return Err(DomainError::記念日リードタイム不足 {
+                anniversary: base.記念日.value,
+                earliest: 最短配送日,
+            });
+        }
+        // 配送希望日は最短配送日から記念日までの間でなければならない
+        if let Some(希望) = &base.配送希望 {
+            if 希望.配送日 < 最短配送日 || 希望.配送日 > base.記念日.value {
+                return Err(DomainError::配送希望日範囲外 {
+                    delivery_date: 希望.配送日,
+                    earliest: 最短配送日,
+                    latest: base.記念日.value,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// 予約内容の変更 (None の項目は変更しない)
+    ///
+    /// メッセージ内容と配送希望は `Some(None)` を指定すると取り消せる。
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct 予約変更内容 {
+        pub メッセージ内容: Option<Option<メッセージ内容>>,
+        pub ラッピング: Option<ラッピング種類>,
+        pub 配送希望: Option<Option<配送希望>>,
+        /// 依頼者が画面で見た変更後の合計金額 (ラッピングを変更する場合は必須)
+        pub 見積金額: Option<金額>,
+    }
+
+    // 例: 状態遷移の関数
+    impl 予約受付済みプレゼント予約型 {
+        /// 予約内容を変更する (発送準備の開始前のみ)
+        ///
+        /// 変更後の内容は、変更日時を受付日時とみなして受付時と同じ不変条件を満たす必要がある。
+        pub fn 予約内容を変更する(
+            self,
+            変更: 予約変更内容,
+            変更日時: DateTime<Tz>,
+            リードタイム: 発送リードタイム,
+            料金計算: &料金計算,
+        ) -> Result<(予約受付済みプレゼント予約型, Vec<ドメインイベント>), DomainError> {
+            let mut base = self.base;
+            if let Some(メッセージ内容) = 変更.メッセージ内容 {
+                base.メッセージ内容 = メッセージ内容;
+            }
+            if let Some(ラッピング) = 変更.ラッピング {
+                base.ラッピング = ラッピング;
+                // 料金内訳のない (料金計算の導入前の) 予約は合計金額を変えない
+                if !base.料金内訳.明細.is_empty() {
+                    base.料金内訳 =
+                        料金計算.ラッピングを変更する(&base.料金内訳, ラッピング);
+                    base.合計金額 = 金額::new(base.料金内訳.合計()?)?;
+                }
+                // 受付と同じく、依頼者が見た金額と変更後の料金が一致する場合のみ変更する
+                let 見積金額 = 変更.見積金額.ok_or_else(|| DomainError::必須項目不足 {
+                    field: "見積金額".to_string(),
+                })?;
+                if 見積金額 != base.合計金額 {
+                    return Err(DomainError::見積金額不一致 {
+                        estimated: 見積金額.value(),
+                        calculated: base.合計金額.value(),
+                    });
+                }
+            }
+            if let Some(配送希望) = 変更.配送希望 {
+                base.配送希望 = 配送希望;
+            }
+            受付条件を検証する(&base, 変更日時, リードタイム)?;
+            let イベント = ドメインイベント::予約内容変更完了(イベント情報 {
+                予約id: base.id,
+                実行者: Some(base.依頼者id),
+                発生日時: 変更日時,
+            });
+            Ok((予約受付済みプレゼント予約型 { base }, vec![イベント]))
+        }
+
+        pub fn 発送準備を開始する(
+            self,
+            梱包担当者id: ユーザーID,
+            開始日時: DateTime<Tz>,
+        ) -> Result<(発送準備中プレゼント予約型, Vec<ドメインイベント>), DomainError> {
+            let イベント = ドメインイベント::予約発送準備開始(イベント情報 {
+                予約id: self.base.id,
+                実行者: Some(梱包担当者id),
+                発生日時: 開始日時,
+            });
+            Ok((
+                発送準備中プレゼント予約型 {
+                    base: self.base,
+                    梱包担当者id, // 受け取ったIDを設定
+                },
+                vec![イベント],
+            ))
+        }
+        pub fn 予約をキャンセルする(
+            self,
+            理由: Option<String>,
+            ポリシー: &キャンセルポリシー,
+            実行者: Option<ユーザーID>,
+            実行日時: DateTime<Tz>,
+        ) -> Result<(キャンセル済みプレゼント予約型, Vec<ドメインイベント>), DomainError> {
+            let 判定 = ポリシー.判定する(
+                予約ステータス::予約受付済み,
+                self.base.記念日までの日数(実行日時),
+                self.base.合計金額,
+            );
+            キャンセルする(self.base, 理由, 判定, 実行者, 実行日時)
+        }
+    }
+
+    impl 発送準備中プレゼント予約型 {
+        pub fn 発送を完了する(
+            self,
+            配送伝票番号: 配送伝票番号,
+            実行者: Option<ユーザーID>,
+            完了日時: DateTime<Tz>,
+        ) -> Result<(発送済みプレゼント予約型, Vec<ドメインイベント>), DomainError> {
+            let イベント = ドメインイベント::予約発送完了(イベント情報 {
+                予約id: self.base.id,
+                実行者,
+                発生日時: 完了日時,
+            });
+            Ok((
+                発送済みプレゼント予約型 {
+                    base: self.base,
+                    配送伝票番号,
+                },
+                vec![イベント],
+            ))
+        }
+        pub fn 予約をキャンセルする(
+            self,
+            理由: Option<String>,
+            ポリシー: &キャンセルポリシー,
+            実行者: Option<ユーザーID>,
+            実行日時: DateTime<Tz>,
+        ) -> Result<(キャンセル済みプレゼント予約型, Vec<ドメインイベント>), DomainError> {
+            let 判定 = ポリシー.判定する(
+                予約ステータス::発送準備中,
+                self.base.記念日までの日数(実行日時),
+                self.base.合計金額,
+            );
+            キャンセルする(self.base, 理由, 判定, 実行者, 実行日時)
+        }
+    }
+
+    impl 発送済みプレゼント予約型 {
+        pub fn 配送完了を記録する(
+            self,
+            記録日時: DateTime<Tz>, // Tokyo -> Tz
+            実行者: Option<ユーザーID>,
+        ) -> Result<(配送完了プレゼント予約型, Vec<ドメインイベント>), DomainError> {
+            let イベント = ドメインイベント::予約配送完了(イベント情報 {
+                予約id: self.base.id,
+                実行者,
+                発生日時: 記録日時,
+            });
+            Ok((
+                配送完了プレゼント予約型 {
+                    base: self.base,
+                    配送伝票番号: self.配送伝票番号,
+                    配送完了日時: 記録日時,
+                },
+                vec![イベント],
+            ))
+        }
+        pub fn 配送失敗を記録する(
+            self,
+            理由: 配送失敗理由,
+            記録日時: DateTime<Tz>,
+            実行者: Option<ユーザーID>,
+        ) -> Result<(配送失敗プレゼント予約型, Vec<ドメインイベント>), DomainError> {
+            let イベント = ドメインイベント::予約配送失敗(イベント情報 {
+                予約id: self.base.id,
+                実行者,
+                発生日時: 記録日時,
+            });
+            Ok((
+                配送失敗プレゼント予約型 {
+                    base: self.base,
+                    配送伝票番号: self.配送伝票番号,
+                    配送失敗理由: 理由,
+                    配送失敗日時: 記録日時,
+                },
+                vec![イベント],
+            ))
+        }
+    }
+
+    impl 配送失敗プレゼント予約型 {
+        pub fn 返送を記録する(
+            self,
+            記録日時: DateTime<Tz>,
+            実行者: Option<ユーザーID>,
+        ) -> Result<(返送済みプレゼント予約型, Vec<ドメインイベント>), DomainError> {
+            let イベント = ドメインイベント::予約返送完了(イベント情報 {
+                予約id: self.base.id,
+                実行者,
+                発生日時: 記録日時,
+            });
+            Ok((
+                返送済みプレゼント予約型 {
+                    base: self.base,
+                    配送伝票番号: self.配送伝票番号,
+                    配送失敗理由: self.配送失敗理由,
+                    配送失敗日時: self.配送失敗日時,
+                    返送日時: 記録日時,
+                },
+                vec![イベント],
+            ))
+        }
+    }
+
+    impl 返送済みプレゼント予約型 {
+        /// 新しい配送伝票番号で発送し直す (返送された荷物と同じ番号は使えない)
+        pub fn 再発送する(
+            self,
+            配送伝票番号: 配送伝票番号,
+            実行者: Option<ユーザーID>,
+            発送日時: DateTime<Tz>,
+        ) -> Result<(発送済みプレゼント予約型, Vec<ドメインイベント>), DomainError> {
+            if 配送伝票番号 == self.配送伝票番号 {
+                return Err(DomainError::不正な配送伝票番号 {
+                    reason: "返送された荷物と同じ配送伝票番号は使えません".to_string(),
+                });
+            }
+            let イベント = ドメインイベント::予約再発送完了(イベント情報 {
+                予約id: self.base.id,
+                実行者,
+                発生日時: 発送日時,
+            });
+            Ok((
+                発送済みプレゼント予約型 {
+                    base: self.base,
+                    配送伝票番号,
+                },
+                vec![イベント],
+            ))
+        }
+        pub fn 予約をキャンセルする(
+            self,
+            理由: Option<String>,
+            ポリシー: &キャンセルポリシー,
+            実行者: Option<ユーザーID>,
+            実行日時: DateTime<Tz>,
+        ) -> Result<(キャンセル済みプレゼント予約型, Vec<ドメインイベント>), DomainError> {
+            let 判定 = ポリシー.判定する(
+                予約ステータス::返送済み,
+                self.base.記念日までの日数(実行日時),
+                self.base.合計金額,
+            );
+            キャンセルする(self.base, 理由, 判定, 実行者, 実行日時)
+        }
+    }
+
+    /// 受付済み・発送準備中・返送済みに共通のキャンセル処理 (キャンセルポリシーで不可の場合はエラー)
+    fn キャンセルする(
+        base: プレゼント予約ベース,
+        理由: Option<String>,
+        判定: キャンセル判定,
+        実行者: Option<ユーザーID>,
+        実行日時: DateTime<Tz>,
+    ) -> Result<(キャンセル済みプレゼント予約型, Vec<ドメインイベント>), DomainError> {
+        let キャンセル料 = match 判定 {
+            キャンセル判定::可 { キャンセル料 } => キャンセル料,
+            キャンセル判定::不可 { 理由 } => {
+                return Err(DomainError::キャンセル不可 { reason: 理由 })
+            }
+        };
+        let イベント = ドメインイベント::予約キャンセル完了(イベント情報 {
+            予約id: base.id,
+            実行者,
+            発生日時: 実行日時,
+        });
+        Ok((
+            キャンセル済みプレゼント予約型 {
+                base,
+                キャンセル理由: 理由,
+                キャンセル日時: 実行日時,
+                キャンセル料,
+            },
+            vec![イベント],
+        ))
+    }
+
+    // 他の状態遷移関数も同様に定義
+
+    // --- 一覧取得 ---
+
+    /// 予約一覧のカーソル (記念日 → 作成日時 → 予約ID の順で並べたときの最後の位置)
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct 予約一覧カーソル {
+        pub 記念日: NaiveDate,
+        pub 作成日時: DateTime<Utc>,
+        pub 予約id: 予約ID,
+    }
+
+    /// 予約一覧の1ページ分
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct 予約一覧ページ {
+        pub 予約リスト: Vec<プレゼント予約状態>,
+        /// 続きがある場合のみ Some
+        pub 次のカーソル: Option<予約一覧カーソル>,
+    }
+
+    /// 予約検索の並び順
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum 予約検索の並び順 {
+        #[default]
+        記念日昇順,
+        記念日降順,
+        作成日時昇順,
+        作成日時降順,
+    }
+
+    /// 予約検索条件 (管理者向け)。None の条件は絞り込まない
+    #[derive(Debug, Clone, PartialEq, Eq, Default)]
+    pub struct 予約検索条件 {
+        /// いずれかのステータスに一致 (空なら絞り込まない)
+        pub ステータス: Vec<予約ステータス>,
+        pub 記念日_開始: Option<NaiveDate>,
+        pub 記念日_終了: Option<NaiveDate>,
+        pub ラッピング: Option<ラッピング種類>,
+        pub 依頼者id: Option<ユーザーID>,
+        pub 届け先id: Option<届け先ID>,
+        pub 作成日時_開始: Option<DateTime<Utc>>,
+        pub 作成日時_終了: Option<DateTime<Utc>>,
+        pub 並び順: 予約検索の並び順,
+    }
+
+    impl 予約検索条件 {
+        /// 作成日時以外の条件に一致するか (作成日時はリポジトリが管理するため別途判定する)
+        pub fn 一致する(&self, reservation: &プレゼント予約状態) -> bool {
+            let base = reservation.base();
+            (self.ステータス.is_empty() || self.ステータス.contains(&reservation.ステータス()))
+                && self.記念日_開始.map_or(true, |d| base.記念日.value >= d)
+                && self.記念日_終了.map_or(true, |d| base.記念日.value <= d)
+                && self.ラッピング.map_or(true, |w| base.ラッピング == w)
+                && self.依頼者id.map_or(true, |id| base.依頼者id == id)
+                && self.届け先id.map_or(true, |id| base.届け先id == id)
+        }
+    }
+
+    /// 予約検索の結果
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct 予約検索結果 {
+        pub 予約リスト: Vec<プレゼント予約状態>,
+        /// ページングに関係なく条件に一致した件数
+        pub 総件数: usize,
+    }
+
+    // --- リポジトリインターフェース (トレイト) ---
+    #[cfg_attr(test, mockall::automock)]
+    #[async_trait]
+    pub trait プレゼント予約Repository: Send + Sync {
+        async fn save(&self, reservation: &プレゼント予約状態) -> Result<(), DomainError>;
+
+        /// 予約を保存し、同じトランザクションで予約の手配商品の在庫を移動する
+        ///
+        /// 引当で引当可能数が足りない商品がある場合は予約も保存せず 在庫不足 を返す。
+        /// 引当解除・出庫はこの予約に引き当てた在庫だけを対象にする (引き当てていなければ何もしない)。
+        async fn save_with_stock_movement(
+            &self,
+            reservation: &プレゼント予約状態,
+            移動: 在庫移動,
+        ) -> Result<(), DomainError>;
+
+        /// 受け付けた予約を在庫の引当とともに保存し、同じトランザクションで Idempotency-Key に予約IDを記録する
+        ///
+        /// キーの記録が確保したときのもの (予約ID未記録) でなくなっていた場合は、
+        /// 予約も保存せず 冪等性キー確保切れ を返す。
+        async fn save_accepted_with_idempotency_key(
+            &self,
+            reservation: &プレゼント予約状態,
+            キー: &冪等性キーの確保,
+        ) -> Result<(), DomainError>;
+
+        async fn find_by_id(
+            &self,
+            id: &予約ID,
+        ) -> Result<Option<プレゼント予約状態>, DomainError>;
+
+        /// 依頼者の予約を 記念日 → 作成日時 の順に、カーソル位置の次から最大 limit 件取得する
+        async fn find_by_requester(
+            &self,
+            依頼者id: &ユーザーID,
+            cursor: Option<予約一覧カーソル>,
+            limit: usize,
+        ) -> Result<予約一覧ページ, InfrastructureError>;
+
+        /// 検索条件に一致する予約を並び順に従って offset 件目から最大 limit 件取得する
+        async fn search(
+            &self,
+            条件: &予約検索条件,
+            offset: usize,
+            limit: usize,
+        ) -> Result<予約検索結果, InfrastructureError>;
+
+        /// リポジトリ（主にDB）への接続性を確認する
+        async fn check_db_connection(&self) -> Result<(), InfrastructureError>; // これで InfrastructureError が見つかるはず
+
+        /// readiness 判定のためにデータストアの詳細な稼働状況を取得する
+        async fn database_status(&self) -> Result<DatabaseStatus, InfrastructureError>;
+    }
+
+    /// 商品カタログの保存先
+    #[cfg_attr(test, mockall::automock)]
+    #[async_trait]
+    pub trait 商品Repository: Send + Sync {
+        async fn save(&self, 商品: &商品) -> Result<(), DomainError>;
+        async fn find_by_id(&self, id: &商品ID) -> Result<Option<商品>, DomainError>;
+
+        /// 検索条件に一致する商品を登録順に offset 件目から最大 limit 件取得する
+        async fn search(
+            &self,
+            条件: &商品検索条件,
+            offset: usize,
+            limit: usize,
+        ) -> Result<商品検索結果, DomainError>;
+    }
+
+    /// 商品の在庫の保存先
+    ///
+    /// 予約による在庫の移動は プレゼント予約Repository::save_with_stock_movement で行う。
+    #[cfg_attr(test, mockall::automock)]
+    #[async_trait]
+    pub trait 商品在庫Repository: Send + Sync {
+        /// 在庫を取得する (在庫を登録していない商品は 在庫なし)
+        async fn find_by_product_id(
+            &self, 商品id: &商品ID
+        ) -> Result<商品在庫, DomainError>;
+
+        /// 在庫数を変更する (引当数より少ない場合は 在庫数不正)
+        async fn set_on_hand(
+            &self,
+            商品id: &商品ID,
+            在庫数: u32,
+        ) -> Result<商品在庫, DomainError>;
+    }
+
+    /// ドメインイベントの発行先 (通知・外部連携などの購読者へ届ける)
+    #[cfg_attr(test, mockall::automock)]
+    #[async_trait]
+    pub trait イベント発行者: Send + Sync {
+        async fn 発行する(
+            &self,
+            イベント: &[ドメインイベント],
+        ) -> Result<(), InfrastructureError>;
+    }
+
+    /// 郵便番号から住所の候補を引く辞書 (日本郵便の郵便番号データ)
+    #[cfg_attr(test, mockall::automock)]
+    #[async_trait]
+    pub trait 郵便番号辞書: Send + Sync {
+        async fn 住所候補を取得する(
+            &self,
+            番号: &郵便番号,
+        ) -> Result<Vec<住所候補>, DomainError>;
+    }
+
+    /// 届け先の保存先
+    #[cfg_attr(test, mockall::automock)]
+    #[async_trait]
+    pub trait 届け先Repository: Send + Sync {
+        async fn save(&self, 届け先: &届け先) -> Result<(), DomainError>;
+        async fn find_by_id(&self, id: &届け先ID) -> Result<Option<届け先>, DomainError>;
+
+        /// 所有者の届け先を登録順に取得する
+        async fn find_by_owner(
+            &self, 所有者id: &ユーザーID
+        ) -> Result<Vec<届け先>, DomainError>;
+
+        async fn delete(&self, id: &届け先ID) -> Result<(), DomainError>;
+    }
+
+    /// ユーザーアカウントの保存先
+    #[cfg_attr(test, mockall::automock)]
+    #[async_trait]
+    pub trait ユーザーアカウントRepository: Send + Sync {
+        /// 新しいアカウントとして保存する
+        ///
+        /// 同じIDのアカウントがあれば ユーザーアカウント登録済み、
+        /// 認証サブジェクトが別のアカウントで使われていれば 認証サブジェクト使用中 とする。
+        async fn insert(
+            &self, アカウント: &ユーザーアカウント
+        ) -> Result<(), DomainError>;
+        async fn save(
+            &self, アカウント: &ユーザーアカウント
+        ) -> Result<(), DomainError>;
+        async fn find_by_id(
+            &self,
+            id: &ユーザーID,
+        ) -> Result<Option<ユーザーアカウント>, DomainError>;
+        async fn find_by_auth_subject(
+            &self,
+            サブジェクト: &認証サブジェクト,
+        ) -> Result<Option<ユーザーアカウント>, DomainError>;
+    }
+} // End of pub mod core
+
+// Re-export all public items from the core module
+pub use core::*;
+
+// --- Domain Tests ---
+#[cfg(test)]
+mod tests {
+    use super::core::*; // Use items from the inner core module now
+    use chrono::{DateTime, NaiveDate, TimeZone, Utc}; // TimeZone, Utc を削除
+    use chrono_tz::Asia::Tokyo;
+    use chrono_tz::Tz;
+    use std::collections::{HashMap, HashSet};
+    use uuid::Uuid; // Tokyo を use -> 削除
+
+    /// テスト用の受付日時 (各テストの記念日より十分前の平日)
+    fn テスト受付日時() -> DateTime<Tz> {
+        Tokyo.with_ymd_and_hms(2024, 12, 2, 10, 0, 0).unwrap()
+    }
+
+    /// テスト用の料金内訳 (送料のみ)
+    fn テスト料金内訳() -> 料金内訳 {
+        料金内訳 {
+            明細: vec![料金明細::new(料金項目::送料, 800, 税区分::標準税率)],
+        }
+    }
+
+    // --- 値オブジェクトのテスト ---
+
+    #[test]
+    fn test_id_value_objects_creation_and_access() {
+        // new() で生成
+        let yoyaku_id = 予約ID::new();
+        let user_id = ユーザーID::new();
+        let todokesaki_id = 届け先ID::new();
+        let shohin_id = 商品ID::new();
+        let shiharai_id = 支払いID::new();
 
         // as_uuid() でアクセスできるか
         assert_ne!(yoyaku_id.as_uuid().to_string(), "");
@@ -412,6 +2600,536 @@ mod tests {
         );
     }
 
+    // --- 届け先テスト ---
+
+    #[test]
+    fn test_氏名_converts_furigana_to_katakana() {
+        let 名前 = 氏名::new(" 山田 太郎 ", "やまだ　ﾀﾛｳ").unwrap();
+        assert_eq!(名前.表記(), "山田 太郎");
+        assert_eq!(名前.フリガナ(), "ヤマダ タロウ");
+    }
+
+    #[test]
+    fn test_氏名_rejects_non_kana_furigana() {
+        assert!(matches!(
+            氏名::new("山田太郎", "山田タロウ"),
+            Err(DomainError::不正なフリガナ { .. })
+        ));
+        assert!(matches!(
+            氏名::new("  ", "ヤマダタロウ"),
+            Err(DomainError::不正な氏名 { .. })
+        ));
+    }
+
+    #[test]
+    fn test_郵便番号_normalizes_hyphen_and_width() {
+        let 番号 = 郵便番号::new("〒１００－０００１").unwrap();
+        assert_eq!(番号.value(), "1000001");
+        assert_eq!(番号.表示(), "100-0001");
+        assert!(matches!(
+            郵便番号::new("100-001"),
+            Err(DomainError::不正な郵便番号 { .. })
+        ));
+    }
+
+    #[test]
+    fn test_都道府県_code_and_name_round_trip() {
+        assert_eq!(都道府県::北海道.コード(), 1);
+        assert_eq!(都道府県::東京都.コード(), 13);
+        assert_eq!(都道府県::沖縄県.コード(), 47);
+        for 県 in 都道府県::一覧 {
+            assert_eq!(都道府県::from_コード(県.コード()), Some(県));
+            assert_eq!(都道府県::from_名前(&県.名前()), Ok(県));
+        }
+        assert_eq!(都道府県::from_コード(0), None);
+        assert_eq!(都道府県::from_コード(48), None);
+        assert!(matches!(
+            都道府県::from_名前("東京"),
+            Err(DomainError::不正な都道府県 { .. })
+        ));
+    }
+
+    #[test]
+    fn test_電話番号_validation() {
+        assert_eq!(電話番号::new("03-1234-5678").unwrap().value(), "0312345678");
+        assert_eq!(
+            電話番号::new("０９０ １２３４ ５６７８").unwrap().value(),
+            "09012345678"
+        );
+        assert!(電話番号::new("1234567890").is_err());
+        assert!(電話番号::new("03-1234-567").is_err());
+        assert!(電話番号::new("090-1234-56789").is_err());
+    }
+
+    #[test]
+    fn test_届け先内容_住所候補と照合する() {
+        let 内容 = 届け先内容 {
+            氏名: 氏名::new("山田 花子", "ヤマダ ハナコ").unwrap(),
+            郵便番号: 郵便番号::new("100-0001").unwrap(),
+            都道府県: 都道府県::東京都,
+            住所: 住所::new("千代田区千代田1-1").unwrap(),
+            建物名: None,
+            電話番号: 電話番号::new("03-1234-5678").unwrap(),
+        };
+        let 候補 = |都道府県, 市区町村: &str| 住所候補 {
+            都道府県,
+            市区町村: 市区町村.to_string(),
+            町域: String::new(),
+        };
+
+        assert_eq!(
+            内容.住所候補と照合する(&[
+                候補(都道府県::東京都, "中央区"),
+                候補(都道府県::東京都, "千代田区"),
+            ]),
+            Ok(())
+        );
+        assert!(matches!(
+            内容.住所候補と照合する(&[候補(都道府県::大阪府, "千代田区")]),
+            Err(DomainError::住所不一致 { .. })
+        ));
+        assert!(matches!(
+            内容.住所候補と照合する(&[]),
+            Err(DomainError::不正な郵便番号 { .. })
+        ));
+    }
+
+    #[test]
+    fn test_住所_and_建物名_reject_too_long() {
+        assert!(住所::new("千代田区千代田1-1").is_ok());
+        assert!(matches!(
+            住所::new(&"あ".repeat(住所::最大文字数 + 1)),
+            Err(DomainError::不正な住所 { .. })
+        ));
+        assert!(matches!(
+            建物名::new("ハイツ\n101"),
+            Err(DomainError::不正な建物名 { .. })
+        ));
+    }
+
+    // --- 商品テスト ---
+
+    fn テスト商品内容() -> 商品内容 {
+        商品内容 {
+            商品名: 商品名::new("バラの花束").unwrap(),
+            説明: 商品説明::new("赤いバラ 12 本の花束です。\r\nメッセージカード付き").unwrap(),
+            価格: 商品価格 {
+                税込価格: 金額::new(5500).unwrap(),
+                税区分: 税区分::標準税率,
+            },
+            画像キー: vec![画像キー::new("products/rose-bouquet/main.jpg").unwrap()],
+        }
+    }
+
+    #[test]
+    fn test_商品価格_validation() {
+        assert!(商品価格::new(商品価格::上限, 税区分::標準税率).is_ok());
+        for value in [0, 商品価格::上限 + 1, u32::MAX] {
+            assert_eq!(
+                商品価格::new(value, 税区分::標準税率),
+                Err(DomainError::不正な商品価格 { value })
+            );
+        }
+    }
+
+    #[test]
+    fn test_画像キー_validation() {
+        assert!(画像キー::new("products/rose_bouquet-01.jpg").is_ok());
+        for value in [
+            "",
+            "/products/a.jpg",
+            "products//a.jpg",
+            "products/../a.jpg",
+            "商品/a.jpg",
+            "a b.jpg",
+        ] {
+            assert!(
+                matches!(
+                    画像キー::new(value),
+                    Err(DomainError::不正な画像キー { .. })
+                ),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_商品_手配できるか確認する() {
+        let mut 商品 = 商品::登録する(テスト商品内容());
+        assert_eq!(
+            商品.内容.説明.value(),
+            "赤いバラ 12 本の花束です。\nメッセージカード付き"
+        );
+        assert_eq!(商品.手配できるか確認する(), Ok(テスト商品内容().価格));
+
+        商品.販売状態を変更する(false);
+        assert_eq!(
+            商品.手配できるか確認する(),
+            Err(DomainError::商品販売終了(商品.id))
+        );
+    }
+
+    #[test]
+    fn test_商品検索条件_一致する() {
+        let 商品 = 商品::登録する(テスト商品内容());
+        let 一致する = |条件: 商品検索条件| 条件.一致する(&商品);
+
+        assert!(一致する(商品検索条件::default()));
+        assert!(一致する(商品検索条件 {
+            キーワード: Some("メッセージカード".to_string()),
+            最低価格: Some(5500),
+            最高価格: Some(5500),
+            販売中のみ: true,
+            ..Default::default()
+        }));
+        assert!(!一致する(商品検索条件 {
+            キーワード: Some("チョコレート".to_string()),
+            ..Default::default()
+        }));
+        assert!(!一致する(商品検索条件 {
+            税区分: Some(税区分::軽減税率),
+            ..Default::default()
+        }));
+        assert!(!一致する(商品検索条件 {
+            最高価格: Some(5499),
+            ..Default::default()
+        }));
+    }
+
+    // --- 在庫テスト ---
+
+    #[test]
+    fn test_商品在庫_引当と出庫() {
+        let 商品id = 商品ID::new();
+        let mut 在庫 = 商品在庫::在庫なし(商品id);
+        assert_eq!(在庫.引き当てる(), Err(DomainError::在庫不足(商品id)));
+
+        在庫.在庫数を変更する(2).unwrap();
+        在庫.引き当てる().unwrap();
+        在庫.引き当てる().unwrap();
+        assert_eq!(在庫.引き当てる(), Err(DomainError::在庫不足(商品id)));
+        assert_eq!(
+            在庫.在庫数を変更する(1),
+            Err(DomainError::在庫数不正 {
+                商品id, 引当数: 2
+            })
+        );
+
+        // キャンセルで戻した分は再び引き当てられ、出庫すると在庫数も減る
+        在庫.引当を解除する();
+        assert_eq!(在庫.引当可能数(), 1);
+        在庫.出庫する();
+        assert_eq!((在庫.在庫数, 在庫.引当数, 在庫.引当可能数()), (1, 0, 1));
+    }
+
+    // --- ユーザーアカウントテスト ---
+
+    fn テストプロフィール() -> プロフィール {
+        プロフィール {
+            メールアドレス: メールアドレス::new("hanako@Example.COM").unwrap(),
+            表示名: 表示名::new("はなこ").unwrap(),
+            既定の差出人名: Some(差出人名::new("山田 花子").unwrap()),
+        }
+    }
+
+    #[test]
+    fn test_メールアドレス_validation() {
+        assert_eq!(
+            メールアドレス::new(" Hanako@Example.COM ").unwrap().value(),
+            "Hanako@example.com"
+        );
+        for value in [
+            "hanako",
+            "@example.com",
+            "hanako@",
+            "hanako@example",
+            "花子@example.com",
+            "han ako@example.com",
+        ] {
+            assert!(
+                matches!(
+                    メールアドレス::new(value),
+                    Err(DomainError::不正なメールアドレス { .. })
+                ),
+                "{value}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_認証サブジェクト_rejects_blank_and_whitespace() {
+        assert_eq!(
+            認証サブジェクト::new(" auth0|abc123 ").unwrap().value(),
+            "auth0|abc123"
+        );
+        assert!(認証サブジェクト::new("  ").is_err());
+        assert!(認証サブジェクト::new("auth0 abc").is_err());
+        assert!(認証サブジェクト::new(&"a".repeat(認証サブジェクト::最大文字数 + 1)).is_err());
+    }
+
+    #[test]
+    fn test_ユーザーアカウント_登録する_and_停止する() {
+        let id = ユーザーID::new();
+        let mut アカウント = ユーザーアカウント::登録する(
+            id,
+            認証サブジェクト::new("auth0|abc123").unwrap(),
+            テストプロフィール(),
+        );
+        assert_eq!(アカウント.ロール, ユーザーロール::顧客);
+        assert_eq!(アカウント.状態, アカウント状態::有効);
+        assert_eq!(アカウント.予約できるか確認する(), Ok(()));
+
+        アカウント.停止する();
+        assert_eq!(
+            アカウント.予約できるか確認する(),
+            Err(DomainError::アカウント停止中(id))
+        );
+
+        アカウント.再開する();
+        assert_eq!(アカウント.予約できるか確認する(), Ok(()));
+    }
+
+    #[test]
+    fn test_ユーザーアカウント_管理操作できるか確認する() {
+        let id = ユーザーID::new();
+        let mut アカウント = ユーザーアカウント::登録する(
+            id,
+            認証サブジェクト::new("auth0|abc123").unwrap(),
+            テストプロフィール(),
+        );
+        assert_eq!(
+            アカウント.管理操作できるか確認する(),
+            Err(DomainError::管理者権限なし(id))
+        );
+
+        アカウント.ロール = ユーザーロール::管理者;
+        assert_eq!(アカウント.管理操作できるか確認する(), Ok(()));
+
+        アカウント.停止する();
+        assert_eq!(
+            アカウント.管理操作できるか確認する(),
+            Err(DomainError::アカウント停止中(id))
+        );
+    }
+
+    // --- 発送リードタイムテスト ---
+
+    #[test]
+    fn test_発送リードタイム_skips_weekends() {
+        let リードタイム = 発送リードタイム::new(3);
+        // 木曜受付 → 金・月・火 の 3 営業日
+        let thursday = NaiveDate::from_ymd_opt(2025, 1, 9).unwrap();
+        assert_eq!(
+            リードタイム.最短配送日(thursday),
+            NaiveDate::from_ymd_opt(2025, 1, 14).unwrap()
+        );
+        // 土曜受付 → 月・火・水
+        let saturday = NaiveDate::from_ymd_opt(2025, 1, 11).unwrap();
+        assert_eq!(
+            リードタイム.最短配送日(saturday),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_発送リードタイム_zero_still_requires_future_date() {
+        let 受付日 = NaiveDate::from_ymd_opt(2025, 1, 9).unwrap();
+        assert_eq!(
+            発送リードタイム::new(0).最短配送日(受付日),
+            NaiveDate::from_ymd_opt(2025, 1, 10).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_予約を受け付ける_fail_anniversary_too_early() {
+        // 2024-12-02 (月) 受付、3 営業日なら 12/5 (木) が最短
+        for (day, expected_ok) in [(1, false), (2, false), (4, false), (5, true)] {
+            let result = 手配予約を受け付ける(
+                ユーザーID::new(),
+                届け先ID::new(),
+                記念日 {
+                    value: NaiveDate::from_ymd_opt(2024, 12, day).unwrap(),
+                },
+                None,
+                ラッピング種類::なし,
+                None,
+                HashSet::from([商品ID::new()]),
+                支払いID::new(),
+                テスト料金内訳(),
+                テスト受付日時(),
+                発送リードタイム::default(),
+            );
+            if expected_ok {
+                assert!(result.is_ok());
+            } else {
+                assert_eq!(
+                    result.err(),
+                    Some(DomainError::記念日リードタイム不足 {
+                        anniversary: NaiveDate::from_ymd_opt(2024, 12, day).unwrap(),
+                        earliest: NaiveDate::from_ymd_opt(2024, 12, 5).unwrap(),
+                    })
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_予約を受け付ける_fail_delivery_date_out_of_range() {
+        // 2024-12-02 (月) 受付、3 営業日なら 12/5 (木) が最短。記念日は 12/24
+        for (day, expected_ok) in [(4, false), (5, true), (24, true), (25, false)] {
+            let result = 手配予約を受け付ける(
+                ユーザーID::new(),
+                届け先ID::new(),
+                記念日 {
+                    value: NaiveDate::from_ymd_opt(2024, 12, 24).unwrap(),
+                },
+                None,
+                ラッピング種類::なし,
+                Some(配送希望 {
+                    配送日: NaiveDate::from_ymd_opt(2024, 12, day).unwrap(),
+                    時間帯: 配送時間帯::午後7時から9時,
+                }),
+                HashSet::from([商品ID::new()]),
+                支払いID::new(),
+                テスト料金内訳(),
+                テスト受付日時(),
+                発送リードタイム::default(),
+            );
+            if expected_ok {
+                assert!(result.is_ok());
+            } else {
+                assert_eq!(
+                    result.err(),
+                    Some(DomainError::配送希望日範囲外 {
+                        delivery_date: NaiveDate::from_ymd_opt(2024, 12, day).unwrap(),
+                        earliest: NaiveDate::from_ymd_opt(2024, 12, 5).unwrap(),
+                        latest: NaiveDate::from_ymd_opt(2024, 12, 24).unwrap(),
+                    })
+                );
+            }
+        }
+    }
+
+    // --- メッセージ内容テスト ---
+
+    #[test]
+    fn test_メッセージ内容_nfkc_normalizes_width() {
+        let メッセージ = メッセージ内容::new("ｱﾘｶﾞﾄｳ　ＡＢＣ１２３").unwrap();
+        assert_eq!(メッセージ.value(), "アリガトウ ABC123");
+        assert_eq!(メッセージ.文字数(), 12);
+    }
+
+    #[test]
+    fn test_メッセージ内容_counts_grapheme_clusters() {
+        // 結合文字や ZWJ 絵文字も 1 文字として数える
+        let family = "👨\u{200D}👩\u{200D}👧";
+        let メッセージ = メッセージ内容::new(&family.repeat(メッセージ内容::最大文字数)).unwrap();
+        assert_eq!(メッセージ.文字数(), メッセージ内容::最大文字数);
+    }
+
+    #[test]
+    fn test_メッセージ内容_fail_too_long() {
+        let result = メッセージ内容::new(&"あ".repeat(メッセージ内容::最大文字数 + 1));
+        assert!(matches!(
+            result,
+            Err(DomainError::不正なメッセージ内容 { .. })
+        ));
+    }
+
+    #[test]
+    fn test_メッセージ内容_half_width_kana_counted_after_normalization() {
+        // 半角の濁点付きカナは正規化後に 1 文字になる
+        let result = メッセージ内容::new(&"ｶﾞ".repeat(メッセージ内容::最大文字数));
+        assert_eq!(result.unwrap().文字数(), メッセージ内容::最大文字数);
+    }
+
+    #[test]
+    fn test_メッセージ内容_newlines_allowed_but_control_characters_rejected() {
+        let メッセージ = メッセージ内容::new("いつも\r\nありがとう").unwrap();
+        assert_eq!(メッセージ.value(), "いつも\nありがとう");
+
+        for invalid in ["ベル\u{7}", "タブ\tあり", "NUL\u{0}"] {
+            assert!(matches!(
+                メッセージ内容::new(invalid),
+                Err(DomainError::不正なメッセージ内容 { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_メッセージ内容_fail_blank() {
+        assert!(matches!(
+            メッセージ内容::new("　 "),
+            Err(DomainError::不正なメッセージ内容 { .. })
+        ));
+    }
+
+    #[test]
+    fn test_メッセージ内容_restores_stored_value_without_validation() {
+        // ルール導入前の文面は 200 文字を超えていても、CR を含んでいてもそのまま復元する
+        let 保存済み = format!("{}\r\n{}", "あ".repeat(メッセージ内容::最大文字数), "ｶﾞ");
+        let メッセージ =
+            メッセージ内容::保存済みの値から復元する(保存済み.clone());
+        assert_eq!(メッセージ.value(), 保存済み);
+    }
+
+    #[test]
+    fn test_配送伝票番号_restores_stored_value_without_validation() {
+        // 形式の検証を導入する前の自由入力の番号もそのまま復元する
+        let 番号 = 配送伝票番号::保存済みの値から復元する(
+            配送業者::ヤマト運輸,
+            "YMT-001".to_string(),
+        );
+        assert_eq!(番号.value(), "YMT-001");
+        assert_eq!(番号.配送業者(), 配送業者::ヤマト運輸);
+    }
+
+    #[test]
+    fn test_配送伝票番号_normalizes_hyphens_and_full_width_digits() {
+        let 番号 = 配送伝票番号::new(配送業者::ヤマト運輸, "１２３４-５６７８-９０１３").unwrap();
+        assert_eq!(番号.value(), "123456789013");
+        assert_eq!(番号.配送業者(), 配送業者::ヤマト運輸);
+    }
+
+    #[test]
+    fn test_配送伝票番号_fail_check_digit_mismatch() {
+        for 業者 in [配送業者::ヤマト運輸, 配送業者::佐川急便] {
+            assert!(matches!(
+                配送伝票番号::new(業者, "1234-5678-9012"),
+                Err(DomainError::不正な配送伝票番号 { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_配送伝票番号_fail_wrong_length_or_non_digits() {
+        for (業者, invalid) in [
+            (配送業者::ヤマト運輸, "12345678903"),
+            (配送業者::佐川急便, "1234567890123"),
+            (配送業者::ヤマト運輸, "slip-12345"),
+            (配送業者::日本郵便, "1234567890"),
+            (配送業者::日本郵便, "12345678901234"),
+            (配送業者::日本郵便, ""),
+        ] {
+            assert!(
+                matches!(
+                    配送伝票番号::new(業者, invalid),
+                    Err(DomainError::不正な配送伝票番号 { .. })
+                ),
+                "{:?} {:?}",
+                業者,
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn test_配送伝票番号_japan_post_accepts_11_to_13_digits() {
+        for valid in ["12345678901", "1234-5678-9012", "1234 5678 90123"] {
+            assert!(配送伝票番号::new(配送業者::日本郵便, valid).is_ok());
+        }
+    }
+
     // --- 予約受付テスト ---
 
     #[test]
@@ -421,16 +3139,19 @@ mod tests {
         let 記念日_obj = 記念日 {
             value: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
         };
-        let message = Some("お誕生日おめでとう！".to_string());
+        let message = Some(メッセージ内容::new("お誕生日おめでとう！").unwrap());
         let wrapping = ラッピング種類::特別;
-        let delivery_time = Some(Tokyo.with_ymd_and_hms(2025, 1, 10, 10, 0, 0).unwrap()); // これは Tz::Asia__Tokyo::Tokyo で呼び出すのが正しい
+        let delivery_time = Some(配送希望 {
+            配送日: NaiveDate::from_ymd_opt(2024, 12, 27).unwrap(),
+            時間帯: 配送時間帯::午前中,
+        });
         let 商品1 = 商品ID::new();
         let mut 商品リスト = HashSet::new();
         商品リスト.insert(商品1);
         let 支払い = 支払いID::new();
-        let 金額_obj = 金額::new(10000).unwrap();
+        let 料金 = テスト料金内訳();
 
-        let result = 予約を受け付ける(
+        let result = 手配予約を受け付ける(
             依頼者,
             届け先,
             記念日_obj.clone(),
@@ -439,21 +3160,34 @@ mod tests {
             delivery_time,
             商品リスト.clone(),
             支払い,
-            金額_obj,
+            料金.clone(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         );
 
         assert!(result.is_ok());
-        let reservation = result.unwrap();
+        let (reservation, イベント) = result.unwrap();
         assert_eq!(reservation.base.依頼者id, 依頼者);
         assert_eq!(reservation.base.届け先id, 届け先);
         assert_eq!(reservation.base.記念日, 記念日_obj);
         assert_eq!(reservation.base.メッセージ内容, message);
         assert_eq!(reservation.base.ラッピング, wrapping);
-        assert_eq!(reservation.base.配送希望日時, delivery_time);
-        assert_eq!(reservation.base.手配商品リスト, 商品リスト);
+        assert_eq!(reservation.base.配送希望, delivery_time);
+        assert_eq!(reservation.base.プレゼント内容.手配商品リスト(), 商品リスト);
         assert_eq!(reservation.base.支払いid, 支払い);
-        assert_eq!(reservation.base.合計金額, 金額_obj);
+        assert_eq!(reservation.base.合計金額, 金額::new(880).unwrap()); // 送料 800 円 + 消費税 80 円
+        assert_eq!(reservation.base.料金内訳, 料金);
         assert_ne!(reservation.base.id.as_uuid().to_string(), "");
+        assert_eq!(
+            イベント,
+            vec![
+                ドメインイベント::プレゼント予約受け付け完了(イベント情報 {
+                    予約id: reservation.base.id,
+                    実行者: Some(依頼者),
+                    発生日時: テスト受付日時(),
+                })
+            ]
+        );
     }
 
     #[test]
@@ -468,9 +3202,8 @@ mod tests {
         let delivery_time = None;
         let 商品リスト = HashSet::new();
         let 支払い = 支払いID::new();
-        let 金額_obj = 金額::new(1).unwrap();
 
-        let result = 予約を受け付ける(
+        let result = 手配予約を受け付ける(
             依頼者,
             届け先,
             記念日_obj.clone(),
@@ -479,13 +3212,237 @@ mod tests {
             delivery_time,
             商品リスト.clone(),
             支払い,
-            金額_obj,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         );
 
         assert!(result.is_err());
         assert_eq!(result.err(), Some(DomainError::予約商品空エラー));
     }
 
+    /// 2025-01-01 が記念日の発送代行予約を受け付ける (配送希望なし)
+    fn 発送代行で受け付ける(
+        説明: &str,
+        倉庫到着予定日: NaiveDate,
+    ) -> Result<(予約受付済みプレゼント予約型, Vec<ドメインイベント>), DomainError> {
+        発送代行予約を受け付ける(
+            ユーザーID::new(),
+            届け先ID::new(),
+            記念日 {
+                value: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            },
+            None,
+            ラッピング種類::標準,
+            None,
+            発送代行プレゼント情報::new(説明)?,
+            倉庫到着予定日,
+            支払いID::new(),
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+    }
+
+    #[test]
+    fn test_発送代行予約を受け付ける_success() {
+        let 到着予定日 = NaiveDate::from_ymd_opt(2024, 12, 20).unwrap();
+        let (reservation, イベント) =
+            発送代行で受け付ける("  手編みのマフラー (紺色)  ", 到着予定日).unwrap();
+
+        assert_eq!(
+            reservation.base.プレゼント内容,
+            プレゼント内容::発送代行 {
+                情報: 発送代行プレゼント情報::new("手編みのマフラー (紺色)").unwrap(),
+                倉庫到着予定日: 到着予定日,
+            }
+        );
+        assert!(reservation.base.プレゼント内容.手配商品リスト().is_empty());
+        assert_eq!(イベント.len(), 1);
+    }
+
+    #[test]
+    fn test_発送代行プレゼント情報_validation() {
+        assert_eq!(
+            発送代行プレゼント情報::new("ＡＢＣ\r\n絵本")
+                .unwrap()
+                .value(),
+            "ABC\n絵本"
+        );
+        assert!(matches!(
+            発送代行プレゼント情報::new("  \n "),
+            Err(DomainError::不正な発送代行プレゼント情報 { .. })
+        ));
+        assert!(matches!(
+            発送代行プレゼント情報::new("絵本\u{7}"),
+            Err(DomainError::不正な発送代行プレゼント情報 { .. })
+        ));
+        let 上限 = "あ".repeat(発送代行プレゼント情報::最大文字数);
+        assert!(発送代行プレゼント情報::new(&上限).is_ok());
+        assert!(matches!(
+            発送代行プレゼント情報::new(&format!("{}あ", 上限)),
+            Err(DomainError::不正な発送代行プレゼント情報 { .. })
+        ));
+    }
+
+    #[test]
+    fn test_発送代行予約を受け付ける_fail_arrival_before_reception() {
+        let result =
+            発送代行で受け付ける("絵本", NaiveDate::from_ymd_opt(2024, 12, 1).unwrap());
+
+        assert_eq!(
+            result.err(),
+            Some(DomainError::倉庫到着予定日不正 {
+                arrival: NaiveDate::from_ymd_opt(2024, 12, 1).unwrap(),
+                earliest: NaiveDate::from_ymd_opt(2024, 12, 2).unwrap(),
+                latest: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_発送代行予約を受け付ける_fail_arrival_after_deadline() {
+        // お届け日 (記念日) 当日の到着では発送が間に合わない
+        let result =
+            発送代行で受け付ける("絵本", NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+
+        assert!(matches!(
+            result,
+            Err(DomainError::倉庫到着予定日不正 { latest, .. })
+                if latest == NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()
+        ));
+    }
+
+    // --- 料金計算のテスト ---
+
+    #[test]
+    fn test_料金計算_applies_tax_rate_per_line() {
+        let 食品 = 商品ID::new();
+        let 雑貨 = 商品ID::new();
+        let 商品価格 = HashMap::from([
+            (
+                食品,
+                商品価格 {
+                    税込価格: 金額::new(2000).unwrap(),
+                    税区分: 税区分::軽減税率,
+                },
+            ),
+            (
+                雑貨,
+                商品価格 {
+                    税込価格: 金額::new(1000).unwrap(),
+                    税区分: 税区分::標準税率,
+                },
+            ),
+        ]);
+        let 内容 = プレゼント内容::手配 {
+            商品idリスト: HashSet::from([食品, 雑貨]),
+        };
+
+        let 内訳 = 料金計算::default()
+            .計算する(&内容, ラッピング種類::標準, &商品価格)
+            .unwrap();
+
+        // 商品 → ラッピング料 → 送料 の順に並び、消費税は行ごとに切り捨てる
+        // 商品は税込価格から内税で計算し、税込金額はカタログの価格のまま変えない
+        let 項目: Vec<料金項目> = 内訳.明細.iter().map(|行| 行.項目).collect();
+        assert_eq!(&項目[2..], &[料金項目::ラッピング料, 料金項目::送料]);
+        assert!(内訳.明細.contains(&料金明細 {
+            項目: 料金項目::商品(食品),
+            税抜金額: 1852,
+            税区分: 税区分::軽減税率,
+            消費税額: 148, // 148.14 円
+        }));
+        assert_eq!(内訳.税抜合計(), Ok(1852 + 910 + 300 + 800));
+        assert_eq!(内訳.消費税額(税区分::軽減税率), Ok(148));
+        assert_eq!(内訳.消費税額(税区分::標準税率), Ok(90 + 30 + 80)); // 90.9 円 + 30 円 + 80 円
+        assert_eq!(内訳.合計(), Ok(2000 + 1000 + 330 + 880));
+    }
+
+    #[test]
+    fn test_料金計算_forwarding_fee_without_wrapping() {
+        let 内容 = プレゼント内容::発送代行 {
+            情報: 発送代行プレゼント情報::new("絵本").unwrap(),
+            倉庫到着予定日: NaiveDate::from_ymd_opt(2024, 12, 20).unwrap(),
+        };
+
+        let 内訳 = 料金計算::default()
+            .計算する(&内容, ラッピング種類::なし, &HashMap::new())
+            .unwrap();
+
+        assert_eq!(
+            内訳.明細,
+            vec![
+                料金明細::new(料金項目::発送代行手数料, 500, 税区分::標準税率),
+                料金明細::new(料金項目::送料, 800, 税区分::標準税率),
+            ]
+        );
+        assert_eq!(内訳.合計(), Ok(1430));
+    }
+
+    #[test]
+    fn test_料金計算_fail_unknown_product_price() {
+        let 商品 = 商品ID::new();
+        let 内容 = プレゼント内容::手配 {
+            商品idリスト: HashSet::from([商品]),
+        };
+
+        let result = 料金計算::default().計算する(&内容, ラッピング種類::なし, &HashMap::new());
+
+        assert_eq!(result, Err(DomainError::商品NotFound(商品)));
+    }
+
+    #[test]
+    fn test_料金内訳_見積と照合する() {
+        let 内訳 = テスト料金内訳();
+
+        assert!(内訳.見積と照合する(金額::new(880).unwrap()).is_ok());
+        assert_eq!(
+            内訳.見積と照合する(金額::new(800).unwrap()),
+            Err(DomainError::見積金額不一致 {
+                estimated: 800,
+                calculated: 880,
+            })
+        );
+    }
+
+    #[test]
+    fn test_料金明細_does_not_overflow_at_upper_bound() {
+        let 外税 = 料金明細::new(料金項目::送料, u32::MAX, 税区分::標準税率);
+        assert_eq!(外税.消費税額, 429_496_729);
+
+        let 内税 =
+            料金明細::税込から(料金項目::商品(商品ID::new()), u32::MAX, 税区分::軽減税率);
+        assert_eq!(内税.消費税額, 318_145_725);
+        assert_eq!(内税.税込金額(), u32::MAX);
+    }
+
+    #[test]
+    fn test_料金内訳_fail_when_total_overflows() {
+        // 上限価格の商品を 430 個並べると合計が u32 に収まらない
+        let 商品価格: HashMap<商品ID, 商品価格> = (0..430)
+            .map(|_| {
+                (
+                    商品ID::new(),
+                    商品価格::new(商品価格::上限, 税区分::標準税率).unwrap(),
+                )
+            })
+            .collect();
+        let 内容 = プレゼント内容::手配 {
+            商品idリスト: 商品価格.keys().copied().collect(),
+        };
+
+        let 内訳 = 料金計算::default()
+            .計算する(&内容, ラッピング種類::なし, &商品価格)
+            .unwrap();
+
+        assert_eq!(内訳.合計(), Err(DomainError::料金上限超過));
+        assert_eq!(
+            内訳.見積と照合する(金額::new(1).unwrap()),
+            Err(DomainError::料金上限超過)
+        );
+    }
+
     // --- 状態遷移テスト ---
 
     #[test]
@@ -493,38 +3450,215 @@ mod tests {
         let 記念日_obj = 記念日 {
             value: NaiveDate::from_ymd_opt(2025, 2, 14).unwrap(),
         };
-        let reservation_received_result = 予約を受け付ける(
+        let reservation_received_result = 手配予約を受け付ける(
             ユーザーID::new(),
             届け先ID::new(),
             記念日_obj.clone(),
-            Some("Happy Valentine!".to_string()),
+            Some(メッセージ内容::new("Happy Valentine!").unwrap()),
             ラッピング種類::標準,
             None,
             create_dummy_product_ids(),
             支払いID::new(),
-            金額::new(5000).unwrap(),
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         );
         assert!(reservation_received_result.is_ok());
-        let reservation_received = reservation_received_result.unwrap();
+        let (reservation_received, _) = reservation_received_result.unwrap();
         let original_base = reservation_received.base.clone();
         let 梱包担当者 = ユーザーID::new(); // 梱包担当者IDを定義
-        let result = reservation_received.発送準備を開始する(梱包担当者); // 引数に梱包担当者IDを渡す
+        let result = reservation_received.発送準備を開始する(梱包担当者, テスト受付日時()); // 引数に梱包担当者IDを渡す
         assert!(result.is_ok());
-        let reservation_preparing = result.unwrap();
+        let (reservation_preparing, イベント) = result.unwrap();
         assert_eq!(reservation_preparing.base, original_base);
         assert_eq!(reservation_preparing.梱包担当者id, 梱包担当者); // 梱包担当者IDのアサーション
+        assert_eq!(
+            イベント,
+            vec![ドメインイベント::予約発送準備開始(
+                イベント情報 {
+                    予約id: original_base.id,
+                    実行者: Some(梱包担当者),
+                    発生日時: テスト受付日時(),
+                }
+            )]
+        );
         assert!(matches!(
             プレゼント予約状態::発送準備中(reservation_preparing),
             プレゼント予約状態::発送準備中(_)
         ));
     }
 
+    fn 変更テスト用予約() -> 予約受付済みプレゼント予約型 {
+        let (reservation, _) = 手配予約を受け付ける(
+            ユーザーID::new(),
+            届け先ID::new(),
+            記念日 {
+                value: NaiveDate::from_ymd_opt(2024, 12, 24).unwrap(),
+            },
+            Some(メッセージ内容::new("メリークリスマス").unwrap()),
+            ラッピング種類::標準,
+            Some(配送希望 {
+                配送日: NaiveDate::from_ymd_opt(2024, 12, 20).unwrap(),
+                時間帯: 配送時間帯::午前中,
+            }),
+            create_dummy_product_ids(),
+            支払いID::new(),
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+        .unwrap();
+        reservation
+    }
+
+    #[test]
+    fn test_予約内容を変更する_success() {
+        let reservation = 変更テスト用予約();
+        let original_base = reservation.base.clone();
+        let 新しい配送希望 = 配送希望 {
+            配送日: NaiveDate::from_ymd_opt(2024, 12, 23).unwrap(),
+            時間帯: 配送時間帯::午後6時から8時,
+        };
+
+        let (changed, イベント) = reservation
+            .予約内容を変更する(
+                予約変更内容 {
+                    メッセージ内容: Some(None),
+                    ラッピング: Some(ラッピング種類::特別),
+                    配送希望: Some(Some(新しい配送希望)),
+                    見積金額: Some(金額::new(original_base.合計金額.value() + 880).unwrap()),
+                },
+                テスト受付日時(),
+                発送リードタイム::default(),
+                &料金計算::default(),
+            )
+            .unwrap();
+
+        assert_eq!(changed.base.id, original_base.id);
+        assert_eq!(changed.base.メッセージ内容, None);
+        assert_eq!(changed.base.ラッピング, ラッピング種類::特別);
+        assert_eq!(changed.base.配送希望, Some(新しい配送希望));
+        assert_eq!(changed.base.記念日, original_base.記念日);
+        // 特別ラッピング料 (800 円 + 税 80 円) が料金内訳と合計金額に加わる
+        assert_eq!(
+            changed.base.料金内訳.明細.last().map(|行| 行.項目),
+            Some(料金項目::送料)
+        );
+        assert!(changed.base.料金内訳.明細.contains(&料金明細::new(
+            料金項目::ラッピング料,
+            800,
+            税区分::標準税率
+        )));
+        assert_eq!(
+            changed.base.合計金額.value(),
+            original_base.合計金額.value() + 880
+        );
+        assert_eq!(
+            イベント,
+            vec![ドメインイベント::予約内容変更完了(
+                イベント情報 {
+                    予約id: original_base.id,
+                    実行者: Some(original_base.依頼者id),
+                    発生日時: テスト受付日時(),
+                }
+            )]
+        );
+    }
+
+    #[test]
+    fn test_予約内容を変更する_keeps_unspecified_fields() {
+        let reservation = 変更テスト用予約();
+        let original_base = reservation.base.clone();
+
+        let (changed, _) = reservation
+            .予約内容を変更する(
+                予約変更内容::default(),
+                テスト受付日時(),
+                発送リードタイム::default(),
+                &料金計算::default(),
+            )
+            .unwrap();
+
+        assert_eq!(changed.base, original_base);
+    }
+
+    #[test]
+    fn test_予約内容を変更する_fail_wrapping_change_without_matching_estimate() {
+        let reservation = 変更テスト用予約();
+        let 変更前の合計 = reservation.base.合計金額.value();
+        let ラッピング変更 = |見積金額: Option<u32>| 予約変更内容 {
+            ラッピング: Some(ラッピング種類::特別),
+            見積金額: 見積金額.map(|value| 金額::new(value).unwrap()),
+            ..Default::default()
+        };
+        let 変更する = |変更| {
+            変更テスト用予約().予約内容を変更する(
+                変更,
+                テスト受付日時(),
+                発送リードタイム::default(),
+                &料金計算::default(),
+            )
+        };
+
+        // 変更前の合計金額のままでは、特別ラッピング料の増額分と一致しない
+        assert_eq!(
+            変更する(ラッピング変更(Some(変更前の合計))),
+            Err(DomainError::見積金額不一致 {
+                estimated: 変更前の合計,
+                calculated: 変更前の合計 + 880,
+            })
+        );
+        assert!(matches!(
+            変更する(ラッピング変更(None)),
+            Err(DomainError::必須項目不足 { .. })
+        ));
+    }
+
+    #[test]
+    fn test_予約内容を変更する_fail_delivery_date_out_of_range() {
+        let result = 変更テスト用予約().予約内容を変更する(
+            予約変更内容 {
+                配送希望: Some(Some(配送希望 {
+                    配送日: NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(),
+                    時間帯: 配送時間帯::指定なし,
+                })),
+                ..Default::default()
+            },
+            テスト受付日時(),
+            発送リードタイム::default(),
+            &料金計算::default(),
+        );
+
+        assert!(matches!(result, Err(DomainError::配送希望日範囲外 { .. })));
+    }
+
+    #[test]
+    fn test_予約内容を変更する_fail_within_lead_time() {
+        // 記念日 (12/24) の 3 営業日前を過ぎてからは変更できない
+        let 変更日時 = Tokyo.with_ymd_and_hms(2024, 12, 20, 10, 0, 0).unwrap();
+        let result = 変更テスト用予約().予約内容を変更する(
+            予約変更内容 {
+                ラッピング: Some(ラッピング種類::なし),
+                見積金額: Some(変更テスト用予約().base.合計金額),
+                ..Default::default()
+            },
+            変更日時,
+            発送リードタイム::default(),
+            &料金計算::default(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(DomainError::記念日リードタイム不足 { .. })
+        ));
+    }
+
     #[test]
     fn test_発送準備中から発送済みへ遷移_success() {
         let 記念日_obj = 記念日 {
             value: NaiveDate::from_ymd_opt(2025, 3, 14).unwrap(),
         };
-        let reservation_received = 予約を受け付ける(
+        let (reservation_received, _) = 手配予約を受け付ける(
             ユーザーID::new(),
             届け先ID::new(),
             記念日_obj.clone(),
@@ -533,23 +3667,38 @@ mod tests {
             None,
             create_dummy_product_ids(),
             支払いID::new(),
-            金額::new(8000).unwrap(),
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
         let 梱包担当者 = ユーザーID::new(); // ダミーの梱包担当者ID
         let reservation_preparing_result =
-            reservation_received.発送準備を開始する(梱包担当者); // IDを渡す
+            reservation_received.発送準備を開始する(梱包担当者, テスト受付日時()); // IDを渡す
         assert!(reservation_preparing_result.is_ok());
-        let reservation_preparing = reservation_preparing_result.unwrap();
+        let (reservation_preparing, _) = reservation_preparing_result.unwrap();
         let original_base = reservation_preparing.base.clone(); // base は変わらないはず
         assert_eq!(reservation_preparing.梱包担当者id, 梱包担当者); // 梱包担当者IDが設定されているか確認
 
-        let slip_number = "1234-5678-9012".to_string();
-        let result = reservation_preparing.発送を完了する(slip_number.clone());
+        let slip_number = 配送伝票番号::new(配送業者::ヤマト運輸, "1234-5678-9013").unwrap();
+        let 発送担当者 = ユーザーID::new();
+        let result = reservation_preparing.発送を完了する(
+            slip_number.clone(),
+            Some(発送担当者),
+            テスト受付日時(),
+        );
         assert!(result.is_ok());
-        let reservation_shipped = result.unwrap();
+        let (reservation_shipped, イベント) = result.unwrap();
         assert_eq!(reservation_shipped.base, original_base); // base は引き継がれる
         assert_eq!(reservation_shipped.配送伝票番号, slip_number);
+        assert_eq!(
+            イベント,
+            vec![ドメインイベント::予約発送完了(イベント情報 {
+                予約id: original_base.id,
+                実行者: Some(発送担当者),
+                発生日時: テスト受付日時(),
+            })]
+        );
         assert!(matches!(
             プレゼント予約状態::発送済み(reservation_shipped),
             プレゼント予約状態::発送済み(_)
@@ -561,30 +3710,42 @@ mod tests {
         let 記念日_obj = 記念日 {
             value: NaiveDate::from_ymd_opt(2025, 4, 1).unwrap(),
         };
-        let reservation_received = 予約を受け付ける(
+        let (reservation_received, _) = 手配予約を受け付ける(
             ユーザーID::new(),
             届け先ID::new(),
             記念日_obj.clone(),
-            Some("Test".to_string()),
+            Some(メッセージ内容::new("Test").unwrap()),
             ラッピング種類::標準,
             None,
             create_dummy_product_ids(),
             支払いID::new(),
-            金額::new(3000).unwrap(),
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
         let 梱包担当者 = ユーザーID::new(); // 梱包担当者IDを追加
-        let reservation_preparing = reservation_received.発送準備を開始する(梱包担当者).unwrap(); // 引数を追加
-        let slip_number = "9876-5432-1098".to_string();
-        let reservation_shipped = reservation_preparing
-            .発送を完了する(slip_number.clone())
+        let (reservation_preparing, _) = reservation_received
+            .発送準備を開始する(梱包担当者, テスト受付日時())
+            .unwrap(); // 引数を追加
+        let slip_number = 配送伝票番号::new(配送業者::佐川急便, "9876-5432-1091").unwrap();
+        let (reservation_shipped, _) = reservation_preparing
+            .発送を完了する(slip_number.clone(), None, テスト受付日時())
             .unwrap();
         let original_base = reservation_shipped.base.clone();
         let completion_time = Utc::now().with_timezone(&Tokyo); // Utc::now() を経由
-        let result = reservation_shipped.配送完了を記録する(completion_time.clone());
+        let result = reservation_shipped.配送完了を記録する(completion_time, None);
         assert!(result.is_ok());
-        let reservation_delivered = result.unwrap();
+        let (reservation_delivered, イベント) = result.unwrap();
         assert_eq!(reservation_delivered.base, original_base);
+        assert_eq!(
+            イベント,
+            vec![ドメインイベント::予約配送完了(イベント情報 {
+                予約id: original_base.id,
+                実行者: None,
+                発生日時: completion_time,
+            })]
+        );
         assert_eq!(reservation_delivered.配送伝票番号, slip_number);
         assert_eq!(reservation_delivered.配送完了日時, completion_time);
         assert!(matches!(
@@ -598,7 +3759,7 @@ mod tests {
         let 記念日_obj = 記念日 {
             value: NaiveDate::from_ymd_opt(2025, 5, 1).unwrap(),
         };
-        let reservation_received = 予約を受け付ける(
+        let (reservation_received, _) = 手配予約を受け付ける(
             ユーザーID::new(),
             届け先ID::new(),
             記念日_obj.clone(),
@@ -607,19 +3768,35 @@ mod tests {
             None,
             create_dummy_product_ids(),
             支払いID::new(),
-            金額::new(1000).unwrap(),
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
         let original_base = reservation_received.base.clone();
         let reason = Some("顧客都合".to_string());
-        let time = Some(Utc::now().with_timezone(&Tokyo)); // Utc::now() を経由
-        let result =
-            reservation_received.予約をキャンセルする(reason.clone(), time.clone());
+        let result = reservation_received.予約をキャンセルする(
+            reason.clone(),
+            &キャンセルポリシー::default(),
+            None,
+            テスト受付日時(),
+        );
         assert!(result.is_ok());
-        let reservation_cancelled = result.unwrap();
+        let (reservation_cancelled, イベント) = result.unwrap();
         assert_eq!(reservation_cancelled.base, original_base);
+        assert_eq!(
+            イベント,
+            vec![ドメインイベント::予約キャンセル完了(
+                イベント情報 {
+                    予約id: original_base.id,
+                    実行者: None,
+                    発生日時: テスト受付日時(),
+                }
+            )]
+        );
         assert_eq!(reservation_cancelled.キャンセル理由, reason);
-        assert_eq!(reservation_cancelled.キャンセル日時, time);
+        assert_eq!(reservation_cancelled.キャンセル日時, テスト受付日時());
+        assert_eq!(reservation_cancelled.キャンセル料, 0); // 記念日まで十分日数があるので無料
         assert!(matches!(
             プレゼント予約状態::キャンセル済み(reservation_cancelled),
             プレゼント予約状態::キャンセル済み(_)
@@ -631,41 +3808,279 @@ mod tests {
         let 記念日_obj = 記念日 {
             value: NaiveDate::from_ymd_opt(2025, 5, 5).unwrap(),
         };
-        let reservation_received = 予約を受け付ける(
+        let (reservation_received, _) = 手配予約を受け付ける(
             ユーザーID::new(),
             届け先ID::new(),
             記念日_obj.clone(),
-            Some("Msg".to_string()),
+            Some(メッセージ内容::new("Msg").unwrap()),
             ラッピング種類::標準,
             None,
             create_dummy_product_ids(),
             支払いID::new(),
-            金額::new(2000).unwrap(),
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
         )
         .unwrap();
         let 梱包担当者 = ユーザーID::new(); // ダミーの梱包担当者ID
         let reservation_preparing_result =
-            reservation_received.発送準備を開始する(梱包担当者); // IDを渡す
+            reservation_received.発送準備を開始する(梱包担当者, テスト受付日時()); // IDを渡す
         assert!(reservation_preparing_result.is_ok());
-        let reservation_preparing = reservation_preparing_result.unwrap();
+        let (reservation_preparing, _) = reservation_preparing_result.unwrap();
         let original_base = reservation_preparing.base.clone(); // base は変わらないはず
         assert_eq!(reservation_preparing.梱包担当者id, 梱包担当者); // 梱包担当者IDが設定されているか確認
 
         let reason = None;
-        let time = Some(Utc::now().with_timezone(&Tokyo)); // Utc::now() を経由
-        let result =
-            reservation_preparing.予約をキャンセルする(reason.clone(), time.clone());
+        let result = reservation_preparing.予約をキャンセルする(
+            reason.clone(),
+            &キャンセルポリシー::default(),
+            None,
+            テスト受付日時(),
+        );
         assert!(result.is_ok());
-        let reservation_cancelled = result.unwrap();
+        let (reservation_cancelled, _) = result.unwrap();
         assert_eq!(reservation_cancelled.base, original_base); // base は引き継がれる
         assert_eq!(reservation_cancelled.キャンセル理由, reason);
-        assert_eq!(reservation_cancelled.キャンセル日時, time);
+        assert_eq!(reservation_cancelled.キャンセル日時, テスト受付日時());
+        assert_eq!(reservation_cancelled.キャンセル料, 264); // 発送準備中は合計金額 880 円の 30%
         assert!(matches!(
             プレゼント予約状態::キャンセル済み(reservation_cancelled),
             プレゼント予約状態::キャンセル済み(_)
         ));
     }
 
+    #[test]
+    fn test_予約をキャンセルする_fail_after_cancellation_deadline() {
+        let 受付日時 = テスト受付日時();
+        let (reservation_received, _) = 手配予約を受け付ける(
+            ユーザーID::new(),
+            届け先ID::new(),
+            記念日 {
+                value: NaiveDate::from_ymd_opt(2024, 12, 10).unwrap(),
+            },
+            None,
+            ラッピング種類::なし,
+            None,
+            create_dummy_product_ids(),
+            支払いID::new(),
+            テスト料金内訳(),
+            受付日時,
+            発送リードタイム::default(),
+        )
+        .unwrap();
+
+        // 記念日当日はキャンセル期限 (1 日前) を過ぎている
+        let 記念日当日 = Tokyo.with_ymd_and_hms(2024, 12, 10, 9, 0, 0).unwrap();
+        let result = reservation_received.予約をキャンセルする(
+            None,
+            &キャンセルポリシー::default(),
+            None,
+            記念日当日,
+        );
+        assert!(matches!(result, Err(DomainError::キャンセル不可 { .. })));
+    }
+
+    #[test]
+    fn test_キャンセルポリシー_fee_depends_on_days_and_state() {
+        let ポリシー = キャンセルポリシー::default();
+        let 合計 = 金額::new(10_000).unwrap();
+        let 判定 = |ステータス, 日数| ポリシー.判定する(ステータス, 日数, 合計);
+
+        // 予約受付済み: 7 日前までは無料、それ以降は 50%、前日を過ぎると不可
+        assert_eq!(
+            判定(予約ステータス::予約受付済み, 7),
+            キャンセル判定::可 {
+                キャンセル料: 0
+            }
+        );
+        assert_eq!(
+            判定(予約ステータス::予約受付済み, 6),
+            キャンセル判定::可 {
+                キャンセル料: 5_000
+            }
+        );
+        assert_eq!(
+            判定(予約ステータス::予約受付済み, 1),
+            キャンセル判定::可 {
+                キャンセル料: 5_000
+            }
+        );
+        assert!(matches!(
+            判定(予約ステータス::予約受付済み, 0),
+            キャンセル判定::不可 { .. }
+        ));
+        // 発送準備中: 日数による料率と発送準備中の料率の高い方
+        assert_eq!(
+            判定(予約ステータス::発送準備中, 30),
+            キャンセル判定::可 {
+                キャンセル料: 3_000
+            }
+        );
+        assert_eq!(
+            判定(予約ステータス::発送準備中, 3),
+            キャンセル判定::可 {
+                キャンセル料: 5_000
+            }
+        );
+        // 発送後・キャンセル後は不可
+        for ステータス in [
+            予約ステータス::発送済み,
+            予約ステータス::配送完了,
+            予約ステータス::配送失敗,
+            予約ステータス::キャンセル済み,
+        ] {
+            assert!(matches!(判定(ステータス, 30), キャンセル判定::不可 { .. }));
+        }
+        // 返送済みは記念日を過ぎていても返送済みの料率でキャンセルできる
+        assert_eq!(
+            判定(予約ステータス::返送済み, -3),
+            キャンセル判定::可 {
+                キャンセル料: 5_000
+            }
+        );
+    }
+
+    #[test]
+    fn test_キャンセルポリシー_is_configurable() {
+        let ポリシー = キャンセルポリシー {
+            キャンセル期限日数: 3,
+            無料キャンセル期限日数: 14,
+            直前キャンセル料率: 20,
+            発送準備中キャンセル料率: 100,
+            返送済みキャンセル料率: 0,
+        };
+        let 合計 = 金額::new(1_001).unwrap();
+        assert_eq!(
+            ポリシー.判定する(予約ステータス::予約受付済み, 10, 合計),
+            キャンセル判定::可 {
+                キャンセル料: 200
+            } // 1 円未満は切り捨て
+        );
+        assert_eq!(
+            ポリシー.判定する(予約ステータス::発送準備中, 10, 合計),
+            キャンセル判定::可 {
+                キャンセル料: 1_001
+            }
+        );
+        assert!(matches!(
+            ポリシー.判定する(予約ステータス::予約受付済み, 2, 合計),
+            キャンセル判定::不可 { .. }
+        ));
+    }
+
+    #[test]
+    fn test_キャンセルポリシー_fee_does_not_overflow_for_large_total() {
+        let ポリシー = キャンセルポリシー {
+            発送準備中キャンセル料率: 100,
+            ..キャンセルポリシー::default()
+        };
+        let 合計 = 金額::new(u32::MAX).unwrap();
+        assert_eq!(
+            ポリシー.判定する(予約ステータス::予約受付済み, 3, 合計),
+            キャンセル判定::可 {
+                キャンセル料: u32::MAX / 2
+            }
+        );
+        assert_eq!(
+            ポリシー.判定する(予約ステータス::発送準備中, 30, 合計),
+            キャンセル判定::可 {
+                キャンセル料: u32::MAX
+            }
+        );
+    }
+
+    /// 発送済みの予約 (ヤマト運輸 1234-5678-9013 で発送)
+    fn 発送済みの予約() -> 発送済みプレゼント予約型 {
+        let (received, _) = 手配予約を受け付ける(
+            ユーザーID::new(),
+            届け先ID::new(),
+            記念日 {
+                value: NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(),
+            },
+            None,
+            ラッピング種類::なし,
+            None,
+            create_dummy_product_ids(),
+            支払いID::new(),
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+        .unwrap();
+        let (preparing, _) = received
+            .発送準備を開始する(ユーザーID::new(), テスト受付日時())
+            .unwrap();
+        let (shipped, _) = preparing
+            .発送を完了する(
+                配送伝票番号::new(配送業者::ヤマト運輸, "1234-5678-9013").unwrap(),
+                None,
+                テスト受付日時(),
+            )
+            .unwrap();
+        shipped
+    }
+
+    #[test]
+    fn test_配送失敗から返送を経て再発送する_success() {
+        let shipped = 発送済みの予約();
+        let original_base = shipped.base.clone();
+        let 失敗日時 = Tokyo.with_ymd_and_hms(2024, 12, 20, 18, 0, 0).unwrap();
+        let 返送日時 = Tokyo.with_ymd_and_hms(2024, 12, 23, 10, 0, 0).unwrap();
+
+        let (failed, イベント) = shipped
+            .配送失敗を記録する(配送失敗理由::宛先不明, 失敗日時, None)
+            .unwrap();
+        assert_eq!(failed.配送失敗理由, 配送失敗理由::宛先不明);
+        assert_eq!(failed.配送失敗日時, 失敗日時);
+        assert_eq!(イベント[0].名前(), "予約配送失敗");
+
+        let (returned, イベント) = failed.返送を記録する(返送日時, None).unwrap();
+        assert_eq!(returned.返送日時, 返送日時);
+        assert_eq!(returned.配送失敗日時, 失敗日時);
+        assert_eq!(イベント[0].名前(), "予約返送完了");
+
+        let 新しい伝票番号 = 配送伝票番号::new(配送業者::佐川急便, "9876-5432-1091").unwrap();
+        let (reshipped, イベント) = returned
+            .再発送する(新しい伝票番号.clone(), None, 返送日時)
+            .unwrap();
+        assert_eq!(reshipped.base, original_base);
+        assert_eq!(reshipped.配送伝票番号, 新しい伝票番号);
+        assert_eq!(イベント[0].名前(), "予約再発送完了");
+    }
+
+    #[test]
+    fn test_再発送する_fail_same_tracking_number() {
+        let (failed, _) = 発送済みの予約()
+            .配送失敗を記録する(配送失敗理由::受取拒否, テスト受付日時(), None)
+            .unwrap();
+        let (returned, _) = failed.返送を記録する(テスト受付日時(), None).unwrap();
+        let 同じ伝票番号 = returned.配送伝票番号.clone();
+
+        let result = returned.再発送する(同じ伝票番号, None, テスト受付日時());
+
+        assert!(matches!(
+            result,
+            Err(DomainError::不正な配送伝票番号 { .. })
+        ));
+    }
+
+    #[test]
+    fn test_返送済みからキャンセル済みへ遷移_after_anniversary() {
+        let (failed, _) = 発送済みの予約()
+            .配送失敗を記録する(配送失敗理由::保管期限切れ, テスト受付日時(), None)
+            .unwrap();
+        let (returned, _) = failed.返送を記録する(テスト受付日時(), None).unwrap();
+        // 記念日 (2024-12-25) を過ぎてから戻ってきた
+        let 実行日時 = Tokyo.with_ymd_and_hms(2025, 1, 6, 10, 0, 0).unwrap();
+
+        let (cancelled, _) = returned
+            .予約をキャンセルする(None, &キャンセルポリシー::default(), None, 実行日時)
+            .unwrap();
+
+        assert_eq!(cancelled.キャンセル日時, 実行日時);
+        assert_eq!(cancelled.キャンセル料, 440); // 合計金額 880 円の 50%
+    }
+
     // Helper function to create a HashSet<商品ID> for tests
     fn create_dummy_product_ids() -> HashSet<商品ID> {
         vec![商品ID::new()].into_iter().collect()
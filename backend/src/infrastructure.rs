@@ -1,61 +1,89 @@
+use crate::application::{IdempotencyBegin, IdempotencyRecord, IdempotencyStore};
 use crate::domain::core::{
-    プレゼント予約ベース, ユーザーID, ラッピング種類, 予約受付済みプレゼント予約型, 商品ID,
-    届け先ID, 支払いID, 記念日, 金額,
+    アカウント状態, プロフィール, メールアドレス, ユーザーアカウント, ユーザーアカウントRepository,
+    ユーザーロール, 差出人名, 表示名, 認証サブジェクト,
+};
+use crate::domain::core::{
+    プレゼント予約ベース, プレゼント内容, メッセージ内容, ユーザーID, ラッピング種類,
+    予約受付済みプレゼント予約型, 商品, 商品ID, 商品Repository, 商品価格, 商品内容, 商品名,
+    商品在庫, 商品在庫Repository, 商品検索条件, 商品検索結果, 商品説明, 在庫移動, 届け先ID,
+    支払いID, 料金内訳, 料金明細, 料金項目, 画像キー, 発送代行プレゼント情報, 税区分, 記念日,
+    配送伝票番号, 配送失敗理由, 配送希望, 配送時間帯, 配送業者, 金額,
+};
+use crate::domain::core::{
+    住所, 届け先, 届け先Repository, 届け先内容, 建物名, 氏名, 郵便番号, 都道府県, 電話番号,
 };
 use crate::domain::{
-    DomainError, InfrastructureError, プレゼント予約Repository, プレゼント予約状態, 予約ID,
+    DatabaseStatus, DomainError, InfrastructureError, イベント発行者, ドメインイベント,
+    プレゼント予約Repository, プレゼント予約状態, 予約ID, 予約ステータス, 予約一覧カーソル,
+    予約一覧ページ, 予約検索の並び順, 予約検索条件, 予約検索結果, 冪等性キーの確保,
 };
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::Deserialize;
+use sqlx::types::Json;
 use sqlx::PgPool;
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 // use dotenv::dotenv; // 未使用
 // use crate::domain::core::予約を受け付ける; // Clippy: unused import
-// use chrono::NaiveDate; // Clippy: unused import
 use chrono_tz::Asia::Tokyo;
 use uuid::Uuid;
 
+pub mod postal_codes;
+
+pub use postal_codes::InMemory郵便番号辞書;
+
 // --- インメモリリポジトリの実装 ---
 
-/* --- 古い商品リポジトリ実装をコメントアウト --- */
-/*
+/// インメモリの商品カタログ (登録順に保持する)
 #[derive(Clone, Default)]
 pub struct InMemory商品Repository {
-    items: Arc<Mutex<HashMap<商品ID, 商品>>>,
+    items: Arc<Mutex<Vec<商品>>>,
 }
 
 impl InMemory商品Repository {
-     pub fn new() -> Self {
-         let mut items_map = HashMap::new();
-         let item1 = 商品 {
-             id: 商品ID::new(),
-             名前: "すごい本".to_string(),
-             価格: 3000,
-         };
-         let item2 = 商品 {
-             id: 商品ID::new(),
-             名前: "便利なツール".to_string(),
-             価格: 5000,
-         };
-         items_map.insert(item1.id, item1);
-         items_map.insert(item2.id, item2);
-
-         Self { items: Arc::new(Mutex::new(items_map)) }
-     }
-
-     #[allow(dead_code)]
-     pub fn get_sample_item_ids(&self) -> Vec<商品ID> {
-         self.items.lock().unwrap().keys().cloned().collect()
-     }
+    pub fn new() -> Self {
+        Self::default()
+    }
 }
 
+#[async_trait]
 impl 商品Repository for InMemory商品Repository {
-    fn find_by_id(&self, id: &商品ID) -> Result<Option<商品>, DomainError> {
-        let items_map = self.items.lock().unwrap();
-        Ok(items_map.get(id).cloned())
+    async fn save(&self, 商品: &商品) -> Result<(), DomainError> {
+        let mut items = self.items.lock().unwrap(); // Mutexをロック
+        match items.iter_mut().find(|item| item.id == 商品.id) {
+            Some(item) => *item = 商品.clone(),
+            None => items.push(商品.clone()),
+        }
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &商品ID) -> Result<Option<商品>, DomainError> {
+        let items = self.items.lock().unwrap(); // Mutexをロック
+        Ok(items.iter().find(|item| item.id == *id).cloned())
+    }
+
+    async fn search(
+        &self,
+        条件: &商品検索条件,
+        offset: usize,
+        limit: usize,
+    ) -> Result<商品検索結果, DomainError> {
+        let items = self.items.lock().unwrap(); // Mutexをロック
+        let matched: Vec<&商品> = items.iter().filter(|item| 条件.一致する(item)).collect();
+        let 総件数 = matched.len();
+        Ok(商品検索結果 {
+            商品リスト: matched
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .cloned()
+                .collect(),
+            総件数,
+        })
     }
 }
-*/
 
 /* --- 古い注文リポジトリ実装をコメントアウト --- */
 /*
@@ -86,22 +114,37 @@ impl 注文Repository for InMemory注文Repository {
 }
 */
 
+/// インメモリで保持する予約 (一覧の並び順のため作成日時も持つ)
+#[derive(Clone)]
+struct StoredReservation {
+    state: プレゼント予約状態,
+    created_at: DateTime<Utc>,
+}
+
+/// インメモリの予約リポジトリ (商品の在庫と予約ごとの引当、Idempotency-Key の記録も保持する)
+///
+/// PgRepository と同じく、予約受付と同時にキーへ予約IDを記録するため IdempotencyStore も実装する。
 #[derive(Clone, Default)]
 pub struct InMemoryプレゼント予約Repository {
-    reservations: Arc<Mutex<HashMap<予約ID, プレゼント予約状態>>>,
+    reservations: Arc<Mutex<HashMap<予約ID, StoredReservation>>>,
+    stocks: Arc<Mutex<HashMap<商品ID, 商品在庫>>>,
+    allocations: Arc<Mutex<HashMap<予約ID, Vec<商品ID>>>>,
+    idempotency_keys: Arc<Mutex<HashMap<(ユーザーID, String), IdempotencyRecord>>>,
 }
 
 impl InMemoryプレゼント予約Repository {
     pub fn new() -> Self {
-        Self {
-            reservations: Arc::new(Mutex::new(HashMap::new())),
-        }
+        Self::default()
     }
-}
 
-#[async_trait]
-impl プレゼント予約Repository for InMemoryプレゼント予約Repository {
-    async fn save(&self, reservation: &プレゼント予約状態) -> Result<(), DomainError> {
+    /// 予約を保存し、指定された在庫の移動も行う (他の Mutex をロックしたまま呼べるよう同期関数にしている)
+    ///
+    /// 状態の確認から保存まで予約のロックを保持し、保存できない場合は在庫も移動しない。
+    fn store_reservation(
+        &self,
+        reservation: &プレゼント予約状態,
+        在庫の移動: Option<在庫移動>,
+    ) -> Result<(), DomainError> {
         let mut reservations_map = self.reservations.lock().unwrap(); // Mutexをロック
 
         // 予約状態からIDを取得 (どの状態でも base.id でアクセスできる)
@@ -110,6 +153,8 @@ impl プレゼント予約Repository for InMemoryプレゼント予約Repository
             プレゼント予約状態::発送準備中(r) => r.base.id,
             プレゼント予約状態::発送済み(r) => r.base.id,
             プレゼント予約状態::配送完了(r) => r.base.id,
+            プレゼント予約状態::配送失敗(r) => r.base.id,
+            プレゼント予約状態::返送済み(r) => r.base.id,
             プレゼント予約状態::キャンセル済み(r) => r.base.id,
         };
 
@@ -117,7 +162,117 @@ impl プレゼント予約Repository for InMemoryプレゼント予約Repository
             "InMemory: Saving reservation {:?} with state: {:?}",
             id, reservation
         );
-        reservations_map.insert(id, reservation.clone());
+        // PgRepository と同じく、読み込んだ後に他の操作で状態が変わっていたら保存しない
+        match reservations_map.get(&id) {
+            Some(stored) => {
+                let 現在 = stored.state.ステータス();
+                if !reservation.遷移元のステータス().contains(&現在) {
+                    return Err(DomainError::不正な状態遷移 {
+                        current_state_type: status_column_value(現在).to_string(),
+                    });
+                }
+            }
+            // 新しく保存できるのは受け付けた予約だけ
+            None if reservation.ステータス() != 予約ステータス::予約受付済み => {
+                return Err(DomainError::予約NotFound(id));
+            }
+            None => {}
+        }
+        if let Some(移動) = 在庫の移動 {
+            self.move_stock(reservation, 移動)?;
+        }
+        // 作成日時は初回保存時のものを維持する
+        let created_at = reservations_map
+            .get(&id)
+            .map(|stored| stored.created_at)
+            .unwrap_or_else(Utc::now);
+        reservations_map.insert(
+            id,
+            StoredReservation {
+                state: reservation.clone(),
+                created_at,
+            },
+        );
+        Ok(())
+    }
+
+    /// 予約の在庫を移動する (引当は全商品を確認してからまとめて反映する)
+    fn move_stock(
+        &self,
+        reservation: &プレゼント予約状態,
+        移動: 在庫移動,
+    ) -> Result<(), DomainError> {
+        let id = reservation.base().id;
+        let mut stocks = self.stocks.lock().unwrap(); // Mutexをロック
+        let mut allocations = self.allocations.lock().unwrap();
+        match 移動 {
+            在庫移動::引当 => {
+                if allocations.contains_key(&id) {
+                    return Ok(()); // 引当済みの予約は引き当て直さない
+                }
+                let 商品idリスト: Vec<商品ID> = reservation
+                    .base()
+                    .プレゼント内容
+                    .手配商品リスト()
+                    .into_iter()
+                    .collect();
+                let mut 引当後 = Vec::with_capacity(商品idリスト.len());
+                for 商品id in &商品idリスト {
+                    let mut 在庫 = stocks
+                        .get(商品id)
+                        .copied()
+                        .unwrap_or_else(|| 商品在庫::在庫なし(*商品id));
+                    在庫.引き当てる()?;
+                    引当後.push(在庫);
+                }
+                for 在庫 in 引当後 {
+                    stocks.insert(在庫.商品id, 在庫);
+                }
+                allocations.insert(id, 商品idリスト);
+            }
+            在庫移動::引当解除 | 在庫移動::出庫 => {
+                for 商品id in allocations.remove(&id).unwrap_or_default() {
+                    if let Some(在庫) = stocks.get_mut(&商品id) {
+                        match 移動 {
+                            在庫移動::出庫 => 在庫.出庫する(),
+                            _ => 在庫.引当を解除する(),
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl プレゼント予約Repository for InMemoryプレゼント予約Repository {
+    async fn save(&self, reservation: &プレゼント予約状態) -> Result<(), DomainError> {
+        self.store_reservation(reservation, None)
+    }
+
+    async fn save_with_stock_movement(
+        &self,
+        reservation: &プレゼント予約状態,
+        移動: 在庫移動,
+    ) -> Result<(), DomainError> {
+        self.store_reservation(reservation, Some(移動))
+    }
+
+    async fn save_accepted_with_idempotency_key(
+        &self,
+        reservation: &プレゼント予約状態,
+        キー: &冪等性キーの確保,
+    ) -> Result<(), DomainError> {
+        // 保存が終わるまでキーの記録をロックし、確保し直されないようにする
+        let mut records = self.idempotency_keys.lock().unwrap(); // Mutexをロック
+        let base = reservation.base();
+        let record = records
+            .get_mut(&(base.依頼者id, キー.キー.clone()))
+            .filter(|record| record.reservation_id.is_none() && record.created_at == キー.確保日時)
+            .ok_or(DomainError::冪等性キー確保切れ)?;
+        self.store_reservation(reservation, Some(在庫移動::引当))?;
+        record.reservation_id = Some(base.id);
         Ok(())
     }
 
@@ -127,220 +282,1125 @@ impl プレゼント予約Repository for InMemoryプレゼント予約Repository
     ) -> Result<Option<プレゼント予約状態>, DomainError> {
         let reservations_map = self.reservations.lock().unwrap(); // Mutexをロック
         println!("InMemory: Finding reservation by id: {:?}", id);
-        let found = reservations_map.get(id).cloned(); // 見つかったらクローンして返す
+        let found = reservations_map.get(id).map(|stored| stored.state.clone()); // 見つかったらクローンして返す
         Ok(found)
     }
 
+    async fn find_by_requester(
+        &self,
+        依頼者id: &ユーザーID,
+        cursor: Option<予約一覧カーソル>,
+        limit: usize,
+    ) -> Result<予約一覧ページ, InfrastructureError> {
+        let reservations_map = self.reservations.lock().unwrap(); // Mutexをロック
+
+        // (記念日, 作成日時, 予約ID) の順で並べ、カーソルより後ろだけを対象にする
+        let mut candidates: Vec<(予約一覧カーソル, &プレゼント予約状態)> = reservations_map
+            .values()
+            .filter(|stored| stored.state.base().依頼者id == *依頼者id)
+            .map(|stored| {
+                let base = stored.state.base();
+                (
+                    予約一覧カーソル {
+                        記念日: base.記念日.value,
+                        作成日時: stored.created_at,
+                        予約id: base.id,
+                    },
+                    &stored.state,
+                )
+            })
+            .filter(|(key, _)| match &cursor {
+                Some(c) => sort_key(key) > sort_key(c),
+                None => true,
+            })
+            .collect();
+        candidates.sort_by(|(a, _), (b, _)| sort_key(a).cmp(&sort_key(b)));
+
+        let has_more = candidates.len() > limit;
+        candidates.truncate(limit);
+        let 次のカーソル = if has_more {
+            candidates.last().map(|(key, _)| key.clone())
+        } else {
+            None
+        };
+        Ok(予約一覧ページ {
+            予約リスト: candidates
+                .into_iter()
+                .map(|(_, state)| state.clone())
+                .collect(),
+            次のカーソル,
+        })
+    }
+
+    async fn search(
+        &self,
+        条件: &予約検索条件,
+        offset: usize,
+        limit: usize,
+    ) -> Result<予約検索結果, InfrastructureError> {
+        let reservations_map = self.reservations.lock().unwrap(); // Mutexをロック
+
+        let mut matched: Vec<&StoredReservation> = reservations_map
+            .values()
+            .filter(|stored| 条件.一致する(&stored.state))
+            .filter(|stored| {
+                条件
+                    .作成日時_開始
+                    .map_or(true, |from| stored.created_at >= from)
+                    && 条件
+                        .作成日時_終了
+                        .map_or(true, |to| stored.created_at <= to)
+            })
+            .collect();
+        // 並び順が同じ場合は予約IDで順序を確定させる (PgRepository と同じ)
+        matched.sort_by(|a, b| {
+            let (a_base, b_base) = (a.state.base(), b.state.base());
+            let ordering = match 条件.並び順 {
+                予約検索の並び順::記念日昇順 => {
+                    a_base.記念日.value.cmp(&b_base.記念日.value)
+                }
+                予約検索の並び順::記念日降順 => {
+                    b_base.記念日.value.cmp(&a_base.記念日.value)
+                }
+                予約検索の並び順::作成日時昇順 => a.created_at.cmp(&b.created_at),
+                予約検索の並び順::作成日時降順 => b.created_at.cmp(&a.created_at),
+            };
+            ordering.then_with(|| a_base.id.as_uuid().cmp(b_base.id.as_uuid()))
+        });
+
+        let 総件数 = matched.len();
+        Ok(予約検索結果 {
+            予約リスト: matched
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .map(|stored| stored.state.clone())
+                .collect(),
+            総件数,
+        })
+    }
+
     /// インメモリリポジトリは常に接続OKとする
     async fn check_db_connection(&self) -> Result<(), InfrastructureError> {
         println!("InMemory: Checking connection (always OK)");
         Ok(())
     }
-}
-
-// --- PostgreSQL リポジトリの実装 (ここから追加) ---
 
-#[derive(Clone)]
-pub struct PgRepository {
-    pool: PgPool,
+    /// インメモリリポジトリは接続プールもマイグレーションも持たない
+    async fn database_status(&self) -> Result<DatabaseStatus, InfrastructureError> {
+        Ok(DatabaseStatus {
+            latency: std::time::Duration::ZERO,
+            migration_version: None,
+            pool_size: 0,
+            idle_connections: 0,
+        })
+    }
 }
 
-impl PgRepository {
-    pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+#[async_trait]
+impl 商品在庫Repository for InMemoryプレゼント予約Repository {
+    async fn find_by_product_id(&self, 商品id: &商品ID) -> Result<商品在庫, DomainError> {
+        let stocks = self.stocks.lock().unwrap(); // Mutexをロック
+        Ok(stocks
+            .get(商品id)
+            .copied()
+            .unwrap_or_else(|| 商品在庫::在庫なし(*商品id)))
     }
 
-    /// データベースへの接続を確認する
-    pub async fn check_db_connection(&self) -> Result<(), sqlx::Error> {
-        // 最も単純なクエリを実行して接続を試みる
-        sqlx::query("SELECT 1")
-            .execute(&self.pool)
-            .await
-            .map(|_| ()) // 成功時は () を返す
-                         // エラー時は sqlx::Error がそのまま返る
+    async fn set_on_hand(
+        &self, 商品id: &商品ID, 在庫数: u32
+    ) -> Result<商品在庫, DomainError> {
+        let mut stocks = self.stocks.lock().unwrap(); // Mutexをロック
+        let 在庫 = stocks
+            .entry(*商品id)
+            .or_insert_with(|| 商品在庫::在庫なし(*商品id));
+        在庫.在庫数を変更する(在庫数)?;
+        Ok(*在庫)
     }
 }
 
-#[async_trait]
-impl プレゼント予約Repository for PgRepository {
-    async fn save(
-        &self, reservation_state: &プレゼント予約状態
-    ) -> Result<(), DomainError> {
-        let mut tx = self.pool.begin().await.map_err(|e| {
-            eprintln!("DB Error: Failed to begin transaction: {}", e);
-            // TODO: より適切なエラーマッピング (例: InfrastructureError::DatabaseError(e))
-            DomainError::予約NotFound(予約ID::new()) // 仮のエラー
-        })?;
+/// カーソルの比較用キー
+fn sort_key(cursor: &予約一覧カーソル) -> (chrono::NaiveDate, DateTime<Utc>, Uuid) {
+    (cursor.記念日, cursor.作成日時, *cursor.予約id.as_uuid())
+}
 
-        match reservation_state {
-            プレゼント予約状態::予約受付済み(r) => {
-                let base = &r.base;
-                let reservation_id = *base.id.as_uuid();
-                let requester_id = *base.依頼者id.as_uuid();
-                let recipient_id = *base.届け先id.as_uuid();
-                let anniversary_date = base.記念日.value; // NaiveDate
-                let message = base.メッセージ内容.as_deref(); // Option<String> -> Option<&str>
-                let wrapping_type = format!("{:?}", base.ラッピング); // Enum -> String (例: "標準")
-                let desired_delivery_date = base.配送希望日時; // Option<DateTime<Tz>>
-                let total_amount = base.合計金額.value() as i32; // u32 -> i32 (DBは INTEGER)
-                let payment_id = *base.支払いid.as_uuid();
-                let status = "Received"; // 状態文字列
+/// reservations テーブルの 1 行 (商品IDと料金明細を集約したもの)
+struct ReservationRow {
+    id: Uuid,
+    requester_id: Uuid,
+    recipient_id: Uuid,
+    anniversary_date: NaiveDate,
+    message: Option<String>,
+    wrapping_type: String,
+    desired_delivery_date: Option<NaiveDate>,
+    desired_delivery_time_slot: Option<String>,
+    total_amount: i32,
+    payment_id: Uuid,
+    status: String,
+    gift_type: String,
+    forwarding_description: Option<String>,
+    forwarding_arrival_date: Option<NaiveDate>,
+    preparation_staff_id: Option<Uuid>,
+    shipping_carrier: Option<String>,
+    shipping_slip_number: Option<String>,
+    delivery_completed_at: Option<DateTime<Utc>>,
+    cancellation_reason: Option<String>,
+    cancelled_at: Option<DateTime<Utc>>,
+    cancellation_fee: Option<i32>,
+    delivery_failure_reason: Option<String>,
+    delivery_failed_at: Option<DateTime<Utc>>,
+    returned_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    product_ids: Vec<Uuid>,
+    charge_lines: Json<Vec<ChargeLineRow>>,
+}
 
-                // reservations テーブルへの UPSERT
-                let result = sqlx::query!(
-                    r#"
-                    INSERT INTO reservations (
-                        id, requester_id, recipient_id, anniversary_date, message,
-                        wrapping_type, desired_delivery_date, total_amount, payment_id, status,
-                        -- updated_at は DEFAULT NOW() または trigger で設定される想定
-                        -- 他の状態固有カラムはデフォルト値またはNULLになる
-                        preparation_staff_id, shipping_slip_number, delivery_completed_at,
-                        cancellation_reason, cancelled_at
-                    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NULL, NULL, NULL, NULL, NULL)
-                    ON CONFLICT (id) DO UPDATE SET
-                        requester_id = EXCLUDED.requester_id,
-                        recipient_id = EXCLUDED.recipient_id,
-                        anniversary_date = EXCLUDED.anniversary_date,
-                        message = EXCLUDED.message,
-                        wrapping_type = EXCLUDED.wrapping_type,
-                        desired_delivery_date = EXCLUDED.desired_delivery_date,
-                        total_amount = EXCLUDED.total_amount,
-                        payment_id = EXCLUDED.payment_id,
-                        status = EXCLUDED.status,
-                        -- 他の状態固有カラムをリセット (NULL に設定)
-                        preparation_staff_id = NULL,
-                        shipping_slip_number = NULL,
-                        delivery_completed_at = NULL,
-                        cancellation_reason = NULL,
-                        cancelled_at = NULL,
-                        updated_at = NOW() -- updated_at を更新
-                    "#,
-                    reservation_id,
-                    requester_id,
-                    recipient_id,
-                    anniversary_date,      // NaiveDate
-                    message,               // Option<&str>
-                    wrapping_type,         // String
-                    desired_delivery_date, // Option<DateTime<Tz>>
-                    total_amount,          // i32
-                    payment_id,
-                    status // &str
+/// reservation_charge_lines テーブルの 1 行 (json_agg で集約したもの)
+#[derive(Deserialize)]
+struct ChargeLineRow {
+    item_kind: String,
+    product_id: Option<Uuid>,
+    amount_excluding_tax: i32,
+    tax_category: String,
+    tax_amount: i32,
+}
+
+/// reservations の行を予約の状態に復元する
+fn reservation_from_db(row: ReservationRow) -> Result<プレゼント予約状態, DomainError> {
+    let id = 予約ID::from_uuid(row.id);
+    let ラッピング = match row.wrapping_type.as_str() {
+        "なし" => ラッピング種類::なし,
+        "標準" => ラッピング種類::標準,
+        "特別" => ラッピング種類::特別,
+        _ => {
+            eprintln!(
+                "DB Error: Unknown wrapping type '{}' for id {}",
+                row.wrapping_type, row.id
+            );
+            return Err(DomainError::予約NotFound(id)); // 不明な値はエラー
+        }
+    };
+    // 負の金額は 0 円として 金額 の検証で弾く
+    let 合計金額 = 金額::new(u32::try_from(row.total_amount).unwrap_or(0)).map_err(|e| {
+        eprintln!(
+            "DB Error: Invalid total amount {} for id {}: {:?}",
+            row.total_amount, row.id, e
+        );
+        DomainError::予約NotFound(id) // 金額変換エラーもエラー扱い (仮)
+    })?;
+    let プレゼント内容 = gift_from_db(
+        &row.gift_type,
+        row.forwarding_description.as_deref(),
+        row.forwarding_arrival_date,
+        row.product_ids.into_iter().map(商品ID::from_uuid).collect(),
+        &id,
+    )?;
+    let 料金内訳 = 料金内訳 {
+        明細: row
+            .charge_lines
+            .0
+            .into_iter()
+            .map(|line| {
+                charge_line_from_db(
+                    &line.item_kind,
+                    line.product_id,
+                    line.amount_excluding_tax,
+                    &line.tax_category,
+                    line.tax_amount,
+                    &id,
                 )
-                .execute(&mut *tx) // &mut *tx で可変参照を渡す
-                .await;
+            })
+            .collect::<Result<_, _>>()?,
+    };
+    let base = プレゼント予約ベース {
+        id,
+        依頼者id: ユーザーID::from_uuid(row.requester_id),
+        届け先id: 届け先ID::from_uuid(row.recipient_id),
+        記念日: 記念日 {
+            value: row.anniversary_date,
+        },
+        メッセージ内容: message_from_db(row.message),
+        ラッピング,
+        配送希望: delivery_preference_from_db(
+            row.desired_delivery_date,
+            row.desired_delivery_time_slot.as_deref(),
+            &id,
+        )?,
+        合計金額,
+        料金内訳,
+        支払いid: 支払いID::from_uuid(row.payment_id),
+        プレゼント内容,
+    };
 
-                if let Err(e) = result {
-                    eprintln!("DB Error: Failed to save reservation: {}", e);
-                    tx.rollback().await.ok(); // ロールバック試行
-                    return Err(DomainError::予約NotFound(base.id)); // 仮
-                }
+    // status に基づいて状態固有のカラムを復元する
+    let state = match row.status.as_str() {
+        "Received" => {
+            プレゼント予約状態::予約受付済み(予約受付済みプレゼント予約型 { base })
+        }
+        "Preparing" => プレゼント予約状態::発送準備中(
+            crate::domain::core::発送準備中プレゼント予約型 {
+                base,
+                梱包担当者id: ユーザーID::from_uuid(required_column(
+                    row.preparation_staff_id,
+                    "preparation_staff_id",
+                    &id,
+                )?),
+            },
+        ),
+        "Shipped" => プレゼント予約状態::発送済み(
+            crate::domain::core::発送済みプレゼント予約型 {
+                base,
+                配送伝票番号: tracking_number_from_db(
+                    row.shipping_carrier.as_deref(),
+                    row.shipping_slip_number.as_deref(),
+                    &id,
+                )?,
+            },
+        ),
+        "Delivered" => プレゼント予約状態::配送完了(
+            crate::domain::core::配送完了プレゼント予約型 {
+                base,
+                配送伝票番号: tracking_number_from_db(
+                    row.shipping_carrier.as_deref(),
+                    row.shipping_slip_number.as_deref(),
+                    &id,
+                )?,
+                配送完了日時: required_column(
+                    row.delivery_completed_at,
+                    "delivery_completed_at",
+                    &id,
+                )?
+                .with_timezone(&Tokyo),
+            },
+        ),
+        "DeliveryFailed" => {
+            let (配送失敗理由, 配送失敗日時) = delivery_failure_from_db(
+                row.delivery_failure_reason.as_deref(),
+                row.delivery_failed_at,
+                &id,
+            )?;
+            プレゼント予約状態::配送失敗(
+                crate::domain::core::配送失敗プレゼント予約型 {
+                    base,
+                    配送伝票番号: tracking_number_from_db(
+                        row.shipping_carrier.as_deref(),
+                        row.shipping_slip_number.as_deref(),
+                        &id,
+                    )?,
+                    配送失敗理由,
+                    配送失敗日時,
+                },
+            )
+        }
+        "Returned" => {
+            let (配送失敗理由, 配送失敗日時) = delivery_failure_from_db(
+                row.delivery_failure_reason.as_deref(),
+                row.delivery_failed_at,
+                &id,
+            )?;
+            プレゼント予約状態::返送済み(
+                crate::domain::core::返送済みプレゼント予約型 {
+                    base,
+                    配送伝票番号: tracking_number_from_db(
+                        row.shipping_carrier.as_deref(),
+                        row.shipping_slip_number.as_deref(),
+                        &id,
+                    )?,
+                    配送失敗理由,
+                    配送失敗日時,
+                    返送日時: required_column(row.returned_at, "returned_at", &id)?
+                        .with_timezone(&Tokyo),
+                },
+            )
+        }
+        "Cancelled" => プレゼント予約状態::キャンセル済み(
+            crate::domain::core::キャンセル済みプレゼント予約型 {
+                base,
+                キャンセル理由: row.cancellation_reason,
+                キャンセル日時: required_column(row.cancelled_at, "cancelled_at", &id)?
+                    .with_timezone(&Tokyo),
+                キャンセル料: required_column(row.cancellation_fee, "cancellation_fee", &id)?
+                    as u32,
+            },
+        ),
+        unknown_status => {
+            eprintln!(
+                "DB Error: Unknown reservation status '{}' for id {}",
+                unknown_status, row.id
+            );
+            return Err(DomainError::予約NotFound(id)); // 不明な状態はエラー
+        }
+    };
+    Ok(state)
+}
 
-                // reservation_products テーブルのクリアと INSERT (ループ処理に変更)
-                let product_ids: Vec<Uuid> =
-                    base.手配商品リスト.iter().map(|id| *id.as_uuid()).collect();
+/// 状態に必須のカラムが NULL でないことを確認する
+fn required_column<T>(value: Option<T>, column: &str, id: &予約ID) -> Result<T, DomainError> {
+    value.ok_or_else(|| {
+        eprintln!("DB Error: {} is NULL for id {:?}", column, id);
+        DomainError::予約NotFound(*id)
+    })
+}
 
-                // 既存の関連を削除 (変更なし)
-                let delete_result = sqlx::query!(
-                    "DELETE FROM reservation_products WHERE reservation_id = $1",
-                    reservation_id
-                )
-                .execute(&mut *tx)
-                .await;
+/// 一覧・検索で行を予約に復元できなかったエラーを変換する
+fn reservation_load_error(e: DomainError) -> InfrastructureError {
+    InfrastructureError::DatabaseError(format!("予約を復元できません: {}", e))
+}
 
-                if let Err(e) = delete_result {
-                    eprintln!("DB Error: Failed to delete reservation products: {}", e);
-                    tx.rollback().await.ok();
-                    return Err(DomainError::予約NotFound(base.id)); // 仮
-                }
+/// reservations.message カラムの値をメッセージ内容に復元する
+///
+/// 文字数などのルールは受付時に検証済みのため、保存済みの文面は検証し直さない
+/// (ルール導入前の予約が読めなくなるのを防ぐ)。
+fn message_from_db(message: Option<String>) -> Option<メッセージ内容> {
+    message.map(メッセージ内容::保存済みの値から復元する)
+}
 
-                // 新しい関連を INSERT (ループ処理)
-                for product_id in product_ids {
-                    let insert_result = sqlx::query!(
-                        "INSERT INTO reservation_products (reservation_id, product_id) VALUES ($1, $2)",
-                        reservation_id,
-                        product_id // 個別の Uuid を渡す
-                    )
-                    .execute(&mut *tx)
-                    .await;
+/// reservations.status カラムの値
+fn status_column_value(status: 予約ステータス) -> &'static str {
+    match status {
+        予約ステータス::予約受付済み => "Received",
+        予約ステータス::発送準備中 => "Preparing",
+        予約ステータス::発送済み => "Shipped",
+        予約ステータス::配送完了 => "Delivered",
+        予約ステータス::配送失敗 => "DeliveryFailed",
+        予約ステータス::返送済み => "Returned",
+        予約ステータス::キャンセル済み => "Cancelled",
+    }
+}
 
-                    if let Err(e) = insert_result {
-                        eprintln!(
-                            "DB Error: Failed to insert reservation product {}: {}",
-                            product_id, e
-                        );
-                        // エラー発生時はトランザクション全体が commit されずに rollback されるので、
-                        // ここで明示的な rollback は必須ではない。
-                        // エラーを返して処理を中断する。
-                        return Err(DomainError::予約NotFound(base.id)); // 仮。より具体的なエラーが良い
-                    }
-                }
-            }
-            プレゼント予約状態::発送準備中(r) => {
-                let base = &r.base;
-                let reservation_id = *base.id.as_uuid();
-                let preparation_staff_id = *r.梱包担当者id.as_uuid();
-                let status = "Preparing";
+/// DB のプレゼント内容のカラムをプレゼント内容に変換する
+fn gift_from_db(
+    gift_type: &str,
+    description: Option<&str>,
+    arrival_date: Option<NaiveDate>,
+    product_ids: HashSet<商品ID>,
+    id: &予約ID,
+) -> Result<プレゼント内容, DomainError> {
+    match (gift_type, description, arrival_date) {
+        ("Arrangement", _, _) => Ok(プレゼント内容::手配 {
+            商品idリスト: product_ids,
+        }),
+        ("Forwarding", Some(description), Some(倉庫到着予定日)) => {
+            let 情報 = 発送代行プレゼント情報::new(description).map_err(|e| {
+                eprintln!(
+                    "DB Error: Invalid forwarding description for id {:?}: {:?}",
+                    id, e
+                );
+                DomainError::予約NotFound(*id) // 変換エラーもエラー扱い (仮)
+            })?;
+            Ok(プレゼント内容::発送代行 {
+                情報,
+                倉庫到着予定日,
+            })
+        }
+        _ => {
+            eprintln!(
+                "DB Error: Invalid gift columns (type '{}') for id {:?}",
+                gift_type, id
+            );
+            Err(DomainError::予約NotFound(*id)) // 仮のエラー
+        }
+    }
+}
 
-                sqlx::query!(
-                    r#"
-                    UPDATE reservations SET
-                        status = $1,
-                        preparation_staff_id = $2,
-                        shipping_slip_number = NULL, -- Reset other state columns
-                        delivery_completed_at = NULL,
-                        cancellation_reason = NULL,
-                        cancelled_at = NULL,
-                        updated_at = NOW()
-                    WHERE id = $3
-                    "#,
-                    status,
-                    preparation_staff_id,
-                    reservation_id
-                )
-                .execute(&mut *tx)
-                .await
-                .map_err(|e| {
-                    eprintln!("DB Error: Failed to update reservation to Preparing: {}", e);
-                    DomainError::予約NotFound(base.id) // 仮
-                })?;
-                // 商品リストの更新は不要 (状態遷移のみ)
+/// 料金項目を DB に保存する項目種別と商品IDに変換する
+fn charge_item_column_values(項目: &料金項目) -> (&'static str, Option<Uuid>) {
+    match 項目 {
+        料金項目::商品(商品id) => ("Product", Some(*商品id.as_uuid())),
+        料金項目::発送代行手数料 => ("ForwardingFee", None),
+        料金項目::ラッピング料 => ("Wrapping", None),
+        料金項目::送料 => ("Shipping", None),
+    }
+}
+
+/// 税区分を DB に保存する文字列に変換する
+fn tax_category_column_value(税区分: 税区分) -> &'static str {
+    match 税区分 {
+        税区分::標準税率 => "Standard",
+        税区分::軽減税率 => "Reduced",
+    }
+}
+
+/// DB の料金明細の行を料金明細に変換する
+fn charge_line_from_db(
+    item_kind: &str,
+    product_id: Option<Uuid>,
+    amount_excluding_tax: i32,
+    tax_category: &str,
+    tax_amount: i32,
+    id: &予約ID,
+) -> Result<料金明細, DomainError> {
+    let invalid = || {
+        eprintln!(
+            "DB Error: Invalid charge line (kind '{}', tax '{}') for id {:?}",
+            item_kind, tax_category, id
+        );
+        DomainError::予約NotFound(*id) // 仮のエラー
+    };
+    let 項目 = match (item_kind, product_id) {
+        ("Product", Some(product_id)) => 料金項目::商品(商品ID::from_uuid(product_id)),
+        ("ForwardingFee", None) => 料金項目::発送代行手数料,
+        ("Wrapping", None) => 料金項目::ラッピング料,
+        ("Shipping", None) => 料金項目::送料,
+        _ => return Err(invalid()),
+    };
+    let 税区分 = match tax_category {
+        "Standard" => 税区分::標準税率,
+        "Reduced" => 税区分::軽減税率,
+        _ => return Err(invalid()),
+    };
+    Ok(料金明細 {
+        項目,
+        税抜金額: u32::try_from(amount_excluding_tax).map_err(|_| invalid())?,
+        税区分,
+        消費税額: u32::try_from(tax_amount).map_err(|_| invalid())?,
+    })
+}
+
+/// 金額を INTEGER のカラムに保存する値に変換する (収まらない場合は 料金上限超過)
+fn amount_column_value(amount: u32) -> Result<i32, DomainError> {
+    i32::try_from(amount).map_err(|_| DomainError::料金上限超過)
+}
+
+/// 配送業者を DB に保存する文字列に変換する
+fn carrier_column_value(配送業者: 配送業者) -> &'static str {
+    match 配送業者 {
+        配送業者::ヤマト運輸 => "Yamato",
+        配送業者::佐川急便 => "Sagawa",
+        配送業者::日本郵便 => "JapanPost",
+    }
+}
+
+/// DB の配送業者と伝票番号のカラムを配送伝票番号に変換する
+///
+/// 伝票番号の形式は発送時に検証済みのため、保存済みの番号は検証し直さない
+/// (形式の検証を導入する前の自由入力の番号が読めなくなるのを防ぐ)。
+fn tracking_number_from_db(
+    carrier: Option<&str>,
+    number: Option<&str>,
+    id: &予約ID,
+) -> Result<配送伝票番号, DomainError> {
+    let 配送業者 = match carrier {
+        Some("Yamato") => 配送業者::ヤマト運輸,
+        Some("Sagawa") => 配送業者::佐川急便,
+        Some("JapanPost") => 配送業者::日本郵便,
+        _ => {
+            eprintln!(
+                "DB Error: Invalid shipping carrier {:?} for id {:?}",
+                carrier, id
+            );
+            return Err(DomainError::予約NotFound(*id)); // 仮のエラー
+        }
+    };
+    let number = number.ok_or_else(|| {
+        eprintln!("DB Error: shipping_slip_number is NULL for id {:?}", id);
+        DomainError::予約NotFound(*id)
+    })?;
+    Ok(配送伝票番号::保存済みの値から復元する(
+        配送業者,
+        number.to_string(),
+    ))
+}
+
+/// 配送失敗理由を DB に保存する文字列に変換する
+fn delivery_failure_reason_column_value(理由: 配送失敗理由) -> &'static str {
+    match 理由 {
+        配送失敗理由::受取拒否 => "Refused",
+        配送失敗理由::宛先不明 => "UnknownAddress",
+        配送失敗理由::保管期限切れ => "Unclaimed",
+    }
+}
+
+/// DB の配送失敗理由と配送失敗日時のカラムを変換する
+fn delivery_failure_from_db(
+    reason: Option<&str>,
+    failed_at: Option<DateTime<Utc>>,
+    id: &予約ID,
+) -> Result<(配送失敗理由, DateTime<chrono_tz::Tz>), DomainError> {
+    let 理由 = match reason {
+        Some("Refused") => 配送失敗理由::受取拒否,
+        Some("UnknownAddress") => 配送失敗理由::宛先不明,
+        Some("Unclaimed") => 配送失敗理由::保管期限切れ,
+        _ => {
+            eprintln!(
+                "DB Error: Invalid delivery failure reason {:?} for id {:?}",
+                reason, id
+            );
+            return Err(DomainError::予約NotFound(*id)); // 仮のエラー
+        }
+    };
+    let failed_at = failed_at.ok_or_else(|| {
+        eprintln!("DB Error: delivery_failed_at is NULL for id {:?}", id);
+        DomainError::予約NotFound(*id)
+    })?;
+    Ok((理由, failed_at.with_timezone(&Tokyo)))
+}
+
+/// 配送時間帯を DB に保存する文字列に変換する
+fn time_slot_column_value(slot: 配送時間帯) -> &'static str {
+    match slot {
+        配送時間帯::指定なし => "Unspecified",
+        配送時間帯::午前中 => "Morning",
+        配送時間帯::午後2時から4時 => "14-16",
+        配送時間帯::午後4時から6時 => "16-18",
+        配送時間帯::午後6時から8時 => "18-20",
+        配送時間帯::午後7時から9時 => "19-21",
+    }
+}
+
+/// DB の配送希望日・時間帯カラムを配送希望に変換する (時間帯が NULL の場合は指定なし扱い)
+fn delivery_preference_from_db(
+    date: Option<NaiveDate>,
+    slot: Option<&str>,
+    id: &予約ID,
+) -> Result<Option<配送希望>, DomainError> {
+    let Some(配送日) = date else {
+        return Ok(None);
+    };
+    let 時間帯 = match slot {
+        None | Some("Unspecified") => 配送時間帯::指定なし,
+        Some("Morning") => 配送時間帯::午前中,
+        Some("14-16") => 配送時間帯::午後2時から4時,
+        Some("16-18") => 配送時間帯::午後4時から6時,
+        Some("18-20") => 配送時間帯::午後6時から8時,
+        Some("19-21") => 配送時間帯::午後7時から9時,
+        Some(other) => {
+            eprintln!(
+                "DB Error: Invalid delivery time slot '{}' for id {:?}",
+                other, id
+            );
+            return Err(DomainError::予約NotFound(*id)); // 仮のエラー
+        }
+    };
+    Ok(Some(配送希望 {
+        配送日, 時間帯
+    }))
+}
+
+/// SQL 側で ORDER BY を切り替えるためのキー
+fn sort_order_key(order: 予約検索の並び順) -> &'static str {
+    match order {
+        予約検索の並び順::記念日昇順 => "anniversary_asc",
+        予約検索の並び順::記念日降順 => "anniversary_desc",
+        予約検索の並び順::作成日時昇順 => "created_asc",
+        予約検索の並び順::作成日時降順 => "created_desc",
+    }
+}
+
+// --- 届け先のインメモリリポジトリ ---
+
+/// インメモリの届け先リポジトリ (登録順を保持する)
+#[derive(Clone, Default)]
+pub struct InMemory届け先Repository {
+    recipients: Arc<Mutex<Vec<届け先>>>,
+}
+
+impl InMemory届け先Repository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl 届け先Repository for InMemory届け先Repository {
+    async fn save(&self, 届け先: &届け先) -> Result<(), DomainError> {
+        let mut recipients = self.recipients.lock().unwrap(); // Mutexをロック
+        match recipients.iter_mut().find(|r| r.id == 届け先.id) {
+            Some(existing) => *existing = 届け先.clone(),
+            None => recipients.push(届け先.clone()),
+        }
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &届け先ID) -> Result<Option<届け先>, DomainError> {
+        let recipients = self.recipients.lock().unwrap(); // Mutexをロック
+        Ok(recipients.iter().find(|r| r.id == *id).cloned())
+    }
+
+    async fn find_by_owner(
+        &self, 所有者id: &ユーザーID
+    ) -> Result<Vec<届け先>, DomainError> {
+        let recipients = self.recipients.lock().unwrap(); // Mutexをロック
+        Ok(recipients
+            .iter()
+            .filter(|r| r.所有者か(所有者id))
+            .cloned()
+            .collect())
+    }
+
+    async fn delete(&self, id: &届け先ID) -> Result<(), DomainError> {
+        let mut recipients = self.recipients.lock().unwrap(); // Mutexをロック
+        recipients.retain(|r| r.id != *id);
+        Ok(())
+    }
+}
+
+// --- ユーザーアカウントのインメモリリポジトリ ---
+
+/// インメモリのユーザーアカウントリポジトリ
+#[derive(Clone, Default)]
+pub struct InMemoryユーザーアカウントRepository {
+    accounts: Arc<Mutex<HashMap<ユーザーID, ユーザーアカウント>>>,
+}
+
+impl InMemoryユーザーアカウントRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ユーザーアカウントRepository for InMemoryユーザーアカウントRepository {
+    async fn insert(
+        &self, アカウント: &ユーザーアカウント
+    ) -> Result<(), DomainError> {
+        let mut accounts = self.accounts.lock().unwrap(); // Mutexをロック
+        if accounts.contains_key(&アカウント.id) {
+            return Err(DomainError::ユーザーアカウント登録済み(
+                アカウント.id,
+            ));
+        }
+        if accounts
+            .values()
+            .any(|a| a.認証サブジェクト == アカウント.認証サブジェクト)
+        {
+            return Err(DomainError::認証サブジェクト使用中);
+        }
+        accounts.insert(アカウント.id, アカウント.clone());
+        Ok(())
+    }
+
+    async fn save(&self, アカウント: &ユーザーアカウント) -> Result<(), DomainError> {
+        let mut accounts = self.accounts.lock().unwrap(); // Mutexをロック
+                                                          // Pg の一意制約と同じく、認証サブジェクトは 1 つのアカウントにしか結び付けない
+        if accounts
+            .values()
+            .any(|a| a.id != アカウント.id && a.認証サブジェクト == アカウント.認証サブジェクト)
+        {
+            return Err(DomainError::認証サブジェクト使用中);
+        }
+        accounts.insert(アカウント.id, アカウント.clone());
+        Ok(())
+    }
+
+    async fn find_by_id(
+        &self,
+        id: &ユーザーID,
+    ) -> Result<Option<ユーザーアカウント>, DomainError> {
+        let accounts = self.accounts.lock().unwrap(); // Mutexをロック
+        Ok(accounts.get(id).cloned())
+    }
+
+    async fn find_by_auth_subject(
+        &self,
+        サブジェクト: &認証サブジェクト,
+    ) -> Result<Option<ユーザーアカウント>, DomainError> {
+        let accounts = self.accounts.lock().unwrap(); // Mutexをロック
+        Ok(accounts
+            .values()
+            .find(|a| a.認証サブジェクト == *サブジェクト)
+            .cloned())
+    }
+}
+
+// --- 冪等性キーのインメモリ保存先 ---
+
+#[async_trait]
+impl IdempotencyStore for InMemoryプレゼント予約Repository {
+    async fn begin(
+        &self,
+        record: &IdempotencyRecord,
+    ) -> Result<IdempotencyBegin, InfrastructureError> {
+        let mut records = self.idempotency_keys.lock().unwrap(); // Mutexをロック
+        let map_key = (record.requester_id, record.key.clone());
+        match records.get(&map_key) {
+            Some(existing)
+                if existing.expires_at > record.created_at
+                    && (existing.reservation_id.is_some()
+                        || existing.lease_expires_at > record.created_at) =>
+            {
+                Ok(IdempotencyBegin::Existing(existing.clone()))
             }
-            プレゼント予約状態::発送済み(r) => {
+            _ => {
+                records.insert(map_key, record.clone());
+                Ok(IdempotencyBegin::Started)
+            }
+        }
+    }
+
+    async fn abandon(&self, record: &IdempotencyRecord) -> Result<(), InfrastructureError> {
+        let mut records = self.idempotency_keys.lock().unwrap(); // Mutexをロック
+        let map_key = (record.requester_id, record.key.clone());
+        // 確保し直した別のリクエストの記録は消さない
+        if records.get(&map_key).is_some_and(|existing| {
+            existing.reservation_id.is_none() && existing.created_at == record.created_at
+        }) {
+            records.remove(&map_key);
+        }
+        Ok(())
+    }
+}
+
+// --- ドメインイベントの発行先 ---
+
+/// 発行されたイベントをメモリに溜めておく発行者 (テストでの確認用)
+#[derive(Clone, Default)]
+pub struct InMemoryイベント発行者 {
+    events: Arc<Mutex<Vec<ドメインイベント>>>,
+}
+
+impl InMemoryイベント発行者 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// これまでに発行されたイベントを発行順に返す
+    pub fn 発行済みイベント(&self) -> Vec<ドメインイベント> {
+        self.events.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl イベント発行者 for InMemoryイベント発行者 {
+    async fn 発行する(
+        &self, イベント: &[ドメインイベント]
+    ) -> Result<(), InfrastructureError> {
+        let mut events = self.events.lock().unwrap(); // Mutexをロック
+        events.extend_from_slice(イベント);
+        Ok(())
+    }
+}
+
+/// イベントをログに出力する発行者 (メッセージブローカー導入までの既定の発行先)
+#[derive(Clone, Copy, Default)]
+pub struct Tracingイベント発行者;
+
+#[async_trait]
+impl イベント発行者 for Tracingイベント発行者 {
+    async fn 発行する(
+        &self, イベント: &[ドメインイベント]
+    ) -> Result<(), InfrastructureError> {
+        for event in イベント {
+            let info = event.情報();
+            tracing::info!(
+                event = event.名前(),
+                reservation_id = %info.予約id.as_uuid(),
+                actor = ?info.実行者.map(|id| *id.as_uuid()),
+                occurred_at = %info.発生日時.to_rfc3339(),
+                "Domain event published"
+            );
+        }
+        Ok(())
+    }
+}
+
+// --- PostgreSQL リポジトリの実装 (ここから追加) ---
+
+#[derive(Clone)]
+pub struct PgRepository {
+    pool: PgPool,
+}
+
+impl PgRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// データベースへの接続を確認する
+    pub async fn check_db_connection(&self) -> Result<(), sqlx::Error> {
+        // 最も単純なクエリを実行して接続を試みる
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map(|_| ()) // 成功時は () を返す
+                         // エラー時は sqlx::Error がそのまま返る
+    }
+}
+
+impl PgRepository {
+    /// 予約を 1 つのトランザクションで保存する (在庫の移動がある場合はコミット前に行う)
+    async fn save_in_transaction(
+        &self,
+        reservation_state: &プレゼント予約状態,
+        在庫の移動: Option<在庫移動>,
+        冪等性キー: Option<&冪等性キーの確保>,
+    ) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            eprintln!("DB Error: Failed to begin transaction: {}", e);
+            // TODO: より適切なエラーマッピング (例: InfrastructureError::DatabaseError(e))
+            DomainError::予約NotFound(予約ID::new()) // 仮のエラー
+        })?;
+        // 読み込んだ後に他のリクエストが状態を変えていたら更新しない
+        let previous_statuses: Vec<String> = reservation_state
+            .遷移元のステータス()
+            .iter()
+            .map(|status| status_column_value(*status).to_string())
+            .collect();
+
+        match reservation_state {
+            プレゼント予約状態::予約受付済み(r) => {
                 let base = &r.base;
                 let reservation_id = *base.id.as_uuid();
-                let shipping_slip_number = &r.配送伝票番号;
-                let status = "Shipped";
+                let requester_id = *base.依頼者id.as_uuid();
+                let recipient_id = *base.届け先id.as_uuid();
+                let anniversary_date = base.記念日.value; // NaiveDate
+                let message = base.メッセージ内容.as_ref().map(|m| m.value()); // Option<メッセージ内容> -> Option<&str>
+                let wrapping_type = format!("{:?}", base.ラッピング); // Enum -> String (例: "標準")
+                let desired_delivery_date = base.配送希望.map(|d| d.配送日); // Option<NaiveDate>
+                let desired_delivery_time_slot =
+                    base.配送希望.map(|d| time_slot_column_value(d.時間帯));
+                // DB は INTEGER のため、収まらない金額を負の数で保存しないよう確認する
+                let total_amount = amount_column_value(base.合計金額.value())?;
+                let payment_id = *base.支払いid.as_uuid();
+                let status = "Received"; // 状態文字列
+                let (gift_type, forwarding_description, forwarding_arrival_date) =
+                    match &base.プレゼント内容 {
+                        プレゼント内容::発送代行 {
+                            情報,
+                            倉庫到着予定日,
+                        } => ("Forwarding", Some(情報.value()), Some(*倉庫到着予定日)),
+                        プレゼント内容::手配 { .. } => ("Arrangement", None, None),
+                    };
 
-                sqlx::query!(
+                // reservations テーブルへの UPSERT
+                let result = sqlx::query!(
                     r#"
-                    UPDATE reservations SET
-                        status = $1,
-                        shipping_slip_number = $2,
-                        preparation_staff_id = NULL, -- Reset other state columns
+                    INSERT INTO reservations (
+                        id, requester_id, recipient_id, anniversary_date, message,
+                        wrapping_type, desired_delivery_date, desired_delivery_time_slot,
+                        total_amount, payment_id, status,
+                        gift_type, forwarding_description, forwarding_arrival_date,
+                        -- updated_at は DEFAULT NOW() または trigger で設定される想定
+                        -- 他の状態固有カラムはデフォルト値またはNULLになる
+                        preparation_staff_id, shipping_carrier, shipping_slip_number, delivery_completed_at,
+                        cancellation_reason, cancelled_at, cancellation_fee,
+                        delivery_failure_reason, delivery_failed_at, returned_at
+                    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL)
+                    ON CONFLICT (id) DO UPDATE SET
+                        requester_id = EXCLUDED.requester_id,
+                        recipient_id = EXCLUDED.recipient_id,
+                        anniversary_date = EXCLUDED.anniversary_date,
+                        message = EXCLUDED.message,
+                        wrapping_type = EXCLUDED.wrapping_type,
+                        desired_delivery_date = EXCLUDED.desired_delivery_date,
+                        desired_delivery_time_slot = EXCLUDED.desired_delivery_time_slot,
+                        total_amount = EXCLUDED.total_amount,
+                        payment_id = EXCLUDED.payment_id,
+                        status = EXCLUDED.status,
+                        gift_type = EXCLUDED.gift_type,
+                        forwarding_description = EXCLUDED.forwarding_description,
+                        forwarding_arrival_date = EXCLUDED.forwarding_arrival_date,
+                        -- 他の状態固有カラムをリセット (NULL に設定)
+                        preparation_staff_id = NULL,
+                        shipping_carrier = NULL,
+                        shipping_slip_number = NULL,
                         delivery_completed_at = NULL,
                         cancellation_reason = NULL,
                         cancelled_at = NULL,
-                        updated_at = NOW()
-                    WHERE id = $3
+                        cancellation_fee = NULL,
+                        delivery_failure_reason = NULL,
+                        delivery_failed_at = NULL,
+                        returned_at = NULL,
+                        updated_at = NOW() -- updated_at を更新
+                    -- 変更できるのは予約受付済みの予約だけ (発送準備中・キャンセル済みなどを受付済みに戻さない)
+                    WHERE reservations.status = 'Received'
                     "#,
-                    status,
-                    shipping_slip_number,
+                    reservation_id,
+                    requester_id,
+                    recipient_id,
+                    anniversary_date,      // NaiveDate
+                    message,               // Option<&str>
+                    wrapping_type,         // String
+                    desired_delivery_date, // Option<NaiveDate>
+                    desired_delivery_time_slot, // Option<&str>
+                    total_amount,          // i32
+                    payment_id,
+                    status, // &str
+                    gift_type,
+                    forwarding_description,  // Option<&str>
+                    forwarding_arrival_date  // Option<NaiveDate>
+                )
+                .execute(&mut *tx) // &mut *tx で可変参照を渡す
+                .await;
+
+                match result {
+                    Err(e) => {
+                        eprintln!("DB Error: Failed to save reservation: {}", e);
+                        tx.rollback().await.ok(); // ロールバック試行
+                        return Err(DomainError::予約NotFound(base.id)); // 仮
+                    }
+                    Ok(done) => {
+                        ensure_transitioned(&mut tx, done.rows_affected(), &base.id).await?
+                    }
+                }
+
+                // reservation_products テーブルのクリアと INSERT (手配の場合のみ商品がある)
+                let product_ids: Vec<Uuid> = base
+                    .プレゼント内容
+                    .手配商品リスト()
+                    .iter()
+                    .map(|id| *id.as_uuid())
+                    .collect();
+
+                // 既存の関連を削除 (変更なし)
+                let delete_result = sqlx::query!(
+                    "DELETE FROM reservation_products WHERE reservation_id = $1",
+                    reservation_id
+                )
+                .execute(&mut *tx)
+                .await;
+
+                if let Err(e) = delete_result {
+                    eprintln!("DB Error: Failed to delete reservation products: {}", e);
+                    tx.rollback().await.ok();
+                    return Err(DomainError::予約NotFound(base.id)); // 仮
+                }
+
+                // 新しい関連を INSERT (ループ処理)
+                for product_id in product_ids {
+                    let insert_result = sqlx::query!(
+                        "INSERT INTO reservation_products (reservation_id, product_id) VALUES ($1, $2)",
+                        reservation_id,
+                        product_id // 個別の Uuid を渡す
+                    )
+                    .execute(&mut *tx)
+                    .await;
+
+                    if let Err(e) = insert_result {
+                        eprintln!(
+                            "DB Error: Failed to insert reservation product {}: {}",
+                            product_id, e
+                        );
+                        // エラー発生時はトランザクション全体が commit されずに rollback されるので、
+                        // ここで明示的な rollback は必須ではない。
+                        // エラーを返して処理を中断する。
+                        return Err(DomainError::予約NotFound(base.id)); // 仮。より具体的なエラーが良い
+                    }
+                }
+
+                // reservation_charge_lines テーブルのクリアと INSERT (料金内訳)
+                sqlx::query!(
+                    "DELETE FROM reservation_charge_lines WHERE reservation_id = $1",
                     reservation_id
                 )
                 .execute(&mut *tx)
                 .await
                 .map_err(|e| {
-                    eprintln!("DB Error: Failed to update reservation to Shipped: {}", e);
+                    eprintln!("DB Error: Failed to delete reservation charge lines: {}", e);
                     DomainError::予約NotFound(base.id) // 仮
                 })?;
-            }
-            プレゼント予約状態::配送完了(r) => {
-                let base = &r.base;
-                let reservation_id = *base.id.as_uuid();
-                let delivery_completed_at = r.配送完了日時; // DateTime<Tz>
-                let status = "Delivered";
+                for (line_no, 行) in base.料金内訳.明細.iter().enumerate() {
+                    let (item_kind, product_id) = charge_item_column_values(&行.項目);
+                    let amount_excluding_tax = amount_column_value(行.税抜金額)?;
+                    let tax_amount = amount_column_value(行.消費税額)?;
+                    sqlx::query!(
+                        r#"
+                        INSERT INTO reservation_charge_lines (
+                            reservation_id, line_no, item_kind, product_id,
+                            amount_excluding_tax, tax_category, tax_amount
+                        ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+                        "#,
+                        reservation_id,
+                        line_no as i32,
+                        item_kind,
+                        product_id, // Option<Uuid>
+                        amount_excluding_tax,
+                        tax_category_column_value(行.税区分),
+                        tax_amount
+                    )
+                    .execute(&mut *tx)
+                    .await
+                    .map_err(|e| {
+                        eprintln!(
+                            "DB Error: Failed to insert reservation charge line {}: {}",
+                            line_no, e
+                        );
+                        DomainError::予約NotFound(base.id) // 仮
+                    })?;
+                }
+            }
+            プレゼント予約状態::発送準備中(r) => {
+                let base = &r.base;
+                let reservation_id = *base.id.as_uuid();
+                let preparation_staff_id = *r.梱包担当者id.as_uuid();
+                let status = "Preparing";
 
-                sqlx::query!(
+                let updated = sqlx::query!(
+                    r#"
+                    UPDATE reservations SET
+                        status = $1,
+                        preparation_staff_id = $2,
+                        shipping_carrier = NULL, -- Reset other state columns
+                        shipping_slip_number = NULL,
+                        delivery_completed_at = NULL,
+                        cancellation_reason = NULL,
+                        cancelled_at = NULL,
+                        cancellation_fee = NULL,
+                        delivery_failure_reason = NULL,
+                        delivery_failed_at = NULL,
+                        returned_at = NULL,
+                        updated_at = NOW()
+                    WHERE id = $3 AND status = ANY($4)
+                    "#,
+                    status,
+                    preparation_staff_id,
+                    reservation_id,
+                    &previous_statuses[..]
+                )
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    eprintln!("DB Error: Failed to update reservation to Preparing: {}", e);
+                    DomainError::予約NotFound(base.id) // 仮
+                })?;
+                ensure_transitioned(&mut tx, updated.rows_affected(), &base.id).await?;
+                // 商品リストの更新は不要 (状態遷移のみ)
+            }
+            プレゼント予約状態::発送済み(r) => {
+                let base = &r.base;
+                let reservation_id = *base.id.as_uuid();
+                let shipping_carrier = carrier_column_value(r.配送伝票番号.配送業者());
+                let shipping_slip_number = r.配送伝票番号.value();
+                let status = "Shipped";
+
+                let updated = sqlx::query!(
+                    r#"
+                    UPDATE reservations SET
+                        status = $1,
+                        shipping_slip_number = $2,
+                        shipping_carrier = $4,
+                        preparation_staff_id = NULL, -- Reset other state columns
+                        delivery_completed_at = NULL,
+                        cancellation_reason = NULL,
+                        cancelled_at = NULL,
+                        cancellation_fee = NULL,
+                        delivery_failure_reason = NULL,
+                        delivery_failed_at = NULL,
+                        returned_at = NULL,
+                        updated_at = NOW()
+                    WHERE id = $3 AND status = ANY($5)
+                    "#,
+                    status,
+                    shipping_slip_number,
+                    reservation_id,
+                    shipping_carrier,
+                    &previous_statuses[..]
+                )
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    eprintln!("DB Error: Failed to update reservation to Shipped: {}", e);
+                    DomainError::予約NotFound(base.id) // 仮
+                })?;
+                ensure_transitioned(&mut tx, updated.rows_affected(), &base.id).await?;
+            }
+            プレゼント予約状態::配送完了(r) => {
+                let base = &r.base;
+                let reservation_id = *base.id.as_uuid();
+                let delivery_completed_at = r.配送完了日時; // DateTime<Tz>
+                let status = "Delivered";
+
+                let updated = sqlx::query!(
                     r#"
                     UPDATE reservations SET
                         status = $1,
@@ -349,12 +1409,17 @@ impl プレゼント予約Repository for PgRepository {
                         -- shipping_slip_number は Shipped 状態で設定済みのはず
                         cancellation_reason = NULL,
                         cancelled_at = NULL,
+                        cancellation_fee = NULL,
+                        delivery_failure_reason = NULL,
+                        delivery_failed_at = NULL,
+                        returned_at = NULL,
                         updated_at = NOW()
-                    WHERE id = $3
+                    WHERE id = $3 AND status = ANY($4)
                     "#,
                     status,
                     delivery_completed_at,
-                    reservation_id
+                    reservation_id,
+                    &previous_statuses[..]
                 )
                 .execute(&mut *tx)
                 .await
@@ -362,28 +1427,118 @@ impl プレゼント予約Repository for PgRepository {
                     eprintln!("DB Error: Failed to update reservation to Delivered: {}", e);
                     DomainError::予約NotFound(base.id) // 仮
                 })?;
+                ensure_transitioned(&mut tx, updated.rows_affected(), &base.id).await?;
+            }
+            プレゼント予約状態::配送失敗(r) => {
+                let base = &r.base;
+                let reservation_id = *base.id.as_uuid();
+                let shipping_carrier = carrier_column_value(r.配送伝票番号.配送業者());
+                let shipping_slip_number = r.配送伝票番号.value();
+                let failure_reason = delivery_failure_reason_column_value(r.配送失敗理由);
+                let failed_at = r.配送失敗日時;
+                let status = "DeliveryFailed";
+
+                let updated = sqlx::query!(
+                    r#"
+                    UPDATE reservations SET
+                        status = $1,
+                        shipping_carrier = $2,
+                        shipping_slip_number = $3,
+                        delivery_failure_reason = $4,
+                        delivery_failed_at = $5,
+                        returned_at = NULL,
+                        cancellation_reason = NULL,
+                        cancelled_at = NULL,
+                        cancellation_fee = NULL,
+                        updated_at = NOW()
+                    WHERE id = $6 AND status = ANY($7)
+                    "#,
+                    status,
+                    shipping_carrier,
+                    shipping_slip_number,
+                    failure_reason,
+                    failed_at,
+                    reservation_id,
+                    &previous_statuses[..]
+                )
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    eprintln!(
+                        "DB Error: Failed to update reservation to DeliveryFailed: {}",
+                        e
+                    );
+                    DomainError::予約NotFound(base.id) // 仮
+                })?;
+                ensure_transitioned(&mut tx, updated.rows_affected(), &base.id).await?;
+            }
+            プレゼント予約状態::返送済み(r) => {
+                let base = &r.base;
+                let reservation_id = *base.id.as_uuid();
+                let shipping_carrier = carrier_column_value(r.配送伝票番号.配送業者());
+                let shipping_slip_number = r.配送伝票番号.value();
+                let failure_reason = delivery_failure_reason_column_value(r.配送失敗理由);
+                let failed_at = r.配送失敗日時;
+                let returned_at = r.返送日時;
+                let status = "Returned";
+
+                let updated = sqlx::query!(
+                    r#"
+                    UPDATE reservations SET
+                        status = $1,
+                        shipping_carrier = $2,
+                        shipping_slip_number = $3,
+                        delivery_failure_reason = $4,
+                        delivery_failed_at = $5,
+                        returned_at = $6,
+                        cancellation_reason = NULL,
+                        cancelled_at = NULL,
+                        cancellation_fee = NULL,
+                        updated_at = NOW()
+                    WHERE id = $7 AND status = ANY($8)
+                    "#,
+                    status,
+                    shipping_carrier,
+                    shipping_slip_number,
+                    failure_reason,
+                    failed_at,
+                    returned_at,
+                    reservation_id,
+                    &previous_statuses[..]
+                )
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    eprintln!("DB Error: Failed to update reservation to Returned: {}", e);
+                    DomainError::予約NotFound(base.id) // 仮
+                })?;
+                ensure_transitioned(&mut tx, updated.rows_affected(), &base.id).await?;
             }
             プレゼント予約状態::キャンセル済み(r) => {
                 let base = &r.base;
                 let reservation_id = *base.id.as_uuid();
                 let cancellation_reason = r.キャンセル理由.as_deref(); // Option<String> -> Option<&str>
-                let cancelled_at = r.キャンセル日時; // Option<DateTime<Tz>>
+                let cancelled_at = r.キャンセル日時;
+                let cancellation_fee = amount_column_value(r.キャンセル料)?;
                 let status = "Cancelled";
 
-                sqlx::query!(
+                let updated = sqlx::query!(
                     r#"
                     UPDATE reservations SET
                         status = $1,
                         cancellation_reason = $2,
                         cancelled_at = $3,
+                        cancellation_fee = $5,
                         -- preparation_staff_id, shipping_slip_number, delivery_completed_at は状態によって設定済みか NULL
                         updated_at = NOW()
-                    WHERE id = $4
+                    WHERE id = $4 AND status = ANY($6)
                     "#,
                     status,
                     cancellation_reason,
                     cancelled_at,
-                    reservation_id
+                    reservation_id,
+                    cancellation_fee,
+                    &previous_statuses[..]
                 )
                  .execute(&mut *tx)
                  .await
@@ -391,467 +1546,2467 @@ impl プレゼント予約Repository for PgRepository {
                      eprintln!("DB Error: Failed to update reservation to Cancelled: {}", e);
                      DomainError::予約NotFound(base.id) // 仮
                  })?;
+                ensure_transitioned(&mut tx, updated.rows_affected(), &base.id).await?;
             }
         }
 
+        if let Some(移動) = 在庫の移動 {
+            move_stock(&mut tx, reservation_state, 移動).await?;
+        }
+        if let Some(キー) = 冪等性キー {
+            record_idempotency_key(&mut tx, reservation_state, キー).await?;
+        }
+
         tx.commit().await.map_err(|e| {
             eprintln!("DB Error: Failed to commit transaction: {}", e);
             // TODO: より適切なエラーマッピング
             DomainError::予約NotFound(予約ID::new())
         })
     }
+}
+
+#[async_trait]
+impl プレゼント予約Repository for PgRepository {
+    async fn save(
+        &self, reservation_state: &プレゼント予約状態
+    ) -> Result<(), DomainError> {
+        self.save_in_transaction(reservation_state, None, None)
+            .await
+    }
+
+    async fn save_with_stock_movement(
+        &self,
+        reservation_state: &プレゼント予約状態,
+        移動: 在庫移動,
+    ) -> Result<(), DomainError> {
+        self.save_in_transaction(reservation_state, Some(移動), None)
+            .await
+    }
+
+    async fn save_accepted_with_idempotency_key(
+        &self,
+        reservation_state: &プレゼント予約状態,
+        キー: &冪等性キーの確保,
+    ) -> Result<(), DomainError> {
+        self.save_in_transaction(reservation_state, Some(在庫移動::引当), Some(キー))
+            .await
+    }
 
     async fn find_by_id(
         &self,
         id: &予約ID,
     ) -> Result<Option<プレゼント予約状態>, DomainError> {
-        let reservation_uuid = *id.as_uuid();
+        // 商品IDと料金明細もまとめて 1 回のクエリで取得する
+        let row = sqlx::query_as!(
+            ReservationRow,
+            r#"
+            SELECT
+                r.id, r.requester_id, r.recipient_id, r.anniversary_date, r.message,
+                r.wrapping_type, r.desired_delivery_date, r.desired_delivery_time_slot,
+                r.total_amount, r.payment_id, r.status,
+                r.gift_type, r.forwarding_description, r.forwarding_arrival_date,
+                r.preparation_staff_id, r.shipping_carrier, r.shipping_slip_number,
+                r.delivery_completed_at, r.cancellation_reason, r.cancelled_at,
+                r.cancellation_fee, r.delivery_failure_reason, r.delivery_failed_at,
+                r.returned_at, r.created_at,
+                ARRAY(
+                    SELECT p.product_id FROM reservation_products p
+                    WHERE p.reservation_id = r.id
+                ) AS "product_ids!",
+                COALESCE((
+                    SELECT json_agg(json_build_object(
+                        'item_kind', c.item_kind,
+                        'product_id', c.product_id,
+                        'amount_excluding_tax', c.amount_excluding_tax,
+                        'tax_category', c.tax_category,
+                        'tax_amount', c.tax_amount
+                    ) ORDER BY c.line_no)
+                    FROM reservation_charge_lines c
+                    WHERE c.reservation_id = r.id
+                ), '[]') AS "charge_lines!: Json<Vec<ChargeLineRow>>"
+            FROM reservations r
+            WHERE r.id = $1
+            "#,
+            id.as_uuid()
+        )
+        .fetch_optional(&self.pool) // 見つからない場合は None を返す
+        .await
+        .map_err(|e| {
+            // TODO: エラーマッピング改善
+            eprintln!(
+                "DB Error: Failed to fetch reservation by id {:?}: {}",
+                id, e
+            );
+            DomainError::予約NotFound(*id) // 仮
+        })?;
+        row.map(reservation_from_db).transpose()
+    }
+
+    async fn find_by_requester(
+        &self,
+        依頼者id: &ユーザーID,
+        cursor: Option<予約一覧カーソル>,
+        limit: usize,
+    ) -> Result<予約一覧ページ, InfrastructureError> {
+        let requester_uuid = *依頼者id.as_uuid();
+        let (cursor_date, cursor_created_at, cursor_id) = match &cursor {
+            Some(c) => (Some(c.記念日), Some(c.作成日時), Some(*c.予約id.as_uuid())),
+            None => (None, None, None),
+        };
+
+        // 続きの有無を判定するため limit + 1 件取得する (キーセットページネーション)
+        // 商品IDと料金明細も同じクエリで取得し、予約ごとに問い合わせない
+        let rows = sqlx::query_as!(
+            ReservationRow,
+            r#"
+            SELECT
+                r.id, r.requester_id, r.recipient_id, r.anniversary_date, r.message,
+                r.wrapping_type, r.desired_delivery_date, r.desired_delivery_time_slot,
+                r.total_amount, r.payment_id, r.status,
+                r.gift_type, r.forwarding_description, r.forwarding_arrival_date,
+                r.preparation_staff_id, r.shipping_carrier, r.shipping_slip_number,
+                r.delivery_completed_at, r.cancellation_reason, r.cancelled_at,
+                r.cancellation_fee, r.delivery_failure_reason, r.delivery_failed_at,
+                r.returned_at, r.created_at,
+                ARRAY(
+                    SELECT p.product_id FROM reservation_products p
+                    WHERE p.reservation_id = r.id
+                ) AS "product_ids!",
+                COALESCE((
+                    SELECT json_agg(json_build_object(
+                        'item_kind', c.item_kind,
+                        'product_id', c.product_id,
+                        'amount_excluding_tax', c.amount_excluding_tax,
+                        'tax_category', c.tax_category,
+                        'tax_amount', c.tax_amount
+                    ) ORDER BY c.line_no)
+                    FROM reservation_charge_lines c
+                    WHERE c.reservation_id = r.id
+                ), '[]') AS "charge_lines!: Json<Vec<ChargeLineRow>>"
+            FROM reservations r
+            WHERE r.requester_id = $1
+              AND ($2::DATE IS NULL
+                   OR (r.anniversary_date, r.created_at, r.id)
+                      > ($2::DATE, $3::TIMESTAMPTZ, $4::UUID))
+            ORDER BY r.anniversary_date, r.created_at, r.id
+            LIMIT $5
+            "#,
+            requester_uuid,
+            cursor_date,
+            cursor_created_at,
+            cursor_id,
+            (limit + 1) as i64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!(
+                "DB Error: Failed to fetch reservations for requester {}: {}",
+                requester_uuid, e
+            );
+            InfrastructureError::DatabaseError(e.to_string())
+        })?;
+
+        let has_more = rows.len() > limit;
+        let mut 予約リスト = Vec::with_capacity(limit);
+        let mut 次のカーソル = None;
+        for row in rows.into_iter().take(limit) {
+            let カーソル = 予約一覧カーソル {
+                記念日: row.anniversary_date,
+                作成日時: row.created_at,
+                予約id: 予約ID::from_uuid(row.id),
+            };
+            予約リスト.push(reservation_from_db(row).map_err(reservation_load_error)?);
+            if has_more {
+                次のカーソル = Some(カーソル);
+            }
+        }
+
+        Ok(予約一覧ページ {
+            予約リスト,
+            次のカーソル,
+        })
+    }
+
+    async fn search(
+        &self,
+        条件: &予約検索条件,
+        offset: usize,
+        limit: usize,
+    ) -> Result<予約検索結果, InfrastructureError> {
+        let statuses: Option<Vec<String>> = if 条件.ステータス.is_empty() {
+            None
+        } else {
+            Some(
+                条件
+                    .ステータス
+                    .iter()
+                    .map(|s| status_column_value(*s).to_string())
+                    .collect(),
+            )
+        };
+        let wrapping_type = 条件.ラッピング.map(|w| format!("{:?}", w)); // save と同じ表現
+        let requester_uuid = 条件.依頼者id.map(|id| *id.as_uuid());
+        let recipient_uuid = 条件.届け先id.map(|id| *id.as_uuid());
+
+        let db_error = |e: sqlx::Error| {
+            eprintln!("DB Error: Failed to search reservations: {}", e);
+            InfrastructureError::DatabaseError(e.to_string())
+        };
+
+        // 条件はすべてバインド変数で渡し、NULL の条件は絞り込まない
+        let 総件数 = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM reservations
+            WHERE ($1::VARCHAR[] IS NULL OR status = ANY($1))
+              AND ($2::DATE IS NULL OR anniversary_date >= $2)
+              AND ($3::DATE IS NULL OR anniversary_date <= $3)
+              AND ($4::VARCHAR IS NULL OR wrapping_type = $4)
+              AND ($5::UUID IS NULL OR requester_id = $5)
+              AND ($6::UUID IS NULL OR recipient_id = $6)
+              AND ($7::TIMESTAMPTZ IS NULL OR created_at >= $7)
+              AND ($8::TIMESTAMPTZ IS NULL OR created_at <= $8)
+            "#,
+            statuses.as_deref(),
+            条件.記念日_開始,
+            条件.記念日_終了,
+            wrapping_type,
+            requester_uuid,
+            recipient_uuid,
+            条件.作成日時_開始,
+            条件.作成日時_終了
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        // 並び順はキーで CASE を切り替え、同順位は id で確定させる
+        // 商品IDと料金明細も同じクエリで取得し、予約ごとに問い合わせない
+        let rows = sqlx::query_as!(
+            ReservationRow,
+            r#"
+            SELECT
+                r.id, r.requester_id, r.recipient_id, r.anniversary_date, r.message,
+                r.wrapping_type, r.desired_delivery_date, r.desired_delivery_time_slot,
+                r.total_amount, r.payment_id, r.status,
+                r.gift_type, r.forwarding_description, r.forwarding_arrival_date,
+                r.preparation_staff_id, r.shipping_carrier, r.shipping_slip_number,
+                r.delivery_completed_at, r.cancellation_reason, r.cancelled_at,
+                r.cancellation_fee, r.delivery_failure_reason, r.delivery_failed_at,
+                r.returned_at, r.created_at,
+                ARRAY(
+                    SELECT p.product_id FROM reservation_products p
+                    WHERE p.reservation_id = r.id
+                ) AS "product_ids!",
+                COALESCE((
+                    SELECT json_agg(json_build_object(
+                        'item_kind', c.item_kind,
+                        'product_id', c.product_id,
+                        'amount_excluding_tax', c.amount_excluding_tax,
+                        'tax_category', c.tax_category,
+                        'tax_amount', c.tax_amount
+                    ) ORDER BY c.line_no)
+                    FROM reservation_charge_lines c
+                    WHERE c.reservation_id = r.id
+                ), '[]') AS "charge_lines!: Json<Vec<ChargeLineRow>>"
+            FROM reservations r
+            WHERE ($1::VARCHAR[] IS NULL OR r.status = ANY($1))
+              AND ($2::DATE IS NULL OR r.anniversary_date >= $2)
+              AND ($3::DATE IS NULL OR r.anniversary_date <= $3)
+              AND ($4::VARCHAR IS NULL OR r.wrapping_type = $4)
+              AND ($5::UUID IS NULL OR r.requester_id = $5)
+              AND ($6::UUID IS NULL OR r.recipient_id = $6)
+              AND ($7::TIMESTAMPTZ IS NULL OR r.created_at >= $7)
+              AND ($8::TIMESTAMPTZ IS NULL OR r.created_at <= $8)
+            ORDER BY
+              CASE WHEN $9 = 'anniversary_asc' THEN r.anniversary_date END ASC,
+              CASE WHEN $9 = 'anniversary_desc' THEN r.anniversary_date END DESC,
+              CASE WHEN $9 = 'created_asc' THEN r.created_at END ASC,
+              CASE WHEN $9 = 'created_desc' THEN r.created_at END DESC,
+              r.id
+            LIMIT $10 OFFSET $11
+            "#,
+            statuses.as_deref(),
+            条件.記念日_開始,
+            条件.記念日_終了,
+            wrapping_type,
+            requester_uuid,
+            recipient_uuid,
+            条件.作成日時_開始,
+            条件.作成日時_終了,
+            sort_order_key(条件.並び順),
+            limit as i64,
+            offset as i64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        let 予約リスト = rows
+            .into_iter()
+            .map(reservation_from_db)
+            .collect::<Result<_, _>>()
+            .map_err(reservation_load_error)?;
+
+        Ok(予約検索結果 {
+            予約リスト,
+            総件数: 総件数 as usize,
+        })
+    }
+
+    /// データベースへの接続を確認する (トレイト実装)
+    async fn check_db_connection(&self) -> Result<(), InfrastructureError> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map(|_| ()) // 成功時は Ok(())
+            .map_err(|e| {
+                eprintln!("Health Check DB Error: {}", e); // エラーログ
+                InfrastructureError::ConnectionError(e.to_string()) // sqlx::Error をラップ
+            })
+    }
+
+    async fn database_status(&self) -> Result<DatabaseStatus, InfrastructureError> {
+        let started = std::time::Instant::now();
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| InfrastructureError::ConnectionError(e.to_string()))?;
+        let latency = started.elapsed();
+
+        // _sqlx_migrations は sqlx migrate が作成する管理テーブル (未作成でエラーになる場合は None)
+        let migration_version: Option<i64> =
+            sqlx::query_scalar("SELECT MAX(version) FROM _sqlx_migrations WHERE success")
+                .fetch_one(&self.pool)
+                .await
+                .ok()
+                .flatten();
+
+        Ok(DatabaseStatus {
+            latency,
+            migration_version,
+            pool_size: self.pool.size(),
+            idle_connections: self.pool.num_idle() as u32,
+        })
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for PgRepository {
+    async fn begin(
+        &self,
+        record: &IdempotencyRecord,
+    ) -> Result<IdempotencyBegin, InfrastructureError> {
+        let requester_uuid = *record.requester_id.as_uuid();
+        let db_error = |e: sqlx::Error| InfrastructureError::DatabaseError(e.to_string());
+
+        // 記録がないか、期限切れか、処理中のまま確保期限を過ぎた場合だけ処理中として書き込む
+        // (判定と書き込みを1文で行う)
+        let started = sqlx::query_scalar!(
+            r#"
+            INSERT INTO idempotency_keys (
+                requester_id, idempotency_key, request_fingerprint, reservation_id,
+                created_at, expires_at, lease_expires_at
+            ) VALUES ($1, $2, $3, NULL, $4, $5, $6)
+            ON CONFLICT (requester_id, idempotency_key) DO UPDATE SET
+                request_fingerprint = EXCLUDED.request_fingerprint,
+                reservation_id = NULL,
+                created_at = EXCLUDED.created_at,
+                expires_at = EXCLUDED.expires_at,
+                lease_expires_at = EXCLUDED.lease_expires_at
+            WHERE idempotency_keys.expires_at <= EXCLUDED.created_at
+                OR (idempotency_keys.reservation_id IS NULL
+                    AND idempotency_keys.lease_expires_at <= EXCLUDED.created_at)
+            RETURNING requester_id
+            "#,
+            requester_uuid,
+            record.key,
+            record.fingerprint,
+            record.created_at,
+            record.expires_at,
+            record.lease_expires_at
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+        if started.is_some() {
+            return Ok(IdempotencyBegin::Started);
+        }
+
+        let existing = sqlx::query!(
+            r#"
+            SELECT request_fingerprint, reservation_id, created_at, expires_at, lease_expires_at
+            FROM idempotency_keys
+            WHERE requester_id = $1 AND idempotency_key = $2
+            "#,
+            requester_uuid,
+            record.key
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?
+        .ok_or_else(|| {
+            // 書き込みに失敗した直後に削除された場合
+            InfrastructureError::DatabaseError(format!(
+                "idempotency key '{}' disappeared while being checked",
+                record.key
+            ))
+        })?;
+
+        Ok(IdempotencyBegin::Existing(IdempotencyRecord {
+            requester_id: record.requester_id,
+            key: record.key.clone(),
+            fingerprint: existing.request_fingerprint,
+            reservation_id: existing.reservation_id.map(予約ID::from_uuid),
+            created_at: existing.created_at,
+            expires_at: existing.expires_at,
+            lease_expires_at: existing.lease_expires_at,
+        }))
+    }
+
+    async fn abandon(&self, record: &IdempotencyRecord) -> Result<(), InfrastructureError> {
+        sqlx::query!(
+            r#"
+            DELETE FROM idempotency_keys
+            WHERE requester_id = $1 AND idempotency_key = $2
+                AND reservation_id IS NULL AND created_at = $3
+            "#,
+            record.requester_id.as_uuid(),
+            record.key,
+            record.created_at
+        )
+        .execute(&self.pool)
+        .await
+        .map(|_| ())
+        .map_err(|e| InfrastructureError::DatabaseError(e.to_string()))
+    }
+}
+
+/// PostgreSQL の届け先リポジトリ
+#[derive(Clone)]
+pub struct Pg届け先Repository {
+    pool: PgPool,
+}
+
+impl Pg届け先Repository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl 届け先Repository for Pg届け先Repository {
+    async fn save(&self, 届け先: &届け先) -> Result<(), DomainError> {
+        let 内容 = &届け先.内容;
+        sqlx::query!(
+            r#"
+            INSERT INTO recipients (
+                id, owner_id, name, name_kana, postal_code, prefecture_code,
+                address, building, phone_number
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (id) DO UPDATE SET
+                name = EXCLUDED.name,
+                name_kana = EXCLUDED.name_kana,
+                postal_code = EXCLUDED.postal_code,
+                prefecture_code = EXCLUDED.prefecture_code,
+                address = EXCLUDED.address,
+                building = EXCLUDED.building,
+                phone_number = EXCLUDED.phone_number,
+                updated_at = NOW()
+            "#,
+            届け先.id.as_uuid(),
+            届け先.所有者id.as_uuid(),
+            内容.氏名.表記(),
+            内容.氏名.フリガナ(),
+            内容.郵便番号.value(),
+            i16::from(内容.都道府県.コード()),
+            内容.住所.value(),
+            内容.建物名.as_ref().map(|b| b.value()),
+            内容.電話番号.value()
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!("DB Error: Failed to save recipient {:?}: {}", 届け先.id, e);
+            DomainError::届け先NotFound(届け先.id) // 仮のエラー
+        })?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &届け先ID) -> Result<Option<届け先>, DomainError> {
+        let row = sqlx::query_as!(
+            RecipientRow,
+            r#"
+            SELECT id, owner_id, name, name_kana, postal_code, prefecture_code,
+                   address, building, phone_number
+            FROM recipients
+            WHERE id = $1
+            "#,
+            id.as_uuid()
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!("DB Error: Failed to fetch recipient {:?}: {}", id, e);
+            DomainError::届け先NotFound(*id) // 仮のエラー
+        })?;
+        row.map(recipient_from_db).transpose()
+    }
+
+    async fn find_by_owner(
+        &self, 所有者id: &ユーザーID
+    ) -> Result<Vec<届け先>, DomainError> {
+        let rows = sqlx::query_as!(
+            RecipientRow,
+            r#"
+            SELECT id, owner_id, name, name_kana, postal_code, prefecture_code,
+                   address, building, phone_number
+            FROM recipients
+            WHERE owner_id = $1
+            ORDER BY created_at, id
+            "#,
+            所有者id.as_uuid()
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!(
+                "DB Error: Failed to fetch recipients of owner {:?}: {}",
+                所有者id, e
+            );
+            DomainError::届け先NotFound(届け先ID::new()) // 仮のエラー
+        })?;
+        rows.into_iter().map(recipient_from_db).collect()
+    }
+
+    async fn delete(&self, id: &届け先ID) -> Result<(), DomainError> {
+        sqlx::query!("DELETE FROM recipients WHERE id = $1", id.as_uuid())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| {
+                eprintln!("DB Error: Failed to delete recipient {:?}: {}", id, e);
+                DomainError::届け先NotFound(*id) // 仮のエラー
+            })?;
+        Ok(())
+    }
+}
+
+/// recipients テーブルの 1 行
+struct RecipientRow {
+    id: Uuid,
+    owner_id: Uuid,
+    name: String,
+    name_kana: String,
+    postal_code: String,
+    prefecture_code: i16,
+    address: String,
+    building: Option<String>,
+    phone_number: String,
+}
+
+/// recipients テーブルの行を届け先に変換する (保存時と同じ検証を通す)
+fn recipient_from_db(row: RecipientRow) -> Result<届け先, DomainError> {
+    let id = 届け先ID::from_uuid(row.id);
+    let invalid = |e: DomainError| {
+        eprintln!("DB Error: Invalid recipient data for id {:?}: {:?}", id, e);
+        DomainError::届け先NotFound(id) // 変換エラーもエラー扱い (仮)
+    };
+    let 都道府県 = u8::try_from(row.prefecture_code)
+        .ok()
+        .and_then(都道府県::from_コード)
+        .ok_or_else(|| {
+            invalid(DomainError::不正な都道府県 {
+                value: row.prefecture_code.to_string(),
+            })
+        })?;
+    Ok(届け先 {
+        id,
+        所有者id: ユーザーID::from_uuid(row.owner_id),
+        内容: 届け先内容 {
+            氏名: 氏名::new(&row.name, &row.name_kana).map_err(invalid)?,
+            郵便番号: 郵便番号::new(&row.postal_code).map_err(invalid)?,
+            都道府県,
+            住所: 住所::new(&row.address).map_err(invalid)?,
+            建物名: row
+                .building
+                .as_deref()
+                .map(建物名::new)
+                .transpose()
+                .map_err(invalid)?,
+            電話番号: 電話番号::new(&row.phone_number).map_err(invalid)?,
+        },
+    })
+}
+
+/// PostgreSQL のユーザーアカウントリポジトリ
+#[derive(Clone)]
+pub struct PgユーザーアカウントRepository {
+    pool: PgPool,
+}
+
+impl PgユーザーアカウントRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ユーザーアカウントRepository for PgユーザーアカウントRepository {
+    async fn insert(
+        &self, アカウント: &ユーザーアカウント
+    ) -> Result<(), DomainError> {
+        let プロフィール = &アカウント.プロフィール;
+        sqlx::query!(
+            r#"
+            INSERT INTO user_accounts (
+                id, auth_subject, email, display_name, default_sender_name, role, status
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            アカウント.id.as_uuid(),
+            アカウント.認証サブジェクト.value(),
+            プロフィール.メールアドレス.value(),
+            プロフィール.表示名.value(),
+            プロフィール.既定の差出人名.as_ref().map(|n| n.value()),
+            role_to_db(アカウント.ロール),
+            account_status_to_db(アカウント.状態)
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            // 同時に登録された場合は一意制約で検出する
+            match e.as_database_error().and_then(|d| d.constraint()) {
+                Some("user_accounts_pkey") => {
+                    return DomainError::ユーザーアカウント登録済み(アカウント.id)
+                }
+                Some("idx_user_accounts_auth_subject") => {
+                    return DomainError::認証サブジェクト使用中
+                }
+                _ => {}
+            }
+            eprintln!(
+                "DB Error: Failed to insert user account {:?}: {}",
+                アカウント.id, e
+            );
+            DomainError::ユーザーアカウントNotFound(アカウント.id) // 仮のエラー
+        })?;
+        Ok(())
+    }
+
+    async fn save(&self, アカウント: &ユーザーアカウント) -> Result<(), DomainError> {
+        let プロフィール = &アカウント.プロフィール;
+        // 認証サブジェクトは登録後に変更しない
+        sqlx::query!(
+            r#"
+            INSERT INTO user_accounts (
+                id, auth_subject, email, display_name, default_sender_name, role, status
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (id) DO UPDATE SET
+                email = EXCLUDED.email,
+                display_name = EXCLUDED.display_name,
+                default_sender_name = EXCLUDED.default_sender_name,
+                role = EXCLUDED.role,
+                status = EXCLUDED.status,
+                updated_at = NOW()
+            "#,
+            アカウント.id.as_uuid(),
+            アカウント.認証サブジェクト.value(),
+            プロフィール.メールアドレス.value(),
+            プロフィール.表示名.value(),
+            プロフィール.既定の差出人名.as_ref().map(|n| n.value()),
+            role_to_db(アカウント.ロール),
+            account_status_to_db(アカウント.状態)
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            // 同時に登録された場合は一意制約で検出する
+            if e.as_database_error().and_then(|d| d.constraint())
+                == Some("idx_user_accounts_auth_subject")
+            {
+                return DomainError::認証サブジェクト使用中;
+            }
+            eprintln!(
+                "DB Error: Failed to save user account {:?}: {}",
+                アカウント.id, e
+            );
+            DomainError::ユーザーアカウントNotFound(アカウント.id) // 仮のエラー
+        })?;
+        Ok(())
+    }
+
+    async fn find_by_id(
+        &self,
+        id: &ユーザーID,
+    ) -> Result<Option<ユーザーアカウント>, DomainError> {
+        let row = sqlx::query_as!(
+            UserAccountRow,
+            r#"
+            SELECT id, auth_subject, email, display_name, default_sender_name, role, status
+            FROM user_accounts
+            WHERE id = $1
+            "#,
+            id.as_uuid()
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!("DB Error: Failed to fetch user account {:?}: {}", id, e);
+            DomainError::ユーザーアカウントNotFound(*id) // 仮のエラー
+        })?;
+        row.map(user_account_from_db).transpose()
+    }
+
+    async fn find_by_auth_subject(
+        &self,
+        サブジェクト: &認証サブジェクト,
+    ) -> Result<Option<ユーザーアカウント>, DomainError> {
+        let row = sqlx::query_as!(
+            UserAccountRow,
+            r#"
+            SELECT id, auth_subject, email, display_name, default_sender_name, role, status
+            FROM user_accounts
+            WHERE auth_subject = $1
+            "#,
+            サブジェクト.value()
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!(
+                "DB Error: Failed to fetch user account by auth subject: {}",
+                e
+            );
+            DomainError::ユーザーアカウントNotFound(ユーザーID::new()) // 仮のエラー
+        })?;
+        row.map(user_account_from_db).transpose()
+    }
+}
+
+/// user_accounts テーブルの 1 行
+struct UserAccountRow {
+    id: Uuid,
+    auth_subject: String,
+    email: String,
+    display_name: String,
+    default_sender_name: Option<String>,
+    role: String,
+    status: String,
+}
+
+fn role_to_db(ロール: ユーザーロール) -> &'static str {
+    match ロール {
+        ユーザーロール::顧客 => "Customer",
+        ユーザーロール::管理者 => "Admin",
+    }
+}
+
+fn account_status_to_db(状態: アカウント状態) -> &'static str {
+    match 状態 {
+        アカウント状態::有効 => "Active",
+        アカウント状態::停止 => "Suspended",
+    }
+}
+
+/// user_accounts テーブルの行をユーザーアカウントに変換する (保存時と同じ検証を通す)
+fn user_account_from_db(row: UserAccountRow) -> Result<ユーザーアカウント, DomainError> {
+    let id = ユーザーID::from_uuid(row.id);
+    let invalid = |e: DomainError| {
+        eprintln!(
+            "DB Error: Invalid user account data for id {:?}: {:?}",
+            id, e
+        );
+        DomainError::ユーザーアカウントNotFound(id) // 変換エラーもエラー扱い (仮)
+    };
+    let ロール = match row.role.as_str() {
+        "Customer" => ユーザーロール::顧客,
+        "Admin" => ユーザーロール::管理者,
+        unknown_role => {
+            eprintln!(
+                "DB Error: Unknown user role '{}' for id {:?}",
+                unknown_role, id
+            );
+            return Err(DomainError::ユーザーアカウントNotFound(id)); // 仮のエラー
+        }
+    };
+    let 状態 = match row.status.as_str() {
+        "Active" => アカウント状態::有効,
+        "Suspended" => アカウント状態::停止,
+        unknown_status => {
+            eprintln!(
+                "DB Error: Unknown account status '{}' for id {:?}",
+                unknown_status, id
+            );
+            return Err(DomainError::ユーザーアカウントNotFound(id)); // 仮のエラー
+        }
+    };
+    Ok(ユーザーアカウント {
+        id,
+        認証サブジェクト: 認証サブジェクト::new(&row.auth_subject).map_err(invalid)?,
+        プロフィール: プロフィール {
+            メールアドレス: メールアドレス::new(&row.email).map_err(invalid)?,
+            表示名: 表示名::new(&row.display_name).map_err(invalid)?,
+            既定の差出人名: row
+                .default_sender_name
+                .as_deref()
+                .map(差出人名::new)
+                .transpose()
+                .map_err(invalid)?,
+        },
+        ロール,
+        状態,
+    })
+}
+
+/// 状態を確認して更新した予約が 0 件だった (読み込んだ後に他のリクエストが状態を変えていた) 場合のエラー
+async fn stale_state_error(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    id: &予約ID,
+) -> DomainError {
+    let status = sqlx::query_scalar!(
+        "SELECT status FROM reservations WHERE id = $1",
+        id.as_uuid()
+    )
+    .fetch_optional(&mut **tx)
+    .await;
+    match status {
+        Ok(Some(status)) => DomainError::不正な状態遷移 {
+            current_state_type: status,
+        },
+        Ok(None) => DomainError::予約NotFound(*id),
+        Err(e) => {
+            eprintln!(
+                "DB Error: Failed to read status of reservation {:?}: {}",
+                id, e
+            );
+            DomainError::予約NotFound(*id) // 仮のエラー
+        }
+    }
+}
+
+/// 状態を確認して更新した予約が 0 件なら、読み込んだ後に他のリクエストが状態を変えていたとしてエラーを返す
+///
+/// 呼び出し元はエラーの場合コミットせず、トランザクションごと取り消す。
+async fn ensure_transitioned(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    rows_affected: u64,
+    id: &予約ID,
+) -> Result<(), DomainError> {
+    if rows_affected == 0 {
+        return Err(stale_state_error(tx, id).await);
+    }
+    Ok(())
+}
+
+/// Idempotency-Key に受け付けた予約IDを記録する (予約の保存と同じトランザクションで実行する)
+///
+/// 確保したときの記録 (予約ID未記録で確保日時が同じもの) がなければ 冪等性キー確保切れ を返し、
+/// 呼び出し元はコミットせずに予約の保存ごと取り消す。
+async fn record_idempotency_key(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    reservation: &プレゼント予約状態,
+    キー: &冪等性キーの確保,
+) -> Result<(), DomainError> {
+    let base = reservation.base();
+    let result = sqlx::query!(
+        r#"
+        UPDATE idempotency_keys SET reservation_id = $3
+        WHERE requester_id = $1 AND idempotency_key = $2
+            AND reservation_id IS NULL AND created_at = $4
+        "#,
+        base.依頼者id.as_uuid(),
+        キー.キー,
+        base.id.as_uuid(),
+        キー.確保日時
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        eprintln!(
+            "DB Error: Failed to record idempotency key for reservation {:?}: {}",
+            base.id, e
+        );
+        DomainError::予約NotFound(base.id) // 仮のエラー
+    })?;
+    if result.rows_affected() == 0 {
+        return Err(DomainError::冪等性キー確保切れ);
+    }
+    Ok(())
+}
+
+/// 予約の手配商品の在庫を移動する (予約の保存と同じトランザクションで実行する)
+async fn move_stock(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    reservation: &プレゼント予約状態,
+    移動: 在庫移動,
+) -> Result<(), DomainError> {
+    let id = reservation.base().id;
+    let reservation_id = *id.as_uuid();
+    let db_error = |e: sqlx::Error| {
+        eprintln!(
+            "DB Error: Failed to move stock for reservation {:?}: {}",
+            id, e
+        );
+        DomainError::予約NotFound(id) // 仮のエラー
+    };
+    match 移動 {
+        在庫移動::引当 => {
+            // 同じ商品を引き当てる予約同士でデッドロックしないよう、商品IDの順に行をロックする
+            let mut product_ids: Vec<Uuid> = reservation
+                .base()
+                .プレゼント内容
+                .手配商品リスト()
+                .iter()
+                .map(|商品id| *商品id.as_uuid())
+                .collect();
+            product_ids.sort();
+            for product_id in product_ids {
+                // 引当済みの商品は引き当て直さない
+                let recorded = sqlx::query!(
+                    r#"
+                    INSERT INTO reservation_stock_allocations (reservation_id, product_id, quantity)
+                    VALUES ($1, $2, 1)
+                    ON CONFLICT (reservation_id, product_id) DO NOTHING
+                    "#,
+                    reservation_id,
+                    product_id
+                )
+                .execute(&mut **tx)
+                .await
+                .map_err(db_error)?;
+                if recorded.rows_affected() == 0 {
+                    continue;
+                }
+                // 引当可能数の確認と引当を 1 つの UPDATE で行う
+                // (同じ商品を同時に引き当てる予約は行ロックで待ち、先の引当を反映した行で条件を再評価する)
+                let allocated = sqlx::query!(
+                    r#"
+                    UPDATE product_stocks
+                    SET allocated = allocated + 1, updated_at = NOW()
+                    WHERE product_id = $1 AND on_hand - allocated >= 1
+                    "#,
+                    product_id
+                )
+                .execute(&mut **tx)
+                .await
+                .map_err(db_error)?;
+                if allocated.rows_affected() == 0 {
+                    // トランザクションはコミットせずに破棄されるため、予約の保存も取り消される
+                    return Err(DomainError::在庫不足(商品ID::from_uuid(product_id)));
+                }
+            }
+        }
+        在庫移動::引当解除 | 在庫移動::出庫 => {
+            let mut rows = sqlx::query!(
+                r#"
+                DELETE FROM reservation_stock_allocations
+                WHERE reservation_id = $1
+                RETURNING product_id, quantity
+                "#,
+                reservation_id
+            )
+            .fetch_all(&mut **tx)
+            .await
+            .map_err(db_error)?;
+            rows.sort_by_key(|row| row.product_id);
+            let ships = 移動 == 在庫移動::出庫;
+            for row in rows {
+                sqlx::query!(
+                    r#"
+                    UPDATE product_stocks
+                    SET allocated = allocated - $2,
+                        on_hand = on_hand - CASE WHEN $3 THEN $2 ELSE 0 END,
+                        updated_at = NOW()
+                    WHERE product_id = $1
+                    "#,
+                    row.product_id,
+                    row.quantity,
+                    ships
+                )
+                .execute(&mut **tx)
+                .await
+                .map_err(db_error)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[async_trait]
+impl 商品在庫Repository for PgRepository {
+    async fn find_by_product_id(&self, 商品id: &商品ID) -> Result<商品在庫, DomainError> {
+        let row = sqlx::query!(
+            "SELECT on_hand, allocated FROM product_stocks WHERE product_id = $1",
+            商品id.as_uuid()
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!(
+                "DB Error: Failed to fetch stock of product {:?}: {}",
+                商品id, e
+            );
+            DomainError::商品NotFound(*商品id) // 仮のエラー
+        })?;
+        match row {
+            Some(row) => stock_from_db(*商品id, row.on_hand, row.allocated),
+            None => Ok(商品在庫::在庫なし(*商品id)),
+        }
+    }
+
+    async fn set_on_hand(
+        &self, 商品id: &商品ID, 在庫数: u32
+    ) -> Result<商品在庫, DomainError> {
+        // 引当数より少なくしないことは更新の条件で確かめる (同時に引き当てる予約と競合しないように)
+        let row = sqlx::query!(
+            r#"
+            INSERT INTO product_stocks (product_id, on_hand)
+            VALUES ($1, $2)
+            ON CONFLICT (product_id) DO UPDATE SET
+                on_hand = EXCLUDED.on_hand,
+                updated_at = NOW()
+            WHERE product_stocks.allocated <= EXCLUDED.on_hand
+            RETURNING on_hand, allocated
+            "#,
+            商品id.as_uuid(),
+            在庫数 as i32
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!(
+                "DB Error: Failed to set stock of product {:?}: {}",
+                商品id, e
+            );
+            DomainError::商品NotFound(*商品id) // 仮のエラー
+        })?;
+        match row {
+            Some(row) => stock_from_db(*商品id, row.on_hand, row.allocated),
+            None => {
+                let 現在の在庫 = self.find_by_product_id(商品id).await?;
+                Err(DomainError::在庫数不正 {
+                    商品id: *商品id,
+                    引当数: 現在の在庫.引当数,
+                })
+            }
+        }
+    }
+}
+
+/// product_stocks テーブルの行を商品在庫に変換する
+fn stock_from_db(
+    商品id: 商品ID,
+    on_hand: i32,
+    allocated: i32,
+) -> Result<商品在庫, DomainError> {
+    match (u32::try_from(on_hand), u32::try_from(allocated)) {
+        (Ok(在庫数), Ok(引当数)) => Ok(商品在庫 {
+            商品id,
+            在庫数,
+            引当数,
+        }),
+        _ => {
+            eprintln!(
+                "DB Error: Invalid stock (on_hand {}, allocated {}) for product {:?}",
+                on_hand, allocated, 商品id
+            );
+            Err(DomainError::商品NotFound(商品id)) // 仮のエラー
+        }
+    }
+}
+
+/// PostgreSQL の商品カタログ
+#[derive(Clone)]
+pub struct Pg商品Repository {
+    pool: PgPool,
+}
+
+impl Pg商品Repository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl 商品Repository for Pg商品Repository {
+    async fn save(&self, 商品: &商品) -> Result<(), DomainError> {
+        let 内容 = &商品.内容;
+        let image_keys: Vec<String> = 内容
+            .画像キー
+            .iter()
+            .map(|key| key.value().to_string())
+            .collect();
+        // 商品価格 の上限で弾いているはずだが、INTEGER に収まらない値を負の数で保存しないよう確認する
+        let 税込価格 = 内容.価格.税込価格.value();
+        let price = i32::try_from(税込価格).map_err(|_| DomainError::不正な商品価格 {
+            value: 税込価格,
+        })?;
+        sqlx::query!(
+            r#"
+            INSERT INTO products (id, name, description, price, tax_category, image_keys, on_sale)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (id) DO UPDATE SET
+                name = EXCLUDED.name,
+                description = EXCLUDED.description,
+                price = EXCLUDED.price,
+                tax_category = EXCLUDED.tax_category,
+                image_keys = EXCLUDED.image_keys,
+                on_sale = EXCLUDED.on_sale,
+                updated_at = NOW()
+            "#,
+            商品.id.as_uuid(),
+            内容.商品名.value(),
+            内容.説明.value(),
+            price,
+            tax_category_column_value(内容.価格.税区分),
+            &image_keys,
+            商品.販売中
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!("DB Error: Failed to save product {:?}: {}", 商品.id, e);
+            DomainError::商品NotFound(商品.id) // 仮のエラー
+        })?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: &商品ID) -> Result<Option<商品>, DomainError> {
+        let row = sqlx::query_as!(
+            ProductRow,
+            r#"
+            SELECT id, name, description, price, tax_category, image_keys, on_sale
+            FROM products
+            WHERE id = $1
+            "#,
+            id.as_uuid()
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            eprintln!("DB Error: Failed to fetch product {:?}: {}", id, e);
+            DomainError::商品NotFound(*id) // 仮のエラー
+        })?;
+        row.map(product_from_db).transpose()
+    }
+
+    async fn search(
+        &self,
+        条件: &商品検索条件,
+        offset: usize,
+        limit: usize,
+    ) -> Result<商品検索結果, DomainError> {
+        let db_error = |e: sqlx::Error| {
+            eprintln!("DB Error: Failed to search products: {}", e);
+            DomainError::商品NotFound(商品ID::new()) // 仮のエラー
+        };
+        let tax_category = 条件.税区分.map(tax_category_column_value);
+        let min_price = 条件.最低価格.map(i64::from);
+        let max_price = 条件.最高価格.map(i64::from);
+
+        // キーワードは LIKE のワイルドカードを気にしなくてよいよう strpos で部分一致を調べる
+        let 総件数 = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!"
+            FROM products
+            WHERE ($1::TEXT IS NULL
+                   OR strpos(lower(name), lower($1)) > 0
+                   OR strpos(lower(description), lower($1)) > 0)
+              AND ($2::VARCHAR IS NULL OR tax_category = $2)
+              AND ($3::BIGINT IS NULL OR price >= $3)
+              AND ($4::BIGINT IS NULL OR price <= $4)
+              AND (NOT $5 OR on_sale)
+            "#,
+            条件.キーワード.as_deref(),
+            tax_category,
+            min_price,
+            max_price,
+            条件.販売中のみ
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        let rows = sqlx::query_as!(
+            ProductRow,
+            r#"
+            SELECT id, name, description, price, tax_category, image_keys, on_sale
+            FROM products
+            WHERE ($1::TEXT IS NULL
+                   OR strpos(lower(name), lower($1)) > 0
+                   OR strpos(lower(description), lower($1)) > 0)
+              AND ($2::VARCHAR IS NULL OR tax_category = $2)
+              AND ($3::BIGINT IS NULL OR price >= $3)
+              AND ($4::BIGINT IS NULL OR price <= $4)
+              AND (NOT $5 OR on_sale)
+            ORDER BY created_at, id
+            LIMIT $6 OFFSET $7
+            "#,
+            条件.キーワード.as_deref(),
+            tax_category,
+            min_price,
+            max_price,
+            条件.販売中のみ,
+            limit as i64,
+            offset as i64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(商品検索結果 {
+            商品リスト: rows
+                .into_iter()
+                .map(product_from_db)
+                .collect::<Result<_, _>>()?,
+            総件数: 総件数 as usize,
+        })
+    }
+}
+
+/// products テーブルの 1 行
+struct ProductRow {
+    id: Uuid,
+    name: String,
+    description: String,
+    price: i32,
+    tax_category: String,
+    image_keys: Vec<String>,
+    on_sale: bool,
+}
+
+/// products テーブルの行を商品に変換する (保存時と同じ検証を通す)
+fn product_from_db(row: ProductRow) -> Result<商品, DomainError> {
+    let id = 商品ID::from_uuid(row.id);
+    let invalid = |e: DomainError| {
+        eprintln!("DB Error: Invalid product data for id {:?}: {:?}", id, e);
+        DomainError::商品NotFound(id) // 変換エラーもエラー扱い (仮)
+    };
+    let 税区分 = match row.tax_category.as_str() {
+        "Standard" => 税区分::標準税率,
+        "Reduced" => 税区分::軽減税率,
+        unknown_tax_category => {
+            eprintln!(
+                "DB Error: Unknown tax category '{}' for product {:?}",
+                unknown_tax_category, id
+            );
+            return Err(DomainError::商品NotFound(id)); // 仮のエラー
+        }
+    };
+    // 負の価格は 0 円として 商品価格 の検証で弾く
+    let 価格 = 商品価格::new(u32::try_from(row.price).unwrap_or(0), 税区分).map_err(invalid)?;
+    Ok(商品 {
+        id,
+        内容: 商品内容 {
+            商品名: 商品名::new(&row.name).map_err(invalid)?,
+            説明: 商品説明::new(&row.description).map_err(invalid)?,
+            価格,
+            画像キー: row
+                .image_keys
+                .iter()
+                .map(|key| 画像キー::new(key))
+                .collect::<Result<_, _>>()
+                .map_err(invalid)?,
+        },
+        販売中: row.on_sale,
+    })
+}
+
+// --- テスト ---
+#[cfg(all(test, not(ci)))]
+mod tests {
+    use super::*;
+    use crate::domain::core::発送リードタイム;
+    use sqlx::postgres::PgPoolOptions;
+    use std::env; // tests モジュール内で use する
+                  // use crate::domain::core::手配予約を受け付ける; // 関数ローカルで use
+
+    // テスト用のヘルパー関数: DB接続プールを取得
+    async fn setup_db_pool() -> sqlx::PgPool {
+        // dotenv().ok();
+        let database_url =
+            env::var("DATABASE_URL").expect("DATABASE_URL must be set for integration tests");
+        PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .expect("Failed to create pool.")
+    }
+
+    // テスト用のヘルパー関数: 各テストの記念日より十分前の受付日時
+    fn テスト受付日時() -> DateTime<chrono_tz::Tz> {
+        use chrono::TimeZone;
+        Tokyo.with_ymd_and_hms(2024, 12, 2, 10, 0, 0).unwrap()
+    }
+
+    /// テスト用の料金内訳 (送料のみ)
+    fn テスト料金内訳() -> 料金内訳 {
+        料金内訳 {
+            明細: vec![料金明細::new(料金項目::送料, 800, 税区分::標準税率)],
+        }
+    }
+
+    // テスト用のヘルパー関数: 税込 4,320 円・軽減税率の販売中の商品を作成
+    fn テスト商品(名前: &str) -> 商品 {
+        商品::登録する(商品内容 {
+            商品名: 商品名::new(名前).unwrap(),
+            説明: 商品説明::new("テスト用の商品です").unwrap(),
+            価格: 商品価格 {
+                税込価格: 金額::new(4320).unwrap(),
+                税区分: 税区分::軽減税率,
+            },
+            画像キー: vec![画像キー::new("products/test/main.jpg").unwrap()],
+        })
+    }
+
+    // テスト用のヘルパー関数: 予約が参照する商品を登録する (reservation_products の外部キーのため)
+    async fn テスト商品を登録する(pool: &sqlx::PgPool) -> 商品ID {
+        let 商品 = テスト商品("テスト商品");
+        Pg商品Repository::new(pool.clone())
+            .save(&商品)
+            .await
+            .expect("Failed to insert test product");
+        商品.id
+    }
+
+    // テスト用のヘルパー関数: ダミーの予約受付済み状態を作成
+    fn create_dummy_received_reservation(商品id: 商品ID) -> プレゼント予約状態 {
+        use crate::domain::core::手配予約を受け付ける; // 関数内で use する例
+        let requester_id = ユーザーID::from_uuid(Uuid::new_v4());
+        let recipient_id = 届け先ID::from_uuid(Uuid::new_v4());
+        let anniversary = 記念日 {
+            value: NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(), // ここで NaiveDate を使う
+        };
+        let product_ids = HashSet::from([商品id]);
+        let payment_id = 支払いID::from_uuid(Uuid::new_v4());
+
+        let (received, _) = 手配予約を受け付ける(
+            requester_id,
+            recipient_id,
+            anniversary,
+            Some(メッセージ内容::new("テストメッセージ").unwrap()),
+            ラッピング種類::標準,
+            Some(配送希望 {
+                配送日: NaiveDate::from_ymd_opt(2024, 12, 20).unwrap(),
+                時間帯: 配送時間帯::午後6時から8時,
+            }),
+            product_ids,
+            payment_id,
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+        .unwrap();
+        プレゼント予約状態::予約受付済み(received)
+    }
+
+    #[tokio::test]
+    async fn test_pg_save_and_find_by_id_received() {
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone()); // PgRepository をインスタンス化
+
+        let reservation_state =
+            create_dummy_received_reservation(テスト商品を登録する(&pool).await);
+        let reservation_id = match &reservation_state {
+            プレゼント予約状態::予約受付済み(r) => r.base.id,
+            _ => panic!("Test setup error: Unexpected initial state"),
+        };
+
+        // テストデータのクリーンアップ (テスト開始前にも実行)
+        sqlx::query!(
+            "DELETE FROM reservation_products WHERE reservation_id = $1",
+            reservation_id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test products data (before test)");
+        sqlx::query!(
+            "DELETE FROM reservations WHERE id = $1",
+            reservation_id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test reservation data (before test)");
+
+        // 保存処理のテスト
+        let save_result = repository.save(&reservation_state).await;
+        assert!(save_result.is_ok(), "save failed: {:?}", save_result.err());
+
+        // 取得処理のテスト
+        let found_reservation = repository.find_by_id(&reservation_id).await;
+        assert!(
+            found_reservation.is_ok(),
+            "find_by_id failed: {:?}",
+            found_reservation.err()
+        );
+        assert!(
+            found_reservation.as_ref().unwrap().is_some(),
+            "Reservation not found after save"
+        );
+
+        // 取得したデータが保存したデータと一致するか検証
+        // 注意: DateTime<Tz> は DB 保存時にマイクロ秒が丸められる可能性があるため、完全一致しない場合がある
+        // ここでは PartialEq を使って比較する
+        assert_eq!(found_reservation.unwrap().unwrap(), reservation_state);
+
+        // テスト後のデータクリーンアップ
+        sqlx::query!(
+            "DELETE FROM reservation_products WHERE reservation_id = $1",
+            reservation_id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test products data (after test)");
+        sqlx::query!(
+            "DELETE FROM reservations WHERE id = $1",
+            reservation_id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test reservation data (after test)");
+    }
+
+    #[tokio::test]
+    async fn test_pg_save_and_find_by_id_cancelled_with_fee() {
+        use crate::domain::core::{キャンセルポリシー, 手配予約を受け付ける};
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone());
+
+        let (received, _) = 手配予約を受け付ける(
+            ユーザーID::new(),
+            届け先ID::new(),
+            記念日 {
+                value: NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(),
+            },
+            None,
+            ラッピング種類::なし,
+            None,
+            [テスト商品を登録する(&pool).await].into_iter().collect(),
+            支払いID::new(),
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+        .unwrap();
+        let reservation_id = received.base.id;
+        repository
+            .save(&プレゼント予約状態::予約受付済み(
+                received.clone(),
+            ))
+            .await
+            .unwrap();
+        let (preparing, _) = received
+            .発送準備を開始する(ユーザーID::new(), テスト受付日時())
+            .unwrap();
+        repository
+            .save(&プレゼント予約状態::発送準備中(
+                preparing.clone(),
+            ))
+            .await
+            .unwrap();
+        let (cancelled, _) = preparing
+            .予約をキャンセルする(
+                Some("顧客都合".to_string()),
+                &キャンセルポリシー::default(),
+                None,
+                テスト受付日時(),
+            )
+            .unwrap();
+        let cancelled_state = プレゼント予約状態::キャンセル済み(cancelled);
+
+        repository.save(&cancelled_state).await.unwrap();
+        let found = repository.find_by_id(&reservation_id).await.unwrap();
+
+        // キャンセル料とキャンセル日時が復元される
+        assert_eq!(found, Some(cancelled_state));
+
+        sqlx::query!(
+            "DELETE FROM reservations WHERE id = $1",
+            reservation_id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test reservation data (after test)");
+    }
+
+    #[tokio::test]
+    async fn test_pg_save_and_find_by_id_returned() {
+        use crate::domain::core::{
+            手配予約を受け付ける, 配送伝票番号, 配送失敗理由, 配送業者
+        };
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone());
+
+        let (received, _) = 手配予約を受け付ける(
+            ユーザーID::new(),
+            届け先ID::new(),
+            記念日 {
+                value: NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(),
+            },
+            None,
+            ラッピング種類::なし,
+            None,
+            [テスト商品を登録する(&pool).await].into_iter().collect(),
+            支払いID::new(),
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+        .unwrap();
+        let reservation_id = received.base.id;
+        repository
+            .save(&プレゼント予約状態::予約受付済み(
+                received.clone(),
+            ))
+            .await
+            .unwrap();
+        let (preparing, _) = received
+            .発送準備を開始する(ユーザーID::new(), テスト受付日時())
+            .unwrap();
+        repository
+            .save(&プレゼント予約状態::発送準備中(
+                preparing.clone(),
+            ))
+            .await
+            .unwrap();
+        let (shipped, _) = preparing
+            .発送を完了する(
+                配送伝票番号::new(配送業者::佐川急便, "9876-5432-1091").unwrap(),
+                None,
+                テスト受付日時(),
+            )
+            .unwrap();
+        repository
+            .save(&プレゼント予約状態::発送済み(shipped.clone()))
+            .await
+            .unwrap();
+        let (failed, _) = shipped
+            .配送失敗を記録する(配送失敗理由::受取拒否, テスト受付日時(), None)
+            .unwrap();
+        let failed_state = プレゼント予約状態::配送失敗(failed.clone());
+        repository.save(&failed_state).await.unwrap();
+        assert_eq!(
+            repository.find_by_id(&reservation_id).await.unwrap(),
+            Some(failed_state)
+        );
+
+        let (returned, _) = failed.返送を記録する(テスト受付日時(), None).unwrap();
+        let returned_state = プレゼント予約状態::返送済み(returned);
+        repository.save(&returned_state).await.unwrap();
+        let found = repository.find_by_id(&reservation_id).await.unwrap();
+
+        // 配送業者・伝票番号・配送失敗理由と日時が復元される
+        assert_eq!(found, Some(returned_state));
+
+        sqlx::query!(
+            "DELETE FROM reservations WHERE id = $1",
+            reservation_id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test reservation data (after test)");
+    }
+
+    #[tokio::test]
+    async fn test_pg_save_and_find_by_id_forwarding() {
+        use crate::domain::core::発送代行予約を受け付ける;
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone());
+
+        let (received, _) = 発送代行予約を受け付ける(
+            ユーザーID::new(),
+            届け先ID::new(),
+            記念日 {
+                value: NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(),
+            },
+            None,
+            ラッピング種類::なし,
+            None,
+            発送代行プレゼント情報::new("木製のオルゴール\n割れ物注意").unwrap(),
+            NaiveDate::from_ymd_opt(2024, 12, 20).unwrap(),
+            支払いID::new(),
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+        .unwrap();
+        let reservation_id = received.base.id;
+        let reservation_state = プレゼント予約状態::予約受付済み(received);
+
+        repository.save(&reservation_state).await.unwrap();
+        let found = repository.find_by_id(&reservation_id).await.unwrap();
+
+        // 商品行は作られず、説明と到着予定日がそのまま復元される
+        assert_eq!(found, Some(reservation_state));
+
+        sqlx::query!(
+            "DELETE FROM reservations WHERE id = $1",
+            reservation_id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test reservation data (after test)");
+    }
+
+    #[tokio::test]
+    async fn test_pg_save_refuses_total_that_does_not_fit_integer() {
+        use crate::domain::core::発送代行予約を受け付ける;
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone());
+
+        // u32 には収まるが INTEGER には収まらない合計金額
+        let (received, _) = 発送代行予約を受け付ける(
+            ユーザーID::new(),
+            届け先ID::new(),
+            記念日 {
+                value: NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(),
+            },
+            None,
+            ラッピング種類::なし,
+            None,
+            発送代行プレゼント情報::new("絵本").unwrap(),
+            NaiveDate::from_ymd_opt(2024, 12, 20).unwrap(),
+            支払いID::new(),
+            料金内訳 {
+                明細: vec![料金明細::new(
+                    料金項目::送料,
+                    3_000_000_000,
+                    税区分::標準税率,
+                )],
+            },
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+        .unwrap();
+        let reservation_id = received.base.id;
+
+        // 負の数に丸めて保存せず、予約も作られない
+        let result = repository
+            .save(&プレゼント予約状態::予約受付済み(received))
+            .await;
+        assert_eq!(result, Err(DomainError::料金上限超過));
+        assert_eq!(repository.find_by_id(&reservation_id).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_pg_find_legacy_message_that_breaks_current_rules() {
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone());
+
+        let reservation_state =
+            create_dummy_received_reservation(テスト商品を登録する(&pool).await);
+        let base = reservation_state.base().clone();
+        repository.save(&reservation_state).await.unwrap();
+
+        // ルール導入前に保存された 200 文字超・CRLF 入りの文面
+        let legacy_message = format!("{}\r\nいつもありがとう", "あ".repeat(250));
+        sqlx::query("UPDATE reservations SET message = $1 WHERE id = $2")
+            .bind(&legacy_message)
+            .bind(base.id.as_uuid())
+            .execute(&pool)
+            .await
+            .expect("Failed to store legacy message");
+
+        // 単体の取得でも一覧でも、保存済みの文面がそのまま読める
+        let found = repository.find_by_id(&base.id).await.unwrap().unwrap();
+        assert_eq!(
+            found.base().メッセージ内容.as_ref().map(|m| m.value()),
+            Some(legacy_message.as_str())
+        );
+        let page = repository
+            .find_by_requester(&base.依頼者id, None, 10)
+            .await
+            .expect("find_by_requester failed");
+        assert_eq!(page.予約リスト.len(), 1);
+        assert_eq!(page.予約リスト[0], found);
+
+        sqlx::query!("DELETE FROM reservations WHERE id = $1", base.id.as_uuid())
+            .execute(&pool)
+            .await
+            .expect("Failed to clean up test reservation data (after test)");
+    }
+
+    #[tokio::test]
+    async fn test_pg_find_legacy_shipping_slip_number_that_breaks_current_format() {
+        use crate::domain::core::{
+            手配予約を受け付ける, 配送伝票番号, 配送業者
+        };
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone());
+        let requester_id = ユーザーID::new();
+
+        let (received, _) = 手配予約を受け付ける(
+            requester_id,
+            届け先ID::new(),
+            記念日 {
+                value: NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(),
+            },
+            None,
+            ラッピング種類::なし,
+            None,
+            [テスト商品を登録する(&pool).await].into_iter().collect(),
+            支払いID::new(),
+            テスト料金内訳(),
+            テスト受付日時(),
+            発送リードタイム::default(),
+        )
+        .unwrap();
+        let reservation_id = received.base.id;
+        repository
+            .save(&プレゼント予約状態::予約受付済み(
+                received.clone(),
+            ))
+            .await
+            .unwrap();
+        let (preparing, _) = received
+            .発送準備を開始する(ユーザーID::new(), テスト受付日時())
+            .unwrap();
+        repository
+            .save(&プレゼント予約状態::発送準備中(
+                preparing.clone(),
+            ))
+            .await
+            .unwrap();
+        let (shipped, _) = preparing
+            .発送を完了する(
+                配送伝票番号::new(配送業者::ヤマト運輸, "1234-5678-9013").unwrap(),
+                None,
+                テスト受付日時(),
+            )
+            .unwrap();
+        repository
+            .save(&プレゼント予約状態::発送済み(shipped))
+            .await
+            .unwrap();
+
+        // 形式の検証を導入する前に保存された、桁数もチェックディジットも満たさない番号
+        sqlx::query("UPDATE reservations SET shipping_slip_number = $1 WHERE id = $2")
+            .bind("YMT-001")
+            .bind(reservation_id.as_uuid())
+            .execute(&pool)
+            .await
+            .expect("Failed to store legacy shipping slip number");
+
+        // 単体の取得でも一覧でも、保存済みの番号がそのまま読める
+        let found = repository.find_by_id(&reservation_id).await.unwrap();
+        let Some(プレゼント予約状態::発送済み(found)) = found else {
+            panic!("Unexpected state: {:?}", found);
+        };
+        assert_eq!(found.配送伝票番号.value(), "YMT-001");
+        assert_eq!(found.配送伝票番号.配送業者(), 配送業者::ヤマト運輸);
+        let page = repository
+            .find_by_requester(&requester_id, None, 10)
+            .await
+            .expect("find_by_requester failed");
+        assert_eq!(page.予約リスト, vec![プレゼント予約状態::発送済み(found)]);
+
+        sqlx::query!(
+            "DELETE FROM reservations WHERE id = $1",
+            reservation_id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test reservation data (after test)");
+    }
+
+    #[tokio::test]
+    async fn test_pg_save_received_does_not_revive_reservation_in_later_state() {
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone());
+        let 受付済み = create_dummy_received_reservation(テスト商品を登録する(&pool).await);
+        let プレゼント予約状態::予約受付済み(received) = 受付済み.clone() else {
+            unreachable!();
+        };
+        let reservation_id = received.base.id;
+        repository.save(&受付済み).await.unwrap();
+        let (preparing, _) = received
+            .発送準備を開始する(ユーザーID::new(), テスト受付日時())
+            .unwrap();
+        let 発送準備中 = プレゼント予約状態::発送準備中(preparing);
+        repository.save(&発送準備中).await.unwrap();
+
+        // 発送準備が始まる前に読み込んだ予約で内容を変更しても、受付済みには戻らない
+        assert_eq!(
+            repository.save(&受付済み).await,
+            Err(DomainError::不正な状態遷移 {
+                current_state_type: "Preparing".to_string(),
+            })
+        );
+        assert_eq!(
+            repository.find_by_id(&reservation_id).await,
+            Ok(Some(発送準備中))
+        );
+
+        sqlx::query!(
+            "DELETE FROM reservations WHERE id = $1",
+            reservation_id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test reservation data (after test)");
+    }
+
+    #[tokio::test]
+    async fn test_pg_find_by_requester_pages_in_anniversary_order() {
+        use crate::domain::core::手配予約を受け付ける;
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone());
+        let requester_id = ユーザーID::new();
+
+        // 記念日がばらばらの予約を3件保存する
+        let mut saved = Vec::new();
+        for (month, day) in [(12, 24), (3, 14), (7, 7)] {
+            let (received, _) = 手配予約を受け付ける(
+                requester_id,
+                届け先ID::new(),
+                記念日 {
+                    value: NaiveDate::from_ymd_opt(2025, month, day).unwrap(),
+                },
+                None,
+                ラッピング種類::なし,
+                None,
+                HashSet::from([テスト商品を登録する(&pool).await]),
+                支払いID::new(),
+                テスト料金内訳(),
+                テスト受付日時(),
+                発送リードタイム::default(),
+            )
+            .unwrap();
+            let state = プレゼント予約状態::予約受付済み(received);
+            repository.save(&state).await.expect("save failed");
+            saved.push(state);
+        }
+
+        // 1ページ目 (2件) と 2ページ目 (残り1件)
+        let first_page = repository
+            .find_by_requester(&requester_id, None, 2)
+            .await
+            .expect("find_by_requester failed");
+        // 商品IDと料金内訳も含めて保存した予約がそのまま復元される
+        assert_eq!(
+            first_page.予約リスト,
+            vec![saved[1].clone(), saved[2].clone()]
+        ); // 3/14, 7/7
+        assert!(first_page.次のカーソル.is_some());
+
+        let second_page = repository
+            .find_by_requester(&requester_id, first_page.次のカーソル, 2)
+            .await
+            .expect("find_by_requester failed");
+        assert_eq!(second_page.予約リスト, vec![saved[0].clone()]); // 12/24
+        assert!(second_page.次のカーソル.is_none());
+
+        // テスト後のデータクリーンアップ (reservation_products は CASCADE で削除される)
+        sqlx::query!(
+            "DELETE FROM reservations WHERE requester_id = $1",
+            requester_id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test reservation data (after test)");
+    }
+
+    #[tokio::test]
+    async fn test_pg_search_filters_sorts_and_counts() {
+        use crate::domain::core::手配予約を受け付ける;
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone());
+        let requester_id = ユーザーID::new();
+
+        // 記念日・ラッピングの異なる予約を3件保存する
+        let mut saved_ids = Vec::new();
+        for (month, day, wrapping) in [
+            (3, 14, ラッピング種類::標準),
+            (12, 24, ラッピング種類::標準),
+            (7, 7, ラッピング種類::特別),
+        ] {
+            let (received, _) = 手配予約を受け付ける(
+                requester_id,
+                届け先ID::new(),
+                記念日 {
+                    value: NaiveDate::from_ymd_opt(2025, month, day).unwrap(),
+                },
+                None,
+                wrapping,
+                None,
+                HashSet::from([テスト商品を登録する(&pool).await]),
+                支払いID::new(),
+                テスト料金内訳(),
+                テスト受付日時(),
+                発送リードタイム::default(),
+            )
+            .unwrap();
+            saved_ids.push(received.base.id);
+            repository
+                .save(&プレゼント予約状態::予約受付済み(received))
+                .await
+                .expect("save failed");
+        }
+
+        // ラッピングで絞り込み、記念日の降順
+        let 条件 = 予約検索条件 {
+            ステータス: vec![予約ステータス::予約受付済み],
+            ラッピング: Some(ラッピング種類::標準),
+            依頼者id: Some(requester_id),
+            並び順: 予約検索の並び順::記念日降順,
+            ..Default::default()
+        };
+        let result = repository
+            .search(&条件, 0, 10)
+            .await
+            .expect("search failed");
+        assert_eq!(result.総件数, 2);
+        assert_eq!(result.予約リスト[0].base().id, saved_ids[1]); // 12/24
+        assert_eq!(result.予約リスト[1].base().id, saved_ids[0]); // 3/14
+
+        // 該当しないステータスでは 0 件
+        let 条件 = 予約検索条件 {
+            ステータス: vec![予約ステータス::発送済み],
+            依頼者id: Some(requester_id),
+            ..Default::default()
+        };
+        let result = repository
+            .search(&条件, 0, 10)
+            .await
+            .expect("search failed");
+        assert_eq!(result.総件数, 0);
+        assert!(result.予約リスト.is_empty());
+
+        // offset が件数を超えても総件数は返る
+        let 条件 = 予約検索条件 {
+            依頼者id: Some(requester_id),
+            ..Default::default()
+        };
+        let result = repository
+            .search(&条件, 5, 10)
+            .await
+            .expect("search failed");
+        assert_eq!(result.総件数, 3);
+        assert!(result.予約リスト.is_empty());
+
+        sqlx::query!(
+            "DELETE FROM reservations WHERE requester_id = $1",
+            requester_id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test reservation data (after test)");
+    }
+
+    #[tokio::test]
+    async fn test_pg_database_status_reports_migration_and_pool() {
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone());
+
+        let status = repository
+            .database_status()
+            .await
+            .expect("database_status failed");
+
+        assert!(status.migration_version.is_some());
+        assert!(status.pool_size >= 1);
+        assert!(status.idle_connections <= status.pool_size);
+    }
 
-        // reservations テーブルから基本情報を取得
-        let maybe_reservation_record = sqlx::query!(
-            r#"
-            SELECT
-                id, requester_id, recipient_id, anniversary_date, message,
-                wrapping_type, desired_delivery_date, total_amount, payment_id,
-                status,
-                -- 状態固有カラム
-                preparation_staff_id,
-                shipping_slip_number,
-                delivery_completed_at,
-                cancellation_reason,
-                cancelled_at
-            FROM reservations
-            WHERE id = $1
-            "#,
-            reservation_uuid
+    /// 予約の依頼者のキーの記録を作る (確保期限は IDEMPOTENCY_KEY_LEASE と同じ 30 秒)
+    fn テスト冪等性キー(
+        予約: &プレゼント予約状態,
+        created_at: DateTime<Utc>,
+    ) -> IdempotencyRecord {
+        IdempotencyRecord {
+            requester_id: 予約.base().依頼者id,
+            key: "retry-key".to_string(),
+            fingerprint: "{\"a\":1}".to_string(),
+            reservation_id: None,
+            created_at,
+            expires_at: created_at + chrono::Duration::hours(24),
+            lease_expires_at: created_at + chrono::Duration::seconds(30),
+        }
+    }
+
+    /// 冪等性キーのテストで作った予約・在庫・キーを削除する
+    async fn 冪等性キーのテストデータを削除する(
+        pool: &sqlx::PgPool,
+        予約: &プレゼント予約状態,
+        商品id: &商品ID,
+    ) {
+        let reservation_id = 予約.base().id.as_uuid();
+        sqlx::query!(
+            "DELETE FROM idempotency_keys WHERE requester_id = $1",
+            予約.base().依頼者id.as_uuid()
         )
-        .fetch_optional(&self.pool) // 見つからない場合は None を返す
+        .execute(pool)
         .await
-        .map_err(|e| {
-            // TODO: エラーマッピング改善
-            eprintln!(
-                "DB Error: Failed to fetch reservation by id {}: {}",
-                reservation_uuid, e
-            );
-            DomainError::予約NotFound(*id) // 仮
-        })?;
+        .expect("Failed to clean up test idempotency data (after test)");
+        sqlx::query!(
+            "DELETE FROM reservation_products WHERE reservation_id = $1",
+            reservation_id
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to clean up test products data (after test)");
+        sqlx::query!("DELETE FROM reservations WHERE id = $1", reservation_id)
+            .execute(pool)
+            .await
+            .expect("Failed to clean up test reservation data (after test)");
+        sqlx::query!(
+            "DELETE FROM product_stocks WHERE product_id = $1",
+            商品id.as_uuid()
+        )
+        .execute(pool)
+        .await
+        .expect("Failed to clean up test stock data (after test)");
+    }
+
+    #[tokio::test]
+    async fn test_pg_idempotency_key_is_recorded_with_reservation_and_expires() {
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone());
+        let 商品id = テスト商品を登録する(&pool).await;
+        repository.set_on_hand(&商品id, 1).await.unwrap();
+        let 予約 = create_dummy_received_reservation(商品id);
+        let now = Utc::now();
+        let record = テスト冪等性キー(&予約, now);
 
-        if let Some(record) = maybe_reservation_record {
-            // レコードが見つかった場合
+        // 初回は開始でき、2回目は処理中の記録が返る
+        assert_eq!(
+            repository.begin(&record).await,
+            Ok(IdempotencyBegin::Started)
+        );
+        let Ok(IdempotencyBegin::Existing(existing)) = repository.begin(&record).await else {
+            panic!("expected an existing record");
+        };
+        assert_eq!(existing.reservation_id, None);
 
-            // reservation_products テーブルから商品IDリストを取得
-            let product_records = sqlx::query!(
-                "SELECT product_id FROM reservation_products WHERE reservation_id = $1",
-                reservation_uuid
-            )
-            .fetch_all(&self.pool)
+        // 予約の保存と同時に予約IDが記録される
+        repository
+            .save_accepted_with_idempotency_key(&予約, &record.確保())
             .await
-            .map_err(|e| {
-                eprintln!(
-                    "DB Error: Failed to fetch reservation products for id {}: {}",
-                    reservation_uuid, e
-                );
-                DomainError::予約NotFound(*id) // 仮
-            })?;
+            .expect("save failed");
+        let 予約id = 予約.base().id;
+        let Ok(IdempotencyBegin::Existing(existing)) = repository.begin(&record).await else {
+            panic!("expected an existing record");
+        };
+        assert_eq!(existing.reservation_id, Some(予約id));
 
-            let product_ids: HashSet<商品ID> = product_records
-                .into_iter()
-                .map(|rec| 商品ID::from_uuid(rec.product_id))
-                .collect();
+        // 完了したキーは確保期限を過ぎても確保し直せない
+        let after_lease = IdempotencyRecord {
+            created_at: now + chrono::Duration::minutes(5),
+            ..record.clone()
+        };
+        assert!(matches!(
+            repository.begin(&after_lease).await,
+            Ok(IdempotencyBegin::Existing(_))
+        ));
 
-            // status に基づいて プレゼント予約状態 を構築
-            let state = match record.status.as_str() {
-                "Received" => {
-                    let wrapping_type = match record.wrapping_type.as_str() {
-                        "なし" => ラッピング種類::なし,
-                        "標準" => ラッピング種類::標準,
-                        "特別" => ラッピング種類::特別,
-                        _ => {
-                            eprintln!(
-                                "DB Error: Unknown wrapping type '{}' for id {}",
-                                record.wrapping_type, reservation_uuid
-                            );
-                            return Err(DomainError::予約NotFound(*id)); // 不明な値はエラー
-                        }
-                    };
-                    let total_amount = 金額::new(record.total_amount as u32).map_err(|e| {
-                        eprintln!(
-                            "DB Error: Invalid total amount {} for id {}: {:?}",
-                            record.total_amount, reservation_uuid, e
-                        );
-                        DomainError::予約NotFound(*id) // 金額変換エラーもエラー扱い (仮)
-                    })?;
-                    let base = プレゼント予約ベース {
-                        id: *id,
-                        依頼者id: ユーザーID::from_uuid(record.requester_id),
-                        届け先id: 届け先ID::from_uuid(record.recipient_id),
-                        記念日: 記念日 {
-                            value: record.anniversary_date,
-                        },
-                        メッセージ内容: record.message,
-                        ラッピング: wrapping_type,
-                        配送希望日時: record
-                            .desired_delivery_date
-                            .map(|dt| dt.with_timezone(&Tokyo)),
-                        合計金額: total_amount,
-                        支払いid: 支払いID::from_uuid(record.payment_id),
-                        手配商品リスト: product_ids.clone(), // Clone product_ids for base
-                    };
-                    プレゼント予約状態::予約受付済み(予約受付済みプレゼント予約型 {
-                        base,
-                    })
-                }
-                "Preparing" => {
-                    let wrapping_type = match record.wrapping_type.as_str() {
-                        "なし" => ラッピング種類::なし,
-                        "標準" => ラッピング種類::標準,
-                        "特別" => ラッピング種類::特別,
-                        _ => {
-                            eprintln!(
-                                "DB Error: Unknown wrapping type '{}' for id {}",
-                                record.wrapping_type, reservation_uuid
-                            );
-                            return Err(DomainError::予約NotFound(*id)); // 不明な値はエラー
-                        }
-                    };
-                    let total_amount = 金額::new(record.total_amount as u32).map_err(|e| {
-                        eprintln!(
-                            "DB Error: Invalid total amount {} for id {}: {:?}",
-                            record.total_amount, reservation_uuid, e
-                        );
-                        DomainError::予約NotFound(*id) // 金額変換エラーもエラー扱い (仮)
-                    })?;
-                    let base = プレゼント予約ベース {
-                        id: *id,
-                        依頼者id: ユーザーID::from_uuid(record.requester_id),
-                        届け先id: 届け先ID::from_uuid(record.recipient_id),
-                        記念日: 記念日 {
-                            value: record.anniversary_date,
-                        },
-                        メッセージ内容: record.message,
-                        ラッピング: wrapping_type,
-                        配送希望日時: record
-                            .desired_delivery_date
-                            .map(|dt| dt.with_timezone(&Tokyo)),
-                        合計金額: total_amount,
-                        支払いid: 支払いID::from_uuid(record.payment_id),
-                        手配商品リスト: product_ids.clone(), // Clone product_ids for base
-                    };
+        // 期限切れのキーは再利用できる
+        let later = IdempotencyRecord {
+            created_at: now + chrono::Duration::hours(25),
+            expires_at: now + chrono::Duration::hours(49),
+            ..record.clone()
+        };
+        assert_eq!(
+            repository.begin(&later).await,
+            Ok(IdempotencyBegin::Started)
+        );
 
-                    let preparation_staff_id = record.preparation_staff_id.ok_or_else(|| {
-                        eprintln!(
-                            "DB Error: preparation_staff_id is NULL for Preparing state, id {}",
-                            reservation_uuid
-                        );
-                        DomainError::予約NotFound(*id)
-                    })?;
+        冪等性キーのテストデータを削除する(&pool, &予約, &商品id).await;
+    }
 
-                    プレゼント予約状態::発送準備中(
-                        crate::domain::core::発送準備中プレゼント予約型 {
-                            base,
-                            梱包担当者id: ユーザーID::from_uuid(preparation_staff_id),
-                        },
-                    )
-                }
-                "Shipped" => {
-                    let wrapping_type = match record.wrapping_type.as_str() {
-                        "なし" => ラッピング種類::なし,
-                        "標準" => ラッピング種類::標準,
-                        "特別" => ラッピング種類::特別,
-                        _ => {
-                            eprintln!(
-                                "DB Error: Unknown wrapping type '{}' for id {}",
-                                record.wrapping_type, reservation_uuid
-                            );
-                            return Err(DomainError::予約NotFound(*id)); // 不明な値はエラー
-                        }
-                    };
-                    let total_amount = 金額::new(record.total_amount as u32).map_err(|e| {
-                        eprintln!(
-                            "DB Error: Invalid total amount {} for id {}: {:?}",
-                            record.total_amount, reservation_uuid, e
-                        );
-                        DomainError::予約NotFound(*id) // 金額変換エラーもエラー扱い (仮)
-                    })?;
-                    let base = プレゼント予約ベース {
-                        id: *id,
-                        依頼者id: ユーザーID::from_uuid(record.requester_id),
-                        届け先id: 届け先ID::from_uuid(record.recipient_id),
-                        記念日: 記念日 {
-                            value: record.anniversary_date,
-                        },
-                        メッセージ内容: record.message,
-                        ラッピング: wrapping_type,
-                        配送希望日時: record
-                            .desired_delivery_date
-                            .map(|dt| dt.with_timezone(&Tokyo)),
-                        合計金額: total_amount,
-                        支払いid: 支払いID::from_uuid(record.payment_id),
-                        手配商品リスト: product_ids.clone(), // Clone product_ids for base
-                    };
+    #[tokio::test]
+    async fn test_pg_abandoned_idempotency_key_is_reclaimed_after_lease() {
+        let pool = setup_db_pool().await;
+        let repository = PgRepository::new(pool.clone());
+        let 商品id = テスト商品を登録する(&pool).await;
+        repository.set_on_hand(&商品id, 1).await.unwrap();
+        let 予約 = create_dummy_received_reservation(商品id);
+        let now = Utc::now();
+        let 中断した確保 = テスト冪等性キー(&予約, now);
+        assert_eq!(
+            repository.begin(&中断した確保).await,
+            Ok(IdempotencyBegin::Started)
+        );
 
-                    let shipping_slip_number = record.shipping_slip_number.ok_or_else(|| {
-                        eprintln!(
-                            "DB Error: shipping_slip_number is NULL for Shipped state, id {}",
-                            reservation_uuid
-                        );
-                        DomainError::予約NotFound(*id)
-                    })?;
+        // 予約IDを記録しないまま確保期限を過ぎたキーは確保し直せる
+        let 再試行 = テスト冪等性キー(&予約, now + chrono::Duration::seconds(31));
+        assert_eq!(
+            repository.begin(&再試行).await,
+            Ok(IdempotencyBegin::Started)
+        );
 
-                    プレゼント予約状態::発送済み(
-                        crate::domain::core::発送済みプレゼント予約型 {
-                            base,
-                            配送伝票番号: shipping_slip_number,
-                        },
-                    )
-                }
-                "Delivered" => {
-                    let wrapping_type = match record.wrapping_type.as_str() {
-                        "なし" => ラッピング種類::なし,
-                        "標準" => ラッピング種類::標準,
-                        "特別" => ラッピング種類::特別,
-                        _ => {
-                            eprintln!(
-                                "DB Error: Unknown wrapping type '{}' for id {}",
-                                record.wrapping_type, reservation_uuid
-                            );
-                            return Err(DomainError::予約NotFound(*id)); // 不明な値はエラー
-                        }
-                    };
-                    let total_amount = 金額::new(record.total_amount as u32).map_err(|e| {
-                        eprintln!(
-                            "DB Error: Invalid total amount {} for id {}: {:?}",
-                            record.total_amount, reservation_uuid, e
-                        );
-                        DomainError::予約NotFound(*id) // 金額変換エラーもエラー扱い (仮)
-                    })?;
-                    let base = プレゼント予約ベース {
-                        id: *id,
-                        依頼者id: ユーザーID::from_uuid(record.requester_id),
-                        届け先id: 届け先ID::from_uuid(record.recipient_id),
-                        記念日: 記念日 {
-                            value: record.anniversary_date,
-                        },
-                        メッセージ内容: record.message,
-                        ラッピング: wrapping_type,
-                        配送希望日時: record
-                            .desired_delivery_date
-                            .map(|dt| dt.with_timezone(&Tokyo)),
-                        合計金額: total_amount,
-                        支払いid: 支払いID::from_uuid(record.payment_id),
-                        手配商品リスト: product_ids.clone(), // Clone product_ids for base
-                    };
+        // 確保し直された後は、中断した側の予約は保存されず在庫も引き当てられない
+        assert_eq!(
+            repository
+                .save_accepted_with_idempotency_key(&予約, &中断した確保.確保())
+                .await,
+            Err(DomainError::冪等性キー確保切れ)
+        );
+        assert_eq!(repository.find_by_id(&予約.base().id).await, Ok(None));
+        let 在庫 = repository.find_by_product_id(&商品id).await.unwrap();
+        assert_eq!(在庫.引当数, 0);
 
-                    let shipping_slip_number = record.shipping_slip_number.ok_or_else(|| {
-                        eprintln!(
-                            "DB Error: shipping_slip_number is NULL for Delivered state, id {}",
-                            reservation_uuid
-                        );
-                        DomainError::予約NotFound(*id)
-                    })?;
-                    let delivery_completed_at = record.delivery_completed_at.ok_or_else(|| {
-                        eprintln!(
-                            "DB Error: delivery_completed_at is NULL for Delivered state, id {}",
-                            reservation_uuid
-                        );
-                        DomainError::予約NotFound(*id)
-                    })?;
+        // 中断した側の破棄では確保し直した記録は消えない
+        repository
+            .abandon(&中断した確保)
+            .await
+            .expect("abandon failed");
+        assert!(matches!(
+            repository.begin(&再試行).await,
+            Ok(IdempotencyBegin::Existing(IdempotencyRecord {
+                reservation_id: None,
+                ..
+            }))
+        ));
 
-                    プレゼント予約状態::配送完了(
-                        crate::domain::core::配送完了プレゼント予約型 {
-                            base,
-                            配送伝票番号: shipping_slip_number,
-                            配送完了日時: delivery_completed_at.with_timezone(&Tokyo),
-                        },
-                    )
-                }
-                "Cancelled" => {
-                    let wrapping_type = match record.wrapping_type.as_str() {
-                        "なし" => ラッピング種類::なし,
-                        "標準" => ラッピング種類::標準,
-                        "特別" => ラッピング種類::特別,
-                        _ => {
-                            eprintln!(
-                                "DB Error: Unknown wrapping type '{}' for id {}",
-                                record.wrapping_type, reservation_uuid
-                            );
-                            return Err(DomainError::予約NotFound(*id)); // 不明な値はエラー
-                        }
-                    };
-                    let total_amount = 金額::new(record.total_amount as u32).map_err(|e| {
-                        eprintln!(
-                            "DB Error: Invalid total amount {} for id {}: {:?}",
-                            record.total_amount, reservation_uuid, e
-                        );
-                        DomainError::予約NotFound(*id) // 金額変換エラーもエラー扱い (仮)
-                    })?;
-                    let base = プレゼント予約ベース {
-                        id: *id,
-                        依頼者id: ユーザーID::from_uuid(record.requester_id),
-                        届け先id: 届け先ID::from_uuid(record.recipient_id),
-                        記念日: 記念日 {
-                            value: record.anniversary_date,
-                        },
-                        メッセージ内容: record.message,
-                        ラッピング: wrapping_type,
-                        配送希望日時: record
-                            .desired_delivery_date
-                            .map(|dt| dt.with_timezone(&Tokyo)),
-                        合計金額: total_amount,
-                        支払いid: 支払いID::from_uuid(record.payment_id),
-                        手配商品リスト: product_ids.clone(), // Clone product_ids for base
-                    };
+        repository
+            .save_accepted_with_idempotency_key(&予約, &再試行.確保())
+            .await
+            .expect("save failed");
+        let 在庫 = repository.find_by_product_id(&商品id).await.unwrap();
+        assert_eq!(在庫.引当数, 1);
 
-                    let cancellation_reason = record.cancellation_reason; // Option<String>
-                    let cancelled_at = record.cancelled_at.map(|dt| dt.with_timezone(&Tokyo)); // Option<DateTime<Tz>>
+        冪等性キーのテストデータを削除する(&pool, &予約, &商品id).await;
+    }
 
-                    プレゼント予約状態::キャンセル済み(
-                        crate::domain::core::キャンセル済みプレゼント予約型 {
-                            base,
-                            キャンセル理由: cancellation_reason,
-                            キャンセル日時: cancelled_at,
-                        },
-                    )
-                }
-                unknown_status => {
-                    eprintln!(
-                        "DB Error: Unknown reservation status '{}' for id {}",
-                        unknown_status, reservation_uuid
-                    );
-                    return Err(DomainError::予約NotFound(*id)); // 不明な状態はエラー
-                }
-            };
-            Ok(Some(state))
-        } else {
-            // レコードが見つからなかった場合
-            Ok(None)
-        }
+    #[tokio::test]
+    async fn test_pg_recipient_save_update_list_and_delete() {
+        let pool = setup_db_pool().await;
+        let repository = Pg届け先Repository::new(pool.clone());
+        let 所有者id = ユーザーID::new();
+        let 内容 = 届け先内容 {
+            氏名: 氏名::new("山田 花子", "ヤマダ ハナコ").unwrap(),
+            郵便番号: 郵便番号::new("100-0001").unwrap(),
+            都道府県: 都道府県::東京都,
+            住所: 住所::new("千代田区千代田1-1").unwrap(),
+            建物名: Some(建物名::new("千代田ハイツ 101").unwrap()),
+            電話番号: 電話番号::new("03-1234-5678").unwrap(),
+        };
+        let mut first = 届け先::登録する(所有者id, 内容.clone());
+        let second = 届け先::登録する(
+            所有者id,
+            届け先内容 {
+                都道府県: 都道府県::沖縄県,
+                建物名: None,
+                ..内容
+            },
+        );
+        repository.save(&first).await.expect("save failed");
+        repository.save(&second).await.expect("save failed");
+
+        // 同じIDで保存すると内容が更新される
+        first.内容.住所 = 住所::new("千代田区丸の内1-1").unwrap();
+        repository.save(&first).await.expect("update failed");
+        assert_eq!(
+            repository.find_by_id(&first.id).await.unwrap(),
+            Some(first.clone())
+        );
+
+        // 所有者の届け先は登録順に取得できる
+        assert_eq!(
+            repository.find_by_owner(&所有者id).await.unwrap(),
+            vec![first.clone(), second.clone()]
+        );
+
+        repository.delete(&first.id).await.expect("delete failed");
+        assert_eq!(repository.find_by_id(&first.id).await.unwrap(), None);
+
+        sqlx::query!(
+            "DELETE FROM recipients WHERE owner_id = $1",
+            所有者id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test recipient data (after test)");
     }
 
-    /// データベースへの接続を確認する (トレイト実装)
-    async fn check_db_connection(&self) -> Result<(), InfrastructureError> {
-        sqlx::query("SELECT 1")
-            .execute(&self.pool)
-            .await
-            .map(|_| ()) // 成功時は Ok(())
-            .map_err(|e| {
-                eprintln!("Health Check DB Error: {}", e); // エラーログ
-                InfrastructureError::ConnectionError(e.to_string()) // sqlx::Error をラップ
-            })
+    #[tokio::test]
+    async fn test_pg_user_account_insert_save_find_and_unique_constraints() {
+        let pool = setup_db_pool().await;
+        let repository = PgユーザーアカウントRepository::new(pool.clone());
+        let サブジェクト = 認証サブジェクト::new(&format!("test|{}", Uuid::new_v4())).unwrap();
+        let mut アカウント = ユーザーアカウント::登録する(
+            ユーザーID::new(),
+            サブジェクト.clone(),
+            プロフィール {
+                メールアドレス: メールアドレス::new("hanako@example.com").unwrap(),
+                表示名: 表示名::new("はなこ").unwrap(),
+                既定の差出人名: Some(差出人名::new("山田 花子").unwrap()),
+            },
+        );
+        repository.insert(&アカウント).await.expect("insert failed");
+
+        // 同じIDで保存すると状態が更新される
+        アカウント.停止する();
+        repository.save(&アカウント).await.expect("update failed");
+        assert_eq!(
+            repository.find_by_id(&アカウント.id).await.unwrap(),
+            Some(アカウント.clone())
+        );
+        assert_eq!(
+            repository
+                .find_by_auth_subject(&サブジェクト)
+                .await
+                .unwrap(),
+            Some(アカウント.clone())
+        );
+
+        // 同じ認証サブジェクトで別のアカウントは保存できない
+        let 重複 = ユーザーアカウント::登録する(
+            ユーザーID::new(),
+            サブジェクト.clone(),
+            アカウント.プロフィール.clone(),
+        );
+        assert_eq!(
+            repository.save(&重複).await,
+            Err(DomainError::認証サブジェクト使用中)
+        );
+
+        // 新規保存では同じIDも同じ認証サブジェクトも受け付けない
+        assert_eq!(
+            repository.insert(&アカウント).await,
+            Err(DomainError::ユーザーアカウント登録済み(アカウント.id))
+        );
+        assert_eq!(
+            repository.insert(&重複).await,
+            Err(DomainError::認証サブジェクト使用中)
+        );
+
+        sqlx::query!(
+            "DELETE FROM user_accounts WHERE auth_subject = $1",
+            サブジェクト.value()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test user account data (after test)");
     }
-}
 
-// --- テスト ---
-#[cfg(all(test, not(ci)))]
-mod tests {
-    use super::*;
-    use chrono::NaiveDate;
-    use sqlx::postgres::PgPoolOptions;
-    use std::env; // tests モジュール内で use する
-                  // use crate::domain::core::予約を受け付ける; // 関数ローカルで use
+    #[tokio::test]
+    async fn test_pg_product_save_find_and_search() {
+        let pool = setup_db_pool().await;
+        let repository = Pg商品Repository::new(pool.clone());
+        // 他のテストの商品と区別するため、商品名に一意なキーワードを含める
+        let キーワード = format!("Bouquet-{}", Uuid::new_v4());
+        let mut 販売中の商品 = テスト商品(&format!("バラの{}", キーワード));
+        let mut 販売終了の商品 = テスト商品(&format!("ユリの{}", キーワード));
+        repository.save(&販売中の商品).await.expect("save failed");
+        repository.save(&販売終了の商品).await.expect("save failed");
 
-    // テスト用のヘルパー関数: DB接続プールを取得
-    async fn setup_db_pool() -> sqlx::PgPool {
-        // dotenv().ok();
-        let database_url =
-            env::var("DATABASE_URL").expect("DATABASE_URL must be set for integration tests");
-        PgPoolOptions::new()
-            .max_connections(5)
-            .connect(&database_url)
+        // 同じIDで保存すると内容と販売状態が更新される
+        販売中の商品.内容.価格.税込価格 = 金額::new(5500).unwrap();
+        repository.save(&販売中の商品).await.expect("update failed");
+        販売終了の商品.販売状態を変更する(false);
+        repository
+            .save(&販売終了の商品)
             .await
-            .expect("Failed to create pool.")
-    }
+            .expect("update failed");
+        assert_eq!(
+            repository.find_by_id(&販売中の商品.id).await.unwrap(),
+            Some(販売中の商品.clone())
+        );
 
-    // テスト用のヘルパー関数: ダミーの予約受付済み状態を作成
-    fn create_dummy_received_reservation() -> プレゼント予約状態 {
-        use crate::domain::core::予約を受け付ける; // 関数内で use する例
-        let requester_id = ユーザーID::from_uuid(Uuid::new_v4());
-        let recipient_id = 届け先ID::from_uuid(Uuid::new_v4());
-        let anniversary = 記念日 {
-            value: NaiveDate::from_ymd_opt(2024, 12, 25).unwrap(), // ここで NaiveDate を使う
+        // キーワードは大文字・小文字を区別しない。販売終了の商品は販売中のみの検索に含めない
+        let 条件 = 商品検索条件 {
+            キーワード: Some(キーワード.to_lowercase()),
+            最低価格: Some(5000),
+            販売中のみ: true,
+            ..Default::default()
         };
-        let product_ids = HashSet::from([商品ID::from_uuid(Uuid::new_v4())]);
-        let payment_id = 支払いID::from_uuid(Uuid::new_v4());
-        let total_amount = 金額::new(5000).unwrap();
+        let 結果 = repository
+            .search(&条件, 0, 10)
+            .await
+            .expect("search failed");
+        assert_eq!(結果.総件数, 1);
+        assert_eq!(結果.商品リスト, vec![販売中の商品.clone()]);
 
-        let received = 予約を受け付ける(
-            requester_id,
-            recipient_id,
-            anniversary,
-            Some("テストメッセージ".to_string()),
-            ラッピング種類::標準,
-            None, // 配送希望日時なし
-            product_ids,
-            payment_id,
-            total_amount,
+        let 条件 = 商品検索条件 {
+            キーワード: Some(キーワード.clone()),
+            ..Default::default()
+        };
+        let 結果 = repository
+            .search(&条件, 1, 10)
+            .await
+            .expect("search failed");
+        assert_eq!(結果.総件数, 2);
+        assert_eq!(結果.商品リスト, vec![販売終了の商品.clone()]);
+
+        sqlx::query!(
+            "DELETE FROM products WHERE id = ANY($1)",
+            &[*販売中の商品.id.as_uuid(), *販売終了の商品.id.as_uuid()]
         )
-        .unwrap();
-        プレゼント予約状態::予約受付済み(received)
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test product data (after test)");
     }
 
     #[tokio::test]
-    async fn test_pg_save_and_find_by_id_received() {
+    async fn test_pg_stock_allocation_is_saved_with_reservation() {
         let pool = setup_db_pool().await;
-        let repository = PgRepository::new(pool.clone()); // PgRepository をインスタンス化
+        let repository = PgRepository::new(pool.clone());
+        let 商品id = テスト商品を登録する(&pool).await;
+        let 先の予約 = create_dummy_received_reservation(商品id);
+        let 後の予約 = create_dummy_received_reservation(商品id);
+        let 在庫 = repository.set_on_hand(&商品id, 1).await.unwrap();
+        assert_eq!(在庫.引当可能数(), 1);
 
-        let reservation_state = create_dummy_received_reservation();
-        let reservation_id = match &reservation_state {
-            プレゼント予約状態::予約受付済み(r) => r.base.id,
-            _ => panic!("Test setup error: Unexpected initial state"),
-        };
+        // 最後の 1 点を引き当てた後は、同じ商品の予約を受け付けない (予約も保存されない)
+        repository
+            .save_with_stock_movement(&先の予約, 在庫移動::引当)
+            .await
+            .expect("allocation failed");
+        assert_eq!(
+            repository
+                .save_with_stock_movement(&後の予約, 在庫移動::引当)
+                .await,
+            Err(DomainError::在庫不足(商品id))
+        );
+        assert_eq!(repository.find_by_id(&後の予約.base().id).await, Ok(None));
+        let 在庫 = repository.find_by_product_id(&商品id).await.unwrap();
+        assert_eq!((在庫.在庫数, 在庫.引当数), (1, 1));
 
-        // テストデータのクリーンアップ (テスト開始前にも実行)
+        // 引当数を下回る在庫数には変更できない
+        assert!(matches!(
+            repository.set_on_hand(&商品id, 0).await,
+            Err(DomainError::在庫数不正 { 引当数: 1, .. })
+        ));
+
+        // 引当を解除すると他の予約が引き当てられ、出庫すると在庫数も減る
+        repository
+            .save_with_stock_movement(&先の予約, 在庫移動::引当解除)
+            .await
+            .expect("release failed");
+        repository
+            .save_with_stock_movement(&後の予約, 在庫移動::引当)
+            .await
+            .expect("allocation failed");
+        repository
+            .save_with_stock_movement(&後の予約, 在庫移動::出庫)
+            .await
+            .expect("shipment failed");
+        let 在庫 = repository.find_by_product_id(&商品id).await.unwrap();
+        assert_eq!((在庫.在庫数, 在庫.引当数), (0, 0));
+
+        // テスト後のデータクリーンアップ
+        let reservation_ids = [*先の予約.base().id.as_uuid(), *後の予約.base().id.as_uuid()];
         sqlx::query!(
-            "DELETE FROM reservation_products WHERE reservation_id = $1",
-            reservation_id.as_uuid()
+            "DELETE FROM reservation_products WHERE reservation_id = ANY($1)",
+            &reservation_ids
         )
         .execute(&pool)
         .await
-        .expect("Failed to clean up test products data (before test)");
+        .expect("Failed to clean up test products data (after test)");
         sqlx::query!(
-            "DELETE FROM reservations WHERE id = $1",
-            reservation_id.as_uuid()
+            "DELETE FROM reservations WHERE id = ANY($1)",
+            &reservation_ids
         )
         .execute(&pool)
         .await
-        .expect("Failed to clean up test reservation data (before test)");
-
-        // 保存処理のテスト
-        let save_result = repository.save(&reservation_state).await;
-        assert!(save_result.is_ok(), "save failed: {:?}", save_result.err());
+        .expect("Failed to clean up test reservation data (after test)");
+        sqlx::query!(
+            "DELETE FROM product_stocks WHERE product_id = $1",
+            商品id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test stock data (after test)");
+    }
 
-        // 取得処理のテスト
-        let found_reservation = repository.find_by_id(&reservation_id).await;
-        assert!(
-            found_reservation.is_ok(),
-            "find_by_id failed: {:?}",
-            found_reservation.err()
-        );
-        assert!(
-            found_reservation.as_ref().unwrap().is_some(),
-            "Reservation not found after save"
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_pg_concurrent_cancel_and_shipment_only_one_wins() {
+        use crate::application::{ApplicationError, FixedClock, プレゼント予約サービス};
+        use crate::domain::core::{配送伝票番号, 配送業者};
+        const 予約数: u32 = 5;
+        let pool = setup_db_pool().await;
+        let repository = Arc::new(PgRepository::new(pool.clone()));
+        let service = Arc::new(
+            プレゼント予約サービス::new(repository.clone())
+                .with_clock(Arc::new(FixedClock(テスト受付日時().with_timezone(&Utc)))),
         );
+        let 商品id = テスト商品を登録する(&pool).await;
+        repository.set_on_hand(&商品id, 予約数).await.unwrap();
+        let mut 予約idリスト = Vec::new();
+        for _ in 0..予約数 {
+            let 予約 = create_dummy_received_reservation(商品id);
+            repository
+                .save_with_stock_movement(&予約, 在庫移動::引当)
+                .await
+                .unwrap();
+            let id = 予約.base().id;
+            service
+                .発送準備を開始する(&id, ユーザーID::new())
+                .await
+                .unwrap();
+            予約idリスト.push(id);
+        }
 
-        // 取得したデータが保存したデータと一致するか検証
-        // 注意: DateTime<Tz> は DB 保存時にマイクロ秒が丸められる可能性があるため、完全一致しない場合がある
-        // ここでは PartialEq を使って比較する
-        assert_eq!(found_reservation.unwrap().unwrap(), reservation_state);
+        // 発送準備中の予約に、キャンセルと発送完了を同時に行う
+        let mut 発送数 = 0;
+        for &id in &予約idリスト {
+            let キャンセル = tokio::spawn({
+                let service = service.clone();
+                async move { service.予約をキャンセルする(&id, None, None).await }
+            });
+            let 発送 = tokio::spawn({
+                let service = service.clone();
+                async move {
+                    let 伝票番号 =
+                        配送伝票番号::new(配送業者::ヤマト運輸, "1234-5678-9013").unwrap();
+                    service.発送を完了する(&id, 伝票番号, None).await
+                }
+            });
+            let (キャンセル, 発送) = (キャンセル.await.unwrap(), 発送.await.unwrap());
+
+            // どちらか一方だけが成功し、もう一方は 不正な状態遷移 になる
+            let 負けた側 = match (&キャンセル, &発送) {
+                (Ok(_), Err(e)) | (Err(e), Ok(_)) => e,
+                _ => panic!(
+                    "exactly one must win: cancel={:?}, ship={:?}",
+                    キャンセル, 発送
+                ),
+            };
+            assert!(
+                matches!(
+                    負けた側,
+                    ApplicationError::Domain(DomainError::不正な状態遷移 { .. })
+                ),
+                "unexpected error: {:?}",
+                負けた側
+            );
+            let found = repository.find_by_id(&id).await.unwrap().unwrap();
+            if 発送.is_ok() {
+                発送数 += 1;
+                assert!(matches!(found, プレゼント予約状態::発送済み(_)));
+            } else {
+                assert!(matches!(found, プレゼント予約状態::キャンセル済み(_)));
+            }
+        }
+
+        // 発送した予約の分だけ出庫され、キャンセルした予約の引当は戻っている
+        let 在庫 = repository.find_by_product_id(&商品id).await.unwrap();
+        assert_eq!((在庫.在庫数, 在庫.引当数), (予約数 - 発送数, 0));
 
         // テスト後のデータクリーンアップ
+        let reservation_ids: Vec<Uuid> = 予約idリスト.iter().map(|id| *id.as_uuid()).collect();
         sqlx::query!(
-            "DELETE FROM reservation_products WHERE reservation_id = $1",
-            reservation_id.as_uuid()
+            "DELETE FROM reservation_products WHERE reservation_id = ANY($1)",
+            &reservation_ids
         )
         .execute(&pool)
         .await
         .expect("Failed to clean up test products data (after test)");
         sqlx::query!(
-            "DELETE FROM reservations WHERE id = $1",
-            reservation_id.as_uuid()
+            "DELETE FROM reservations WHERE id = ANY($1)",
+            &reservation_ids
         )
         .execute(&pool)
         .await
         .expect("Failed to clean up test reservation data (after test)");
+        sqlx::query!(
+            "DELETE FROM product_stocks WHERE product_id = $1",
+            商品id.as_uuid()
+        )
+        .execute(&pool)
+        .await
+        .expect("Failed to clean up test stock data (after test)");
     }
 
     // TODO: 他の状態 (発送準備中、発送済みなど) の save/find_by_id テストケースを追加
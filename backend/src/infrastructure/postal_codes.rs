@@ -0,0 +1,212 @@
+// 日本郵便の郵便番号データ (KEN_ALL.CSV) を読み込むインメモリの郵便番号辞書
+//
+// 読み込むのは UTF-8 版 (utf_ken_all.csv) と同じ 15 列の形式。
+// 起動時に一度だけ読み込み、実行時にネットワークへはアクセスしない。
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::domain::core::{住所候補, 郵便番号, 郵便番号辞書, 都道府県};
+use crate::domain::DomainError;
+
+/// KEN_ALL の列位置
+const 郵便番号の列: usize = 2;
+const 都道府県名の列: usize = 6;
+const 市区町村名の列: usize = 7;
+const 町域名の列: usize = 8;
+const 列数: usize = 15;
+
+/// 郵便番号ごとの住所候補を保持する郵便番号辞書
+#[derive(Debug, Clone, Default)]
+pub struct InMemory郵便番号辞書 {
+    index: HashMap<String, Vec<住所候補>>,
+}
+
+impl InMemory郵便番号辞書 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 住所候補を登録する (同じ候補は重複して登録しない)
+    pub fn 登録する(&mut self, 番号: &郵便番号, 候補: 住所候補) {
+        let 候補リスト = self.index.entry(番号.value().to_string()).or_default();
+        if !候補リスト.contains(&候補) {
+            候補リスト.push(候補);
+        }
+    }
+
+    /// 登録されている郵便番号の数
+    pub fn 件数(&self) -> usize {
+        self.index.len()
+    }
+
+    /// KEN_ALL 形式 (UTF-8) のファイルを読み込む
+    pub fn from_ken_all_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Self::from_ken_all_csv(BufReader::new(file))
+    }
+
+    /// KEN_ALL 形式 (UTF-8) の CSV を読み込む
+    ///
+    /// 町域が長く複数行に分割されている行 (括弧が閉じていない行) は次の行と連結する。
+    pub fn from_ken_all_csv(reader: impl BufRead) -> io::Result<Self> {
+        let mut 辞書 = Self::new();
+        let mut 分割中の町域: Option<(郵便番号, String)> = None;
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim_start_matches('\u{feff}').trim_end();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = |reason: String| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} 行目: {}", i + 1, reason),
+                )
+            };
+            let fields = split_csv_line(line);
+            if fields.len() != 列数 {
+                return Err(invalid(format!(
+                    "列数が {} ではありません ({} 列)",
+                    列数,
+                    fields.len()
+                )));
+            }
+            let 番号 = 郵便番号::new(&fields[郵便番号の列]).map_err(|e| invalid(e.to_string()))?;
+            let 都道府県 =
+                都道府県::from_名前(&fields[都道府県名の列]).map_err(|e| invalid(e.to_string()))?;
+            let 市区町村 = fields[市区町村名の列].clone();
+
+            let 町域 = match 分割中の町域.take() {
+                Some((前の番号, 前半)) if 前の番号 == 番号 => {
+                    前半 + &fields[町域名の列]
+                }
+                Some(_) => return Err(invalid("分割された町域の続きがありません".to_string())),
+                None => fields[町域名の列].clone(),
+            };
+            if 町域.contains('（') && !町域.contains('）') {
+                分割中の町域 = Some((番号, 町域));
+                continue;
+            }
+            辞書.登録する(
+                &番号,
+                住所候補 {
+                    都道府県,
+                    市区町村,
+                    町域: 町域を整える(&町域),
+                },
+            );
+        }
+        if let Some((番号, _)) = 分割中の町域 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} の町域が閉じていません", 番号.表示()),
+            ));
+        }
+        Ok(辞書)
+    }
+}
+
+#[async_trait]
+impl 郵便番号辞書 for InMemory郵便番号辞書 {
+    async fn 住所候補を取得する(
+        &self,
+        番号: &郵便番号,
+    ) -> Result<Vec<住所候補>, DomainError> {
+        Ok(self.index.get(番号.value()).cloned().unwrap_or_default())
+    }
+}
+
+/// 町域名から住所の入力に使わない部分を取り除く
+///
+/// 「以下に掲載がない場合」などの注記は町域なし、括弧内の補足 (丁目・番地の範囲など) は取り除く。
+fn 町域を整える(町域: &str) -> String {
+    if 町域 == "以下に掲載がない場合" || 町域.ends_with("の次に番地がくる場合")
+    {
+        return String::new();
+    }
+    match 町域.find('（') {
+        Some(start) => 町域[..start].to_string(),
+        None => 町域.to_string(),
+    }
+}
+
+/// CSV の 1 行を列に分割する (ダブルクォートで囲まれた列と "" のエスケープに対応する)
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"13101,"100  ","1000001","ﾄｳｷｮｳﾄ","ﾁﾖﾀﾞｸ","ﾁﾖﾀﾞ","東京都","千代田区","千代田",0,0,0,0,0,0
+01101,"060  ","0600001","ﾎｯｶｲﾄﾞｳ","ｻｯﾎﾟﾛｼﾁｭｳｵｳｸ","ｷﾀ1ｼﾞｮｳﾆｼ(1-19ﾁｮｳﾒ)","北海道","札幌市中央区","北一条西（１～１９丁目）",1,0,1,0,0,0
+03302,"02851","0285102","ｲﾜﾃｹﾝ","ｲﾜﾃｸﾞﾝｸｽﾞﾏｷﾏﾁ","ｸｽﾞﾏｷ(ﾀﾞｲ40ﾁﾜﾘ<57ﾊﾞﾝﾁ125､176ｦﾉｿﾞｸ>-ﾀﾞｲ45","岩手県","岩手郡葛巻町","葛巻（第４０地割「５７番地１２５、１７６を除く」～第４５",0,1,0,0,0,0
+03302,"02851","0285102","ｲﾜﾃｹﾝ","ｲﾜﾃｸﾞﾝｸｽﾞﾏｷﾏﾁ","ﾁﾜﾘ)","岩手県","岩手郡葛巻町","地割）",0,1,0,0,0,0
+13101,"100  ","1000000","ﾄｳｷｮｳﾄ","ﾁﾖﾀﾞｸ","ｲｶﾆｹｲｻｲｶﾞﾅｲﾊﾞｱｲ","東京都","千代田区","以下に掲載がない場合",0,0,0,0,0,0
+"#;
+
+    fn 候補(辞書: &InMemory郵便番号辞書, 番号: &str) -> Vec<住所候補> {
+        辞書.index.get(番号).cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn test_from_ken_all_csv_builds_index() {
+        let 辞書 = InMemory郵便番号辞書::from_ken_all_csv(SAMPLE.as_bytes()).unwrap();
+
+        assert_eq!(辞書.件数(), 4);
+        assert_eq!(
+            候補(&辞書, "1000001"),
+            vec![住所候補 {
+                都道府県: 都道府県::東京都,
+                市区町村: "千代田区".to_string(),
+                町域: "千代田".to_string(),
+            }]
+        );
+        // 括弧内の補足と「以下に掲載がない場合」は町域に含めない
+        assert_eq!(候補(&辞書, "0600001")[0].町域, "北一条西");
+        assert_eq!(候補(&辞書, "1000000")[0].町域, "");
+        // 複数行に分割された町域は 1 件にまとめる
+        assert_eq!(
+            候補(&辞書, "0285102"),
+            vec![住所候補 {
+                都道府県: 都道府県::岩手県,
+                市区町村: "岩手郡葛巻町".to_string(),
+                町域: "葛巻".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_from_ken_all_csv_rejects_malformed_rows() {
+        let result = InMemory郵便番号辞書::from_ken_all_csv(
+            r#"13101,"100  ","1000001","ﾄｳｷｮｳﾄ","ﾁﾖﾀﾞｸ","ﾁﾖﾀﾞ","東京","千代田区","千代田",0,0,0,0,0,0"#
+                .as_bytes(),
+        );
+
+        let error = result.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("1 行目"));
+    }
+}
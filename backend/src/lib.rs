@@ -2,6 +2,209 @@
 
 // モジュール宣言
 pub mod application;
+pub mod config;
 pub mod domain;
 pub mod infrastructure;
+pub mod openapi;
 pub mod routes; // コメントアウト解除
+
+use axum::extract::FromRef;
+use axum::http::HeaderValue;
+use axum::{
+    middleware,
+    routing::{get, post, put},
+    Router,
+};
+use std::sync::Arc;
+use tower_http::cors::{Any, CorsLayer};
+use tower_http::trace::{DefaultMakeSpan, TraceLayer};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::application::{
+    プレゼント予約サービス, ユーザーアカウントサービス, 商品カタログサービス, 届け先サービス,
+};
+use crate::config::{AppConfig, CURRENT_API_VERSION};
+use crate::openapi::ApiDoc;
+use crate::routes::accounts::{get_my_account, register_my_account, update_my_profile};
+use crate::routes::admin_accounts::{reactivate_account, suspend_account};
+use crate::routes::admin_products::{
+    create_product, get_product_stock, update_product, update_product_stock,
+};
+use crate::routes::admin_reservations::{
+    cancel_reservation, complete_shipment, preview_cancellation, record_delivery,
+    record_delivery_failure, record_return, reship_reservation, search_reservations,
+    start_preparation,
+};
+use crate::routes::deprecation::{add_deprecation_headers, DeprecationPolicy};
+use crate::routes::health_check::{health_check, liveness, readiness};
+use crate::routes::postal_codes::lookup_postal_code;
+use crate::routes::products::{get_product, search_products};
+use crate::routes::recipients::{
+    create_recipient, delete_recipient, get_recipient, list_my_recipients, update_recipient,
+};
+use crate::routes::reservations::{
+    create_reservation, get_reservation, list_my_reservations, preview_my_cancellation,
+    update_reservation,
+};
+
+/// ルーターで共有するアプリケーションサービス
+///
+/// ハンドラは必要なサービスだけを `State<Arc<...>>` で受け取る (FromRef で取り出す)。
+#[derive(Clone)]
+pub struct AppState {
+    pub reservations: Arc<プレゼント予約サービス>,
+    pub recipients: Arc<届け先サービス>,
+    pub accounts: Arc<ユーザーアカウントサービス>,
+    pub products: Arc<商品カタログサービス>,
+}
+
+impl FromRef<AppState> for Arc<プレゼント予約サービス> {
+    fn from_ref(state: &AppState) -> Self {
+        state.reservations.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<届け先サービス> {
+    fn from_ref(state: &AppState) -> Self {
+        state.recipients.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<ユーザーアカウントサービス> {
+    fn from_ref(state: &AppState) -> Self {
+        state.accounts.clone()
+    }
+}
+
+impl FromRef<AppState> for Arc<商品カタログサービス> {
+    fn from_ref(state: &AppState) -> Self {
+        state.products.clone()
+    }
+}
+
+/// アプリケーション全体のルーターを組み立てる
+///
+/// main.rs と結合テストの両方から使う。リポジトリの実装はサービスに注入済みのものを使うため、
+/// InMemory リポジトリを渡せば Postgres なしで HTTP レベルのテストができる。
+///
+/// API は /api/v1 にマウントする。旧パス (/api/...) は設定で有効な場合のみ、
+/// Deprecation / Sunset ヘッダー付きで同じルートを提供する。
+pub fn build_app(state: AppState, config: &AppConfig) -> Router {
+    let v1 = api_routes().with_state(state);
+    let current_prefix = format!("/api/{}", CURRENT_API_VERSION);
+
+    let mut app = Router::new().nest(&current_prefix, v1.clone());
+    if config.legacy_api_enabled {
+        let policy = Arc::new(DeprecationPolicy {
+            deprecated_since: None,
+            sunset: config.legacy_api_sunset,
+            successor: Some(current_prefix.clone()),
+        });
+        app = app.nest(
+            "/api",
+            v1.layer(middleware::map_response_with_state(
+                policy,
+                add_deprecation_headers,
+            )),
+        );
+    }
+
+    if config.swagger_ui_enabled {
+        app = app.merge(
+            SwaggerUi::new("/swagger-ui")
+                .url("/api-docs/openapi.json", ApiDoc::with_servers(config)),
+        );
+    }
+
+    app.layer(
+        TraceLayer::new_for_http().make_span_with(DefaultMakeSpan::default().include_headers(true)),
+    )
+    .layer(cors_layer(config))
+}
+
+/// バージョンのプレフィックスを除いた API のルート
+fn api_routes() -> Router<AppState> {
+    Router::new()
+        .route("/health", get(health_check))
+        .route("/health/live", get(liveness))
+        .route("/health/ready", get(readiness))
+        .route("/reservations", post(create_reservation))
+        .route(
+            "/reservations/{id}",
+            get(get_reservation).patch(update_reservation),
+        )
+        .route(
+            "/me",
+            get(get_my_account)
+                .post(register_my_account)
+                .put(update_my_profile),
+        )
+        .route("/me/reservations", get(list_my_reservations))
+        .route(
+            "/me/reservations/{id}/cancellation",
+            get(preview_my_cancellation),
+        )
+        .route(
+            "/recipients",
+            get(list_my_recipients).post(create_recipient),
+        )
+        .route(
+            "/recipients/{id}",
+            get(get_recipient)
+                .put(update_recipient)
+                .delete(delete_recipient),
+        )
+        .route("/postal-codes/{code}", get(lookup_postal_code))
+        .route("/products", get(search_products))
+        .route("/products/{id}", get(get_product))
+        .route("/admin/reservations", get(search_reservations))
+        .route(
+            "/admin/reservations/{id}/preparation",
+            post(start_preparation),
+        )
+        .route("/admin/reservations/{id}/shipment", post(complete_shipment))
+        .route("/admin/reservations/{id}/delivery", post(record_delivery))
+        .route(
+            "/admin/reservations/{id}/delivery-failure",
+            post(record_delivery_failure),
+        )
+        .route("/admin/reservations/{id}/return", post(record_return))
+        .route(
+            "/admin/reservations/{id}/reshipment",
+            post(reship_reservation),
+        )
+        .route(
+            "/admin/reservations/{id}/cancellation",
+            get(preview_cancellation).post(cancel_reservation),
+        )
+        .route("/admin/users/{id}/suspension", post(suspend_account))
+        .route("/admin/users/{id}/reactivation", post(reactivate_account))
+        .route("/admin/products", post(create_product))
+        .route("/admin/products/{id}", put(update_product))
+        .route(
+            "/admin/products/{id}/stock",
+            get(get_product_stock).put(update_product_stock),
+        )
+}
+
+/// 設定に応じた CORS レイヤー (許可オリジン未指定の場合はすべて許可する)
+fn cors_layer(config: &AppConfig) -> CorsLayer {
+    if config.cors_allowed_origins.is_empty() {
+        return CorsLayer::very_permissive();
+    }
+    let origins: Vec<HeaderValue> = config
+        .cors_allowed_origins
+        .iter()
+        .filter_map(|origin| match origin.parse() {
+            Ok(value) => Some(value),
+            Err(_) => {
+                tracing::warn!("Ignoring invalid CORS origin: {}", origin);
+                None
+            }
+        })
+        .collect();
+    CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods(Any)
+        .allow_headers(Any)
+}
@@ -2,42 +2,25 @@
 // use std::sync::Arc;
 // use std::net::TcpListener; // tokio を使うため不要
 use anyhow::Result;
-use axum::{routing::get, Router};
 use dotenvy::dotenv;
 use sqlx::postgres::PgPoolOptions;
 use std::{env, net::SocketAddr, sync::Arc};
-use tower_http::cors::CorsLayer;
-use tower_http::trace::{DefaultMakeSpan, TraceLayer};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-use utoipa::OpenApi;
-use utoipa_swagger_ui::SwaggerUi;
 
 // クレートから必要なモジュールや型をインポート (修正)
 use ddd_sample_jp::{
-    application::プレゼント予約サービス, infrastructure::PgRepository,
-    routes::health_check::health_check,
+    application::{
+        プレゼント予約サービス, ユーザーアカウントサービス, 商品カタログサービス, 届け先サービス,
+    },
+    build_app,
+    config::AppConfig,
+    infrastructure::{
+        InMemory郵便番号辞書, PgRepository, PgユーザーアカウントRepository, Pg商品Repository,
+        Pg届け先Repository, Tracingイベント発行者,
+    },
+    AppState,
 };
 
-// --- OpenAPI ドキュメント定義 ---
-#[derive(OpenApi)]
-#[openapi(
-    paths(
-        ddd_sample_jp::routes::health_check::health_check
-    ),
-    components(
-        schemas(
-            // TODO: スキーマを追加
-        )
-    ),
-    tags(
-        (name = "Health", description = "Health check endpoint")
-    ),
-    servers(
-        (url = "http://localhost:8080/api", description = "Local development server")
-    ),
-)]
-struct ApiDoc;
-
 // --- Main / Presentation Layer ---
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -62,21 +45,56 @@ async fn main() -> Result<()> {
 
     // --- 依存関係の構築 (DI) --- (PgRepository を使用)
     let repository = Arc::new(PgRepository::new(pool.clone()));
-    let reservation_service = Arc::new(プレゼント予約サービス::new(repository));
-
-    // --- OpenAPI ドキュメント生成 ---
-    let openapi = ApiDoc::openapi();
+    let recipient_repository = Arc::new(Pg届け先Repository::new(pool.clone()));
+    let account_repository = Arc::new(PgユーザーアカウントRepository::new(pool.clone()));
+    let product_repository = Arc::new(Pg商品Repository::new(pool.clone()));
+    let config = AppConfig::from_env();
+    let reservation_service = Arc::new(
+        プレゼント予約サービス::new(repository.clone())
+            .with_idempotency_store(repository.clone())
+            .with_recipients(recipient_repository.clone())
+            .with_accounts(account_repository.clone())
+            .with_event_publisher(Arc::new(Tracingイベント発行者))
+            .with_products(product_repository.clone())
+            .with_shipping_lead_time(config.shipping_lead_time())
+            .with_cancellation_policy(config.cancellation_policy),
+    );
 
-    // --- ルーターの設定 --- (State を修正)
-    let app = Router::new()
-        .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", openapi.clone()))
-        .route("/api/health", get(health_check))
-        .layer(
-            TraceLayer::new_for_http()
-                .make_span_with(DefaultMakeSpan::default().include_headers(true)),
-        )
-        .layer(CorsLayer::very_permissive())
-        .with_state(reservation_service);
+    // --- ルーターの設定 (ルート・Swagger UI・各種レイヤーは build_app で組み立てる) ---
+    let mut recipient_service =
+        届け先サービス::new(recipient_repository, repository.clone());
+    match &config.postal_code_data_path {
+        // 郵便番号データは起動時にメモリへ読み込む (実行時にネットワークへはアクセスしない)
+        Some(path) => {
+            let postal_codes = InMemory郵便番号辞書::from_ken_all_file(path)
+                .expect("Failed to load postal code data.");
+            tracing::info!(
+                "Loaded {} postal codes from {}",
+                postal_codes.件数(),
+                path.display()
+            );
+            recipient_service = recipient_service.with_postal_codes(Arc::new(postal_codes));
+        }
+        None => {
+            tracing::warn!("POSTAL_CODE_DATA_PATH is not set; postal code lookup is disabled")
+        }
+    }
+    let recipient_service = Arc::new(recipient_service);
+    let app = build_app(
+        AppState {
+            reservations: reservation_service,
+            recipients: recipient_service,
+            accounts: Arc::new(ユーザーアカウントサービス::new(
+                account_repository,
+            )),
+            // 商品の在庫は予約と同じトランザクションで引き当てるため、予約のリポジトリが管理する
+            products: Arc::new(商品カタログサービス::new(
+                product_repository,
+                repository,
+            )),
+        },
+        &config,
+    );
 
     // --- サーバーの起動 ---
     let addr_str = env::var("LISTEN_ADDR").unwrap_or_else(|_| "0.0.0.0:3000".to_string());
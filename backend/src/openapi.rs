@@ -0,0 +1,127 @@
+use utoipa::openapi::server::ServerBuilder;
+use utoipa::OpenApi;
+
+use crate::config::{AppConfig, CURRENT_API_VERSION};
+
+// --- OpenAPI ドキュメント定義 ---
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        crate::routes::health_check::health_check,
+        crate::routes::health_check::liveness,
+        crate::routes::health_check::readiness,
+        crate::routes::reservations::create_reservation,
+        crate::routes::reservations::get_reservation,
+        crate::routes::reservations::update_reservation,
+        crate::routes::reservations::list_my_reservations,
+        crate::routes::reservations::preview_my_cancellation,
+        crate::routes::recipients::create_recipient,
+        crate::routes::recipients::list_my_recipients,
+        crate::routes::recipients::get_recipient,
+        crate::routes::recipients::update_recipient,
+        crate::routes::recipients::delete_recipient,
+        crate::routes::postal_codes::lookup_postal_code,
+        crate::routes::products::search_products,
+        crate::routes::products::get_product,
+        crate::routes::accounts::register_my_account,
+        crate::routes::accounts::get_my_account,
+        crate::routes::accounts::update_my_profile,
+        crate::routes::admin_reservations::search_reservations,
+        crate::routes::admin_reservations::start_preparation,
+        crate::routes::admin_reservations::complete_shipment,
+        crate::routes::admin_reservations::record_delivery,
+        crate::routes::admin_reservations::record_delivery_failure,
+        crate::routes::admin_reservations::record_return,
+        crate::routes::admin_reservations::reship_reservation,
+        crate::routes::admin_reservations::preview_cancellation,
+        crate::routes::admin_reservations::cancel_reservation,
+        crate::routes::admin_accounts::suspend_account,
+        crate::routes::admin_accounts::reactivate_account,
+        crate::routes::admin_products::create_product,
+        crate::routes::admin_products::update_product,
+        crate::routes::admin_products::get_product_stock,
+        crate::routes::admin_products::update_product_stock
+    ),
+    components(
+        schemas(
+            crate::routes::reservations::CreateReservationRequest,
+            crate::routes::reservations::UpdateReservationRequest,
+            crate::routes::reservations::CreateReservationResponse,
+            crate::routes::reservations::WrappingType,
+            crate::routes::reservations::DeliveryTimeSlot,
+            crate::routes::reservations::DesiredDelivery,
+            crate::routes::reservations::ForwardingGift,
+            crate::routes::reservations::GiftType,
+            crate::routes::reservations::TaxCategory,
+            crate::routes::reservations::ShippingCarrier,
+            crate::routes::reservations::DeliveryFailureReason,
+            crate::routes::reservations::ChargeItem,
+            crate::routes::reservations::ChargeLine,
+            crate::routes::reservations::ChargeBreakdown,
+            crate::routes::reservations::ReservationBase,
+            crate::routes::reservations::ReceivedReservation,
+            crate::routes::reservations::PreparingReservation,
+            crate::routes::reservations::ShippedReservation,
+            crate::routes::reservations::DeliveredReservation,
+            crate::routes::reservations::DeliveryFailedReservation,
+            crate::routes::reservations::ReturnedReservation,
+            crate::routes::reservations::CancelledReservation,
+            crate::routes::reservations::ReservationResponse,
+            crate::routes::reservations::ReservationStatus,
+            crate::routes::reservations::ReservationSummary,
+            crate::routes::reservations::ReservationPage,
+            crate::routes::reservations::CancellationPreview,
+            crate::routes::recipients::RecipientRequest,
+            crate::routes::recipients::RecipientResponse,
+            crate::routes::postal_codes::AddressCandidate,
+            crate::routes::postal_codes::PostalCodeLookupResponse,
+            crate::routes::products::ProductResponse,
+            crate::routes::products::ProductSearchResult,
+            crate::routes::accounts::UserRole,
+            crate::routes::accounts::AccountStatus,
+            crate::routes::accounts::ProfileRequest,
+            crate::routes::accounts::RegisterAccountRequest,
+            crate::routes::accounts::AccountResponse,
+            crate::routes::admin_reservations::StartPreparationRequest,
+            crate::routes::admin_reservations::CompleteShipmentRequest,
+            crate::routes::admin_reservations::RecordDeliveryRequest,
+            crate::routes::admin_reservations::RecordDeliveryFailureRequest,
+            crate::routes::admin_reservations::CancelReservationRequest,
+            crate::routes::admin_reservations::ReservationSort,
+            crate::routes::admin_reservations::ReservationSearchResult,
+            crate::routes::admin_products::ProductRequest,
+            crate::routes::admin_products::UpdateProductRequest,
+            crate::routes::admin_products::StockRequest,
+            crate::routes::admin_products::StockResponse,
+            crate::routes::health_check::HealthStatus,
+            crate::routes::health_check::LivenessResponse,
+            crate::routes::health_check::ReadinessError,
+            crate::routes::health_check::DatabaseHealth,
+            crate::routes::health_check::ReadinessComponents,
+            crate::routes::health_check::ReadinessResponse,
+            crate::routes::error::ProblemDetails
+        )
+    ),
+    tags(
+        (name = "Health", description = "Health check endpoints (liveness / readiness)"),
+        (name = "Reservations", description = "プレゼント予約 endpoints"),
+        (name = "Recipients", description = "届け先 endpoints"),
+        (name = "PostalCodes", description = "郵便番号による住所検索 endpoints"),
+        (name = "Accounts", description = "ユーザーアカウント endpoints"),
+        (name = "Products", description = "商品カタログ endpoints"),
+        (name = "Admin", description = "管理者向け endpoints")
+    ),
+)]
+pub struct ApiDoc;
+
+impl ApiDoc {
+    /// servers を設定から組み立てた OpenAPI ドキュメントを生成する
+    pub fn with_servers(config: &AppConfig) -> utoipa::openapi::OpenApi {
+        let mut doc = Self::openapi();
+        doc.servers = Some(vec![ServerBuilder::new()
+            .url(config.api_base_url())
+            .description(Some(format!("API {}", CURRENT_API_VERSION)))
+            .build()]);
+        doc
+    }
+}
@@ -0,0 +1,218 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::{ApplicationError, ユーザーアカウントサービス};
+use crate::domain::{
+    DomainError, アカウント状態, プロフィール, メールアドレス, ユーザーアカウント, ユーザーロール,
+    差出人名, 表示名, 認証サブジェクト,
+};
+use crate::routes::current_user::CurrentUser;
+use crate::routes::error::ProblemDetails;
+
+// --- DTO ---
+
+/// ユーザーの役割 (API 表現)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum UserRole {
+    顧客,
+    管理者,
+}
+
+impl From<ユーザーロール> for UserRole {
+    fn from(value: ユーザーロール) -> Self {
+        match value {
+            ユーザーロール::顧客 => UserRole::顧客,
+            ユーザーロール::管理者 => UserRole::管理者,
+        }
+    }
+}
+
+/// アカウントの状態 (API 表現)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum AccountStatus {
+    有効,
+    /// 停止中のアカウントは予約できない
+    停止,
+}
+
+impl From<アカウント状態> for AccountStatus {
+    fn from(value: アカウント状態) -> Self {
+        match value {
+            アカウント状態::有効 => AccountStatus::有効,
+            アカウント状態::停止 => AccountStatus::停止,
+        }
+    }
+}
+
+/// プロフィールの変更リクエスト (すべての項目を指定する)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProfileRequest {
+    /// メールアドレス
+    #[schema(example = "hanako@example.com")]
+    pub email: String,
+    /// 表示名 (50 文字以内)
+    #[schema(example = "はなこ")]
+    pub display_name: String,
+    /// 既定の差出人名 (50 文字以内。空文字は未指定とみなす)
+    #[serde(default)]
+    #[schema(example = "山田 花子")]
+    pub default_sender_name: Option<String>,
+}
+
+impl ProfileRequest {
+    fn to_profile(&self) -> Result<プロフィール, DomainError> {
+        Ok(プロフィール {
+            メールアドレス: メールアドレス::new(&self.email)?,
+            表示名: 表示名::new(&self.display_name)?,
+            既定の差出人名: self
+                .default_sender_name
+                .as_deref()
+                .filter(|n| !n.trim().is_empty())
+                .map(差出人名::new)
+                .transpose()?,
+        })
+    }
+}
+
+/// アカウント登録リクエスト
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RegisterAccountRequest {
+    /// 認証基盤が発行した利用者の識別子 (OpenID Connect の sub)
+    #[schema(example = "auth0|0123456789abcdef")]
+    pub auth_subject: String,
+    #[serde(flatten)]
+    pub profile: ProfileRequest,
+}
+
+/// ユーザーアカウント
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AccountResponse {
+    /// ユーザーID
+    pub id: Uuid,
+    /// 認証基盤が発行した利用者の識別子
+    pub auth_subject: String,
+    /// メールアドレス
+    pub email: String,
+    /// 表示名
+    pub display_name: String,
+    /// 既定の差出人名
+    pub default_sender_name: Option<String>,
+    /// ロール
+    pub role: UserRole,
+    /// アカウントの状態
+    pub status: AccountStatus,
+}
+
+impl From<&ユーザーアカウント> for AccountResponse {
+    fn from(アカウント: &ユーザーアカウント) -> Self {
+        let プロフィール = &アカウント.プロフィール;
+        Self {
+            id: *アカウント.id.as_uuid(),
+            auth_subject: アカウント.認証サブジェクト.value().to_string(),
+            email: プロフィール.メールアドレス.value().to_string(),
+            display_name: プロフィール.表示名.value().to_string(),
+            default_sender_name: プロフィール
+                .既定の差出人名
+                .as_ref()
+                .map(|n| n.value().to_string()),
+            role: アカウント.ロール.into(),
+            status: アカウント.状態.into(),
+        }
+    }
+}
+
+// --- ハンドラ ---
+
+#[utoipa::path(
+    post,
+    path = "/me",
+    tag = "Accounts",
+    params(("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID")),
+    request_body = RegisterAccountRequest,
+    responses(
+        (status = 201, description = "Account registered as an active customer", body = AccountResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Account already exists or the auth subject is used by another account",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Auth subject, email, display name or sender name is invalid",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the account",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /me リクエストに対するハンドラ
+pub async fn register_my_account(
+    State(account_service): State<Arc<ユーザーアカウントサービス>>,
+    CurrentUser(ユーザーid): CurrentUser,
+    Json(request): Json<RegisterAccountRequest>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let サブジェクト = 認証サブジェクト::new(&request.auth_subject)?;
+    let アカウント = account_service
+        .アカウントを登録する(ユーザーid, サブジェクト, request.profile.to_profile()?)
+        .await?;
+    Ok((
+        StatusCode::CREATED,
+        Json(AccountResponse::from(&アカウント)),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/me",
+    tag = "Accounts",
+    params(("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID")),
+    responses(
+        (status = 200, description = "The current user's account", body = AccountResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Account is not registered",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to load the account",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// GET /me リクエストに対するハンドラ
+pub async fn get_my_account(
+    State(account_service): State<Arc<ユーザーアカウントサービス>>,
+    CurrentUser(ユーザーid): CurrentUser,
+) -> Result<Json<AccountResponse>, ApplicationError> {
+    let アカウント = account_service.アカウント取得(&ユーザーid).await?;
+    Ok(Json(AccountResponse::from(&アカウント)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/me",
+    tag = "Accounts",
+    params(("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID")),
+    request_body = ProfileRequest,
+    responses(
+        (status = 200, description = "Profile updated", body = AccountResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Account is not registered",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Email, display name or sender name is invalid",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the account",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// PUT /me リクエストに対するハンドラ
+pub async fn update_my_profile(
+    State(account_service): State<Arc<ユーザーアカウントサービス>>,
+    CurrentUser(ユーザーid): CurrentUser,
+    Json(request): Json<ProfileRequest>,
+) -> Result<Json<AccountResponse>, ApplicationError> {
+    let アカウント = account_service
+        .プロフィールを変更する(&ユーザーid, request.to_profile()?)
+        .await?;
+    Ok(Json(AccountResponse::from(&アカウント)))
+}
@@ -0,0 +1,76 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::{ApplicationError, ユーザーアカウントサービス};
+use crate::domain::ユーザーID;
+use crate::routes::accounts::AccountResponse;
+use crate::routes::current_user::AdminUser;
+use crate::routes::error::ProblemDetails;
+
+// --- ハンドラ ---
+
+#[utoipa::path(
+    post,
+    path = "/admin/users/{id}/suspension",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "ユーザーID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID")
+    ),
+    responses(
+        (status = 200, description = "Account suspended (new reservations are refused)", body = AccountResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Account not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the account",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /admin/users/{id}/suspension リクエストに対するハンドラ
+pub async fn suspend_account(
+    State(account_service): State<Arc<ユーザーアカウントサービス>>,
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<AccountResponse>, ApplicationError> {
+    let アカウント = account_service
+        .アカウントを停止する(&ユーザーID::from_uuid(id))
+        .await?;
+    Ok(Json(AccountResponse::from(&アカウント)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/users/{id}/reactivation",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "ユーザーID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID")
+    ),
+    responses(
+        (status = 200, description = "Account reactivated", body = AccountResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Account not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the account",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /admin/users/{id}/reactivation リクエストに対するハンドラ
+pub async fn reactivate_account(
+    State(account_service): State<Arc<ユーザーアカウントサービス>>,
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<AccountResponse>, ApplicationError> {
+    let アカウント = account_service
+        .アカウントを再開する(&ユーザーID::from_uuid(id))
+        .await?;
+    Ok(Json(AccountResponse::from(&アカウント)))
+}
@@ -0,0 +1,238 @@
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::{ApplicationError, 商品カタログサービス};
+use crate::domain::{
+    商品ID, 商品価格, 商品内容, 商品名, 商品在庫, 商品説明, 画像キー
+};
+use crate::routes::current_user::AdminUser;
+use crate::routes::error::ProblemDetails;
+use crate::routes::products::{catalog_error_response, ProductResponse};
+use crate::routes::reservations::TaxCategory;
+
+// --- DTO ---
+
+/// 商品の登録・変更リクエスト (すべての項目を指定する)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProductRequest {
+    /// 商品名 (100 文字以内)
+    #[schema(example = "バラの花束")]
+    pub name: String,
+    /// 商品説明 (2000 文字以内)
+    #[serde(default)]
+    pub description: String,
+    /// 税込価格 (円、10,000,000 円以下)
+    #[schema(example = 5500, minimum = 1, maximum = 10_000_000)]
+    pub price: u32,
+    /// 税区分
+    pub tax_category: TaxCategory,
+    /// 商品画像のキー (先頭がメイン画像)
+    #[serde(default)]
+    #[schema(example = json!(["products/rose-bouquet/main.jpg"]))]
+    pub image_keys: Vec<String>,
+}
+
+impl ProductRequest {
+    fn to_content(&self) -> Result<商品内容, ApplicationError> {
+        Ok(商品内容 {
+            商品名: 商品名::new(&self.name)?,
+            説明: 商品説明::new(&self.description)?,
+            価格: 商品価格::new(self.price, self.tax_category.into())?,
+            画像キー: self
+                .image_keys
+                .iter()
+                .map(|key| 画像キー::new(key))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// 商品の変更リクエスト
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateProductRequest {
+    #[serde(flatten)]
+    pub product: ProductRequest,
+    /// 販売中か (false にすると新しい予約では選べなくなる)
+    pub on_sale: bool,
+}
+
+/// 在庫数の変更リクエスト
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StockRequest {
+    /// 倉庫にある数 (引き当て済みの数より少なくはできない)
+    #[schema(example = 10)]
+    pub on_hand: u32,
+}
+
+/// 商品の在庫
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StockResponse {
+    /// 商品ID
+    pub product_id: Uuid,
+    /// 倉庫にある数
+    pub on_hand: u32,
+    /// 受付済みで未発送の予約に引き当てた数
+    pub allocated: u32,
+    /// 新しい予約に引き当てられる数
+    pub available: u32,
+}
+
+impl From<&商品在庫> for StockResponse {
+    fn from(在庫: &商品在庫) -> Self {
+        Self {
+            product_id: *在庫.商品id.as_uuid(),
+            on_hand: 在庫.在庫数,
+            allocated: 在庫.引当数,
+            available: 在庫.引当可能数(),
+        }
+    }
+}
+
+// --- ハンドラ ---
+
+#[utoipa::path(
+    post,
+    path = "/admin/products",
+    tag = "Admin",
+    params(("x-user-id" = Uuid, Header, description = "操作した管理者のID")),
+    request_body = ProductRequest,
+    responses(
+        (status = 201, description = "Product registered as on sale", body = ProductResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Name, description, price or image key is invalid",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the product",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /admin/products リクエストに対するハンドラ
+pub async fn create_product(
+    State(catalog_service): State<Arc<商品カタログサービス>>,
+    _admin: AdminUser,
+    Json(request): Json<ProductRequest>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let 商品 = catalog_service
+        .商品を登録する(request.to_content()?)
+        .await?;
+    Ok((StatusCode::CREATED, Json(ProductResponse::from(&商品))))
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/products/{id}",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "商品ID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID")
+    ),
+    request_body = UpdateProductRequest,
+    responses(
+        (status = 200, description = "Product updated (accepted reservations keep their charges)", body = ProductResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Product not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Name, description, price or image key is invalid",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the product",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// PUT /admin/products/{id} リクエストに対するハンドラ
+pub async fn update_product(
+    State(catalog_service): State<Arc<商品カタログサービス>>,
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateProductRequest>,
+) -> Result<Json<ProductResponse>, Response> {
+    let 内容 = request
+        .product
+        .to_content()
+        .map_err(IntoResponse::into_response)?;
+    let 商品 = catalog_service
+        .商品を変更する(&商品ID::from_uuid(id), 内容, request.on_sale)
+        .await
+        .map_err(catalog_error_response)?;
+    Ok(Json(ProductResponse::from(&商品)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/products/{id}/stock",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "商品ID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID")
+    ),
+    responses(
+        (status = 200, description = "Stock of the product", body = StockResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Product not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to load the stock",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// GET /admin/products/{id}/stock リクエストに対するハンドラ
+pub async fn get_product_stock(
+    State(catalog_service): State<Arc<商品カタログサービス>>,
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<StockResponse>, Response> {
+    let 在庫 = catalog_service
+        .在庫取得(&商品ID::from_uuid(id))
+        .await
+        .map_err(catalog_error_response)?;
+    Ok(Json(StockResponse::from(&在庫)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/products/{id}/stock",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "商品ID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID")
+    ),
+    request_body = StockRequest,
+    responses(
+        (status = 200, description = "Stock updated", body = StockResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Product not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "On-hand quantity is less than the allocated quantity",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the stock",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// PUT /admin/products/{id}/stock リクエストに対するハンドラ
+pub async fn update_product_stock(
+    State(catalog_service): State<Arc<商品カタログサービス>>,
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+    Json(request): Json<StockRequest>,
+) -> Result<Json<StockResponse>, Response> {
+    let 在庫 = catalog_service
+        .在庫数を変更する(&商品ID::from_uuid(id), request.on_hand)
+        .await
+        .map_err(catalog_error_response)?;
+    Ok(Json(StockResponse::from(&在庫)))
+}
@@ -0,0 +1,502 @@
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+use chrono_tz::Asia::Tokyo;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::{ApplicationError, プレゼント予約サービス};
+use crate::domain::{
+    ユーザーID, 予約ID, 予約検索の並び順, 予約検索条件, 届け先ID, 配送伝票番号
+};
+use crate::routes::current_user::AdminUser;
+use crate::routes::error::ProblemDetails;
+use crate::routes::reservations::{
+    CancellationPreview, DeliveryFailureReason, ReservationResponse, ReservationStatus,
+    ReservationSummary, ShippingCarrier, WrappingType, DEFAULT_PAGE_SIZE,
+};
+
+// --- DTO ---
+
+/// 発送準備開始リクエスト
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StartPreparationRequest {
+    /// 梱包担当者ID
+    pub staff_id: Uuid,
+}
+
+/// 発送完了リクエスト
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CompleteShipmentRequest {
+    /// 配送業者
+    pub carrier: ShippingCarrier,
+    /// 配送伝票番号 (ハイフン・全角数字も可)
+    pub shipping_slip_number: String,
+}
+
+/// 配送完了記録リクエスト
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecordDeliveryRequest {
+    /// 配送完了日時 (タイムゾーン付き)
+    pub delivered_at: DateTime<FixedOffset>,
+}
+
+/// 配送失敗記録リクエスト
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecordDeliveryFailureRequest {
+    /// 配送失敗理由
+    pub reason: DeliveryFailureReason,
+    /// 配送失敗日時 (タイムゾーン付き)
+    pub failed_at: DateTime<FixedOffset>,
+}
+
+/// 予約キャンセルリクエスト
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CancelReservationRequest {
+    /// キャンセル理由
+    pub reason: Option<String>,
+}
+
+/// 予約検索の並び順 (API 表現)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReservationSort {
+    #[default]
+    AnniversaryDateAsc,
+    AnniversaryDateDesc,
+    CreatedAtAsc,
+    CreatedAtDesc,
+}
+
+impl From<ReservationSort> for 予約検索の並び順 {
+    fn from(sort: ReservationSort) -> Self {
+        match sort {
+            ReservationSort::AnniversaryDateAsc => 予約検索の並び順::記念日昇順,
+            ReservationSort::AnniversaryDateDesc => 予約検索の並び順::記念日降順,
+            ReservationSort::CreatedAtAsc => 予約検索の並び順::作成日時昇順,
+            ReservationSort::CreatedAtDesc => 予約検索の並び順::作成日時降順,
+        }
+    }
+}
+
+/// 予約検索のクエリパラメータ
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchReservationsQuery {
+    /// ステータス (カンマ区切りで複数指定可。例: Received,Preparing)
+    pub status: Option<String>,
+    /// 記念日の開始日 (この日を含む)
+    pub anniversary_from: Option<NaiveDate>,
+    /// 記念日の終了日 (この日を含む)
+    pub anniversary_to: Option<NaiveDate>,
+    /// ラッピング種類
+    pub wrapping_type: Option<WrappingType>,
+    /// 依頼者ID
+    pub requester_id: Option<Uuid>,
+    /// 届け先ID
+    pub recipient_id: Option<Uuid>,
+    /// 作成日時の開始 (この時刻を含む)
+    pub created_from: Option<DateTime<FixedOffset>>,
+    /// 作成日時の終了 (この時刻を含む)
+    pub created_to: Option<DateTime<FixedOffset>>,
+    /// 並び順 (既定 anniversary_date_asc)
+    pub sort: Option<ReservationSort>,
+    /// 先頭から読み飛ばす件数 (既定 0)
+    pub offset: Option<usize>,
+    /// 取得件数 (既定 20, 最大 100)
+    pub limit: Option<usize>,
+}
+
+impl SearchReservationsQuery {
+    /// クエリパラメータをドメインの検索条件に変換する
+    fn to_criteria(&self) -> Result<予約検索条件, ApplicationError> {
+        let ステータス = match self.status.as_deref() {
+            Some(list) => list
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| s.parse::<ReservationStatus>().map(Into::into))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|reason| ApplicationError::InvalidInput {
+                    field: "status".to_string(),
+                    reason,
+                })?,
+            None => Vec::new(),
+        };
+        Ok(予約検索条件 {
+            ステータス,
+            記念日_開始: self.anniversary_from,
+            記念日_終了: self.anniversary_to,
+            ラッピング: self.wrapping_type.map(Into::into),
+            依頼者id: self.requester_id.map(ユーザーID::from_uuid),
+            届け先id: self.recipient_id.map(届け先ID::from_uuid),
+            作成日時_開始: self.created_from.map(|dt| dt.with_timezone(&Utc)),
+            作成日時_終了: self.created_to.map(|dt| dt.with_timezone(&Utc)),
+            並び順: self.sort.unwrap_or_default().into(),
+        })
+    }
+}
+
+/// 予約検索の結果
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReservationSearchResult {
+    pub items: Vec<ReservationSummary>,
+    /// 条件に一致した件数 (ページングに関係しない)
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+// --- ハンドラ ---
+
+#[utoipa::path(
+    get,
+    path = "/admin/reservations",
+    tag = "Admin",
+    params(
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID"),
+        SearchReservationsQuery
+    ),
+    responses(
+        (status = 200, description = "Reservations matching the criteria", body = ReservationSearchResult),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid search criteria",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to search reservations",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// GET /admin/reservations リクエストに対するハンドラ
+pub async fn search_reservations(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    _admin: AdminUser,
+    Query(query): Query<SearchReservationsQuery>,
+) -> Result<Json<ReservationSearchResult>, ApplicationError> {
+    let criteria = query.to_criteria()?;
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    let result = reservation_service
+        .予約検索(&criteria, offset, limit)
+        .await?;
+    Ok(Json(ReservationSearchResult {
+        items: result
+            .予約リスト
+            .iter()
+            .map(ReservationSummary::from)
+            .collect(),
+        total: result.総件数,
+        offset,
+        limit,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/reservations/{id}/preparation",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "予約ID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID")
+    ),
+    request_body = StartPreparationRequest,
+    responses(
+        (status = 200, description = "Preparation started", body = ReservationResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Reservation not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Transition not allowed from the current state",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the reservation",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /admin/reservations/{id}/preparation リクエストに対するハンドラ
+pub async fn start_preparation(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+    Json(request): Json<StartPreparationRequest>,
+) -> Result<Json<ReservationResponse>, ApplicationError> {
+    let state = reservation_service
+        .発送準備を開始する(
+            &予約ID::from_uuid(id),
+            ユーザーID::from_uuid(request.staff_id),
+        )
+        .await?;
+    Ok(Json(ReservationResponse::from(&state)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/reservations/{id}/shipment",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "予約ID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID (イベントの実行者として記録する)")
+    ),
+    request_body = CompleteShipmentRequest,
+    responses(
+        (status = 200, description = "Shipment completed", body = ReservationResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Reservation not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Transition not allowed from the current state",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the reservation",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /admin/reservations/{id}/shipment リクエストに対するハンドラ
+pub async fn complete_shipment(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    AdminUser(実行者): AdminUser,
+    Path(id): Path<Uuid>,
+    Json(request): Json<CompleteShipmentRequest>,
+) -> Result<Json<ReservationResponse>, ApplicationError> {
+    let state = reservation_service
+        .発送を完了する(
+            &予約ID::from_uuid(id),
+            配送伝票番号::new(request.carrier.into(), &request.shipping_slip_number)?,
+            Some(実行者),
+        )
+        .await?;
+    Ok(Json(ReservationResponse::from(&state)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/reservations/{id}/delivery",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "予約ID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID (イベントの実行者として記録する)")
+    ),
+    request_body = RecordDeliveryRequest,
+    responses(
+        (status = 200, description = "Delivery recorded", body = ReservationResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Reservation not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Transition not allowed from the current state",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the reservation",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /admin/reservations/{id}/delivery リクエストに対するハンドラ
+pub async fn record_delivery(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    AdminUser(実行者): AdminUser,
+    Path(id): Path<Uuid>,
+    Json(request): Json<RecordDeliveryRequest>,
+) -> Result<Json<ReservationResponse>, ApplicationError> {
+    let state = reservation_service
+        .配送完了を記録する(
+            &予約ID::from_uuid(id),
+            request.delivered_at.with_timezone(&Tokyo),
+            Some(実行者),
+        )
+        .await?;
+    Ok(Json(ReservationResponse::from(&state)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/reservations/{id}/delivery-failure",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "予約ID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID (イベントの実行者として記録する)")
+    ),
+    request_body = RecordDeliveryFailureRequest,
+    responses(
+        (status = 200, description = "Delivery failure recorded", body = ReservationResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Reservation not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Transition not allowed from the current state",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the reservation",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /admin/reservations/{id}/delivery-failure リクエストに対するハンドラ
+pub async fn record_delivery_failure(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    AdminUser(実行者): AdminUser,
+    Path(id): Path<Uuid>,
+    Json(request): Json<RecordDeliveryFailureRequest>,
+) -> Result<Json<ReservationResponse>, ApplicationError> {
+    let state = reservation_service
+        .配送失敗を記録する(
+            &予約ID::from_uuid(id),
+            request.reason.into(),
+            request.failed_at.with_timezone(&Tokyo),
+            Some(実行者),
+        )
+        .await?;
+    Ok(Json(ReservationResponse::from(&state)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/reservations/{id}/return",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "予約ID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID (イベントの実行者として記録する)")
+    ),
+    responses(
+        (status = 200, description = "Return to the warehouse recorded", body = ReservationResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Reservation not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Transition not allowed from the current state",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the reservation",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /admin/reservations/{id}/return リクエストに対するハンドラ
+pub async fn record_return(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    AdminUser(実行者): AdminUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReservationResponse>, ApplicationError> {
+    // 返送日時はサーバー側の受付時刻とする
+    let state = reservation_service
+        .返送を記録する(&予約ID::from_uuid(id), Some(実行者))
+        .await?;
+    Ok(Json(ReservationResponse::from(&state)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/reservations/{id}/reshipment",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "予約ID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID (イベントの実行者として記録する)")
+    ),
+    request_body = CompleteShipmentRequest,
+    responses(
+        (status = 200, description = "Reservation shipped again", body = ReservationResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Reservation not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Transition not allowed from the current state",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid tracking number, or the same number as the returned parcel",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the reservation",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /admin/reservations/{id}/reshipment リクエストに対するハンドラ
+pub async fn reship_reservation(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    AdminUser(実行者): AdminUser,
+    Path(id): Path<Uuid>,
+    Json(request): Json<CompleteShipmentRequest>,
+) -> Result<Json<ReservationResponse>, ApplicationError> {
+    let state = reservation_service
+        .再発送する(
+            &予約ID::from_uuid(id),
+            配送伝票番号::new(request.carrier.into(), &request.shipping_slip_number)?,
+            Some(実行者),
+        )
+        .await?;
+    Ok(Json(ReservationResponse::from(&state)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/reservations/{id}/cancellation",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "予約ID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID")
+    ),
+    responses(
+        (status = 200, description = "Cancellation preview (the reservation is not changed)", body = CancellationPreview),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Reservation not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to load the reservation",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// GET /admin/reservations/{id}/cancellation リクエストに対するハンドラ
+pub async fn preview_cancellation(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    _admin: AdminUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CancellationPreview>, ApplicationError> {
+    let 判定 = reservation_service
+        .キャンセルを試算する(&予約ID::from_uuid(id))
+        .await?;
+    Ok(Json(判定.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/reservations/{id}/cancellation",
+    tag = "Admin",
+    params(
+        ("id" = Uuid, Path, description = "予約ID"),
+        ("x-user-id" = Uuid, Header, description = "操作した管理者のID (イベントの実行者として記録する)")
+    ),
+    request_body = CancelReservationRequest,
+    responses(
+        (status = 200, description = "Reservation cancelled", body = ReservationResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "User is not an active administrator",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Reservation not found",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Transition not allowed from the current state, or denied by the cancellation policy",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the reservation",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /admin/reservations/{id}/cancellation リクエストに対するハンドラ
+pub async fn cancel_reservation(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    AdminUser(実行者): AdminUser,
+    Path(id): Path<Uuid>,
+    Json(request): Json<CancelReservationRequest>,
+) -> Result<Json<ReservationResponse>, ApplicationError> {
+    // キャンセル日時はサーバー側の受付時刻とする
+    let state = reservation_service
+        .予約をキャンセルする(&予約ID::from_uuid(id), request.reason, Some(実行者))
+        .await?;
+    Ok(Json(ReservationResponse::from(&state)))
+}
@@ -0,0 +1,69 @@
+use axum::extract::{FromRef, FromRequestParts};
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::ユーザーアカウントサービス;
+use crate::domain::ユーザーID;
+use crate::routes::error::ProblemDetails;
+
+/// 認証済みユーザーを示すヘッダ
+///
+/// 認証基盤 (ADR 0009) の導入までは、前段が検証済みのユーザーIDをこのヘッダで渡す想定とする。
+pub const USER_ID_HEADER: &str = "x-user-id";
+
+/// リクエストを送ったユーザー
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CurrentUser(pub ユーザーID);
+
+impl<S> FromRequestParts<S> for CurrentUser
+where
+    S: Send + Sync,
+{
+    type Rejection = ProblemDetails;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let unauthorized = || {
+            ProblemDetails::new(
+                StatusCode::UNAUTHORIZED,
+                "UNAUTHENTICATED",
+                "ユーザーを特定できません".to_string(),
+                Some(USER_ID_HEADER.to_string()),
+            )
+        };
+        let value = parts.headers.get(USER_ID_HEADER).ok_or_else(unauthorized)?;
+        let id = value
+            .to_str()
+            .ok()
+            .and_then(|v| Uuid::parse_str(v).ok())
+            .ok_or_else(unauthorized)?;
+        Ok(CurrentUser(ユーザーID::from_uuid(id)))
+    }
+}
+
+/// 管理者としてリクエストを送ったユーザー
+///
+/// ヘッダがない場合は 401、有効な管理者のアカウントでない場合は 403 とする。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdminUser(pub ユーザーID);
+
+impl<S> FromRequestParts<S> for AdminUser
+where
+    Arc<ユーザーアカウントサービス>: FromRef<S>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let CurrentUser(id) = CurrentUser::from_request_parts(parts, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        Arc::<ユーザーアカウントサービス>::from_ref(state)
+            .管理者を確認する(&id)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        Ok(AdminUser(id))
+    }
+}
@@ -0,0 +1,56 @@
+use axum::extract::State;
+use axum::http::HeaderValue;
+use axum::response::Response;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+
+/// 非推奨の API に付与するヘッダーの内容
+///
+/// - `Deprecation` (RFC 9745): 非推奨になった日時。未指定の場合は "true"
+/// - `Sunset` (RFC 8594): 提供終了予定日時
+/// - `Link`: 移行先のバージョン (rel="successor-version")
+#[derive(Debug, Clone, Default)]
+pub struct DeprecationPolicy {
+    pub deprecated_since: Option<DateTime<Utc>>,
+    pub sunset: Option<DateTime<Utc>>,
+    /// 移行先のパス (例: /api/v1)
+    pub successor: Option<String>,
+}
+
+impl DeprecationPolicy {
+    fn deprecation_value(&self) -> String {
+        match self.deprecated_since {
+            Some(since) => format!("@{}", since.timestamp()),
+            None => "true".to_string(),
+        }
+    }
+}
+
+/// HTTP-date 形式 (例: Sun, 06 Nov 1994 08:49:37 GMT)
+fn http_date(dt: &DateTime<Utc>) -> String {
+    dt.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+/// レスポンスに非推奨を示すヘッダーを付与する (axum::middleware::map_response_with_state 用)
+pub async fn add_deprecation_headers(
+    State(policy): State<Arc<DeprecationPolicy>>,
+    mut response: Response,
+) -> Response {
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&policy.deprecation_value()) {
+        headers.insert("deprecation", value);
+    }
+    if let Some(value) = policy
+        .sunset
+        .as_ref()
+        .and_then(|sunset| HeaderValue::from_str(&http_date(sunset)).ok())
+    {
+        headers.insert("sunset", value);
+    }
+    if let Some(value) = policy.successor.as_ref().and_then(|successor| {
+        HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", successor)).ok()
+    }) {
+        headers.append(axum::http::header::LINK, value);
+    }
+    response
+}
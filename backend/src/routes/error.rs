@@ -0,0 +1,316 @@
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::application::ApplicationError;
+use crate::domain::DomainError;
+
+/// RFC 7807 形式のエラーレスポンス (application/problem+json)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProblemDetails {
+    /// 問題の種類を表す URI
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// 問題の概要
+    pub title: String,
+    /// HTTP ステータスコード
+    pub status: u16,
+    /// 問題の詳細 (ドメインエラーのメッセージ。データストアの障害では固定の文言)
+    pub detail: String,
+    /// 機械判読用のエラーコード
+    pub code: String,
+    /// 問題のあった項目 (特定できる場合のみ)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+}
+
+/// problem+json の Content-Type
+pub const PROBLEM_JSON_CONTENT_TYPE: &str = "application/problem+json";
+
+impl ProblemDetails {
+    pub(crate) fn new(
+        status: StatusCode,
+        code: &str,
+        detail: String,
+        field: Option<String>,
+    ) -> Self {
+        Self {
+            type_: format!("/problems/{}", code.to_lowercase().replace('_', "-")),
+            title: status.canonical_reason().unwrap_or("Error").to_string(),
+            status: status.as_u16(),
+            detail,
+            code: code.to_string(),
+            field,
+        }
+    }
+}
+
+impl IntoResponse for ProblemDetails {
+    fn into_response(self) -> Response {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        (
+            status,
+            [(header::CONTENT_TYPE, PROBLEM_JSON_CONTENT_TYPE)],
+            Json(self),
+        )
+            .into_response()
+    }
+}
+
+/// DomainError を (ステータス, エラーコード, 問題のあった項目) に対応付ける
+fn classify_domain_error(e: &DomainError) -> (StatusCode, &'static str, Option<String>) {
+    match e {
+        DomainError::予約NotFound(_) => (StatusCode::NOT_FOUND, "RESERVATION_NOT_FOUND", None),
+        DomainError::不正な状態遷移 { .. } => {
+            (StatusCode::CONFLICT, "INVALID_STATE_TRANSITION", None)
+        }
+        DomainError::キャンセル不可 { .. } => {
+            (StatusCode::CONFLICT, "CANCELLATION_NOT_ALLOWED", None)
+        }
+        DomainError::必須項目不足 { field } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "REQUIRED_FIELD_MISSING",
+            Some(field.clone()),
+        ),
+        DomainError::予約商品空エラー => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "RESERVATION_ITEMS_EMPTY",
+            Some("product_ids".to_string()),
+        ),
+        DomainError::商品NotFound(_) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "PRODUCT_NOT_FOUND",
+            Some("product_ids".to_string()),
+        ),
+        DomainError::商品販売終了(_) => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "PRODUCT_DISCONTINUED",
+            Some("product_ids".to_string()),
+        ),
+        DomainError::不正な商品名 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_PRODUCT_NAME",
+            Some("name".to_string()),
+        ),
+        DomainError::不正な商品説明 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_PRODUCT_DESCRIPTION",
+            Some("description".to_string()),
+        ),
+        DomainError::不正な画像キー { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_IMAGE_KEY",
+            Some("image_keys".to_string()),
+        ),
+        DomainError::不正な商品価格 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_PRICE",
+            Some("price".to_string()),
+        ),
+        DomainError::在庫不足(_) => (
+            StatusCode::CONFLICT,
+            "OUT_OF_STOCK",
+            Some("product_ids".to_string()),
+        ),
+        DomainError::在庫数不正 { .. } => (
+            StatusCode::CONFLICT,
+            "STOCK_BELOW_ALLOCATED",
+            Some("on_hand".to_string()),
+        ),
+        DomainError::不正な金額エラー { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_AMOUNT",
+            Some("total_amount".to_string()),
+        ),
+        DomainError::記念日リードタイム不足 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "ANNIVERSARY_TOO_EARLY",
+            Some("anniversary_date".to_string()),
+        ),
+        DomainError::配送希望日範囲外 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "DELIVERY_DATE_OUT_OF_RANGE",
+            Some("desired_delivery.date".to_string()),
+        ),
+        DomainError::不正なメッセージ内容 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_MESSAGE",
+            Some("message".to_string()),
+        ),
+        DomainError::冪等性キー確保切れ => (
+            StatusCode::CONFLICT,
+            "IDEMPOTENCY_KEY_IN_PROGRESS",
+            Some("Idempotency-Key".to_string()),
+        ),
+        DomainError::見積金額不一致 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "ESTIMATE_MISMATCH",
+            Some("total_amount".to_string()),
+        ),
+        DomainError::料金上限超過 => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "CHARGE_LIMIT_EXCEEDED",
+            Some("product_ids".to_string()),
+        ),
+        DomainError::不正な発送代行プレゼント情報 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_GIFT_DESCRIPTION",
+            Some("forwarding_gift.description".to_string()),
+        ),
+        DomainError::倉庫到着予定日不正 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_WAREHOUSE_ARRIVAL_DATE",
+            Some("forwarding_gift.warehouse_arrival_date".to_string()),
+        ),
+        DomainError::不正な配送伝票番号 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_TRACKING_NUMBER",
+            Some("shipping_slip_number".to_string()),
+        ),
+        DomainError::届け先NotFound(_) => (
+            StatusCode::NOT_FOUND,
+            "RECIPIENT_NOT_FOUND",
+            Some("recipient_id".to_string()),
+        ),
+        DomainError::届け先使用中(_) => (StatusCode::CONFLICT, "RECIPIENT_IN_USE", None),
+        DomainError::不正な氏名 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_NAME",
+            Some("name".to_string()),
+        ),
+        DomainError::不正なフリガナ { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_NAME_KANA",
+            Some("name_kana".to_string()),
+        ),
+        DomainError::不正な郵便番号 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_POSTAL_CODE",
+            Some("postal_code".to_string()),
+        ),
+        DomainError::不正な都道府県 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_PREFECTURE",
+            Some("prefecture".to_string()),
+        ),
+        DomainError::不正な住所 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_ADDRESS",
+            Some("address".to_string()),
+        ),
+        DomainError::不正な建物名 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_BUILDING",
+            Some("building".to_string()),
+        ),
+        DomainError::不正な電話番号 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_PHONE_NUMBER",
+            Some("phone_number".to_string()),
+        ),
+        DomainError::住所不一致 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "ADDRESS_MISMATCH",
+            Some("address".to_string()),
+        ),
+        DomainError::郵便番号NotFound(_) => (
+            StatusCode::NOT_FOUND,
+            "POSTAL_CODE_NOT_FOUND",
+            Some("postal_code".to_string()),
+        ),
+        DomainError::ユーザーアカウントNotFound(_) => {
+            (StatusCode::NOT_FOUND, "ACCOUNT_NOT_FOUND", None)
+        }
+        DomainError::ユーザーアカウント登録済み(_) => {
+            (StatusCode::CONFLICT, "ACCOUNT_ALREADY_EXISTS", None)
+        }
+        DomainError::認証サブジェクト使用中 => (
+            StatusCode::CONFLICT,
+            "AUTH_SUBJECT_IN_USE",
+            Some("auth_subject".to_string()),
+        ),
+        DomainError::アカウント停止中(_) => {
+            (StatusCode::FORBIDDEN, "ACCOUNT_SUSPENDED", None)
+        }
+        DomainError::アカウント未登録(_) => {
+            (StatusCode::FORBIDDEN, "ACCOUNT_NOT_REGISTERED", None)
+        }
+        DomainError::管理者権限なし(_) => (StatusCode::FORBIDDEN, "ADMIN_REQUIRED", None),
+        DomainError::不正な認証サブジェクト { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_AUTH_SUBJECT",
+            Some("auth_subject".to_string()),
+        ),
+        DomainError::不正なメールアドレス { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_EMAIL",
+            Some("email".to_string()),
+        ),
+        DomainError::不正な表示名 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_DISPLAY_NAME",
+            Some("display_name".to_string()),
+        ),
+        DomainError::不正な差出人名 { .. } => (
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_SENDER_NAME",
+            Some("default_sender_name".to_string()),
+        ),
+    }
+}
+
+impl From<&ApplicationError> for ProblemDetails {
+    fn from(e: &ApplicationError) -> Self {
+        match e {
+            ApplicationError::Domain(domain_error) => {
+                let (status, code, field) = classify_domain_error(domain_error);
+                ProblemDetails::new(status, code, domain_error.to_string(), field)
+            }
+            // ドライバのエラー文をそのまま返さない (詳細は into_response でログに出す)
+            ApplicationError::Repository(_) => ProblemDetails::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "REPOSITORY_UNAVAILABLE",
+                "一時的に処理できません。しばらくしてから再度お試しください".to_string(),
+                None,
+            ),
+            ApplicationError::InvalidInput { field, .. } => ProblemDetails::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "INVALID_INPUT",
+                e.to_string(),
+                Some(field.clone()),
+            ),
+            ApplicationError::IdempotencyKeyReused => ProblemDetails::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "IDEMPOTENCY_KEY_REUSED",
+                e.to_string(),
+                Some("Idempotency-Key".to_string()),
+            ),
+            ApplicationError::IdempotencyKeyInProgress => ProblemDetails::new(
+                StatusCode::CONFLICT,
+                "IDEMPOTENCY_KEY_IN_PROGRESS",
+                e.to_string(),
+                Some("Idempotency-Key".to_string()),
+            ),
+            ApplicationError::Unexpected(_) => ProblemDetails::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "UNEXPECTED_ERROR",
+                e.to_string(),
+                None,
+            ),
+        }
+    }
+}
+
+impl IntoResponse for ApplicationError {
+    fn into_response(self) -> Response {
+        let problem = ProblemDetails::from(&self);
+        if problem.status >= 500 {
+            tracing::error!("Request failed: {:?}", self);
+        } else {
+            tracing::debug!("Request rejected: {:?}", self);
+        }
+        problem.into_response()
+    }
+}
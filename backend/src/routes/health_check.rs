@@ -2,10 +2,14 @@ use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
 use axum::Json;
+use serde::{Deserialize, Serialize};
 use serde_json;
 use std::sync::Arc;
+use utoipa::ToSchema;
 
-use crate::application::プレゼント予約サービス;
+use crate::application::{
+    ComponentStatus, DatabaseReadiness, ReadinessFailure, ReadinessReport, プレゼント予約サービス,
+};
 // use crate::domain::core::プレゼント予約Repository; // 不要になったので削除
 
 // utoipa の path マクロを追加
@@ -28,14 +32,148 @@ pub async fn health_check(
     match reservation_service.check_health().await {
         Ok(_) => {
             tracing::debug!("Health check successful.");
-            (StatusCode::OK, Json(serde_json::json!({"status": "OK"})))
+            (StatusCode::OK, Json(serde_json::json!({"status": "OK"}))).into_response()
         }
         Err(e) => {
             tracing::error!("Health check failed: {:?}", e);
-            (
-                StatusCode::SERVICE_UNAVAILABLE,
-                Json(serde_json::json!({ "error": e.to_string() })),
-            )
+            e.into_response() // problem+json (503) で返す
         }
     }
 }
+
+// --- liveness / readiness ---
+
+/// コンポーネントの稼働状態 (API 表現)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+impl From<ComponentStatus> for HealthStatus {
+    fn from(status: ComponentStatus) -> Self {
+        match status {
+            ComponentStatus::Up => HealthStatus::Up,
+            ComponentStatus::Down => HealthStatus::Down,
+        }
+    }
+}
+
+/// liveness レスポンス
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LivenessResponse {
+    pub status: HealthStatus,
+}
+
+/// readiness 判定に失敗した理由 (API 表現)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ReadinessError {
+    Unavailable,
+    Timeout,
+}
+
+impl From<ReadinessFailure> for ReadinessError {
+    fn from(failure: ReadinessFailure) -> Self {
+        match failure {
+            ReadinessFailure::Unavailable => ReadinessError::Unavailable,
+            ReadinessFailure::Timeout => ReadinessError::Timeout,
+        }
+    }
+}
+
+/// データベースの readiness
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DatabaseHealth {
+    pub status: HealthStatus,
+    /// 疎通確認クエリの往復時間 (ミリ秒)
+    pub latency_ms: Option<f64>,
+    /// 適用済みの最新マイグレーションのバージョン
+    pub migration_version: Option<i64>,
+    /// 接続プールの接続数
+    pub pool_size: Option<u32>,
+    /// 接続プールのアイドル接続数
+    pub pool_idle: Option<u32>,
+    /// 失敗した理由の種類 (Down の場合のみ。詳細はサーバーのログに出す)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ReadinessError>,
+}
+
+impl From<&DatabaseReadiness> for DatabaseHealth {
+    fn from(readiness: &DatabaseReadiness) -> Self {
+        let details = readiness.details.as_ref();
+        Self {
+            status: readiness.status.into(),
+            latency_ms: details.map(|d| d.latency.as_secs_f64() * 1000.0),
+            migration_version: details.and_then(|d| d.migration_version),
+            pool_size: details.map(|d| d.pool_size),
+            pool_idle: details.map(|d| d.idle_connections),
+            error: readiness.error.map(ReadinessError::from),
+        }
+    }
+}
+
+/// 依存コンポーネントごとの readiness
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReadinessComponents {
+    pub database: DatabaseHealth,
+}
+
+/// readiness レスポンス
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReadinessResponse {
+    /// すべてのコンポーネントが UP の場合のみ UP
+    pub status: HealthStatus,
+    pub components: ReadinessComponents,
+}
+
+impl From<&ReadinessReport> for ReadinessResponse {
+    fn from(report: &ReadinessReport) -> Self {
+        Self {
+            status: report.status.into(),
+            components: ReadinessComponents {
+                database: DatabaseHealth::from(&report.database),
+            },
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    tag = "Health",
+    responses(
+        (status = 200, description = "Process is alive", body = LivenessResponse)
+    )
+)]
+// GET /health/live リクエストに対するハンドラ (依存コンポーネントを確認しない)
+pub async fn liveness() -> Json<LivenessResponse> {
+    Json(LivenessResponse {
+        status: HealthStatus::Up,
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "Health",
+    responses(
+        (status = 200, description = "Ready to serve traffic", body = ReadinessResponse),
+        (status = 503, description = "A component is not ready", body = ReadinessResponse)
+    )
+)]
+// GET /health/ready リクエストに対するハンドラ
+pub async fn readiness(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let report = reservation_service.check_readiness().await;
+    let status_code = match report.status {
+        ComponentStatus::Up => StatusCode::OK,
+        ComponentStatus::Down => {
+            tracing::warn!("Readiness check failed: {:?}", report);
+            StatusCode::SERVICE_UNAVAILABLE
+        }
+    };
+    (status_code, Json(ReadinessResponse::from(&report)))
+}
@@ -1 +1,12 @@
+pub mod accounts;
+pub mod admin_accounts;
+pub mod admin_products;
+pub mod admin_reservations;
+pub mod current_user;
+pub mod deprecation;
+pub mod error;
 pub mod health_check;
+pub mod postal_codes;
+pub mod products;
+pub mod recipients;
+pub mod reservations;
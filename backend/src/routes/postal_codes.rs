@@ -0,0 +1,78 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::application::{ApplicationError, 届け先サービス};
+use crate::domain::{DomainError, 住所候補, 郵便番号};
+use crate::routes::error::ProblemDetails;
+
+// --- DTO ---
+
+/// 郵便番号に対応する住所の候補
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AddressCandidate {
+    /// 都道府県
+    #[schema(example = "東京都")]
+    pub prefecture: String,
+    /// 市区町村
+    #[schema(example = "千代田区")]
+    pub city: String,
+    /// 町域 (郵便番号が市区町村内の特定の町域に対応しない場合は空文字)
+    #[schema(example = "千代田")]
+    pub town: String,
+}
+
+impl From<&住所候補> for AddressCandidate {
+    fn from(候補: &住所候補) -> Self {
+        Self {
+            prefecture: 候補.都道府県.名前(),
+            city: 候補.市区町村.clone(),
+            town: 候補.町域.clone(),
+        }
+    }
+}
+
+/// 郵便番号の検索結果
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PostalCodeLookupResponse {
+    /// 郵便番号 (ハイフン区切り)
+    #[schema(example = "100-0001")]
+    pub postal_code: String,
+    /// 住所の候補 (1 件以上)
+    pub candidates: Vec<AddressCandidate>,
+}
+
+// --- ハンドラ ---
+
+#[utoipa::path(
+    get,
+    path = "/postal-codes/{code}",
+    tag = "PostalCodes",
+    params(("code" = String, Path, description = "郵便番号 (7 桁。ハイフンは省略可)")),
+    responses(
+        (status = 200, description = "Address candidates for the postal code", body = PostalCodeLookupResponse),
+        (status = 404, description = "Postal code is not in the postal code data",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Postal code is not 7 digits",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Postal code data is not loaded",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// GET /postal-codes/{code} リクエストに対するハンドラ
+pub async fn lookup_postal_code(
+    State(recipient_service): State<Arc<届け先サービス>>,
+    Path(code): Path<String>,
+) -> Result<Json<PostalCodeLookupResponse>, ApplicationError> {
+    let 番号 = 郵便番号::new(&code)?;
+    let 候補 = recipient_service.住所候補を検索する(&番号).await?;
+    if 候補.is_empty() {
+        return Err(DomainError::郵便番号NotFound(番号.表示()).into());
+    }
+    Ok(Json(PostalCodeLookupResponse {
+        postal_code: 番号.表示(),
+        candidates: 候補.iter().map(AddressCandidate::from).collect(),
+    }))
+}
@@ -0,0 +1,187 @@
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::{ApplicationError, 商品カタログサービス};
+use crate::domain::{DomainError, 商品, 商品ID, 商品検索条件};
+use crate::routes::error::ProblemDetails;
+use crate::routes::reservations::{TaxCategory, DEFAULT_PAGE_SIZE};
+
+// --- DTO ---
+
+/// 商品
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProductResponse {
+    /// 商品ID
+    pub id: Uuid,
+    /// 商品名
+    #[schema(example = "バラの花束")]
+    pub name: String,
+    /// 商品説明
+    pub description: String,
+    /// 税込価格 (円)
+    #[schema(example = 5500)]
+    pub price: u32,
+    /// 税区分
+    pub tax_category: TaxCategory,
+    /// 商品画像のキー (先頭がメイン画像)
+    #[schema(example = json!(["products/rose-bouquet/main.jpg"]))]
+    pub image_keys: Vec<String>,
+    /// 販売中か (販売を終了した商品は予約できない)
+    pub on_sale: bool,
+}
+
+impl From<&商品> for ProductResponse {
+    fn from(商品: &商品) -> Self {
+        let 内容 = &商品.内容;
+        Self {
+            id: *商品.id.as_uuid(),
+            name: 内容.商品名.value().to_string(),
+            description: 内容.説明.value().to_string(),
+            price: 内容.価格.税込価格.value(),
+            tax_category: 内容.価格.税区分.into(),
+            image_keys: 内容
+                .画像キー
+                .iter()
+                .map(|key| key.value().to_string())
+                .collect(),
+            on_sale: 商品.販売中,
+        }
+    }
+}
+
+/// 商品検索のクエリパラメータ
+#[derive(Debug, Clone, Default, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SearchProductsQuery {
+    /// 商品名・説明に含まれる文字列
+    pub keyword: Option<String>,
+    /// 税区分
+    pub tax_category: Option<TaxCategory>,
+    /// 税込価格の下限 (この金額を含む)
+    pub min_price: Option<u32>,
+    /// 税込価格の上限 (この金額を含む)
+    pub max_price: Option<u32>,
+    /// 先頭から読み飛ばす件数 (既定 0)
+    pub offset: Option<usize>,
+    /// 取得件数 (既定 20, 最大 100)
+    pub limit: Option<usize>,
+}
+
+impl SearchProductsQuery {
+    /// クエリパラメータをドメインの検索条件に変換する (カタログには販売中の商品だけを表示する)
+    fn to_criteria(&self) -> 商品検索条件 {
+        商品検索条件 {
+            キーワード: self
+                .keyword
+                .as_deref()
+                .map(str::trim)
+                .filter(|k| !k.is_empty())
+                .map(str::to_string),
+            税区分: self.tax_category.map(Into::into),
+            最低価格: self.min_price,
+            最高価格: self.max_price,
+            販売中のみ: true,
+        }
+    }
+}
+
+/// 商品検索の結果
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProductSearchResult {
+    pub items: Vec<ProductResponse>,
+    /// 条件に一致した件数 (ページングに関係しない)
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+/// 商品IDを直接指定する API のエラーをレスポンスに変換する
+///
+/// 予約受付では存在しない商品は入力の誤り (422) だが、商品を指定する API ではリソースがない (404) とする。
+pub(crate) fn catalog_error_response(e: ApplicationError) -> Response {
+    match e {
+        ApplicationError::Domain(DomainError::商品NotFound(id)) => ProblemDetails::new(
+            StatusCode::NOT_FOUND,
+            "PRODUCT_NOT_FOUND",
+            DomainError::商品NotFound(id).to_string(),
+            None,
+        )
+        .into_response(),
+        other => other.into_response(),
+    }
+}
+
+// --- ハンドラ ---
+
+#[utoipa::path(
+    get,
+    path = "/products",
+    tag = "Products",
+    params(SearchProductsQuery),
+    responses(
+        (status = 200, description = "Products on sale matching the criteria", body = ProductSearchResult),
+        (status = 422, description = "Invalid search criteria",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to search products",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// GET /products リクエストに対するハンドラ
+pub async fn search_products(
+    State(catalog_service): State<Arc<商品カタログサービス>>,
+    Query(query): Query<SearchProductsQuery>,
+) -> Result<Json<ProductSearchResult>, ApplicationError> {
+    let offset = query.offset.unwrap_or(0);
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+    let result = catalog_service
+        .商品を検索する(&query.to_criteria(), offset, limit)
+        .await?;
+    Ok(Json(ProductSearchResult {
+        items: result
+            .商品リスト
+            .iter()
+            .map(ProductResponse::from)
+            .collect(),
+        total: result.総件数,
+        offset,
+        limit,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/products/{id}",
+    tag = "Products",
+    params(("id" = Uuid, Path, description = "商品ID")),
+    responses(
+        (status = 200, description = "Product on sale", body = ProductResponse),
+        (status = 404, description = "Product not found or no longer on sale",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to load the product",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// GET /products/{id} リクエストに対するハンドラ
+pub async fn get_product(
+    State(catalog_service): State<Arc<商品カタログサービス>>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ProductResponse>, Response> {
+    let 商品id = 商品ID::from_uuid(id);
+    let 商品 = catalog_service
+        .商品詳細取得(&商品id)
+        .await
+        .map_err(catalog_error_response)?;
+    // 販売を終了した商品はカタログに表示しない
+    if !商品.販売中 {
+        return Err(catalog_error_response(
+            DomainError::商品NotFound(商品id).into(),
+        ));
+    }
+    Ok(Json(ProductResponse::from(&商品)))
+}
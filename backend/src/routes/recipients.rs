@@ -0,0 +1,268 @@
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::{ApplicationError, 届け先サービス};
+use crate::config::CURRENT_API_VERSION;
+use crate::domain::{
+    DomainError, 住所, 届け先, 届け先ID, 届け先内容, 建物名, 氏名, 郵便番号, 都道府県, 電話番号,
+};
+use crate::routes::current_user::CurrentUser;
+use crate::routes::error::ProblemDetails;
+
+// --- DTO ---
+
+/// 届け先の登録・変更リクエスト (変更時もすべての項目を指定する)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecipientRequest {
+    /// 氏名 (50 文字以内)
+    #[schema(example = "山田 花子")]
+    pub name: String,
+    /// フリガナ (100 文字以内。ひらがな・半角カナは全角カタカナに揃える)
+    #[schema(example = "ヤマダ ハナコ")]
+    pub name_kana: String,
+    /// 郵便番号 (7 桁。ハイフンは省略可)
+    #[schema(example = "100-0001")]
+    pub postal_code: String,
+    /// 都道府県
+    #[schema(example = "東京都")]
+    pub prefecture: String,
+    /// 住所 (市区町村・町域・番地。100 文字以内)
+    #[schema(example = "千代田区千代田1-1")]
+    pub address: String,
+    /// 建物名・部屋番号 (100 文字以内。空文字は未指定とみなす)
+    #[serde(default)]
+    pub building: Option<String>,
+    /// 電話番号 (0 から始まる 10〜11 桁。ハイフンは省略可)
+    #[schema(example = "03-1234-5678")]
+    pub phone_number: String,
+}
+
+impl RecipientRequest {
+    fn to_contents(&self) -> Result<届け先内容, DomainError> {
+        Ok(届け先内容 {
+            氏名: 氏名::new(&self.name, &self.name_kana)?,
+            郵便番号: 郵便番号::new(&self.postal_code)?,
+            都道府県: 都道府県::from_名前(&self.prefecture)?,
+            住所: 住所::new(&self.address)?,
+            建物名: self
+                .building
+                .as_deref()
+                .filter(|b| !b.trim().is_empty())
+                .map(建物名::new)
+                .transpose()?,
+            電話番号: 電話番号::new(&self.phone_number)?,
+        })
+    }
+}
+
+/// 届け先
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RecipientResponse {
+    /// 届け先ID
+    pub id: Uuid,
+    /// 氏名
+    pub name: String,
+    /// フリガナ (全角カタカナ)
+    pub name_kana: String,
+    /// 郵便番号 (ハイフン区切り)
+    #[schema(example = "100-0001")]
+    pub postal_code: String,
+    /// 都道府県
+    pub prefecture: String,
+    /// 住所 (市区町村以降)
+    pub address: String,
+    /// 建物名・部屋番号
+    pub building: Option<String>,
+    /// 電話番号 (ハイフンなし)
+    #[schema(example = "0312345678")]
+    pub phone_number: String,
+}
+
+impl From<&届け先> for RecipientResponse {
+    fn from(届け先: &届け先) -> Self {
+        let 内容 = &届け先.内容;
+        Self {
+            id: *届け先.id.as_uuid(),
+            name: 内容.氏名.表記().to_string(),
+            name_kana: 内容.氏名.フリガナ().to_string(),
+            postal_code: 内容.郵便番号.表示(),
+            prefecture: 内容.都道府県.名前(),
+            address: 内容.住所.value().to_string(),
+            building: 内容.建物名.as_ref().map(|b| b.value().to_string()),
+            phone_number: 内容.電話番号.value().to_string(),
+        }
+    }
+}
+
+// --- ハンドラ ---
+
+#[utoipa::path(
+    post,
+    path = "/recipients",
+    tag = "Recipients",
+    params(("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID")),
+    request_body = RecipientRequest,
+    responses(
+        (status = 201, description = "Recipient registered", body = RecipientResponse,
+            headers(("Location" = String, description = "URL of the created recipient"))),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Name, postal code, prefecture, address or phone number is invalid, or the address does not match the postal code",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the recipient",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /recipients リクエストに対するハンドラ
+pub async fn create_recipient(
+    State(recipient_service): State<Arc<届け先サービス>>,
+    CurrentUser(所有者id): CurrentUser,
+    Json(request): Json<RecipientRequest>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let 届け先 = recipient_service
+        .届け先を登録する(所有者id, request.to_contents()?)
+        .await?;
+
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(
+        header::LOCATION,
+        HeaderValue::from_str(&format!(
+            "/api/{}/recipients/{}",
+            CURRENT_API_VERSION,
+            届け先.id.as_uuid()
+        ))
+        .expect("UUID is a valid header value"),
+    );
+    Ok((
+        StatusCode::CREATED,
+        response_headers,
+        Json(RecipientResponse::from(&届け先)),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/recipients",
+    tag = "Recipients",
+    params(("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID")),
+    responses(
+        (status = 200, description = "The current user's recipients in registration order",
+            body = Vec<RecipientResponse>),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to load recipients",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// GET /recipients リクエストに対するハンドラ
+pub async fn list_my_recipients(
+    State(recipient_service): State<Arc<届け先サービス>>,
+    CurrentUser(所有者id): CurrentUser,
+) -> Result<Json<Vec<RecipientResponse>>, ApplicationError> {
+    let 届け先リスト = recipient_service.届け先一覧取得(&所有者id).await?;
+    Ok(Json(
+        届け先リスト.iter().map(RecipientResponse::from).collect(),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/recipients/{id}",
+    tag = "Recipients",
+    params(
+        ("id" = Uuid, Path, description = "届け先ID"),
+        ("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID")
+    ),
+    responses(
+        (status = 200, description = "Recipient found", body = RecipientResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Recipient not found for the current user",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to load the recipient",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// GET /recipients/{id} リクエストに対するハンドラ
+pub async fn get_recipient(
+    State(recipient_service): State<Arc<届け先サービス>>,
+    CurrentUser(所有者id): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<RecipientResponse>, ApplicationError> {
+    let 届け先 = recipient_service
+        .届け先詳細取得(&所有者id, &届け先ID::from_uuid(id))
+        .await?;
+    Ok(Json(RecipientResponse::from(&届け先)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/recipients/{id}",
+    tag = "Recipients",
+    params(
+        ("id" = Uuid, Path, description = "届け先ID"),
+        ("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID")
+    ),
+    request_body = RecipientRequest,
+    responses(
+        (status = 200, description = "Recipient updated", body = RecipientResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Recipient not found for the current user",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Name, postal code, prefecture, address or phone number is invalid, or the address does not match the postal code",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the recipient",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// PUT /recipients/{id} リクエストに対するハンドラ
+pub async fn update_recipient(
+    State(recipient_service): State<Arc<届け先サービス>>,
+    CurrentUser(所有者id): CurrentUser,
+    Path(id): Path<Uuid>,
+    Json(request): Json<RecipientRequest>,
+) -> Result<Json<RecipientResponse>, ApplicationError> {
+    let 届け先 = recipient_service
+        .届け先を変更する(&所有者id, &届け先ID::from_uuid(id), request.to_contents()?)
+        .await?;
+    Ok(Json(RecipientResponse::from(&届け先)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/recipients/{id}",
+    tag = "Recipients",
+    params(
+        ("id" = Uuid, Path, description = "届け先ID"),
+        ("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID")
+    ),
+    responses(
+        (status = 204, description = "Recipient deleted"),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Recipient not found for the current user",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Recipient is used by a reservation in progress",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to delete the recipient",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// DELETE /recipients/{id} リクエストに対するハンドラ
+pub async fn delete_recipient(
+    State(recipient_service): State<Arc<届け先サービス>>,
+    CurrentUser(所有者id): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, ApplicationError> {
+    recipient_service
+        .届け先を削除する(&所有者id, &届け先ID::from_uuid(id))
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,1097 @@
+use axum::extract::{Path, Query, State};
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::IntoResponse;
+use axum::Json;
+use chrono::{DateTime, FixedOffset, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::application::{
+    ApplicationError, IdempotencyKey, IdempotentOutcome, プレゼント予約サービス,
+};
+use crate::config::CURRENT_API_VERSION;
+use crate::domain::{
+    DomainError, キャンセル判定, プレゼント予約ベース, プレゼント予約状態, プレゼント内容,
+    メッセージ内容, ユーザーID, ラッピング種類, 予約ID, 予約ステータス, 予約一覧カーソル,
+    予約変更内容, 冪等性キーの確保, 商品ID, 届け先ID, 支払いID, 料金内訳, 料金項目,
+    発送代行プレゼント情報, 税区分, 記念日, 配送失敗理由, 配送希望, 配送時間帯, 配送業者, 金額,
+};
+use crate::routes::current_user::CurrentUser;
+use crate::routes::error::ProblemDetails;
+
+// --- DTO ---
+
+/// ラッピング種類 (API 表現)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum WrappingType {
+    なし,
+    標準,
+    特別,
+}
+
+impl From<WrappingType> for ラッピング種類 {
+    fn from(value: WrappingType) -> Self {
+        match value {
+            WrappingType::なし => ラッピング種類::なし,
+            WrappingType::標準 => ラッピング種類::標準,
+            WrappingType::特別 => ラッピング種類::特別,
+        }
+    }
+}
+
+impl From<ラッピング種類> for WrappingType {
+    fn from(value: ラッピング種類) -> Self {
+        match value {
+            ラッピング種類::なし => WrappingType::なし,
+            ラッピング種類::標準 => WrappingType::標準,
+            ラッピング種類::特別 => WrappingType::特別,
+        }
+    }
+}
+
+/// 配送時間帯 (API 表現)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum DeliveryTimeSlot {
+    指定なし,
+    午前中,
+    #[serde(rename = "14-16時")]
+    午後2時から4時,
+    #[serde(rename = "16-18時")]
+    午後4時から6時,
+    #[serde(rename = "18-20時")]
+    午後6時から8時,
+    #[serde(rename = "19-21時")]
+    午後7時から9時,
+}
+
+impl From<DeliveryTimeSlot> for 配送時間帯 {
+    fn from(value: DeliveryTimeSlot) -> Self {
+        match value {
+            DeliveryTimeSlot::指定なし => 配送時間帯::指定なし,
+            DeliveryTimeSlot::午前中 => 配送時間帯::午前中,
+            DeliveryTimeSlot::午後2時から4時 => 配送時間帯::午後2時から4時,
+            DeliveryTimeSlot::午後4時から6時 => 配送時間帯::午後4時から6時,
+            DeliveryTimeSlot::午後6時から8時 => 配送時間帯::午後6時から8時,
+            DeliveryTimeSlot::午後7時から9時 => 配送時間帯::午後7時から9時,
+        }
+    }
+}
+
+impl From<配送時間帯> for DeliveryTimeSlot {
+    fn from(value: 配送時間帯) -> Self {
+        match value {
+            配送時間帯::指定なし => DeliveryTimeSlot::指定なし,
+            配送時間帯::午前中 => DeliveryTimeSlot::午前中,
+            配送時間帯::午後2時から4時 => DeliveryTimeSlot::午後2時から4時,
+            配送時間帯::午後4時から6時 => DeliveryTimeSlot::午後4時から6時,
+            配送時間帯::午後6時から8時 => DeliveryTimeSlot::午後6時から8時,
+            配送時間帯::午後7時から9時 => DeliveryTimeSlot::午後7時から9時,
+        }
+    }
+}
+
+/// 配送希望 (配送日と時間帯)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DesiredDelivery {
+    /// 配送希望日 (最短配送日から記念日までの日付)
+    pub date: NaiveDate,
+    /// 配送時間帯 (省略時は指定なし)
+    #[serde(default = "default_time_slot")]
+    pub time_slot: DeliveryTimeSlot,
+}
+
+fn default_time_slot() -> DeliveryTimeSlot {
+    DeliveryTimeSlot::指定なし
+}
+
+impl From<DesiredDelivery> for 配送希望 {
+    fn from(value: DesiredDelivery) -> Self {
+        Self {
+            配送日: value.date,
+            時間帯: value.time_slot.into(),
+        }
+    }
+}
+
+impl From<配送希望> for DesiredDelivery {
+    fn from(value: 配送希望) -> Self {
+        Self {
+            date: value.配送日,
+            time_slot: value.時間帯.into(),
+        }
+    }
+}
+
+/// 発送代行で預かるプレゼントの情報
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ForwardingGift {
+    /// プレゼントの説明 (書記素単位で 500 文字以内。改行以外の制御文字は不可)
+    pub description: String,
+    /// 倉庫への到着予定日 (受付日から配送日の前日まで)
+    pub warehouse_arrival_date: NaiveDate,
+}
+
+/// プレゼント内容の種類 (API 表現)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum GiftType {
+    発送代行,
+    手配,
+}
+
+/// プレゼント予約受付リクエスト
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateReservationRequest {
+    /// 届け先ID
+    pub recipient_id: Uuid,
+    /// 記念日
+    pub anniversary_date: NaiveDate,
+    /// メッセージ内容 (書記素単位で 200 文字以内。NFKC 正規化して保存する。改行以外の制御文字は不可)
+    pub message: Option<String>,
+    /// ラッピング種類
+    pub wrapping_type: WrappingType,
+    /// 配送希望 (日付と時間帯)
+    pub desired_delivery: Option<DesiredDelivery>,
+    /// 手配商品IDリスト (手配の場合に指定する)
+    #[serde(default)]
+    pub product_ids: Vec<Uuid>,
+    /// 発送代行プレゼント情報 (発送代行の場合に指定する。product_ids とは併用できない)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub forwarding_gift: Option<ForwardingGift>,
+    /// 画面に表示した合計金額 (円、税込)。サーバーで計算した料金と一致しない場合は受け付けない
+    pub total_amount: u32,
+}
+
+impl CreateReservationRequest {
+    fn to_gift(&self) -> Result<プレゼント内容, ApplicationError> {
+        match &self.forwarding_gift {
+            Some(_) if !self.product_ids.is_empty() => Err(ApplicationError::InvalidInput {
+                field: "forwarding_gift".to_string(),
+                reason: "forwarding_gift と product_ids は同時に指定できません".to_string(),
+            }),
+            Some(gift) => Ok(プレゼント内容::発送代行 {
+                情報: 発送代行プレゼント情報::new(&gift.description)?,
+                倉庫到着予定日: gift.warehouse_arrival_date,
+            }),
+            None => Ok(プレゼント内容::手配 {
+                商品idリスト: self
+                    .product_ids
+                    .iter()
+                    .copied()
+                    .map(商品ID::from_uuid)
+                    .collect(),
+            }),
+        }
+    }
+}
+
+/// 予約内容の変更リクエスト
+///
+/// 省略した項目は変更しない。`message` と `desired_delivery` は null を指定すると取り消す。
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct UpdateReservationRequest {
+    /// メッセージ内容 (null で取り消し)
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>, nullable)]
+    pub message: Option<Option<String>>,
+    /// ラッピング種類
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrapping_type: Option<WrappingType>,
+    /// 配送希望 (null で取り消し)
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<DesiredDelivery>, nullable)]
+    pub desired_delivery: Option<Option<DesiredDelivery>>,
+    /// 画面に表示した変更後の合計金額 (税込、円)。`wrapping_type` を指定する場合は必須
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(example = 6080)]
+    pub total_amount: Option<u32>,
+}
+
+/// JSON に項目が存在する場合は (null であっても) Some にする
+fn deserialize_present<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+impl UpdateReservationRequest {
+    fn to_changes(&self) -> Result<予約変更内容, DomainError> {
+        let メッセージ内容 = match &self.message {
+            Some(Some(message)) => Some(Some(メッセージ内容::new(message)?)),
+            Some(None) => Some(None),
+            None => None,
+        };
+        // ラッピングを変えると合計金額が変わるため、受付と同じく見積金額を照合する
+        if self.wrapping_type.is_some() && self.total_amount.is_none() {
+            return Err(DomainError::必須項目不足 {
+                field: "total_amount".to_string(),
+            });
+        }
+        Ok(予約変更内容 {
+            メッセージ内容,
+            ラッピング: self.wrapping_type.map(Into::into),
+            配送希望: self.desired_delivery.map(|d| d.map(Into::into)),
+            見積金額: self.total_amount.map(金額::new).transpose()?,
+        })
+    }
+}
+
+/// プレゼント予約受付レスポンス
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateReservationResponse {
+    /// 予約ID
+    pub reservation_id: Uuid,
+}
+
+/// 消費税の税区分 (API 表現)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum TaxCategory {
+    /// 10%
+    標準税率,
+    /// 8%
+    軽減税率,
+}
+
+impl From<税区分> for TaxCategory {
+    fn from(value: 税区分) -> Self {
+        match value {
+            税区分::標準税率 => TaxCategory::標準税率,
+            税区分::軽減税率 => TaxCategory::軽減税率,
+        }
+    }
+}
+
+impl From<TaxCategory> for 税区分 {
+    fn from(value: TaxCategory) -> Self {
+        match value {
+            TaxCategory::標準税率 => 税区分::標準税率,
+            TaxCategory::軽減税率 => 税区分::軽減税率,
+        }
+    }
+}
+
+/// 配送業者 (API 表現)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum ShippingCarrier {
+    ヤマト運輸,
+    佐川急便,
+    日本郵便,
+}
+
+impl From<ShippingCarrier> for 配送業者 {
+    fn from(value: ShippingCarrier) -> Self {
+        match value {
+            ShippingCarrier::ヤマト運輸 => 配送業者::ヤマト運輸,
+            ShippingCarrier::佐川急便 => 配送業者::佐川急便,
+            ShippingCarrier::日本郵便 => 配送業者::日本郵便,
+        }
+    }
+}
+
+impl From<配送業者> for ShippingCarrier {
+    fn from(value: 配送業者) -> Self {
+        match value {
+            配送業者::ヤマト運輸 => ShippingCarrier::ヤマト運輸,
+            配送業者::佐川急便 => ShippingCarrier::佐川急便,
+            配送業者::日本郵便 => ShippingCarrier::日本郵便,
+        }
+    }
+}
+
+/// 配送失敗理由 (API 表現)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum DeliveryFailureReason {
+    受取拒否,
+    宛先不明,
+    保管期限切れ,
+}
+
+impl From<DeliveryFailureReason> for 配送失敗理由 {
+    fn from(value: DeliveryFailureReason) -> Self {
+        match value {
+            DeliveryFailureReason::受取拒否 => 配送失敗理由::受取拒否,
+            DeliveryFailureReason::宛先不明 => 配送失敗理由::宛先不明,
+            DeliveryFailureReason::保管期限切れ => 配送失敗理由::保管期限切れ,
+        }
+    }
+}
+
+impl From<配送失敗理由> for DeliveryFailureReason {
+    fn from(value: 配送失敗理由) -> Self {
+        match value {
+            配送失敗理由::受取拒否 => DeliveryFailureReason::受取拒否,
+            配送失敗理由::宛先不明 => DeliveryFailureReason::宛先不明,
+            配送失敗理由::保管期限切れ => DeliveryFailureReason::保管期限切れ,
+        }
+    }
+}
+
+/// 料金項目 (API 表現)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum ChargeItem {
+    商品,
+    発送代行手数料,
+    ラッピング料,
+    送料,
+}
+
+/// 料金明細の 1 行
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChargeLine {
+    /// 料金項目
+    pub item: ChargeItem,
+    /// 商品ID (商品の明細のみ)
+    pub product_id: Option<Uuid>,
+    /// 税抜金額 (円)
+    pub amount_excluding_tax: u32,
+    /// 税区分
+    pub tax_category: TaxCategory,
+    /// 消費税額 (円、1 円未満切り捨て)
+    pub tax: u32,
+}
+
+/// 料金内訳
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChargeBreakdown {
+    /// 明細 (料金計算の導入前に受け付けた予約では空)
+    pub lines: Vec<ChargeLine>,
+    /// 税抜合計 (円)
+    pub subtotal: u32,
+    /// 10% 対象の消費税額 (円)
+    pub standard_rate_tax: u32,
+    /// 8% 対象の消費税額 (円)
+    pub reduced_rate_tax: u32,
+}
+
+impl From<&料金内訳> for ChargeBreakdown {
+    fn from(内訳: &料金内訳) -> Self {
+        let lines = 内訳
+            .明細
+            .iter()
+            .map(|行| {
+                let (item, product_id) = match 行.項目 {
+                    料金項目::商品(商品id) => (ChargeItem::商品, Some(*商品id.as_uuid())),
+                    料金項目::発送代行手数料 => (ChargeItem::発送代行手数料, None),
+                    料金項目::ラッピング料 => (ChargeItem::ラッピング料, None),
+                    料金項目::送料 => (ChargeItem::送料, None),
+                };
+                ChargeLine {
+                    item,
+                    product_id,
+                    amount_excluding_tax: 行.税抜金額,
+                    tax_category: 行.税区分.into(),
+                    tax: 行.消費税額,
+                }
+            })
+            .collect();
+        // 合計が上限を超える料金内訳は受付・保存の時点で弾いているため、ここで超えることはない
+        Self {
+            lines,
+            subtotal: 内訳.税抜合計().unwrap_or(u32::MAX),
+            standard_rate_tax: 内訳.消費税額(税区分::標準税率).unwrap_or(u32::MAX),
+            reduced_rate_tax: 内訳.消費税額(税区分::軽減税率).unwrap_or(u32::MAX),
+        }
+    }
+}
+
+/// 各状態に共通の予約情報
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReservationBase {
+    /// 予約ID
+    pub reservation_id: Uuid,
+    /// 依頼者ID
+    pub requester_id: Uuid,
+    /// 届け先ID
+    pub recipient_id: Uuid,
+    /// 記念日
+    pub anniversary_date: NaiveDate,
+    /// メッセージ内容
+    pub message: Option<String>,
+    /// ラッピング種類
+    pub wrapping_type: WrappingType,
+    /// 配送希望
+    pub desired_delivery: Option<DesiredDelivery>,
+    /// プレゼント内容の種類
+    pub gift_type: GiftType,
+    /// 手配商品IDリスト (発送代行の場合は空)
+    pub product_ids: Vec<Uuid>,
+    /// 発送代行プレゼント情報 (手配の場合は null)
+    pub forwarding_gift: Option<ForwardingGift>,
+    /// 料金内訳
+    pub charges: ChargeBreakdown,
+    /// 合計金額 (円、税込)
+    pub total_amount: u32,
+    /// 支払いID
+    pub payment_id: Uuid,
+}
+
+impl From<&プレゼント予約ベース> for ReservationBase {
+    fn from(base: &プレゼント予約ベース) -> Self {
+        let mut product_ids: Vec<Uuid> = base
+            .プレゼント内容
+            .手配商品リスト()
+            .iter()
+            .map(|id| *id.as_uuid())
+            .collect();
+        product_ids.sort(); // HashSet の順序に依存しないよう並べ替える
+        let (gift_type, forwarding_gift) = match &base.プレゼント内容 {
+            プレゼント内容::発送代行 {
+                情報,
+                倉庫到着予定日,
+            } => (
+                GiftType::発送代行,
+                Some(ForwardingGift {
+                    description: 情報.value().to_string(),
+                    warehouse_arrival_date: *倉庫到着予定日,
+                }),
+            ),
+            プレゼント内容::手配 { .. } => (GiftType::手配, None),
+        };
+        Self {
+            reservation_id: *base.id.as_uuid(),
+            requester_id: *base.依頼者id.as_uuid(),
+            recipient_id: *base.届け先id.as_uuid(),
+            anniversary_date: base.記念日.value,
+            message: base.メッセージ内容.as_ref().map(|m| m.value().to_string()),
+            wrapping_type: base.ラッピング.into(),
+            desired_delivery: base.配送希望.map(Into::into),
+            gift_type,
+            product_ids,
+            forwarding_gift,
+            charges: (&base.料金内訳).into(),
+            total_amount: base.合計金額.value(),
+            payment_id: *base.支払いid.as_uuid(),
+        }
+    }
+}
+
+/// 予約受付済みの予約
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReceivedReservation {
+    #[serde(flatten)]
+    pub base: ReservationBase,
+}
+
+/// 発送準備中の予約
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PreparingReservation {
+    #[serde(flatten)]
+    pub base: ReservationBase,
+    /// 梱包担当者ID
+    pub preparation_staff_id: Uuid,
+}
+
+/// 発送済みの予約
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ShippedReservation {
+    #[serde(flatten)]
+    pub base: ReservationBase,
+    /// 配送業者
+    pub carrier: ShippingCarrier,
+    /// 配送伝票番号 (ハイフンなしに正規化済み)
+    pub shipping_slip_number: String,
+}
+
+/// 配送完了の予約
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeliveredReservation {
+    #[serde(flatten)]
+    pub base: ReservationBase,
+    /// 配送業者
+    pub carrier: ShippingCarrier,
+    /// 配送伝票番号 (ハイフンなしに正規化済み)
+    pub shipping_slip_number: String,
+    /// 配送完了日時
+    pub delivery_completed_at: DateTime<FixedOffset>,
+}
+
+/// 配送失敗の予約 (荷物は返送される途中)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct DeliveryFailedReservation {
+    #[serde(flatten)]
+    pub base: ReservationBase,
+    /// 配送業者
+    pub carrier: ShippingCarrier,
+    /// 配送伝票番号
+    pub shipping_slip_number: String,
+    /// 配送失敗理由
+    pub delivery_failure_reason: DeliveryFailureReason,
+    /// 配送失敗日時
+    pub delivery_failed_at: DateTime<FixedOffset>,
+}
+
+/// 返送済みの予約 (再発送かキャンセルを待つ)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReturnedReservation {
+    #[serde(flatten)]
+    pub base: ReservationBase,
+    /// 配送業者
+    pub carrier: ShippingCarrier,
+    /// 返送された荷物の配送伝票番号
+    pub shipping_slip_number: String,
+    /// 配送失敗理由
+    pub delivery_failure_reason: DeliveryFailureReason,
+    /// 配送失敗日時
+    pub delivery_failed_at: DateTime<FixedOffset>,
+    /// 返送日時
+    pub returned_at: DateTime<FixedOffset>,
+}
+
+/// キャンセル済みの予約
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CancelledReservation {
+    #[serde(flatten)]
+    pub base: ReservationBase,
+    /// キャンセル理由
+    pub cancellation_reason: Option<String>,
+    /// キャンセル日時
+    pub cancelled_at: DateTime<FixedOffset>,
+    /// キャンセル料 (円、税込)
+    pub cancellation_fee: u32,
+}
+
+/// プレゼント予約のレスポンス (ADR 0003 の状態ごとの型を `status` で判別する)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "status")]
+pub enum ReservationResponse {
+    Received(ReceivedReservation),
+    Preparing(PreparingReservation),
+    Shipped(ShippedReservation),
+    Delivered(DeliveredReservation),
+    DeliveryFailed(DeliveryFailedReservation),
+    Returned(ReturnedReservation),
+    Cancelled(CancelledReservation),
+}
+
+impl From<&プレゼント予約状態> for ReservationResponse {
+    fn from(state: &プレゼント予約状態) -> Self {
+        match state {
+            プレゼント予約状態::予約受付済み(r) => {
+                Self::Received(ReceivedReservation {
+                    base: (&r.base).into(),
+                })
+            }
+            プレゼント予約状態::発送準備中(r) => {
+                Self::Preparing(PreparingReservation {
+                    base: (&r.base).into(),
+                    preparation_staff_id: *r.梱包担当者id.as_uuid(),
+                })
+            }
+            プレゼント予約状態::発送済み(r) => Self::Shipped(ShippedReservation {
+                base: (&r.base).into(),
+                carrier: r.配送伝票番号.配送業者().into(),
+                shipping_slip_number: r.配送伝票番号.value().to_string(),
+            }),
+            プレゼント予約状態::配送完了(r) => Self::Delivered(DeliveredReservation {
+                base: (&r.base).into(),
+                carrier: r.配送伝票番号.配送業者().into(),
+                shipping_slip_number: r.配送伝票番号.value().to_string(),
+                delivery_completed_at: r.配送完了日時.fixed_offset(),
+            }),
+            プレゼント予約状態::配送失敗(r) => {
+                Self::DeliveryFailed(DeliveryFailedReservation {
+                    base: (&r.base).into(),
+                    carrier: r.配送伝票番号.配送業者().into(),
+                    shipping_slip_number: r.配送伝票番号.value().to_string(),
+                    delivery_failure_reason: r.配送失敗理由.into(),
+                    delivery_failed_at: r.配送失敗日時.fixed_offset(),
+                })
+            }
+            プレゼント予約状態::返送済み(r) => Self::Returned(ReturnedReservation {
+                base: (&r.base).into(),
+                carrier: r.配送伝票番号.配送業者().into(),
+                shipping_slip_number: r.配送伝票番号.value().to_string(),
+                delivery_failure_reason: r.配送失敗理由.into(),
+                delivery_failed_at: r.配送失敗日時.fixed_offset(),
+                returned_at: r.返送日時.fixed_offset(),
+            }),
+            プレゼント予約状態::キャンセル済み(r) => {
+                Self::Cancelled(CancelledReservation {
+                    base: (&r.base).into(),
+                    cancellation_reason: r.キャンセル理由.clone(),
+                    cancelled_at: r.キャンセル日時.fixed_offset(),
+                    cancellation_fee: r.キャンセル料,
+                })
+            }
+        }
+    }
+}
+
+/// 予約ステータス
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum ReservationStatus {
+    Received,
+    Preparing,
+    Shipped,
+    Delivered,
+    DeliveryFailed,
+    Returned,
+    Cancelled,
+}
+
+impl From<予約ステータス> for ReservationStatus {
+    fn from(status: 予約ステータス) -> Self {
+        match status {
+            予約ステータス::予約受付済み => ReservationStatus::Received,
+            予約ステータス::発送準備中 => ReservationStatus::Preparing,
+            予約ステータス::発送済み => ReservationStatus::Shipped,
+            予約ステータス::配送完了 => ReservationStatus::Delivered,
+            予約ステータス::配送失敗 => ReservationStatus::DeliveryFailed,
+            予約ステータス::返送済み => ReservationStatus::Returned,
+            予約ステータス::キャンセル済み => ReservationStatus::Cancelled,
+        }
+    }
+}
+
+impl From<ReservationStatus> for 予約ステータス {
+    fn from(status: ReservationStatus) -> Self {
+        match status {
+            ReservationStatus::Received => 予約ステータス::予約受付済み,
+            ReservationStatus::Preparing => 予約ステータス::発送準備中,
+            ReservationStatus::Shipped => 予約ステータス::発送済み,
+            ReservationStatus::Delivered => 予約ステータス::配送完了,
+            ReservationStatus::DeliveryFailed => 予約ステータス::配送失敗,
+            ReservationStatus::Returned => 予約ステータス::返送済み,
+            ReservationStatus::Cancelled => 予約ステータス::キャンセル済み,
+        }
+    }
+}
+
+impl From<&プレゼント予約状態> for ReservationStatus {
+    fn from(state: &プレゼント予約状態) -> Self {
+        state.ステータス().into()
+    }
+}
+
+impl std::str::FromStr for ReservationStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Received" => Ok(ReservationStatus::Received),
+            "Preparing" => Ok(ReservationStatus::Preparing),
+            "Shipped" => Ok(ReservationStatus::Shipped),
+            "Delivered" => Ok(ReservationStatus::Delivered),
+            "DeliveryFailed" => Ok(ReservationStatus::DeliveryFailed),
+            "Returned" => Ok(ReservationStatus::Returned),
+            "Cancelled" => Ok(ReservationStatus::Cancelled),
+            other => Err(format!("未知のステータスです: {}", other)),
+        }
+    }
+}
+
+/// 予約一覧の1件分の概要
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReservationSummary {
+    /// 予約ID
+    pub reservation_id: Uuid,
+    /// 予約ステータス
+    pub status: ReservationStatus,
+    /// 依頼者ID
+    pub requester_id: Uuid,
+    /// 届け先ID
+    pub recipient_id: Uuid,
+    /// 記念日
+    pub anniversary_date: NaiveDate,
+    /// ラッピング種類
+    pub wrapping_type: WrappingType,
+    /// 合計金額 (円)
+    pub total_amount: u32,
+}
+
+impl From<&プレゼント予約状態> for ReservationSummary {
+    fn from(state: &プレゼント予約状態) -> Self {
+        let base = state.base();
+        Self {
+            reservation_id: *base.id.as_uuid(),
+            status: state.into(),
+            requester_id: *base.依頼者id.as_uuid(),
+            recipient_id: *base.届け先id.as_uuid(),
+            anniversary_date: base.記念日.value,
+            wrapping_type: base.ラッピング.into(),
+            total_amount: base.合計金額.value(),
+        }
+    }
+}
+
+/// 予約一覧のページ
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReservationPage {
+    pub items: Vec<ReservationSummary>,
+    /// 次のページを取得するためのカーソル (最後のページでは null)
+    pub next_cursor: Option<String>,
+}
+
+/// キャンセルの試算結果 (キャンセル前に可否とキャンセル料を確認する)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CancellationPreview {
+    /// 今キャンセルできるか
+    pub cancellable: bool,
+    /// キャンセル料 (円、税込)。キャンセルできない場合は null
+    pub cancellation_fee: Option<u32>,
+    /// キャンセルできない理由
+    pub reason: Option<String>,
+}
+
+impl From<キャンセル判定> for CancellationPreview {
+    fn from(判定: キャンセル判定) -> Self {
+        match 判定 {
+            キャンセル判定::可 { キャンセル料 } => Self {
+                cancellable: true,
+                cancellation_fee: Some(キャンセル料),
+                reason: None,
+            },
+            キャンセル判定::不可 { 理由 } => Self {
+                cancellable: false,
+                cancellation_fee: None,
+                reason: Some(理由),
+            },
+        }
+    }
+}
+
+/// 一覧取得のクエリパラメータ
+#[derive(Debug, Clone, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListReservationsQuery {
+    /// 前のページの next_cursor
+    pub cursor: Option<String>,
+    /// 取得件数 (既定 20, 最大 100)
+    pub limit: Option<usize>,
+}
+
+/// 一覧取得の既定件数
+pub(crate) const DEFAULT_PAGE_SIZE: usize = 20;
+
+/// カーソルを不透明なトークンに変換する
+pub(crate) fn encode_cursor(cursor: &予約一覧カーソル) -> String {
+    let raw = format!(
+        "{}|{}|{}",
+        cursor.記念日,
+        cursor.作成日時.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+        cursor.予約id.as_uuid()
+    );
+    raw.bytes().fold(String::new(), |mut token, b| {
+        let _ = write!(token, "{:02x}", b);
+        token
+    })
+}
+
+/// トークンからカーソルを復元する
+pub(crate) fn decode_cursor(token: &str) -> Result<予約一覧カーソル, ApplicationError> {
+    let invalid = || ApplicationError::InvalidInput {
+        field: "cursor".to_string(),
+        reason: "カーソルの形式が不正です".to_string(),
+    };
+    if token.len() % 2 != 0 || !token.is_ascii() {
+        return Err(invalid());
+    }
+    let bytes = (0..token.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&token[i..i + 2], 16))
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| invalid())?;
+    let raw = String::from_utf8(bytes).map_err(|_| invalid())?;
+    let mut parts = raw.split('|');
+    let (Some(date), Some(created_at), Some(id), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    Ok(予約一覧カーソル {
+        記念日: date.parse().map_err(|_| invalid())?,
+        作成日時: DateTime::parse_from_rfc3339(created_at)
+            .map_err(|_| invalid())?
+            .with_timezone(&Utc),
+        予約id: 予約ID::from_uuid(Uuid::parse_str(id).map_err(|_| invalid())?),
+    })
+}
+
+// --- ハンドラ ---
+
+/// 冪等性キーのヘッダー名
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// 以前の結果を再送したことを示すレスポンスヘッダー名
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+/// 冪等性キーの最大長
+const IDEMPOTENCY_KEY_MAX_LEN: usize = 255;
+
+/// Idempotency-Key ヘッダーを読み取り、依頼者とリクエスト内容に紐づける (ヘッダーがなければ None)
+fn idempotency_key_from(
+    headers: &HeaderMap,
+    依頼者id: ユーザーID,
+    request: &CreateReservationRequest,
+) -> Result<Option<IdempotencyKey>, ApplicationError> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+    let invalid = || ApplicationError::InvalidInput {
+        field: "Idempotency-Key".to_string(),
+        reason: format!(
+            "1 文字以上 {} 文字以下の ASCII 文字列を指定してください",
+            IDEMPOTENCY_KEY_MAX_LEN
+        ),
+    };
+    let key = value.to_str().map_err(|_| invalid())?.trim();
+    if key.is_empty() || key.len() > IDEMPOTENCY_KEY_MAX_LEN {
+        return Err(invalid());
+    }
+    let fingerprint =
+        serde_json::to_string(request).map_err(|e| ApplicationError::Unexpected(e.to_string()))?;
+    Ok(Some(IdempotencyKey {
+        requester_id: 依頼者id,
+        key: key.to_string(),
+        fingerprint,
+    }))
+}
+
+/// リクエストをドメインの型に変換して予約を受け付ける
+async fn accept_reservation(
+    reservation_service: &プレゼント予約サービス,
+    依頼者id: ユーザーID,
+    request: CreateReservationRequest,
+    冪等性キー: Option<冪等性キーの確保>,
+) -> Result<予約ID, ApplicationError> {
+    let 見積金額 = 金額::new(request.total_amount)?;
+    let プレゼント内容 = request.to_gift()?;
+
+    reservation_service
+        .プレゼント予約受付(
+            依頼者id,
+            届け先ID::from_uuid(request.recipient_id),
+            記念日 {
+                value: request.anniversary_date,
+            },
+            request
+                .message
+                .as_deref()
+                .map(メッセージ内容::new)
+                .transpose()?,
+            request.wrapping_type.into(),
+            request.desired_delivery.map(Into::into),
+            プレゼント内容,
+            支払いID::new(), // 支払い処理は MVP 以降で実装予定のため、ここで採番する
+            見積金額,
+            冪等性キー,
+        )
+        .await
+}
+
+#[utoipa::path(
+    post,
+    path = "/reservations",
+    tag = "Reservations",
+    params(
+        ("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID (予約の依頼者になる)"),
+        ("Idempotency-Key" = Option<String>, Header,
+            description = "再送時に同じ予約を重複して作成しないためのキー (依頼者ごとに 24 時間有効)")
+    ),
+    request_body = CreateReservationRequest,
+    responses(
+        (status = 201, description = "Reservation accepted (or replayed for a retried Idempotency-Key)",
+            body = CreateReservationResponse,
+            headers(
+                ("Location" = String, description = "URL of the created reservation"),
+                ("Idempotent-Replayed" = Option<String>, description = "\"true\" when the response is a replay")
+            )),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 403, description = "Requester has no registered account, or the account is suspended",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "A product is out of stock, or a request with the same Idempotency-Key is still being processed",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Request violates a domain rule, or the Idempotency-Key was reused with a different body",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the reservation",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// POST /reservations リクエストに対するハンドラ
+pub async fn create_reservation(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    CurrentUser(依頼者id): CurrentUser,
+    headers: HeaderMap,
+    Json(request): Json<CreateReservationRequest>,
+) -> Result<impl IntoResponse, ApplicationError> {
+    let outcome = match idempotency_key_from(&headers, 依頼者id, &request)? {
+        Some(idempotency_key) => {
+            reservation_service
+                .冪等に予約を受け付ける(&idempotency_key, |確保| {
+                    accept_reservation(&reservation_service, 依頼者id, request, 確保)
+                })
+                .await?
+        }
+        None => IdempotentOutcome::Created(
+            accept_reservation(&reservation_service, 依頼者id, request, None).await?,
+        ),
+    };
+
+    let reservation_id = *outcome.予約id().as_uuid();
+    let mut response_headers = HeaderMap::new();
+    response_headers.insert(
+        header::LOCATION,
+        HeaderValue::from_str(&format!(
+            "/api/{}/reservations/{}",
+            CURRENT_API_VERSION, reservation_id
+        ))
+        .expect("UUID is a valid header value"),
+    );
+    if let IdempotentOutcome::Replayed(_) = outcome {
+        response_headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    }
+    Ok((
+        StatusCode::CREATED,
+        response_headers,
+        Json(CreateReservationResponse { reservation_id }),
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/reservations/{id}",
+    tag = "Reservations",
+    params(
+        ("id" = Uuid, Path, description = "予約ID"),
+        ("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID")
+    ),
+    responses(
+        (status = 200, description = "Reservation found", body = ReservationResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Reservation not found for the current user",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to load the reservation",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// GET /reservations/{id} リクエストに対するハンドラ
+pub async fn get_reservation(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    CurrentUser(依頼者id): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ReservationResponse>, ApplicationError> {
+    let 予約id = 予約ID::from_uuid(id);
+    let state = reservation_service
+        .予約詳細取得(&予約id, &依頼者id)
+        .await?
+        .ok_or(ApplicationError::Domain(DomainError::予約NotFound(
+            予約id,
+        )))?;
+    Ok(Json(ReservationResponse::from(&state)))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/reservations/{id}",
+    tag = "Reservations",
+    params(
+        ("id" = Uuid, Path, description = "予約ID"),
+        ("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID")
+    ),
+    request_body = UpdateReservationRequest,
+    responses(
+        (status = 200, description = "Reservation updated", body = ReservationResponse),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Reservation not found for the current user",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 409, description = "Reservation can no longer be changed (preparation already started)",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Changed contents violate a domain rule, or total_amount is missing or does not match the recalculated charges",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to persist the reservation",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// PATCH /reservations/{id} リクエストに対するハンドラ
+pub async fn update_reservation(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    CurrentUser(依頼者id): CurrentUser,
+    Path(id): Path<Uuid>,
+    Json(request): Json<UpdateReservationRequest>,
+) -> Result<Json<ReservationResponse>, ApplicationError> {
+    let state = reservation_service
+        .予約内容を変更する(&予約ID::from_uuid(id), &依頼者id, request.to_changes()?)
+        .await?;
+    Ok(Json(ReservationResponse::from(&state)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/me/reservations",
+    tag = "Reservations",
+    params(
+        ("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID"),
+        ListReservationsQuery
+    ),
+    responses(
+        (status = 200, description = "A page of the requester's reservations", body = ReservationPage),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 422, description = "Invalid cursor or limit",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to load reservations",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// GET /me/reservations リクエストに対するハンドラ
+pub async fn list_my_reservations(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    CurrentUser(依頼者id): CurrentUser,
+    Query(query): Query<ListReservationsQuery>,
+) -> Result<Json<ReservationPage>, ApplicationError> {
+    let cursor = query.cursor.as_deref().map(decode_cursor).transpose()?;
+    let page = reservation_service
+        .依頼者の予約一覧取得(&依頼者id, cursor, query.limit.unwrap_or(DEFAULT_PAGE_SIZE))
+        .await?;
+    Ok(Json(ReservationPage {
+        items: page
+            .予約リスト
+            .iter()
+            .map(ReservationSummary::from)
+            .collect(),
+        next_cursor: page.次のカーソル.as_ref().map(encode_cursor),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/me/reservations/{id}/cancellation",
+    tag = "Reservations",
+    params(
+        ("id" = Uuid, Path, description = "予約ID"),
+        ("x-user-id" = Uuid, Header, description = "リクエストしたユーザーのID")
+    ),
+    responses(
+        (status = 200, description = "Cancellation preview (the reservation is not changed)", body = CancellationPreview),
+        (status = 401, description = "User could not be identified",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 404, description = "Reservation not found for the current user",
+            body = ProblemDetails, content_type = "application/problem+json"),
+        (status = 503, description = "Failed to load the reservation",
+            body = ProblemDetails, content_type = "application/problem+json")
+    )
+)]
+// GET /me/reservations/{id}/cancellation リクエストに対するハンドラ
+pub async fn preview_my_cancellation(
+    State(reservation_service): State<Arc<プレゼント予約サービス>>,
+    CurrentUser(依頼者id): CurrentUser,
+    Path(id): Path<Uuid>,
+) -> Result<Json<CancellationPreview>, ApplicationError> {
+    let 判定 = reservation_service
+        .依頼者のキャンセルを試算する(&予約ID::from_uuid(id), &依頼者id)
+        .await?;
+    Ok(Json(判定.into()))
+}
@@ -0,0 +1,187 @@
+mod common;
+
+use common::{spawn_app, test_requester_id};
+use serde_json::json;
+use uuid::Uuid;
+
+fn valid_account_body() -> serde_json::Value {
+    json!({
+        "auth_subject": format!("auth0|{}", Uuid::new_v4().simple()),
+        "email": "Jiro.Suzuki@Example.COM",
+        "display_name": "じろう",
+        "default_sender_name": "鈴木 次郎"
+    })
+}
+
+#[tokio::test]
+async fn register_and_update_my_account() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let user_id = Uuid::new_v4().to_string();
+    let body = valid_account_body();
+
+    // Act: 登録前は 404、登録すると有効な顧客になる
+    let before = client
+        .get(format!("{}/api/v1/me", &address))
+        .header("x-user-id", &user_id)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let created = client
+        .post(format!("{}/api/v1/me", &address))
+        .header("x-user-id", &user_id)
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(before.status(), reqwest::StatusCode::NOT_FOUND);
+    let problem: serde_json::Value = before.json().await.unwrap();
+    assert_eq!(problem["code"], "ACCOUNT_NOT_FOUND");
+    assert_eq!(created.status(), reqwest::StatusCode::CREATED);
+    let account: serde_json::Value = created.json().await.unwrap();
+    assert_eq!(account["id"], user_id);
+    assert_eq!(account["auth_subject"], body["auth_subject"]);
+    assert_eq!(account["email"], "Jiro.Suzuki@example.com");
+    assert_eq!(account["role"], "顧客");
+    assert_eq!(account["status"], "有効");
+
+    // Act: プロフィールを変更する (空の差出人名は未指定とみなす)
+    let updated: serde_json::Value = client
+        .put(format!("{}/api/v1/me", &address))
+        .header("x-user-id", &user_id)
+        .json(&json!({
+            "email": "jiro@example.com",
+            "display_name": "鈴木",
+            "default_sender_name": ""
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    let fetched: serde_json::Value = client
+        .get(format!("{}/api/v1/me", &address))
+        .header("x-user-id", &user_id)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(updated["display_name"], "鈴木");
+    assert_eq!(updated["default_sender_name"], serde_json::Value::Null);
+    assert_eq!(updated["auth_subject"], body["auth_subject"]);
+    assert_eq!(fetched, updated);
+}
+
+#[tokio::test]
+async fn register_my_account_returns_409_for_duplicates_and_422_for_invalid_fields() {
+    // Arrange: test_requester_id のアカウントは登録済み (認証サブジェクトは test|requester)
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let mut taken_subject = valid_account_body();
+    taken_subject["auth_subject"] = json!("test|requester");
+    let mut invalid_email = valid_account_body();
+    invalid_email["email"] = json!("jiro@localhost");
+
+    for (user_id, body, status, code) in [
+        (
+            test_requester_id(),
+            valid_account_body(),
+            reqwest::StatusCode::CONFLICT,
+            "ACCOUNT_ALREADY_EXISTS",
+        ),
+        (
+            Uuid::new_v4(),
+            taken_subject,
+            reqwest::StatusCode::CONFLICT,
+            "AUTH_SUBJECT_IN_USE",
+        ),
+        (
+            Uuid::new_v4(),
+            invalid_email,
+            reqwest::StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_EMAIL",
+        ),
+    ] {
+        // Act
+        let response = client
+            .post(format!("{}/api/v1/me", &address))
+            .header("x-user-id", user_id.to_string())
+            .json(&body)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        // Assert
+        assert_eq!(response.status(), status);
+        let problem: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(problem["code"], code);
+    }
+}
+
+#[tokio::test]
+async fn my_account_requires_user() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act
+    let response = client
+        .get(format!("{}/api/v1/me", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn suspend_account_requires_administrator() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let url = format!(
+        "{}/api/v1/admin/users/{}/suspension",
+        &address,
+        test_requester_id()
+    );
+
+    // Act & Assert: ユーザーを特定できない場合は 401
+    let response = client
+        .post(&url)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    // Act & Assert: 顧客は 403
+    let response = client
+        .post(&url)
+        .header("x-user-id", test_requester_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "ADMIN_REQUIRED");
+
+    // アカウントは停止されていない
+    let account: serde_json::Value = client
+        .get(format!("{}/api/v1/me", &address))
+        .header("x-user-id", test_requester_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(account["status"], "有効");
+}
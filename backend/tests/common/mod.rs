@@ -0,0 +1,186 @@
+use axum::serve;
+use chrono::{TimeZone, Utc};
+use ddd_sample_jp::application::{
+    FixedClock, プレゼント予約サービス, ユーザーアカウントサービス, 商品カタログサービス,
+    届け先サービス,
+};
+use ddd_sample_jp::config::AppConfig;
+use ddd_sample_jp::domain::{
+    プロフィール, メールアドレス, ユーザーID, ユーザーアカウント, ユーザーアカウントRepository,
+    ユーザーロール, 住所, 商品, 商品ID, 商品Repository, 商品価格, 商品内容, 商品名,
+    商品在庫Repository, 商品説明, 届け先, 届け先ID, 届け先Repository, 届け先内容, 氏名, 税区分,
+    表示名, 認証サブジェクト, 郵便番号, 都道府県, 金額, 電話番号,
+};
+use ddd_sample_jp::infrastructure::{
+    InMemoryイベント発行者, InMemoryプレゼント予約Repository, InMemoryユーザーアカウントRepository,
+    InMemory商品Repository, InMemory届け先Repository, InMemory郵便番号辞書,
+}; // テストでは InMemory を使う
+use ddd_sample_jp::{build_app, AppState};
+use std::sync::Arc;
+use uuid::Uuid;
+
+// テストアプリケーションの現在時刻 (2025-01-06 月曜 10:00 JST)。記念日はこれより後の日付を使う
+pub fn test_now() -> chrono::DateTime<Utc> {
+    Utc.with_ymd_and_hms(2025, 1, 6, 1, 0, 0).unwrap()
+}
+
+// テストアプリケーションのカタログに登録してある販売中の商品 (税込 4,320 円・軽減税率)
+#[allow(dead_code)] // 使わないテストクレートもある
+pub fn test_product_id() -> Uuid {
+    Uuid::from_u128(0x0195_8a1c_4000_7000_8000_0000_0000_0001)
+}
+
+// テストアプリケーションのカタログに登録してある販売終了の商品
+#[allow(dead_code)] // 使わないテストクレートもある
+pub fn test_discontinued_product_id() -> Uuid {
+    Uuid::from_u128(0x0195_8a1c_4000_7000_8000_0000_0000_0004)
+}
+
+// テストアプリケーションにアカウント (有効な顧客) と届け先を登録してある依頼者
+#[allow(dead_code)] // 使わないテストクレートもある
+pub fn test_requester_id() -> Uuid {
+    Uuid::from_u128(0x0195_8a1c_4000_7000_8000_0000_0000_0002)
+}
+
+// テストアプリケーションにアカウント (有効な管理者) を登録してあるユーザー
+#[allow(dead_code)] // 使わないテストクレートもある
+pub fn test_admin_id() -> Uuid {
+    Uuid::from_u128(0x0195_8a1c_4000_7000_8000_0000_0000_0005)
+}
+
+// テストアプリケーションに登録してある test_requester_id の届け先
+#[allow(dead_code)] // 使わないテストクレートもある
+pub fn test_recipient_id() -> Uuid {
+    Uuid::from_u128(0x0195_8a1c_4000_7000_8000_0000_0000_0003)
+}
+
+// 本番と同じルーター (build_app) を InMemory リポジトリで起動し、アドレスを返すヘルパー関数
+pub async fn spawn_app() -> String {
+    spawn_app_with_config(AppConfig::default()).await
+}
+
+// 設定を指定してテストアプリケーションを起動するヘルパー関数
+#[allow(dead_code)] // 使わないテストクレートもある
+pub async fn spawn_app_with_config(config: AppConfig) -> String {
+    spawn_app_with_events(config).await.0
+}
+
+// 発行されたドメインイベントを確認できるようにしてテストアプリケーションを起動するヘルパー関数
+#[allow(dead_code)] // 使わないテストクレートもある
+pub async fn spawn_app_with_events(config: AppConfig) -> (String, InMemoryイベント発行者) {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0") // 0番ポートでランダムなポートを確保
+        .await
+        .expect("Failed to bind random port");
+    let address = format!("http://{}", listener.local_addr().unwrap());
+
+    // テスト用の依存関係 (InMemory リポジトリを使用)
+    let repository = Arc::new(InMemoryプレゼント予約Repository::new());
+    let recipients = Arc::new(InMemory届け先Repository::new());
+    recipients
+        .save(&届け先 {
+            id: 届け先ID::from_uuid(test_recipient_id()),
+            所有者id: ユーザーID::from_uuid(test_requester_id()),
+            内容: 届け先内容 {
+                氏名: 氏名::new("山田 花子", "ヤマダ ハナコ").unwrap(),
+                郵便番号: 郵便番号::new("100-0001").unwrap(),
+                都道府県: 都道府県::東京都,
+                住所: 住所::new("千代田区千代田1-1").unwrap(),
+                建物名: None,
+                電話番号: 電話番号::new("03-1234-5678").unwrap(),
+            },
+        })
+        .await
+        .unwrap();
+    let accounts = Arc::new(InMemoryユーザーアカウントRepository::new());
+    accounts
+        .save(&ユーザーアカウント::登録する(
+            ユーザーID::from_uuid(test_requester_id()),
+            認証サブジェクト::new("test|requester").unwrap(),
+            プロフィール {
+                メールアドレス: メールアドレス::new("hanako@example.com").unwrap(),
+                表示名: 表示名::new("はなこ").unwrap(),
+                既定の差出人名: None,
+            },
+        ))
+        .await
+        .unwrap();
+    let mut admin = ユーザーアカウント::登録する(
+        ユーザーID::from_uuid(test_admin_id()),
+        認証サブジェクト::new("test|admin").unwrap(),
+        プロフィール {
+            メールアドレス: メールアドレス::new("admin@example.com").unwrap(),
+            表示名: 表示名::new("管理者").unwrap(),
+            既定の差出人名: None,
+        },
+    );
+    admin.ロール = ユーザーロール::管理者;
+    accounts.save(&admin).await.unwrap();
+    let events = InMemoryイベント発行者::new();
+    let products = Arc::new(InMemory商品Repository::new());
+    for (id, 名前, 販売中) in [
+        (test_product_id(), "焼き菓子の詰め合わせ", true),
+        (
+            test_discontinued_product_id(),
+            "季節のフルーツゼリー",
+            false,
+        ),
+    ] {
+        products
+            .save(&商品 {
+                id: 商品ID::from_uuid(id),
+                内容: 商品内容 {
+                    商品名: 商品名::new(名前).unwrap(),
+                    説明: 商品説明::default(),
+                    価格: 商品価格 {
+                        税込価格: 金額::new(4320).unwrap(),
+                        税区分: 税区分::軽減税率,
+                    },
+                    画像キー: vec![],
+                },
+                販売中,
+            })
+            .await
+            .unwrap();
+    }
+    // 受付時に在庫を引き当てるので、販売中の商品には十分な在庫を用意しておく
+    repository
+        .set_on_hand(&商品ID::from_uuid(test_product_id()), 100)
+        .await
+        .unwrap();
+    let reservation_service = Arc::new(
+        プレゼント予約サービス::new(repository.clone())
+            .with_recipients(recipients.clone())
+            .with_accounts(accounts.clone())
+            .with_idempotency_store(repository.clone())
+            .with_event_publisher(Arc::new(events.clone()))
+            .with_products(products.clone())
+            .with_clock(Arc::new(FixedClock(test_now())))
+            .with_shipping_lead_time(config.shipping_lead_time())
+            .with_cancellation_policy(config.cancellation_policy),
+    );
+    // 日本郵便の郵便番号データ (utf_ken_all.csv) から抜き出した数行だけを読み込む
+    let postal_codes = InMemory郵便番号辞書::from_ken_all_file(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/utf_ken_all_sample.csv"
+    ))
+    .expect("Failed to load postal code fixture");
+    let recipient_service = Arc::new(
+        届け先サービス::new(recipients, repository.clone())
+            .with_postal_codes(Arc::new(postal_codes)),
+    );
+    let app = build_app(
+        AppState {
+            reservations: reservation_service,
+            recipients: recipient_service,
+            accounts: Arc::new(ユーザーアカウントサービス::new(accounts)),
+            products: Arc::new(商品カタログサービス::new(products, repository)),
+        },
+        &config,
+    );
+
+    tokio::spawn(async move {
+        serve(listener, app.into_make_service()).await.unwrap();
+    });
+
+    (address, events)
+}
@@ -1,68 +1,160 @@
-use axum::{serve, Router};
-use ddd_sample_jp::application::プレゼント予約サービス;
-use ddd_sample_jp::infrastructure::InMemoryプレゼント予約Repository; // テストでは InMemory を使う
-use dotenv::dotenv;
-use reqwest;
-// DB接続も必要に応じて準備
-use std::sync::Arc;
-use tokio;
-
-// テスト用のアプリケーションを起動し、アドレスとポートを返すヘルパー関数
-async fn spawn_test_app() -> String {
-    // 最初から tokio::net::TcpListener を使う
-    let listener = tokio::net::TcpListener::bind("127.0.0.1:0") // 0番ポートでランダムなポートを確保
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::{spawn_app, spawn_app_with_config};
+use ddd_sample_jp::config::AppConfig;
+
+#[tokio::test]
+async fn health_check_works() {
+    // Arrange: 本番と同じルーターでテストアプリケーションを起動
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act: /api/v1/health エンドポイントにリクエスト送信
+    let response = client
+        .get(format!("{}/api/v1/health", &address))
+        .send()
         .await
-        .expect("Failed to bind random port");
-    let addr = listener.local_addr().unwrap(); // ポート番号を取得
-    let address = format!("http://{}", addr);
-
-    // .env 読み込み (テスト用 DB URL など)
-    dotenv().ok();
-
-    // テスト用のデータベース接続プールを作成 (health check では不要だが、他のテストで必要になる可能性)
-    // let database_url = env::var("DATABASE_URL").expect("DATABASE_URL must be set for tests");
-    // let db_pool = PgPool::connect(&database_url)
-    //     .await
-    //     .expect("Failed to create test Postgres connection pool.");
-
-    // テスト用の依存関係 (InMemory リポジトリを使用)
-    let repository = Arc::new(InMemoryプレゼント予約Repository::new());
-    let reservation_service = Arc::new(プレゼント予約サービス::new(repository.clone()));
-
-    // テスト用の Axum ルーター (main.rs と同様に設定)
-    let app = Router::new()
-        .route(
-            "/health",
-            axum::routing::get(|| async { axum::http::StatusCode::OK }),
-        ) // health エンドポイントを定義
-        // .route("/", axum::routing::get(|| async { "Hello, Test!" })) // 必要なら他のルートも
-        .with_state(reservation_service); // サービスを State として渡す
-
-    // Tokio の TcpListener はそのまま使う
-    tokio::spawn(async move {
-        serve(listener, app.into_make_service()) // listener を直接渡す
-            .await
-            .unwrap();
-    });
-
-    address
+        .expect("Failed to execute request.");
+
+    // Assert: ステータスコード 200 OK とボディを確認
+    assert!(response.status().is_success());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "OK");
 }
 
 #[tokio::test]
-async fn health_check_works() {
-    // Arrange: テストアプリケーションを起動
-    let address = spawn_test_app().await;
+async fn openapi_document_is_served() {
+    // Arrange
+    let address = spawn_app().await;
     let client = reqwest::Client::new();
 
-    // Act: /health エンドポイントにリクエスト送信
+    // Act
     let response = client
-        .get(&format!("{}/health", &address))
+        .get(format!("{}/api-docs/openapi.json", &address))
         .send()
         .await
         .expect("Failed to execute request.");
 
-    // Assert: ステータスコード 200 OK を確認
+    // Assert: 予約関連のパスが含まれ、servers は設定から組み立てられている
+    assert!(response.status().is_success());
+    let document: serde_json::Value = response.json().await.unwrap();
+    assert!(document["paths"]["/reservations"].is_object());
+    assert_eq!(
+        document["servers"][0]["url"],
+        "http://localhost:8080/api/v1"
+    );
+}
+
+#[tokio::test]
+async fn openapi_servers_follow_public_base_url() {
+    // Arrange
+    let address = spawn_app_with_config(AppConfig {
+        public_base_url: "https://api.example.com".to_string(),
+        ..AppConfig::default()
+    })
+    .await;
+    let client = reqwest::Client::new();
+
+    // Act
+    let document: serde_json::Value = client
+        .get(format!("{}/api-docs/openapi.json", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(
+        document["servers"][0]["url"],
+        "https://api.example.com/api/v1"
+    );
+}
+
+#[tokio::test]
+async fn legacy_paths_are_marked_deprecated() {
+    // Arrange
+    let address = spawn_app_with_config(AppConfig {
+        legacy_api_sunset: Some(Utc.with_ymd_and_hms(2026, 3, 31, 0, 0, 0).unwrap()),
+        ..AppConfig::default()
+    })
+    .await;
+    let client = reqwest::Client::new();
+
+    // Act: バージョンなしの旧パス
+    let legacy = client
+        .get(format!("{}/api/health", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    // Act: 現在のバージョン
+    let current = client
+        .get(format!("{}/api/v1/health", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: 旧パスにだけ Deprecation / Sunset / Link が付く
+    assert!(legacy.status().is_success());
+    assert_eq!(legacy.headers()["deprecation"], "true");
+    assert_eq!(legacy.headers()["sunset"], "Tue, 31 Mar 2026 00:00:00 GMT");
+    assert_eq!(
+        legacy.headers()["link"],
+        "</api/v1>; rel=\"successor-version\""
+    );
+    assert!(current.status().is_success());
+    assert!(current.headers().get("deprecation").is_none());
+}
+
+#[tokio::test]
+async fn legacy_paths_can_be_disabled() {
+    // Arrange
+    let address = spawn_app_with_config(AppConfig {
+        legacy_api_enabled: false,
+        ..AppConfig::default()
+    })
+    .await;
+    let client = reqwest::Client::new();
+
+    // Act
+    let response = client
+        .get(format!("{}/api/health", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn liveness_and_readiness_report_up() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act & Assert: liveness は依存なしで UP
+    let response = client
+        .get(format!("{}/api/v1/health/live", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert!(response.status().is_success());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "UP");
+
+    // Act & Assert: readiness はコンポーネントごとの状態を返す
+    let response = client
+        .get(format!("{}/api/v1/health/ready", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
     assert!(response.status().is_success());
-    // ボディが空であることも確認 (axum::http::StatusCode::OK は空のボディを返す)
-    assert_eq!(Some(0), response.content_length());
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "UP");
+    assert_eq!(body["components"]["database"]["status"], "UP");
+    assert_eq!(body["components"]["database"]["pool_size"], 0);
+    assert!(body["components"]["database"]["error"].is_null());
 }
@@ -0,0 +1,89 @@
+mod common;
+
+use common::spawn_app;
+use serde_json::json;
+
+#[tokio::test]
+async fn lookup_postal_code_returns_address_candidates() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act: ハイフンなし・全角でも検索できる
+    let response = client
+        .get(format!("{}/api/v1/postal-codes/０６０００01", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: 町域の括弧内の補足は含めない
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(
+        body,
+        json!({
+            "postal_code": "060-0001",
+            "candidates": [
+                { "prefecture": "北海道", "city": "札幌市中央区", "town": "北一条西" }
+            ]
+        })
+    );
+}
+
+#[tokio::test]
+async fn lookup_postal_code_returns_all_candidates_across_prefectures() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act: 498-0000 は愛知県と三重県にまたがる
+    let response = client
+        .get(format!("{}/api/v1/postal-codes/498-0000", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: 「以下に掲載がない場合」は町域なしになる
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(
+        body["candidates"],
+        json!([
+            { "prefecture": "愛知県", "city": "弥富市", "town": "" },
+            { "prefecture": "三重県", "city": "桑名郡木曽岬町", "town": "" }
+        ])
+    );
+}
+
+#[tokio::test]
+async fn lookup_postal_code_returns_404_for_unknown_code_and_422_for_malformed_code() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    for (code, status, problem_code) in [
+        (
+            "999-9999",
+            reqwest::StatusCode::NOT_FOUND,
+            "POSTAL_CODE_NOT_FOUND",
+        ),
+        (
+            "060-001",
+            reqwest::StatusCode::UNPROCESSABLE_ENTITY,
+            "INVALID_POSTAL_CODE",
+        ),
+    ] {
+        // Act
+        let response = client
+            .get(format!("{}/api/v1/postal-codes/{}", &address, code))
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        // Assert
+        assert_eq!(response.status(), status);
+        let problem: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(problem["code"], problem_code);
+        assert_eq!(problem["field"], "postal_code");
+    }
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use common::{
+    spawn_app, test_admin_id, test_discontinued_product_id, test_product_id, test_requester_id,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+#[tokio::test]
+async fn search_products_returns_only_products_on_sale() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act: 販売終了の商品も同じ税区分・価格帯だが、カタログには表示しない
+    let response = client
+        .get(format!(
+            "{}/api/v1/products?tax_category=軽減税率&min_price=4000&max_price=5000",
+            &address
+        ))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["total"], 1);
+    assert_eq!(body["items"][0]["id"], test_product_id().to_string());
+    assert_eq!(body["items"][0]["price"], 4320);
+    assert_eq!(body["items"][0]["on_sale"], true);
+}
+
+#[tokio::test]
+async fn get_product_returns_404_for_unknown_or_discontinued_products() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    for id in [Uuid::new_v4(), test_discontinued_product_id()] {
+        // Act
+        let response = client
+            .get(format!("{}/api/v1/products/{}", &address, id))
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        // Assert
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+        let problem: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(problem["code"], "PRODUCT_NOT_FOUND");
+    }
+}
+
+#[tokio::test]
+async fn admin_can_register_and_discontinue_a_product() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let product = json!({
+        "name": "Rose Bouquet",
+        "description": "赤いバラ 12 本の花束です。",
+        "price": 5500,
+        "tax_category": "標準税率",
+        "image_keys": ["products/rose-bouquet/main.jpg"]
+    });
+
+    // Act: 登録した商品はキーワードで検索できる
+    let created = client
+        .post(format!("{}/api/v1/admin/products", &address))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&product)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(created.status(), reqwest::StatusCode::CREATED);
+    let created: serde_json::Value = created.json().await.unwrap();
+    let id = created["id"].as_str().unwrap().to_string();
+    let found: serde_json::Value = client
+        .get(format!("{}/api/v1/products?keyword=rose", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(found["items"][0]["id"], id.as_str());
+
+    // 販売を終了するとカタログから外れる
+    let mut discontinued = product.clone();
+    discontinued["on_sale"] = json!(false);
+    let updated = client
+        .put(format!("{}/api/v1/admin/products/{}", &address, id))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&discontinued)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(updated.status(), reqwest::StatusCode::OK);
+    let found: serde_json::Value = client
+        .get(format!("{}/api/v1/products?keyword=rose", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(found["total"], 0);
+
+    // 不正な画像キーは登録できない
+    let mut invalid = product.clone();
+    invalid["image_keys"] = json!(["../secret.jpg"]);
+    let response = client
+        .post(format!("{}/api/v1/admin/products", &address))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&invalid)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "INVALID_IMAGE_KEY");
+
+    // 上限を超える価格は登録できない (DB の INTEGER に収まらない値も含む)
+    for price in [10_000_001_u64, 3_000_000_000] {
+        let mut invalid = product.clone();
+        invalid["price"] = json!(price);
+        let response = client
+            .post(format!("{}/api/v1/admin/products", &address))
+            .header("x-user-id", test_admin_id().to_string())
+            .json(&invalid)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+        assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+        let problem: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(problem["code"], "INVALID_PRICE");
+        assert_eq!(problem["field"], "price");
+    }
+}
+
+#[tokio::test]
+async fn create_product_requires_administrator() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let product = json!({
+        "name": "Rose Bouquet",
+        "description": "赤いバラ 12 本の花束です。",
+        "price": 5500,
+        "tax_category": "標準税率",
+        "image_keys": []
+    });
+
+    // Act: 顧客が商品を登録しようとする
+    let response = client
+        .post(format!("{}/api/v1/admin/products", &address))
+        .header("x-user-id", test_requester_id().to_string())
+        .json(&product)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: 403 で、商品は登録されない
+    assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "ADMIN_REQUIRED");
+    let found: serde_json::Value = client
+        .get(format!("{}/api/v1/products?keyword=rose", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    assert!(found["items"].as_array().unwrap().is_empty());
+}
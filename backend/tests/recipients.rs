@@ -0,0 +1,232 @@
+mod common;
+
+use common::{spawn_app, test_recipient_id, test_requester_id};
+use serde_json::json;
+use uuid::Uuid;
+
+fn valid_recipient_body() -> serde_json::Value {
+    json!({
+        "name": "鈴木 次郎",
+        "name_kana": "すずき じろう",
+        "postal_code": "〒０６０－０００１",
+        "prefecture": "北海道",
+        "address": "札幌市中央区北1条西1-1",
+        "building": "",
+        "phone_number": "011-123-4567"
+    })
+}
+
+#[tokio::test]
+async fn create_recipient_normalizes_and_lists_own_recipients() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let owner_id = Uuid::new_v4();
+
+    // Act
+    let response = client
+        .post(format!("{}/api/v1/recipients", &address))
+        .header("x-user-id", owner_id.to_string())
+        .json(&valid_recipient_body())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: 入力は正規化して保存される
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+    let location = response.headers()["location"].to_str().unwrap().to_string();
+    let created: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(
+        location,
+        format!("/api/v1/recipients/{}", created["id"].as_str().unwrap())
+    );
+    assert_eq!(created["name_kana"], "スズキ ジロウ");
+    assert_eq!(created["postal_code"], "060-0001");
+    assert_eq!(created["building"], serde_json::Value::Null);
+    assert_eq!(created["phone_number"], "0111234567");
+
+    // 一覧には自分の届け先だけが含まれる
+    let list: serde_json::Value = client
+        .get(format!("{}/api/v1/recipients", &address))
+        .header("x-user-id", owner_id.to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(list, json!([created]));
+}
+
+#[tokio::test]
+async fn create_recipient_returns_422_for_invalid_fields() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    for (field, value, code) in [
+        ("name_kana", "Suzuki Jiro", "INVALID_NAME_KANA"),
+        ("postal_code", "060-001", "INVALID_POSTAL_CODE"),
+        ("prefecture", "北海", "INVALID_PREFECTURE"),
+        ("phone_number", "123-4567", "INVALID_PHONE_NUMBER"),
+        // 郵便番号データとの照合
+        ("postal_code", "999-9999", "INVALID_POSTAL_CODE"),
+        ("address", "旭川市1条通1-1", "ADDRESS_MISMATCH"),
+    ] {
+        let mut body = valid_recipient_body();
+        body[field] = json!(value);
+
+        // Act
+        let response = client
+            .post(format!("{}/api/v1/recipients", &address))
+            .header("x-user-id", Uuid::new_v4().to_string())
+            .json(&body)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        // Assert
+        assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+        let problem: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(problem["code"], code);
+        assert_eq!(problem["field"], field);
+    }
+}
+
+#[tokio::test]
+async fn recipients_are_hidden_from_other_users() {
+    // Arrange: test_requester_id の届け先に別のユーザーがアクセスする
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let url = format!("{}/api/v1/recipients/{}", &address, test_recipient_id());
+    let other_user = Uuid::new_v4().to_string();
+
+    // Act
+    let get = client
+        .get(&url)
+        .header("x-user-id", &other_user)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let put = client
+        .put(&url)
+        .header("x-user-id", &other_user)
+        .json(&valid_recipient_body())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let delete = client
+        .delete(&url)
+        .header("x-user-id", &other_user)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let anonymous = client
+        .get(&url)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    for response in [get, put, delete] {
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+        let problem: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(problem["code"], "RECIPIENT_NOT_FOUND");
+    }
+    assert_eq!(anonymous.status(), reqwest::StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn update_and_delete_own_recipient() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let owner_id = Uuid::new_v4().to_string();
+    let created: serde_json::Value = client
+        .post(format!("{}/api/v1/recipients", &address))
+        .header("x-user-id", &owner_id)
+        .json(&valid_recipient_body())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    let url = format!(
+        "{}/api/v1/recipients/{}",
+        &address,
+        created["id"].as_str().unwrap()
+    );
+
+    // Act: 建物名を追加する
+    let mut body = valid_recipient_body();
+    body["building"] = json!("札幌タワー 1201");
+    let updated = client
+        .put(&url)
+        .header("x-user-id", &owner_id)
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let deleted = client
+        .delete(&url)
+        .header("x-user-id", &owner_id)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let after_delete = client
+        .get(&url)
+        .header("x-user-id", &owner_id)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(updated.status(), reqwest::StatusCode::OK);
+    let updated: serde_json::Value = updated.json().await.unwrap();
+    assert_eq!(updated["id"], created["id"]);
+    assert_eq!(updated["building"], "札幌タワー 1201");
+    assert_eq!(deleted.status(), reqwest::StatusCode::NO_CONTENT);
+    assert_eq!(after_delete.status(), reqwest::StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn delete_recipient_returns_409_while_reservation_in_progress() {
+    // Arrange: 登録済みの届け先で予約を受け付ける
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/api/v1/reservations", &address))
+        .header("x-user-id", test_requester_id().to_string())
+        .json(&json!({
+            "recipient_id": test_recipient_id(),
+            "anniversary_date": "2025-12-24",
+            "wrapping_type": "なし",
+            "forwarding_gift": {
+                "description": "手編みのマフラー",
+                "warehouse_arrival_date": "2025-12-10"
+            },
+            "total_amount": 1430
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+
+    // Act
+    let response = client
+        .delete(format!(
+            "{}/api/v1/recipients/{}",
+            &address,
+            test_recipient_id()
+        ))
+        .header("x-user-id", test_requester_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "RECIPIENT_IN_USE");
+}
@@ -0,0 +1,1600 @@
+mod common;
+
+use common::{
+    spawn_app, spawn_app_with_config, spawn_app_with_events, test_admin_id,
+    test_discontinued_product_id, test_product_id, test_recipient_id, test_requester_id,
+};
+use ddd_sample_jp::config::AppConfig;
+use ddd_sample_jp::domain::ユーザーID;
+use serde_json::json;
+use uuid::Uuid;
+
+// 依頼者 (x-user-id ヘッダー) として予約受付をリクエストするヘルパー関数
+fn post_reservation(
+    client: &reqwest::Client,
+    address: &str,
+    requester_id: Uuid,
+) -> reqwest::RequestBuilder {
+    client
+        .post(format!("{}/api/v1/reservations", address))
+        .header("x-user-id", requester_id.to_string())
+}
+
+// 予約を作成し、予約IDを返すヘルパー関数
+async fn create_reservation_via_api(client: &reqwest::Client, address: &str) -> String {
+    let created: serde_json::Value = post_reservation(client, address, test_requester_id())
+        .json(&valid_request_body())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    created["reservation_id"].as_str().unwrap().to_string()
+}
+
+fn valid_request_body() -> serde_json::Value {
+    json!({
+        "recipient_id": test_recipient_id(),
+        "anniversary_date": "2025-12-24",
+        "message": "いつもありがとう",
+        "wrapping_type": "標準",
+        "desired_delivery": { "date": "2025-12-24", "time_slot": "14-16時" },
+        "product_ids": [test_product_id()],
+        "total_amount": 5530
+    })
+}
+
+// 新しい依頼者のアカウントと届け先を API で登録し、その依頼者のIDと予約リクエストを返すヘルパー関数
+async fn request_body_for_new_requester(
+    client: &reqwest::Client,
+    address: &str,
+) -> (Uuid, serde_json::Value) {
+    let requester_id = Uuid::new_v4();
+    client
+        .post(format!("{}/api/v1/me", address))
+        .header("x-user-id", requester_id.to_string())
+        .json(&json!({
+            "auth_subject": format!("test|{}", requester_id),
+            "email": "ichiro@example.com",
+            "display_name": "いちろう"
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let recipient: serde_json::Value = client
+        .post(format!("{}/api/v1/recipients", address))
+        .header("x-user-id", requester_id.to_string())
+        .json(&json!({
+            "name": "佐藤 一郎",
+            "name_kana": "サトウ イチロウ",
+            "postal_code": "530-0001",
+            "prefecture": "大阪府",
+            "address": "大阪市北区梅田1-1",
+            "phone_number": "06-1234-5678"
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    let mut body = valid_request_body();
+    body["recipient_id"] = recipient["id"].clone();
+    (requester_id, body)
+}
+
+#[tokio::test]
+async fn create_reservation_returns_201_with_location() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act
+    let response = post_reservation(&client, &address, test_requester_id())
+        .json(&valid_request_body())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: 201 Created と Location ヘッダ、予約IDを確認
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+    let location = response
+        .headers()
+        .get("location")
+        .expect("Location header is missing")
+        .to_str()
+        .unwrap()
+        .to_string();
+    let body: serde_json::Value = response.json().await.unwrap();
+    let reservation_id = body["reservation_id"].as_str().unwrap();
+    assert!(Uuid::parse_str(reservation_id).is_ok());
+    assert_eq!(location, format!("/api/v1/reservations/{}", reservation_id));
+}
+
+#[tokio::test]
+async fn create_reservation_takes_requester_from_current_user() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act & Assert: ユーザーを特定できない場合は 401
+    let response = client
+        .post(format!("{}/api/v1/reservations", &address))
+        .json(&valid_request_body())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    // Act: 本文で別の依頼者IDを指定しても無視される
+    let mut body = valid_request_body();
+    body["requester_id"] = json!(Uuid::new_v4());
+    let created: serde_json::Value = post_reservation(&client, &address, test_requester_id())
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+
+    // Assert: 依頼者は x-user-id のユーザーになる
+    let reservation: serde_json::Value = client
+        .get(format!(
+            "{}/api/v1/reservations/{}",
+            &address,
+            created["reservation_id"].as_str().unwrap()
+        ))
+        .header("x-user-id", test_requester_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(reservation["requester_id"], json!(test_requester_id()));
+}
+
+#[tokio::test]
+async fn create_reservation_returns_422_for_empty_products() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let mut body = valid_request_body();
+    body["product_ids"] = json!([]);
+
+    // Act
+    let response = post_reservation(&client, &address, test_requester_id())
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: ドメインルール違反は 422 (problem+json)
+    assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/problem+json"
+    );
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["status"], 422);
+    assert_eq!(problem["code"], "RESERVATION_ITEMS_EMPTY");
+    assert_eq!(problem["field"], "product_ids");
+    assert_eq!(problem["detail"], "予約に商品が含まれていません");
+}
+
+#[tokio::test]
+async fn get_reservation_returns_state_tagged_representation() {
+    // Arrange: 予約を作成しておく
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let request_body = valid_request_body();
+    let created: serde_json::Value = post_reservation(&client, &address, test_requester_id())
+        .json(&request_body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    let reservation_id = created["reservation_id"].as_str().unwrap();
+
+    // Act
+    let response = client
+        .get(format!(
+            "{}/api/v1/reservations/{}",
+            &address, reservation_id
+        ))
+        .header("x-user-id", test_requester_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: status タグと共通フィールドを確認
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "Received");
+    assert_eq!(body["reservation_id"], reservation_id);
+    assert_eq!(body["requester_id"], json!(test_requester_id()));
+    assert_eq!(body["wrapping_type"], "標準");
+    assert_eq!(body["desired_delivery"]["date"], "2025-12-24");
+    assert_eq!(body["desired_delivery"]["time_slot"], "14-16時");
+    assert_eq!(body["gift_type"], "手配");
+    assert_eq!(body["product_ids"], request_body["product_ids"]);
+    assert_eq!(body["forwarding_gift"], serde_json::Value::Null);
+    assert_eq!(body["total_amount"], 5530);
+}
+
+#[tokio::test]
+async fn reservation_records_itemized_charges() {
+    // Arrange: 商品 4,000 円 (8%)・標準ラッピング 300 円 (10%)・送料 800 円 (10%)
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+
+    // Act
+    let body: serde_json::Value = client
+        .get(format!(
+            "{}/api/v1/reservations/{}",
+            &address, reservation_id
+        ))
+        .header("x-user-id", test_requester_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+
+    // Assert
+    let charges = &body["charges"];
+    assert_eq!(
+        charges["lines"],
+        json!([
+            { "item": "商品", "product_id": test_product_id(), "amount_excluding_tax": 4000,
+              "tax_category": "軽減税率", "tax": 320 },
+            { "item": "ラッピング料", "product_id": null, "amount_excluding_tax": 300,
+              "tax_category": "標準税率", "tax": 30 },
+            { "item": "送料", "product_id": null, "amount_excluding_tax": 800,
+              "tax_category": "標準税率", "tax": 80 }
+        ])
+    );
+    assert_eq!(charges["subtotal"], 5100);
+    assert_eq!(charges["standard_rate_tax"], 110);
+    assert_eq!(charges["reduced_rate_tax"], 320);
+    assert_eq!(body["total_amount"], 5530);
+}
+
+#[tokio::test]
+async fn create_reservation_returns_404_for_recipient_of_other_user() {
+    // Arrange: 別の依頼者が登録した届け先と、登録されていない届け先
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let (_, other_users_recipient) = request_body_for_new_requester(&client, &address).await;
+    let mut unknown_recipient = valid_request_body();
+    unknown_recipient["recipient_id"] = json!(Uuid::new_v4());
+
+    for body in [other_users_recipient, unknown_recipient] {
+        // Act
+        let response = post_reservation(&client, &address, test_requester_id())
+            .json(&body)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        // Assert
+        assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+        let problem: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(problem["code"], "RECIPIENT_NOT_FOUND");
+        assert_eq!(problem["field"], "recipient_id");
+    }
+}
+
+#[tokio::test]
+async fn create_reservation_returns_403_while_requester_account_is_suspended() {
+    // Arrange: 管理者が依頼者のアカウントを停止する
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let suspended: serde_json::Value = client
+        .post(format!(
+            "{}/api/v1/admin/users/{}/suspension",
+            &address,
+            test_requester_id()
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(suspended["status"], "停止");
+
+    // Act
+    let response = post_reservation(&client, &address, test_requester_id())
+        .json(&valid_request_body())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "ACCOUNT_SUSPENDED");
+
+    // Act & Assert: 登録していない別の ID を名乗っても受け付けない
+    let response = post_reservation(&client, &address, Uuid::new_v4())
+        .json(&valid_request_body())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "ACCOUNT_NOT_REGISTERED");
+
+    // 再開すると予約できる
+    let reactivated = client
+        .post(format!(
+            "{}/api/v1/admin/users/{}/reactivation",
+            &address,
+            test_requester_id()
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(reactivated.status(), reqwest::StatusCode::OK);
+    let response = post_reservation(&client, &address, test_requester_id())
+        .json(&valid_request_body())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn create_reservation_rejects_mismatched_estimate_and_unavailable_products() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let mut stale_estimate = valid_request_body();
+    stale_estimate["total_amount"] = json!(5000);
+    let mut unknown_product = valid_request_body();
+    unknown_product["product_ids"] = json!([Uuid::new_v4()]);
+    let mut discontinued_product = valid_request_body();
+    discontinued_product["product_ids"] = json!([test_discontinued_product_id()]);
+
+    for (body, code, field) in [
+        (stale_estimate, "ESTIMATE_MISMATCH", "total_amount"),
+        (unknown_product, "PRODUCT_NOT_FOUND", "product_ids"),
+        (discontinued_product, "PRODUCT_DISCONTINUED", "product_ids"),
+    ] {
+        // Act
+        let response = post_reservation(&client, &address, test_requester_id())
+            .json(&body)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        // Assert
+        assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+        let problem: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(problem["code"], code);
+        assert_eq!(problem["field"], field);
+    }
+}
+
+#[tokio::test]
+async fn get_reservation_returns_404_when_missing() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act
+    let response = client
+        .get(format!(
+            "{}/api/v1/reservations/{}",
+            &address,
+            Uuid::new_v4()
+        ))
+        .header("x-user-id", test_requester_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "RESERVATION_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn get_reservation_is_scoped_to_requester() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+    let url = format!("{}/api/v1/reservations/{}", &address, reservation_id);
+
+    // Act & Assert: 他のユーザーの予約は存在しないものとして扱う (届け先の住所を返さない)
+    let response = client
+        .get(&url)
+        .header("x-user-id", Uuid::new_v4().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "RESERVATION_NOT_FOUND");
+
+    // Act & Assert: ユーザーを特定できない場合は 401
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn admin_transitions_drive_reservation_to_delivered() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+    let admin_url = format!("{}/api/v1/admin/reservations/{}", &address, reservation_id);
+    let staff_id = Uuid::new_v4();
+
+    // Act & Assert: 発送準備開始
+    let response = client
+        .post(format!("{}/preparation", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "staff_id": staff_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "Preparing");
+    assert_eq!(body["preparation_staff_id"], json!(staff_id));
+
+    // Act & Assert: 発送完了
+    let response = client
+        .post(format!("{}/shipment", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "carrier": "ヤマト運輸", "shipping_slip_number": "1234-5678-9013" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "Shipped");
+    assert_eq!(body["carrier"], "ヤマト運輸");
+    assert_eq!(body["shipping_slip_number"], "123456789013");
+
+    // Act & Assert: 配送完了記録
+    let response = client
+        .post(format!("{}/delivery", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "delivered_at": "2025-12-24T15:30:00+09:00" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "Delivered");
+    assert_eq!(body["delivery_completed_at"], "2025-12-24T15:30:00+09:00");
+}
+
+#[tokio::test]
+async fn admin_transitions_handle_failed_delivery_and_reshipment() {
+    // Arrange: 発送済みの予約
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+    let admin_url = format!("{}/api/v1/admin/reservations/{}", &address, reservation_id);
+    client
+        .post(format!("{}/preparation", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "staff_id": Uuid::new_v4() }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    client
+        .post(format!("{}/shipment", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "carrier": "ヤマト運輸", "shipping_slip_number": "1234-5678-9013" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Act & Assert: 配送失敗
+    let response = client
+        .post(format!("{}/delivery-failure", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "reason": "宛先不明", "failed_at": "2025-12-23T18:00:00+09:00" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "DeliveryFailed");
+    assert_eq!(body["delivery_failure_reason"], "宛先不明");
+    assert_eq!(body["shipping_slip_number"], "123456789013");
+
+    // Act & Assert: 返送済み
+    let response = client
+        .post(format!("{}/return", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "Returned");
+    assert!(body["returned_at"].is_string());
+
+    // Act & Assert: 同じ伝票番号では再発送できない
+    let response = client
+        .post(format!("{}/reshipment", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "carrier": "ヤマト運輸", "shipping_slip_number": "123456789013" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+
+    // Act & Assert: 新しい伝票番号で再発送すると発送済みに戻る
+    let response = client
+        .post(format!("{}/reshipment", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "carrier": "日本郵便", "shipping_slip_number": "1234-5678-9012" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "Shipped");
+    assert_eq!(body["carrier"], "日本郵便");
+    assert_eq!(body["shipping_slip_number"], "123456789012");
+
+    // Assert: 発送済みからは返送を記録できない
+    let response = client
+        .post(format!("{}/return", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn admin_cancellation_records_reason() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+
+    // Act
+    let response = client
+        .post(format!(
+            "{}/api/v1/admin/reservations/{}/cancellation",
+            &address, reservation_id
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "reason": "顧客都合" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "Cancelled");
+    assert_eq!(body["cancellation_reason"], "顧客都合");
+    assert!(body["cancelled_at"].is_string());
+    assert_eq!(body["cancellation_fee"], 0); // 記念日まで十分日数があるので無料
+}
+
+#[tokio::test]
+async fn admin_cancellation_preview_shows_fee_for_preparing_reservation() {
+    // Arrange: 発送準備中の予約 (合計金額 5,530 円)
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+    let admin_url = format!("{}/api/v1/admin/reservations/{}", &address, reservation_id);
+    client
+        .post(format!("{}/preparation", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "staff_id": Uuid::new_v4() }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Act: キャンセル前に試算する
+    let response = client
+        .get(format!("{}/cancellation", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: 発送準備中のキャンセル料率 30%
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let preview: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(preview["cancellable"], true);
+    assert_eq!(preview["cancellation_fee"], 1659);
+
+    // Act: 試算どおりのキャンセル料でキャンセルされる
+    let response = client
+        .post(format!("{}/cancellation", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "reason": null }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "Cancelled");
+    assert_eq!(body["cancellation_fee"], 1659);
+
+    // Assert: キャンセル済みの予約は試算でもキャンセル不可
+    let preview: serde_json::Value = client
+        .get(format!("{}/cancellation", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(preview["cancellable"], false);
+    assert!(preview["cancellation_fee"].is_null());
+    assert!(preview["reason"].is_string());
+}
+
+#[tokio::test]
+async fn requester_can_preview_cancellation_of_own_reservation() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+    let url = format!(
+        "{}/api/v1/me/reservations/{}/cancellation",
+        &address, reservation_id
+    );
+
+    // Act
+    let response = client
+        .get(&url)
+        .header("x-user-id", test_requester_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: 記念日まで十分日数があるので無料
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let preview: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(preview["cancellable"], true);
+    assert_eq!(preview["cancellation_fee"], 0);
+
+    // Act & Assert: 他の依頼者の予約は見つからない
+    let response = client
+        .get(&url)
+        .header("x-user-id", Uuid::new_v4().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "RESERVATION_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn admin_cancellation_returns_409_when_denied_by_policy() {
+    // Arrange: 記念日の 400 日前までしかキャンセルを受け付けないポリシー
+    let mut config = AppConfig::default();
+    config.cancellation_policy.キャンセル期限日数 = 400;
+    let address = spawn_app_with_config(config).await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+
+    // Act
+    let response = client
+        .post(format!(
+            "{}/api/v1/admin/reservations/{}/cancellation",
+            &address, reservation_id
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "reason": "顧客都合" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "CANCELLATION_NOT_ALLOWED");
+}
+
+#[tokio::test]
+async fn admin_status_change_requires_administrator() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+    let url = format!(
+        "{}/api/v1/admin/reservations/{}/cancellation",
+        &address, reservation_id
+    );
+
+    // Act & Assert: ユーザーを特定できない場合は 401
+    let response = client
+        .post(&url)
+        .json(&json!({ "reason": null }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    // Act & Assert: 顧客は自分の予約でも 403
+    let response = client
+        .post(&url)
+        .header("x-user-id", test_requester_id().to_string())
+        .json(&json!({ "reason": null }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::FORBIDDEN);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "ADMIN_REQUIRED");
+
+    // 予約はキャンセルされていない
+    let reservation: serde_json::Value = client
+        .get(format!(
+            "{}/api/v1/reservations/{}",
+            &address, reservation_id
+        ))
+        .header("x-user-id", test_requester_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(reservation["status"], "Received");
+}
+
+#[tokio::test]
+async fn admin_shipment_is_rejected_before_preparation() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+
+    // Act: 予約受付済みから直接発送完了はできない
+    let response = client
+        .post(format!(
+            "{}/api/v1/admin/reservations/{}/shipment",
+            &address, reservation_id
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "carrier": "ヤマト運輸", "shipping_slip_number": "1234-5678-9013" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: 不正な状態遷移は 409
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "INVALID_STATE_TRANSITION");
+}
+
+#[tokio::test]
+async fn admin_shipment_returns_422_for_invalid_tracking_number() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+    let admin_url = format!("{}/api/v1/admin/reservations/{}", &address, reservation_id);
+    client
+        .post(format!("{}/preparation", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "staff_id": Uuid::new_v4() }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Act: チェックディジットが合わない伝票番号
+    let response = client
+        .post(format!("{}/shipment", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "carrier": "ヤマト運輸", "shipping_slip_number": "1234-5678-9012" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "INVALID_TRACKING_NUMBER");
+    assert_eq!(problem["field"], "shipping_slip_number");
+}
+
+#[tokio::test]
+async fn create_reservation_returns_422_for_zero_amount() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let mut body = valid_request_body();
+    body["total_amount"] = json!(0);
+
+    // Act
+    let response = post_reservation(&client, &address, test_requester_id())
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "INVALID_AMOUNT");
+    assert_eq!(problem["field"], "total_amount");
+}
+
+#[tokio::test]
+async fn list_my_reservations_pages_by_anniversary_date() {
+    // Arrange: 同じ依頼者で記念日の異なる予約を3件作成する
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let (requester_id, base_body) = request_body_for_new_requester(&client, &address).await;
+    for date in ["2025-12-24", "2025-03-14", "2025-07-07"] {
+        let mut body = base_body.clone();
+        body["anniversary_date"] = json!(date);
+        body["desired_delivery"] = json!({ "date": date });
+        post_reservation(&client, &address, requester_id)
+            .json(&body)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+    }
+    // 別の依頼者の予約は含まれない
+    create_reservation_via_api(&client, &address).await;
+
+    // Act: 1ページ目
+    let first_page: serde_json::Value = client
+        .get(format!("{}/api/v1/me/reservations?limit=2", &address))
+        .header("x-user-id", requester_id.to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+
+    // Assert
+    let items = first_page["items"].as_array().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0]["anniversary_date"], "2025-03-14");
+    assert_eq!(items[1]["anniversary_date"], "2025-07-07");
+    assert_eq!(items[0]["status"], "Received");
+    let next_cursor = first_page["next_cursor"].as_str().unwrap();
+
+    // Act: 2ページ目
+    let second_page: serde_json::Value = client
+        .get(format!(
+            "{}/api/v1/me/reservations?limit=2&cursor={}",
+            &address, next_cursor
+        ))
+        .header("x-user-id", requester_id.to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+
+    // Assert
+    let items = second_page["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["anniversary_date"], "2025-12-24");
+    assert!(second_page["next_cursor"].is_null());
+}
+
+#[tokio::test]
+async fn list_my_reservations_requires_user_and_valid_cursor() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act & Assert: ユーザーを特定できない場合は 401
+    let response = client
+        .get(format!("{}/api/v1/me/reservations", &address))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+
+    // Act & Assert: 不正なカーソルは 422
+    let response = client
+        .get(format!("{}/api/v1/me/reservations?cursor=zz", &address))
+        .header("x-user-id", Uuid::new_v4().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["field"], "cursor");
+}
+
+#[tokio::test]
+async fn admin_search_filters_and_sorts_reservations() {
+    // Arrange: 同じ依頼者で記念日・ラッピングの異なる予約を3件作成し、1件をキャンセルする
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let (requester_id, base_body) = request_body_for_new_requester(&client, &address).await;
+    let mut ids = Vec::new();
+    for (date, wrapping, total) in [
+        ("2025-03-14", "標準", 5530),
+        ("2025-12-24", "標準", 5530),
+        ("2025-07-07", "特別", 6080),
+    ] {
+        let mut body = base_body.clone();
+        body["anniversary_date"] = json!(date);
+        body["desired_delivery"] = json!({ "date": date });
+        body["wrapping_type"] = json!(wrapping);
+        body["total_amount"] = json!(total);
+        let created: serde_json::Value = post_reservation(&client, &address, requester_id)
+            .json(&body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+            .json()
+            .await
+            .unwrap();
+        ids.push(created["reservation_id"].as_str().unwrap().to_string());
+    }
+    client
+        .post(format!(
+            "{}/api/v1/admin/reservations/{}/cancellation",
+            &address, ids[0]
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "reason": null }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    // 別の依頼者の予約は含まれない
+    create_reservation_via_api(&client, &address).await;
+
+    // Act: 依頼者・ステータス・ラッピングで絞り込み、記念日の降順で並べる
+    let result: serde_json::Value = client
+        .get(format!(
+            "{}/api/v1/admin/reservations?requester_id={}&status=Received&wrapping_type=標準&sort=anniversary_date_desc",
+            &address, requester_id
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(result["total"], 1);
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["reservation_id"], ids[1].as_str());
+
+    // Act: 記念日の範囲と offset/limit でページングする
+    let result: serde_json::Value = client
+        .get(format!(
+            "{}/api/v1/admin/reservations?requester_id={}&anniversary_from=2025-03-01&anniversary_to=2025-08-01&offset=1&limit=1",
+            &address, requester_id
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+
+    // Assert: 記念日の昇順で 2 件中 2 件目
+    assert_eq!(result["total"], 2);
+    let items = result["items"].as_array().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["anniversary_date"], "2025-07-07");
+}
+
+#[tokio::test]
+async fn admin_search_rejects_unknown_status() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act
+    let response = client
+        .get(format!(
+            "{}/api/v1/admin/reservations?status=Lost",
+            &address
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["field"], "status");
+}
+
+#[tokio::test]
+async fn create_reservation_replays_response_for_same_idempotency_key() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let body = valid_request_body();
+    let send = |body: serde_json::Value| {
+        post_reservation(&client, &address, test_requester_id())
+            .header("Idempotency-Key", "order-123")
+            .json(&body)
+            .send()
+    };
+
+    // Act: 同じキー・同じ内容で2回送信する
+    let first = send(body.clone())
+        .await
+        .expect("Failed to execute request.");
+    let second = send(body.clone())
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: 2回目は同じ予約IDが再送される
+    assert_eq!(first.status(), reqwest::StatusCode::CREATED);
+    assert_eq!(second.status(), reqwest::StatusCode::CREATED);
+    assert!(first.headers().get("idempotent-replayed").is_none());
+    assert_eq!(second.headers()["idempotent-replayed"], "true");
+    assert_eq!(first.headers()["location"], second.headers()["location"]);
+    let first_body: serde_json::Value = first.json().await.unwrap();
+    let second_body: serde_json::Value = second.json().await.unwrap();
+    assert_eq!(first_body["reservation_id"], second_body["reservation_id"]);
+
+    // Act & Assert: 同じキーで内容を変えると 422
+    let mut changed = body.clone();
+    changed["total_amount"] = json!(6000);
+    let response = send(changed).await.expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "IDEMPOTENCY_KEY_REUSED");
+}
+
+#[tokio::test]
+async fn idempotency_key_is_scoped_to_requester() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act: 別の依頼者が同じキーを使う
+    let mut ids = Vec::new();
+    for (requester_id, body) in [
+        (test_requester_id(), valid_request_body()),
+        request_body_for_new_requester(&client, &address).await,
+    ] {
+        let created: serde_json::Value = post_reservation(&client, &address, requester_id)
+            .header("Idempotency-Key", "shared-key")
+            .json(&body)
+            .send()
+            .await
+            .expect("Failed to execute request.")
+            .json()
+            .await
+            .unwrap();
+        ids.push(created["reservation_id"].clone());
+    }
+
+    // Assert: それぞれ別の予約として受け付けられる
+    assert_ne!(ids[0], ids[1]);
+}
+
+#[tokio::test]
+async fn create_reservation_normalizes_and_validates_message() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    // Act: 半角カナ・全角英数は正規化して保存される
+    let mut body = valid_request_body();
+    body["message"] = json!("ｵﾒﾃﾞﾄｳ！ＡＢＣ");
+    let created: serde_json::Value = post_reservation(&client, &address, test_requester_id())
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    let reservation: serde_json::Value = client
+        .get(format!(
+            "{}/api/v1/reservations/{}",
+            &address,
+            created["reservation_id"].as_str().unwrap()
+        ))
+        .header("x-user-id", test_requester_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(reservation["message"], "オメデトウ!ABC");
+
+    // Act & Assert: 201 文字は 422
+    let mut body = valid_request_body();
+    body["message"] = json!("あ".repeat(201));
+    let response = post_reservation(&client, &address, test_requester_id())
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "INVALID_MESSAGE");
+    assert_eq!(problem["field"], "message");
+}
+
+#[tokio::test]
+async fn create_reservation_rejects_anniversary_within_lead_time() {
+    // Arrange: 2025-01-06 (月) 受付、既定の 3 営業日なら 1/9 (木) が最短
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let mut body = valid_request_body();
+    body["anniversary_date"] = json!("2025-01-08");
+
+    // Act
+    let response = post_reservation(&client, &address, test_requester_id())
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "ANNIVERSARY_TOO_EARLY");
+    assert_eq!(problem["field"], "anniversary_date");
+
+    // Act & Assert: 最短日なら受け付けられる
+    body["anniversary_date"] = json!("2025-01-09");
+    body["desired_delivery"] = json!(null);
+    let response = post_reservation(&client, &address, test_requester_id())
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::CREATED);
+}
+
+#[tokio::test]
+async fn create_reservation_rejects_delivery_date_outside_window() {
+    // Arrange: 2025-01-06 (月) 受付なので最短配送日は 1/9、記念日は 12/24
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    for date in ["2025-01-08", "2025-12-25"] {
+        let mut body = valid_request_body();
+        body["desired_delivery"] = json!({ "date": date, "time_slot": "午前中" });
+
+        // Act
+        let response = post_reservation(&client, &address, test_requester_id())
+            .json(&body)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        // Assert
+        assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+        let problem: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(problem["code"], "DELIVERY_DATE_OUT_OF_RANGE");
+        assert_eq!(problem["field"], "desired_delivery.date");
+    }
+}
+
+#[tokio::test]
+async fn create_forwarding_reservation_without_products() {
+    // Arrange: 発送代行では商品IDの代わりにプレゼントの説明と倉庫到着予定日を指定する
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let mut body = valid_request_body();
+    body.as_object_mut().unwrap().remove("product_ids");
+    body["forwarding_gift"] = json!({
+        "description": "手作りのアルバム",
+        "warehouse_arrival_date": "2025-12-20"
+    });
+    body["total_amount"] = json!(1760); // 発送代行手数料 550 円 + ラッピング料 330 円 + 送料 880 円
+
+    // Act
+    let created: serde_json::Value = post_reservation(&client, &address, test_requester_id())
+        .json(&body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    let reservation: serde_json::Value = client
+        .get(format!(
+            "{}/api/v1/reservations/{}",
+            &address,
+            created["reservation_id"].as_str().unwrap()
+        ))
+        .header("x-user-id", test_requester_id().to_string())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(reservation["gift_type"], "発送代行");
+    assert_eq!(reservation["product_ids"], json!([]));
+    assert_eq!(
+        reservation["forwarding_gift"]["description"],
+        "手作りのアルバム"
+    );
+    assert_eq!(
+        reservation["forwarding_gift"]["warehouse_arrival_date"],
+        "2025-12-20"
+    );
+}
+
+#[tokio::test]
+async fn create_forwarding_reservation_validates_gift() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let forwarding = |description: &str, arrival: &str| {
+        let mut body = valid_request_body();
+        body["product_ids"] = json!([]);
+        body["forwarding_gift"] =
+            json!({ "description": description, "warehouse_arrival_date": arrival });
+        body
+    };
+    let mut both = forwarding("手作りのアルバム", "2025-12-20");
+    both["product_ids"] = json!([Uuid::new_v4()]);
+    let cases = [
+        (both, "INVALID_INPUT", "forwarding_gift"),
+        (
+            forwarding("   ", "2025-12-20"),
+            "INVALID_GIFT_DESCRIPTION",
+            "forwarding_gift.description",
+        ),
+        // 配送希望日 (12/24) 当日の到着では発送が間に合わない
+        (
+            forwarding("手作りのアルバム", "2025-12-24"),
+            "INVALID_WAREHOUSE_ARRIVAL_DATE",
+            "forwarding_gift.warehouse_arrival_date",
+        ),
+    ];
+
+    for (body, code, field) in cases {
+        // Act
+        let response = post_reservation(&client, &address, test_requester_id())
+            .json(&body)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        // Assert
+        assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+        let problem: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(problem["code"], code);
+        assert_eq!(problem["field"], field);
+    }
+}
+
+#[tokio::test]
+async fn update_reservation_changes_contents_before_preparation() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let request_body = valid_request_body();
+    let created: serde_json::Value = post_reservation(&client, &address, test_requester_id())
+        .json(&request_body)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    let reservation_id = created["reservation_id"].as_str().unwrap();
+
+    // Act: メッセージを取り消し、ラッピングと配送希望を変更する
+    let response = client
+        .patch(format!(
+            "{}/api/v1/reservations/{}",
+            &address, reservation_id
+        ))
+        .header("x-user-id", test_requester_id().to_string())
+        .json(&json!({
+            "message": null,
+            "wrapping_type": "特別",
+            "desired_delivery": { "date": "2025-12-20", "time_slot": "午前中" },
+            "total_amount": 6080
+        }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let body: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(body["status"], "Received");
+    assert!(body["message"].is_null());
+    assert_eq!(body["wrapping_type"], "特別");
+    assert_eq!(body["desired_delivery"]["date"], "2025-12-20");
+    assert_eq!(body["desired_delivery"]["time_slot"], "午前中");
+    assert_eq!(body["anniversary_date"], "2025-12-24");
+    assert_eq!(body["total_amount"], 6080); // 特別ラッピング料に差し替わる
+}
+
+#[tokio::test]
+async fn update_reservation_requires_estimate_matching_new_wrapping_charges() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+    let url = format!("{}/api/v1/reservations/{}", &address, reservation_id);
+    let requester_id = test_requester_id().to_string();
+
+    for (change, code) in [
+        // 合計金額が変わるのに見積金額がない
+        (json!({ "wrapping_type": "特別" }), "REQUIRED_FIELD_MISSING"),
+        // 変更前の合計金額のままでは特別ラッピング料の増額分と一致しない
+        (
+            json!({ "wrapping_type": "特別", "total_amount": 5530 }),
+            "ESTIMATE_MISMATCH",
+        ),
+    ] {
+        // Act
+        let response = client
+            .patch(&url)
+            .header("x-user-id", &requester_id)
+            .json(&change)
+            .send()
+            .await
+            .expect("Failed to execute request.");
+
+        // Assert
+        assert_eq!(response.status(), reqwest::StatusCode::UNPROCESSABLE_ENTITY);
+        let problem: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(problem["code"], code);
+        assert_eq!(problem["field"], "total_amount");
+    }
+
+    // 変更されていない
+    let body: serde_json::Value = client
+        .get(&url)
+        .header("x-user-id", &requester_id)
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["wrapping_type"], "標準");
+    assert_eq!(body["total_amount"], 5530);
+}
+
+#[tokio::test]
+async fn update_reservation_rejects_other_users_and_started_preparation() {
+    // Arrange
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let requester_id = test_requester_id();
+    let created: serde_json::Value = post_reservation(&client, &address, requester_id)
+        .json(&valid_request_body())
+        .send()
+        .await
+        .expect("Failed to execute request.")
+        .json()
+        .await
+        .unwrap();
+    let reservation_id = created["reservation_id"].as_str().unwrap();
+    let change = json!({ "wrapping_type": "なし", "total_amount": 5200 });
+
+    // Act & Assert: 他のユーザーからは見つからない
+    let response = client
+        .patch(format!(
+            "{}/api/v1/reservations/{}",
+            &address, reservation_id
+        ))
+        .header("x-user-id", Uuid::new_v4().to_string())
+        .json(&change)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_FOUND);
+
+    // Act & Assert: 発送準備が始まった予約は変更できない
+    client
+        .post(format!(
+            "{}/api/v1/admin/reservations/{}/preparation",
+            &address, reservation_id
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "staff_id": Uuid::new_v4() }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    let response = client
+        .patch(format!(
+            "{}/api/v1/reservations/{}",
+            &address, reservation_id
+        ))
+        .header("x-user-id", requester_id.to_string())
+        .json(&change)
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "INVALID_STATE_TRANSITION");
+}
+
+#[tokio::test]
+async fn state_transitions_publish_domain_events() {
+    // Arrange
+    let (address, events) = spawn_app_with_events(AppConfig::default()).await;
+    let client = reqwest::Client::new();
+    let reservation_id = create_reservation_via_api(&client, &address).await;
+    let staff_id = Uuid::new_v4();
+
+    // Act: 発送準備 (梱包担当者を指定) → 発送 (操作した管理者を x-user-id で指定)
+    client
+        .post(format!(
+            "{}/api/v1/admin/reservations/{}/preparation",
+            &address, reservation_id
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "staff_id": staff_id }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    client
+        .post(format!(
+            "{}/api/v1/admin/reservations/{}/shipment",
+            &address, reservation_id
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "carrier": "ヤマト運輸", "shipping_slip_number": "1234-5678-9013" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+
+    // Assert: 発生順にイベントが発行され、予約IDと実行者が記録されている
+    let published = events.発行済みイベント();
+    let names: Vec<&str> = published.iter().map(|e| e.名前()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "プレゼント予約受け付け完了",
+            "予約発送準備開始",
+            "予約発送完了"
+        ]
+    );
+    for event in &published {
+        assert_eq!(event.情報().予約id.as_uuid().to_string(), reservation_id);
+    }
+    assert_eq!(
+        published[1].情報().実行者,
+        Some(ユーザーID::from_uuid(staff_id))
+    );
+    assert_eq!(
+        published[2].情報().実行者,
+        Some(ユーザーID::from_uuid(test_admin_id()))
+    );
+}
+
+#[tokio::test]
+async fn stock_is_allocated_at_acceptance_and_released_or_consumed_by_admin() {
+    // Arrange: 商品の在庫を残り 1 点にする
+    let address = spawn_app().await;
+    let client = reqwest::Client::new();
+    let stock_url = format!(
+        "{}/api/v1/admin/products/{}/stock",
+        &address,
+        test_product_id()
+    );
+    let response = client
+        .put(&stock_url)
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "on_hand": 1 }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let get_stock = || async {
+        client
+            .get(&stock_url)
+            .header("x-user-id", test_admin_id().to_string())
+            .send()
+            .await
+            .expect("Failed to execute request.")
+            .json::<serde_json::Value>()
+            .await
+            .unwrap()
+    };
+
+    // Act & Assert: 最後の 1 点を引き当てた後の予約は在庫不足で受け付けない
+    let first_id = create_reservation_via_api(&client, &address).await;
+    let response = post_reservation(&client, &address, test_requester_id())
+        .json(&valid_request_body())
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "OUT_OF_STOCK");
+    assert_eq!(problem["field"], "product_ids");
+    let stock = get_stock().await;
+    assert_eq!(
+        (stock["allocated"].as_u64(), stock["available"].as_u64()),
+        (Some(1), Some(0))
+    );
+
+    // 引当数を下回る在庫数には変更できない
+    let response = client
+        .put(&stock_url)
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "on_hand": 0 }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+    let problem: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(problem["code"], "STOCK_BELOW_ALLOCATED");
+
+    // キャンセルすると引当が解除され、次の予約が引き当てられる
+    let response = client
+        .post(format!(
+            "{}/api/v1/admin/reservations/{}/cancellation",
+            &address, first_id
+        ))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "reason": "顧客都合" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let second_id = create_reservation_via_api(&client, &address).await;
+
+    // 発送を完了すると在庫数からも差し引かれる
+    let admin_url = format!("{}/api/v1/admin/reservations/{}", &address, second_id);
+    let response = client
+        .post(format!("{}/preparation", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "staff_id": Uuid::new_v4() }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let response = client
+        .post(format!("{}/shipment", admin_url))
+        .header("x-user-id", test_admin_id().to_string())
+        .json(&json!({ "carrier": "ヤマト運輸", "shipping_slip_number": "1234-5678-9013" }))
+        .send()
+        .await
+        .expect("Failed to execute request.");
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+    let stock = get_stock().await;
+    assert_eq!(stock["on_hand"], 0);
+    assert_eq!(stock["allocated"], 0);
+}